@@ -769,6 +769,7 @@ programCommand("show_item")
       item.edition ? item.edition.toBase58() : "Not cached on object"
     );
     log.info("tokensStaked:", item.tokensStaked.toNumber());
+    log.info("weightedTokensStaked:", item.weightedTokensStaked.toNumber());
 
     log.info("Item Data:");
     log.info(
@@ -280,7 +280,54 @@ programCommand("update_match_from_oracle")
     });
   });
 
-programCommand("disburse_tokens_by_oracle")
+programCommand("update_match_economics")
+  .requiredOption(
+    "-cp, --config-path <string>",
+    "JSON file with match settings"
+  )
+  .action(async (files: string[], cmd) => {
+    const { keypair, env, configPath, rpcUrl } = cmd.opts();
+
+    const walletKeyPair = loadWalletKey(keypair);
+    const anchorProgram = await getMatchesProgram(walletKeyPair, env, rpcUrl);
+
+    if (configPath === undefined) {
+      throw new Error("The configPath is undefined");
+    }
+    const configString = fs.readFileSync(configPath);
+
+    //@ts-ignore
+    const config = JSON.parse(configString);
+
+    await anchorProgram.updateMatchEconomics(
+      {
+        entryFeeAmount: new BN(config.entryFee.amount),
+        rake: config.rake
+          ? {
+              treasury: new web3.PublicKey(config.rake.treasury),
+              basisPoints: config.rake.basisPoints,
+            }
+          : null,
+        payoutSchedule: config.payoutSchedule || { winnerTakeAll: true },
+      },
+      {
+        winOracle: config.winOracle
+          ? new web3.PublicKey(config.winOracle)
+          : (
+              await PDA.getOracle(
+                new web3.PublicKey(config.oracleState.seed),
+
+                config.oracleState.authority
+                  ? new web3.PublicKey(config.oracleState.authority)
+                  : walletKeyPair.publicKey
+              )
+            )[0],
+        entryFeeMint: new web3.PublicKey(config.entryFee.mint),
+      }
+    );
+  });
+
+programCommand("disburse_prize_pool")
   .requiredOption(
     "-cp, --config-path <string>",
     "JSON file with match settings"
@@ -311,18 +358,74 @@ programCommand("disburse_tokens_by_oracle")
           )
         )[0];
     const oracleInstance = await anchorProgram.fetchWinOracle(winOracle);
-    for (let i = 0; i < oracleInstance.tokenTransfers.length; i++) {
-      const tfer = oracleInstance.tokenTransfers[i];
+    const matchInstance = await anchorProgram.fetchMatch(
+      (await PDA.getMatch(winOracle))[0]
+    );
 
-      await anchorProgram.disburseTokensByOracle(
+    // pay every ranked place the schedule covers, then the rake
+    const places = matchInstance.payoutSchedule.topN
+      ? matchInstance.payoutSchedule.topN.basis_points.length
+      : 1;
+    const ranked = Math.min(places, oracleInstance.rankings.length);
+    for (let i = 0; i < ranked; i++) {
+      await anchorProgram.disbursePrizePool({ rank: new BN(i) }, { winOracle });
+    }
+    if (matchInstance.rake) {
+      await anchorProgram.disbursePrizePool({ rank: null }, { winOracle });
+    }
+  });
+
+programCommand("disburse_tokens_by_oracle")
+  .requiredOption(
+    "-cp, --config-path <string>",
+    "JSON file with match settings"
+  )
+  .option(
+    "-b, --batch-size <string>",
+    "Number of token deltas to disburse per transaction"
+  )
+  .action(async (files: string[], cmd) => {
+    const { keypair, env, configPath, rpcUrl, batchSize } = cmd.opts();
+
+    const walletKeyPair = loadWalletKey(keypair);
+    const anchorProgram = await getMatchesProgram(walletKeyPair, env, rpcUrl);
+
+    if (configPath === undefined) {
+      throw new Error("The configPath is undefined");
+    }
+    const configString = fs.readFileSync(configPath);
+
+    //@ts-ignore
+    const config = JSON.parse(configString);
+
+    const winOracle = config.winOracle
+      ? new web3.PublicKey(config.winOracle)
+      : (
+          await PDA.getOracle(
+            new web3.PublicKey(config.oracleState.seed),
+
+            config.oracleState.authority
+              ? new web3.PublicKey(config.oracleState.authority)
+              : walletKeyPair.publicKey
+          )
+        )[0];
+    const oracleInstance = await anchorProgram.fetchWinOracle(winOracle);
+    const size = batchSize ? parseInt(batchSize) : 1;
+    for (let i = 0; i < oracleInstance.tokenTransfers.length; i += size) {
+      const tfers = oracleInstance.tokenTransfers.slice(i, i + size);
+
+      await anchorProgram.disburseTokensByOracleBatch(
         {
-          tokenDeltaProofInfo: null,
+          tokenDeltas: tfers.map((_, j) => ({
+            tokenDeltaProofInfo: null,
+            index: new BN(i + j),
+          })),
         },
         {
           winOracle,
         },
         {
-          tokenDelta: tfer,
+          tokenDeltas: tfers,
         }
       );
     }
@@ -427,6 +530,7 @@ programCommand("create_or_update_oracle")
       tokenTransfers: config.oracleState.tokenTransfers,
       space: config.space ? new BN(config.space) : new BN(150),
       finalized: config.oracleState.finalized,
+      rankings: config.oracleState.rankings || null,
     });
   });
 
@@ -516,7 +620,13 @@ programCommand("show_match")
         ? new Date(u.minimumAllowedEntryTime * 1000)
         : "Unset"
     );
-    log.info("Current token transfer index:", u.currentTokenTransferIndex);
+    log.info("Token Transfers Completed:", u.tokenTransfersCompleted);
+    if (u.entryFee) {
+      log.info("Entry Fee Mint:", u.entryFee.mint.toBase58());
+      log.info("Entry Fee Amount:", u.entryFee.amount.toNumber());
+      log.info("Prize Pool:", u.prizePool);
+      log.info("Prize Pool Paid:", u.prizePoolPaid);
+    }
     log.info("Token Types Added:", u.tokenTypesAdded);
     log.info("Token Types Removed:", u.tokenTypesRemoved);
     log.info("Token Entry Validations:");
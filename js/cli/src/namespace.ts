@@ -79,6 +79,7 @@ CLI.programCommandWithConfig(
       prettyName: config.prettyName,
       permissivenessSettings: config.permissivenessSettings,
       whitelistedStakingMints: whitelistedStakingMints,
+      refundPaymentOnLeave: config.refundPaymentOnLeave ?? null,
     };
 
     const updateNsAccounts: Instructions.Namespace.UpdateNamespaceAccounts = {
@@ -178,9 +179,16 @@ CLI.programCommandWithConfig(
         State.Namespace.RaindropsProgram.getRaindropsProgramFromAddress(
           config.raindropsProgram
         ),
+      collectionNftMints: (config.collectionNftMints ?? []).map(
+        (mint: string) => new web3.PublicKey(mint)
+      ),
     };
 
-    await namespaceProgram.joinNamespace(accounts);
+    const args: Instructions.Namespace.JoinNamespaceArgs = {
+      allowlistProofs: config.allowlistProofs ?? [],
+    };
+
+    await namespaceProgram.joinNamespace(accounts, args);
 
     log.info("Artifact joined to Namespace");
   }
@@ -224,7 +232,12 @@ CLI.programCommandWithConfig(
         ),
     };
 
-    await namespaceProgram.cacheArtifact(accounts);
+    const args: Instructions.Namespace.CacheArtifactArgs = {
+      sortKey:
+        config.sortKey !== undefined ? new anchor.BN(config.sortKey) : null,
+    };
+
+    await namespaceProgram.cacheArtifact(accounts, args);
 
     log.info("Artifact cached to Namespace");
   }
@@ -256,6 +269,93 @@ CLI.programCommandWithConfig(
   }
 );
 
+CLI.programCommandWithConfig(
+  "compact_index",
+  async (config, options, _files) => {
+    const { keypair, env, rpcUrl } = options;
+
+    const namespaceProgram = await initNsProgram(rpcUrl, env, keypair);
+
+    const args: Instructions.Namespace.CompactIndexArgs = {
+      fromPage: new anchor.BN(config.fromPage),
+      toPage: new anchor.BN(config.toPage),
+    };
+
+    const accounts: Instructions.Namespace.CompactIndexAccounts = {
+      namespaceMint: new web3.PublicKey(config.mint),
+      artifacts: config.artifacts.map((artifact) => ({
+        artifact: new web3.PublicKey(artifact.artifact),
+        raindropsProgram:
+          State.Namespace.RaindropsProgram.getRaindropsProgramFromAddress(
+            artifact.raindropsProgram
+          ),
+      })),
+    };
+
+    await namespaceProgram.compactIndex(args, accounts);
+
+    log.info(
+      `Moved ${config.artifacts.length} artifacts from page ${config.fromPage} to page ${config.toPage}`
+    );
+  }
+);
+
+CLI.programCommandWithConfig(
+  "initialize_protocol_fee_config",
+  async (config, options, _files) => {
+    const { keypair, env, rpcUrl } = options;
+
+    const namespaceProgram = await initNsProgram(rpcUrl, env, keypair);
+
+    await namespaceProgram.initializeProtocolFeeConfig(
+      { feeAmount: new anchor.BN(config.feeAmount) },
+      {
+        feeMint: new web3.PublicKey(config.feeMint),
+        feeVault: new web3.PublicKey(config.feeVault),
+      }
+    );
+
+    log.info("Protocol fee config initialized");
+  }
+);
+
+CLI.programCommandWithConfig(
+  "update_protocol_fee_config",
+  async (config, options, _files) => {
+    const { keypair, env, rpcUrl } = options;
+
+    const namespaceProgram = await initNsProgram(rpcUrl, env, keypair);
+
+    await namespaceProgram.updateProtocolFeeConfig(
+      {
+        admin: config.admin ? new web3.PublicKey(config.admin) : null,
+        feeAmount: config.feeAmount ? new anchor.BN(config.feeAmount) : null,
+      },
+      {
+        feeMint: new web3.PublicKey(config.feeMint),
+        feeVault: new web3.PublicKey(config.feeVault),
+      }
+    );
+
+    log.info("Protocol fee config updated");
+  }
+);
+
+CLI.programCommandWithConfig(
+  "set_namespace_protocol_fee_exempt",
+  async (config, options, _files) => {
+    const { keypair, env, rpcUrl } = options;
+
+    const namespaceProgram = await initNsProgram(rpcUrl, env, keypair);
+
+    await namespaceProgram.setNamespaceProtocolFeeExempt(config.exempt, {
+      namespaceMint: new web3.PublicKey(config.mint),
+    });
+
+    log.info(`Namespace protocol fee exempt: ${config.exempt}`);
+  }
+);
+
 CLI.Program.parseAsync(process.argv);
 
 async function initNsProgram(
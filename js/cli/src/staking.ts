@@ -28,6 +28,7 @@ CLI.programCommandWithConfig(
         artifactMint: new web3.PublicKey(config.artifactMint),
         stakingAmount: new BN(config.stakingAmount),
         stakingPermissivenessToUse: config.stakingPermissivenessToUse || null,
+        lockupTier: config.lockupTier ?? null,
       },
       {
         artifactClass: new web3.PublicKey(config.artifactClass),
@@ -108,6 +109,7 @@ CLI.programCommandWithConfig(
         artifactClassMint: new web3.PublicKey(config.artifactClassMint),
         artifactMint: new web3.PublicKey(config.artifactMint),
         stakingPermissivenessToUse: config.stakingPermissivenessToUse || null,
+        amount: config.amount ? new BN(config.amount) : null,
       },
       {
         artifactClass: new web3.PublicKey(config.artifactClass),
@@ -167,4 +169,48 @@ CLI.programCommandWithConfig(
   }
 );
 
+CLI.programCommandWithConfig(
+  "set_lockup_tiers",
+  async (config, options, _files) => {
+    const { keypair, env, rpcUrl } = options;
+
+    const stakingProgram = await StakingProgram.getProgramWithWalletKeyPair(
+      StakingProgram,
+      await Wallet.loadWalletKey(keypair),
+      env,
+      rpcUrl
+    );
+
+    const { txid } = await stakingProgram.setLockupTiers(
+      {
+        classIndex: new BN(config.classIndex),
+        parentClassIndex: config.parentClassIndex
+          ? new BN(config.parentClassIndex)
+          : null,
+        artifactClassMint: new web3.PublicKey(config.artifactClassMint),
+        updatePermissivenessToUse: config.updatePermissivenessToUse || null,
+        tiers: config.tiers.map((tier) => ({
+          lockDuration: new BN(tier.lockDuration),
+          multiplierBps: new BN(tier.multiplierBps),
+        })),
+      },
+      {
+        artifactClass: new web3.PublicKey(config.artifactClass),
+        parentClassMint: config.parentClassMint
+          ? new web3.PublicKey(config.parentClassMint)
+          : null,
+        parentClass: config.parentClass
+          ? new web3.PublicKey(config.parentClass)
+          : null,
+        metadataUpdateAuthority: config.metadataUpdateAuthority
+          ? new web3.PublicKey(config.metadataUpdateAuthority)
+          : null,
+      }
+    );
+
+    log.setLevel("info");
+    log.info(`Transaction signature: ${txid}`);
+  }
+);
+
 CLI.Program.parseAsync(process.argv);
@@ -1 +1,2 @@
 export const PREFIX = "matches";
+export const PRIZE_POOL = "prize_pool";
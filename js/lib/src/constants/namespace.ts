@@ -1,2 +1,4 @@
 export const PREFIX = "namespace";
 export const GATEKEEPER_PREFIX = "gatekeeper";
+export const PROTOCOL_FEE_PREFIX = "protocol_fee";
+export const MEMBERSHIP_PAYMENT_PREFIX = "membership_payment";
@@ -48,3 +48,7 @@ export const TOKEN_METADATA_PROGRAM_ID = new web3.PublicKey(
 export const MPL_AUTH_RULES_PROGRAM_ID = new web3.PublicKey(
   "auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg"
 );
+
+export const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
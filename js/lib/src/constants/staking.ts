@@ -1,2 +1,6 @@
 export const PREFIX = "staking";
 export const STAKING_COUNTER = "counter";
+export const REWARD_POOL = "reward_pool";
+export const STAKE_REWARDS = "stake_rewards";
+export const STAKE_POSITIONS = "positions";
+export const LOCKUP_TIERS = "lockup_tiers";
@@ -58,6 +58,7 @@ import {
   UpdateVariantAccounts,
   UpdateTargetTraitVariant,
   UpdateTargetSelectionTraitVariant,
+  UpdateTargetSelectionClassVariant,
   UPDATE_STATE_PREFIX,
   PayForUpdateAccounts,
   FungiblePaymentAssetClass,
@@ -79,6 +80,7 @@ import {
   RemoveTraitAccounts,
   PaymentDetailsExpanded,
   TRAIT_CONFLICTS_PREFIX,
+  VARIANT_OPTION_COUNTER_PREFIX,
   AddTraitConflictsAccounts,
   AddTraitConflictsArgs,
   TraitConflicts,
@@ -97,6 +99,30 @@ import {
   PaymentDetails,
   TraitGate,
   MigrateAvatarAccountAccounts,
+  MigrateTraitAccountAccounts,
+  MigrateUpdateStateAccounts,
+  UPDATE_STATE_LEGACY_VERSION,
+  NativePaymentAssetClass,
+  SplitPaymentAction,
+  TransferPaymentNativeAccounts,
+  TransferPaymentNativeArgs,
+  formatPaymentDetailsForIx,
+  parsePaymentAlternatives,
+  TRAIT_RENTAL_PREFIX,
+  CreateTraitRentalAccounts,
+  CreateTraitRentalArgs,
+  CancelTraitRentalAccounts,
+  RentTraitAccounts,
+  ReclaimTraitRentalAccounts,
+  UpdateStatTemplateAccounts,
+  UpdateStatTemplateArgs,
+  RemoveStatTemplateAccounts,
+  RemoveStatTemplateArgs,
+  RefreshAvatarStatsAccounts,
+  parseStatTemplates,
+  parseStatModifiers,
+  parseStats,
+  TraitRental,
 } from "./state";
 import {
   AVATAR_RAIN_VAULT_DEVNET,
@@ -149,6 +175,7 @@ export class AvatarClient {
       attributeMetadata: formattedAttributeMetadata,
       variantMetadata: args.variantMetadata,
       globalRenderingConfigUri: args.globalRenderingConfigUri,
+      statTemplates: args.statTemplates ? args.statTemplates : [],
     };
 
     const tx = await this.program.methods
@@ -265,13 +292,10 @@ export class AvatarClient {
       attributeIds: args.attributeIds,
       variantMetadata: args.variantMetadata.map((vm) => vm.formatForIx()),
       traitStatus: args.traitStatus,
-      equipPaymentDetails: args.equipPaymentDetails
-        ? args.equipPaymentDetails
-        : null,
-      removePaymentDetails: args.removePaymentDetails
-        ? args.removePaymentDetails
-        : null,
+      equipPaymentDetails: formatPaymentDetailsForIx(args.equipPaymentDetails),
+      removePaymentDetails: formatPaymentDetailsForIx(args.removePaymentDetails),
       traitGate: args.traitGate ? args.traitGate.formatForIx() : null,
+      statModifiers: args.statModifiers ? args.statModifiers : [],
     };
 
     const tx = await this.program.methods
@@ -807,10 +831,18 @@ export class AvatarClient {
     const updateStateData = await this.getUpdateState(updateState);
 
     let traitAccount: anchor.web3.PublicKey | null = null;
+    let traitData: Trait | null = null;
     if (updateStateData.target instanceof UpdateTargetTraitVariant) {
       traitAccount = updateStateData.target.trait;
+      traitData = await this.getTrait(traitAccount);
     }
 
+    const variantOptionCounter = await this.getVariantOptionCounterAddress(
+      avatarData.avatarClass,
+      updateStateData.target,
+      traitData
+    );
+
     const avatarAuthority = await this.getNftHolder(
       this.provider.connection,
       avatarData.mint
@@ -827,6 +859,7 @@ export class AvatarClient {
         avatar: accounts.avatar,
         updateState: updateState,
         traitAccount: traitAccount,
+        variantOptionCounter: variantOptionCounter,
         payer: accounts.payer,
         avatarMintAta: avatarAuthorityAta,
         avatarAuthority: avatarAuthority,
@@ -854,13 +887,10 @@ export class AvatarClient {
       variantOption: args.variantOption
         ? args.variantOption.formatForIx()
         : null,
-      equipPaymentDetails: args.equipPaymentDetails
-        ? args.equipPaymentDetails
-        : null,
-      removePaymentDetails: args.removePaymentDetails
-        ? args.removePaymentDetails
-        : null,
+      equipPaymentDetails: formatPaymentDetailsForIx(args.equipPaymentDetails),
+      removePaymentDetails: formatPaymentDetailsForIx(args.removePaymentDetails),
       traitGate: args.traitGate ? args.traitGate.formatForIx() : null,
+      statModifiers: args.statModifiers ? args.statModifiers : null,
     };
 
     const trait = traitPDA(accounts.avatarClass, accounts.traitMint);
@@ -948,6 +978,79 @@ export class AvatarClient {
     return tx;
   }
 
+  async updateStatTemplate(
+    accounts: UpdateStatTemplateAccounts,
+    args: UpdateStatTemplateArgs
+  ): Promise<anchor.web3.Transaction> {
+    const avatarClassData = await this.getAvatarClass(accounts.avatarClass);
+
+    const avatarClassMintAta = splToken.getAssociatedTokenAddressSync(
+      avatarClassData.mint,
+      accounts.authority
+    );
+
+    const tx = await this.program.methods
+      .updateStatTemplate(args)
+      .accounts({
+        avatarClass: accounts.avatarClass,
+        avatarClassMintAta: avatarClassMintAta,
+        authority: accounts.authority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.authority);
+
+    return tx;
+  }
+
+  async removeStatTemplate(
+    accounts: RemoveStatTemplateAccounts,
+    args: RemoveStatTemplateArgs
+  ): Promise<anchor.web3.Transaction> {
+    const avatarClassData = await this.getAvatarClass(accounts.avatarClass);
+
+    const avatarClassMintAta = splToken.getAssociatedTokenAddressSync(
+      avatarClassData.mint,
+      accounts.authority
+    );
+
+    const tx = await this.program.methods
+      .removeStatTemplate(args)
+      .accounts({
+        avatarClass: accounts.avatarClass,
+        avatarClassMintAta: avatarClassMintAta,
+        authority: accounts.authority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.authority);
+
+    return tx;
+  }
+
+  // permissionless, recomputes the cached stats from the equipped traits and class templates
+  async refreshAvatarStats(
+    accounts: RefreshAvatarStatsAccounts
+  ): Promise<anchor.web3.Transaction> {
+    const avatarData = await this.getAvatar(accounts.avatar);
+
+    const tx = await this.program.methods
+      .refreshAvatarStats()
+      .accounts({
+        avatarClass: avatarData.avatarClass,
+        avatar: accounts.avatar,
+        payer: accounts.payer,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.payer);
+
+    return tx;
+  }
+
   async createPaymentMethod(
     accounts: CreatePaymentMethodAccounts,
     args: CreatePaymentMethodArgs
@@ -1155,12 +1258,19 @@ export class AvatarClient {
         traitAccount = traitData.traitAddress;
       }
 
+      const variantOptionCounter = await this.getVariantOptionCounterAddress(
+        avatarData.avatarClass,
+        args.updateTarget,
+        traitData
+      );
+
       tx = await this.program.methods
         .beginVariantUpdate(ixArgs)
         .accounts({
           avatarClass: avatarData.avatarClass,
           updateState: updateState,
           traitAccount: traitAccount,
+          variantOptionCounter: variantOptionCounter,
           avatar: accounts.avatar,
           avatarMintAta: avatarAuthorityAta,
           authority: avatarAuthority,
@@ -1175,6 +1285,41 @@ export class AvatarClient {
     return tx;
   }
 
+  // return the variant option counter address if the selected option has a supply cap
+  private async getVariantOptionCounterAddress(
+    avatarClass: anchor.web3.PublicKey,
+    updateTarget: UpdateTarget | UpdateTargetSelection,
+    traitData: Trait | null
+  ): Promise<anchor.web3.PublicKey | null> {
+    let option: VariantOption;
+    if (
+      updateTarget instanceof UpdateTargetTraitVariant ||
+      updateTarget instanceof UpdateTargetSelectionTraitVariant
+    ) {
+      option = traitData.findVariantOption(
+        updateTarget.variantId,
+        updateTarget.optionId
+      );
+    } else if (
+      updateTarget instanceof UpdateTargetClassVariant ||
+      updateTarget instanceof UpdateTargetSelectionClassVariant
+    ) {
+      const avatarClassData = await this.getAvatarClass(avatarClass);
+      option = avatarClassData.findVariantOption(
+        updateTarget.variantId,
+        updateTarget.optionId
+      );
+    } else {
+      return null;
+    }
+
+    if (!option.hasSupplyCap()) {
+      return null;
+    }
+
+    return variantOptionCounterPDA(avatarClass, updateTarget);
+  }
+
   async cancelUpdate(
     accounts: CancelUpdateAccounts,
     args: CancelUpdateArgs
@@ -1399,7 +1544,10 @@ export class AvatarClient {
           });
           txns.push(tx);
         }
-        if (paymentMethodData.action instanceof TransferPaymentAction) {
+        if (
+          paymentMethodData.action instanceof TransferPaymentAction ||
+          paymentMethodData.action instanceof SplitPaymentAction
+        ) {
           const transferAccounts: TransferPaymentAccounts = {
             avatar: updateStateData.avatar,
             authority: accounts.authority,
@@ -1415,6 +1563,21 @@ export class AvatarClient {
         }
       }
 
+      // if native
+      if (paymentMethodData.assetClass instanceof NativePaymentAssetClass) {
+        const transferNativeAccounts: TransferPaymentNativeAccounts = {
+          avatar: updateStateData.avatar,
+          authority: accounts.authority,
+          paymentMethod: paymentMethodAddress,
+        };
+
+        const tx = await this.transferPaymentNative(transferNativeAccounts, {
+          amount: args.amount,
+          updateTarget: args.updateTarget,
+        });
+        txns.push(tx);
+      }
+
       // if non fungible
       if (
         paymentMethodData.assetClass instanceof NonFungiblePaymentAssetClass
@@ -1520,35 +1683,95 @@ export class AvatarClient {
       avatarAuthority
     );
 
-    const paymentDestination = splToken.getAssociatedTokenAddressSync(
-      accounts.paymentMint,
-      (paymentMethodData.action as TransferPaymentAction).treasury
+    // split payments send to every recipient ata, passed as remaining accounts
+    const treasuries: anchor.web3.PublicKey[] =
+      paymentMethodData.action instanceof SplitPaymentAction
+        ? paymentMethodData.action.recipients.map(
+            (recipient) => recipient.treasury
+          )
+        : [(paymentMethodData.action as TransferPaymentAction).treasury];
+
+    const destinations = treasuries.map((treasury) =>
+      splToken.getAssociatedTokenAddressSync(accounts.paymentMint, treasury)
     );
 
-    const initDestinationAtaIx =
+    const initDestinationAtaIxns = treasuries.map((treasury, i) =>
       splToken.createAssociatedTokenAccountIdempotentInstruction(
         this.provider.publicKey,
-        paymentDestination,
-        (paymentMethodData.action as TransferPaymentAction).treasury,
+        destinations[i],
+        treasury,
         accounts.paymentMint,
         splToken.TOKEN_PROGRAM_ID,
         splToken.ASSOCIATED_TOKEN_PROGRAM_ID
-      );
+      )
+    );
+
+    const isSplit = paymentMethodData.action instanceof SplitPaymentAction;
 
     const tx = await this.program.methods
-      .transferPayment(args)
-      .preInstructions([initDestinationAtaIx])
+      .transferPayment({ amount: args.amount })
+      .preInstructions(initDestinationAtaIxns)
       .accounts({
         updateState: updateState,
         paymentMethod: accounts.paymentMethod,
         paymentMint: accounts.paymentMint,
         paymentSource: paymentSource,
-        paymentDestination: paymentDestination,
+        paymentDestination: isSplit ? null : destinations[0],
         authority: accounts.authority,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: splToken.TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts(
+        isSplit
+          ? destinations.map((destination) => ({
+              pubkey: destination,
+              isSigner: false,
+              isWritable: true,
+            }))
+          : []
+      )
+      .transaction();
+
+    await this.setPayer(tx, accounts.authority);
+
+    return tx;
+  }
+
+  async transferPaymentNative(
+    accounts: TransferPaymentNativeAccounts,
+    args: TransferPaymentNativeArgs
+  ): Promise<anchor.web3.Transaction> {
+    const paymentMethodData = await this.getPaymentMethod(
+      accounts.paymentMethod
+    );
+
+    const updateState = updateStatePDA(accounts.avatar, args.updateTarget);
+
+    const isSplit = paymentMethodData.action instanceof SplitPaymentAction;
+
+    const tx = await this.program.methods
+      .transferPaymentNative({ amount: args.amount })
+      .accounts({
+        updateState: updateState,
+        paymentMethod: accounts.paymentMethod,
+        paymentDestination: isSplit
+          ? null
+          : (paymentMethodData.action as TransferPaymentAction).treasury,
+        authority: accounts.authority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        isSplit
+          ? (paymentMethodData.action as SplitPaymentAction).recipients.map(
+              (recipient) => ({
+                pubkey: recipient.treasury,
+                isSigner: false,
+                isWritable: true,
+              })
+            )
+          : []
+      )
       .transaction();
 
     await this.setPayer(tx, accounts.authority);
@@ -2045,6 +2268,55 @@ export class AvatarClient {
     return oldAvatarData;
   }
 
+  async migrateTraitAccount(
+    accounts: MigrateTraitAccountAccounts
+  ): Promise<anchor.web3.Transaction> {
+    const authority = new anchor.web3.PublicKey(
+      "3kkFMBB6Hg3HTR4e6c9CKaPrUUcrjA694aGTJrbVG675"
+    );
+
+    const tx = await this.program.methods
+      .migrateTraitAccount()
+      .accounts({
+        traitAccount: accounts.traitAccount,
+        authority: authority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, authority);
+
+    return tx;
+  }
+
+  async getTraitPreMigration(
+    traitAccount: anchor.web3.PublicKey
+  ): Promise<any> {
+    const accountInfo = await this.provider.connection.getAccountInfo(
+      traitAccount
+    );
+    const coder = new anchor.BorshAccountsCoder(Idls.AvatarIDL);
+    const oldTraitData = coder.decodeUnchecked("oldTrait", accountInfo.data);
+    return oldTraitData;
+  }
+
+  async migrateUpdateState(
+    accounts: MigrateUpdateStateAccounts
+  ): Promise<anchor.web3.Transaction> {
+    const tx = await this.program.methods
+      .migrateUpdateState()
+      .accounts({
+        updateState: accounts.updateState,
+        payer: accounts.payer,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.payer);
+
+    return tx;
+  }
+
   async getAvatar(
     avatar: anchor.web3.PublicKey,
     getInProgressUpdates?: boolean
@@ -2071,11 +2343,25 @@ export class AvatarClient {
             paymentMethod: vsRaw.paymentDetails.paymentMethod,
             amount: amount,
             uiAmount: uiAmount,
+            alternatives: parsePaymentAlternatives(
+              vsRaw.paymentDetails.alternatives
+            ),
           };
         }
 
         vs.push(
-          new VariantOption(vsRaw.variantId, vsRaw.optionId, paymentDetails, tg)
+          new VariantOption(
+            vsRaw.variantId,
+            vsRaw.optionId,
+            paymentDetails,
+            tg,
+            undefined,
+            {
+              startTime: vsRaw.startTime,
+              endTime: vsRaw.endTime,
+              maxSelections: vsRaw.maxSelections,
+            }
+          )
         );
       }
       let tg = null;
@@ -2089,6 +2375,8 @@ export class AvatarClient {
         traitAddress: td.traitAddress,
         variantSelection: vs,
         traitGate: tg,
+        rental: td.rental ? new anchor.web3.PublicKey(td.rental) : null,
+        statModifiers: parseStatModifiers(td.statModifiers),
       });
     }
 
@@ -2113,6 +2401,9 @@ export class AvatarClient {
           paymentMethod: variant.paymentDetails.paymentMethod,
           amount: amount,
           uiAmount: uiAmount,
+          alternatives: parsePaymentAlternatives(
+            variant.paymentDetails.alternatives
+          ),
         };
       }
 
@@ -2121,7 +2412,13 @@ export class AvatarClient {
           variant.variantId,
           variant.optionId,
           paymentDetails,
-          tg
+          tg,
+          undefined,
+          {
+            startTime: variant.startTime,
+            endTime: variant.endTime,
+            maxSelections: variant.maxSelections,
+          }
         )
       );
     }
@@ -2132,7 +2429,9 @@ export class AvatarClient {
       avatarDataRaw.mint,
       "",
       traits,
-      variants
+      variants,
+      parseStats(avatarDataRaw.stats),
+      (avatarDataRaw.namespaces as any[]) ?? []
     );
 
     if (getInProgressUpdates) {
@@ -2172,6 +2471,9 @@ export class AvatarClient {
             paymentMethod: optRaw.paymentDetails.paymentMethod,
             amount: amount,
             uiAmount: uiAmount,
+            alternatives: parsePaymentAlternatives(
+              optRaw.paymentDetails.alternatives
+            ),
           };
         }
 
@@ -2180,7 +2482,13 @@ export class AvatarClient {
             optRaw.variantId,
             optRaw.optionId,
             paymentDetails,
-            tg
+            tg,
+            undefined,
+            {
+              startTime: optRaw.startTime,
+              endTime: optRaw.endTime,
+              maxSelections: optRaw.maxSelections,
+            }
           )
         );
       }
@@ -2212,7 +2520,9 @@ export class AvatarClient {
       new anchor.BN(avatarClassData.paymentIndex),
       attributeMetadata,
       variantMetadata,
-      avatarClassData.globalRenderingConfigUri
+      avatarClassData.globalRenderingConfigUri,
+      parseStatTemplates(avatarClassData.statTemplates),
+      (avatarClassData.namespaces as any[]) ?? []
     );
 
     return avatarClass;
@@ -2254,7 +2564,13 @@ export class AvatarClient {
             optRaw.variantId,
             optRaw.optionId,
             paymentDetails,
-            tg
+            tg,
+            undefined,
+            {
+              startTime: optRaw.startTime,
+              endTime: optRaw.endTime,
+              maxSelections: optRaw.maxSelections,
+            }
           )
         );
       }
@@ -2328,7 +2644,8 @@ export class AvatarClient {
       variantMetadata,
       equipPaymentDetailsExpanded,
       removePaymentDetailsExpanded,
-      tg
+      tg,
+      parseStatModifiers(traitData.statModifiers)
     );
   }
 
@@ -2337,9 +2654,16 @@ export class AvatarClient {
   ): Promise<UpdateState | null> {
     let updateStateRaw;
     try {
-      updateStateRaw = await this.program.account.updateState.fetch(
+      const accountInfo = await this.provider.connection.getAccountInfo(
         updateState
       );
+      // update states begun before the version byte start with the initialized flag,
+      // they read back without payment alternatives until they are migrated
+      const coder = new anchor.BorshAccountsCoder(Idls.AvatarIDL);
+      updateStateRaw =
+        accountInfo.data[8] === UPDATE_STATE_LEGACY_VERSION
+          ? coder.decodeUnchecked("oldUpdateState", accountInfo.data)
+          : coder.decode("updateState", accountInfo.data);
     } catch (_e) {
       return null;
     }
@@ -2347,7 +2671,10 @@ export class AvatarClient {
     return new UpdateState(
       updateStateRaw.initialized,
       updateStateRaw.avatar,
-      parseUpdateTarget(updateStateRaw.target)
+      parseUpdateTarget(
+        updateStateRaw.target,
+        updateStateRaw.paymentAlternatives
+      )
     );
   }
 
@@ -2398,6 +2725,215 @@ export class AvatarClient {
     };
   }
 
+  async createTraitRental(
+    accounts: CreateTraitRentalAccounts,
+    args: CreateTraitRentalArgs
+  ): Promise<anchor.web3.Transaction> {
+    const trait = traitPDA(accounts.avatarClass, accounts.traitMint);
+    const traitRental = traitRentalPDA(trait, accounts.lender);
+
+    const traitSource = splToken.getAssociatedTokenAddressSync(
+      accounts.traitMint,
+      accounts.lender
+    );
+
+    const traitEscrow = splToken.getAssociatedTokenAddressSync(
+      accounts.traitMint,
+      traitRental,
+      true
+    );
+
+    const tx = await this.program.methods
+      .createTraitRental(args)
+      .accounts({
+        traitAccount: trait,
+        traitMint: accounts.traitMint,
+        traitRental: traitRental,
+        traitSource: traitSource,
+        traitEscrow: traitEscrow,
+        lender: accounts.lender,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.lender);
+
+    return tx;
+  }
+
+  async cancelTraitRental(
+    accounts: CancelTraitRentalAccounts
+  ): Promise<anchor.web3.Transaction> {
+    const trait = traitPDA(accounts.avatarClass, accounts.traitMint);
+    const traitRental = traitRentalPDA(trait, accounts.lender);
+
+    const traitEscrow = splToken.getAssociatedTokenAddressSync(
+      accounts.traitMint,
+      traitRental,
+      true
+    );
+
+    const traitDestination = splToken.getAssociatedTokenAddressSync(
+      accounts.traitMint,
+      accounts.lender
+    );
+
+    const tx = await this.program.methods
+      .cancelTraitRental()
+      .accounts({
+        traitAccount: trait,
+        traitMint: accounts.traitMint,
+        traitRental: traitRental,
+        traitEscrow: traitEscrow,
+        traitDestination: traitDestination,
+        lender: accounts.lender,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.lender);
+
+    return tx;
+  }
+
+  async rentTrait(
+    accounts: RentTraitAccounts
+  ): Promise<anchor.web3.Transaction> {
+    const avatarData = await this.getAvatar(accounts.avatar);
+
+    const trait = traitPDA(avatarData.avatarClass, accounts.traitMint);
+    const traitConflicts = traitConflictsPDA(avatarData.avatarClass, trait);
+    const traitRental = traitRentalPDA(trait, accounts.lender);
+
+    const avatarMintAta = splToken.getAssociatedTokenAddressSync(
+      avatarData.mint,
+      accounts.renter
+    );
+
+    const traitEscrow = splToken.getAssociatedTokenAddressSync(
+      accounts.traitMint,
+      traitRental,
+      true
+    );
+
+    const avatarTraitAta = splToken.getAssociatedTokenAddressSync(
+      accounts.traitMint,
+      accounts.avatar,
+      true
+    );
+
+    const tx = await this.program.methods
+      .rentTrait()
+      .accounts({
+        avatarClass: avatarData.avatarClass,
+        avatar: accounts.avatar,
+        avatarMintAta: avatarMintAta,
+        traitAccount: trait,
+        traitMint: accounts.traitMint,
+        traitConflicts: traitConflicts,
+        traitRental: traitRental,
+        traitEscrow: traitEscrow,
+        avatarTraitAta: avatarTraitAta,
+        lender: accounts.lender,
+        renter: accounts.renter,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.renter);
+
+    return tx;
+  }
+
+  // permissionless, returns an expired rental to the lender
+  async reclaimTraitRental(
+    accounts: ReclaimTraitRentalAccounts
+  ): Promise<anchor.web3.Transaction> {
+    const traitRentalData = await this.getTraitRental(accounts.traitRental);
+    if (traitRentalData.avatar === null) {
+      throw new Error(`trait rental is not rented: ${accounts.traitRental}`);
+    }
+
+    const avatarData = await this.getAvatar(traitRentalData.avatar);
+    const traitData = await this.getTrait(traitRentalData.traitAccount);
+
+    const avatarAuthority = await this.getNftHolder(
+      this.provider.connection,
+      avatarData.mint
+    );
+    const avatarMintAta = splToken.getAssociatedTokenAddressSync(
+      avatarData.mint,
+      avatarAuthority
+    );
+
+    const avatarTraitAta = splToken.getAssociatedTokenAddressSync(
+      traitData.traitMint,
+      traitRentalData.avatar,
+      true
+    );
+
+    const traitDestination = splToken.getAssociatedTokenAddressSync(
+      traitData.traitMint,
+      traitRentalData.lender
+    );
+
+    const tx = await this.program.methods
+      .reclaimTraitRental()
+      .accounts({
+        avatarClass: avatarData.avatarClass,
+        avatar: traitRentalData.avatar,
+        avatarMintAta: avatarMintAta,
+        avatarAuthority: avatarAuthority,
+        traitAccount: traitRentalData.traitAccount,
+        traitMint: traitData.traitMint,
+        traitRental: accounts.traitRental,
+        avatarTraitAta: avatarTraitAta,
+        traitDestination: traitDestination,
+        lender: traitRentalData.lender,
+        payer: accounts.payer,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .transaction();
+
+    await this.setPayer(tx, accounts.payer);
+
+    return tx;
+  }
+
+  async getTraitRental(
+    traitRental: anchor.web3.PublicKey
+  ): Promise<TraitRental> {
+    const traitRentalRaw = await this.program.account.traitRental.fetch(
+      traitRental
+    );
+
+    return {
+      avatarClass: new anchor.web3.PublicKey(traitRentalRaw.avatarClass),
+      traitAccount: new anchor.web3.PublicKey(traitRentalRaw.traitAccount),
+      lender: new anchor.web3.PublicKey(traitRentalRaw.lender),
+      duration: new anchor.BN(traitRentalRaw.duration),
+      fee: new anchor.BN(traitRentalRaw.fee),
+      avatar: traitRentalRaw.avatar
+        ? new anchor.web3.PublicKey(traitRentalRaw.avatar)
+        : null,
+      expiry: traitRentalRaw.expiry
+        ? new anchor.BN(traitRentalRaw.expiry)
+        : null,
+    };
+  }
+
   async getNftHolder(
     connection: anchor.web3.Connection,
     mint: anchor.web3.PublicKey
@@ -2629,6 +3165,8 @@ export class AvatarClient {
       const uiAmount =
         div.toNumber() + mod.toNumber() / Math.pow(10, mintData.decimals);
       return uiAmount;
+    } else if (paymentMethodData.assetClass instanceof NativePaymentAssetClass) {
+      return amount.toNumber() / anchor.web3.LAMPORTS_PER_SOL;
     }
 
     throw new Error(
@@ -2691,6 +3229,35 @@ export function updateStatePDA(
   )[0];
 }
 
+export function variantOptionCounterPDA(
+  avatarClass: anchor.web3.PublicKey,
+  updateTarget: UpdateTarget | UpdateTargetSelection
+): anchor.web3.PublicKey {
+  const hash = hashUpdateTarget(updateTarget);
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(VARIANT_OPTION_COUNTER_PREFIX),
+      avatarClass.toBuffer(),
+      hash,
+    ],
+    Constants.ProgramIds.AVATAR_ID
+  )[0];
+}
+
+export function traitRentalPDA(
+  traitAccount: anchor.web3.PublicKey,
+  lender: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(TRAIT_RENTAL_PREFIX),
+      traitAccount.toBuffer(),
+      lender.toBuffer(),
+    ],
+    Constants.ProgramIds.AVATAR_ID
+  )[0];
+}
+
 export function verifiedPaymentMintPDA(
   paymentMethod: anchor.web3.PublicKey,
   paymentMint: anchor.web3.PublicKey
@@ -1,6 +1,7 @@
 import * as anchor from "@project-serum/anchor-0.26.0";
 import { sha256 } from "js-sha256";
 import { AvatarClient, updateStatePDA } from "./avatar";
+import { NamespaceAndIndex } from "../../../state/common";
 
 export const AVATAR_CLASS_PREFIX = "avatar_class";
 export const AVATAR_PREFIX = "avatar";
@@ -9,6 +10,8 @@ export const TRAIT_CONFLICTS_PREFIX = "trait_conflicts";
 export const PAYMENT_METHOD_PREFIX = "payment_method";
 export const UPDATE_STATE_PREFIX = "update_state";
 export const VERIFIED_PAYMENT_MINT_PREFIX = "verified_payment_mint";
+export const VARIANT_OPTION_COUNTER_PREFIX = "variant_option_counter";
+export const TRAIT_RENTAL_PREFIX = "trait_rental";
 
 export interface CreateAvatarClassAccounts {
   avatarClassMint: anchor.web3.PublicKey;
@@ -19,6 +22,66 @@ export interface CreateAvatarClassArgs {
   attributeMetadata: AttributeMetadata[];
   variantMetadata: VariantMetadata[];
   globalRenderingConfigUri: string;
+  statTemplates?: StatTemplate[];
+}
+
+// class level definition of a numeric stat, values are clamped to the min and max
+export interface StatTemplate {
+  id: number;
+  name: string;
+  baseValue: anchor.BN;
+  minValue: anchor.BN;
+  maxValue: anchor.BN;
+}
+
+// applied to a stat while the trait is equipped, multiply is in basis points (10_000 = 1x)
+export interface StatModifier {
+  statId: number;
+  add: anchor.BN;
+  multiply: number;
+}
+
+// cached stat value on the avatar
+export interface Stat {
+  id: number;
+  value: anchor.BN;
+}
+
+export function parseStatTemplates(data: any): StatTemplate[] {
+  if (!data) {
+    return [];
+  }
+
+  return data.map((statTemplate: any) => ({
+    id: statTemplate.id,
+    name: statTemplate.name,
+    baseValue: new anchor.BN(statTemplate.baseValue),
+    minValue: new anchor.BN(statTemplate.minValue),
+    maxValue: new anchor.BN(statTemplate.maxValue),
+  }));
+}
+
+export function parseStatModifiers(data: any): StatModifier[] {
+  if (!data) {
+    return [];
+  }
+
+  return data.map((statModifier: any) => ({
+    statId: statModifier.statId,
+    add: new anchor.BN(statModifier.add),
+    multiply: statModifier.multiply,
+  }));
+}
+
+export function parseStats(data: any): Stat[] {
+  if (!data) {
+    return [];
+  }
+
+  return data.map((stat: any) => ({
+    id: stat.id,
+    value: new anchor.BN(stat.value),
+  }));
 }
 
 export class VariantMetadata {
@@ -116,6 +179,7 @@ export interface CreateTraitArgs {
   equipPaymentDetails?: PaymentDetails;
   removePaymentDetails?: PaymentDetails;
   traitGate?: TraitGate;
+  statModifiers?: StatModifier[];
 }
 
 export interface BootTraitsAccounts {
@@ -177,6 +241,7 @@ export interface UpdateTraitArgs {
   equipPaymentDetails?: PaymentDetails;
   removePaymentDetails?: PaymentDetails;
   traitGate?: TraitGate;
+  statModifiers?: StatModifier[];
 }
 
 export interface UpdateClassVariantMetadataAccounts {
@@ -197,6 +262,29 @@ export interface UpdateAttributeMetadataArgs {
   attributeMetadata: AttributeMetadata;
 }
 
+export interface UpdateStatTemplateAccounts {
+  avatarClass: anchor.web3.PublicKey;
+  authority: anchor.web3.PublicKey;
+}
+
+export interface UpdateStatTemplateArgs {
+  statTemplate: StatTemplate;
+}
+
+export interface RemoveStatTemplateAccounts {
+  avatarClass: anchor.web3.PublicKey;
+  authority: anchor.web3.PublicKey;
+}
+
+export interface RemoveStatTemplateArgs {
+  statId: number;
+}
+
+export interface RefreshAvatarStatsAccounts {
+  avatar: anchor.web3.PublicKey;
+  payer: anchor.web3.PublicKey;
+}
+
 export interface CreatePaymentMethodAccounts {
   avatarClass: anchor.web3.PublicKey;
   authority: anchor.web3.PublicKey;
@@ -248,6 +336,17 @@ export interface TransferPaymentArgs {
   updateTarget: UpdateTargetSelection;
 }
 
+export interface TransferPaymentNativeAccounts {
+  avatar: anchor.web3.PublicKey;
+  paymentMethod: anchor.web3.PublicKey;
+  authority: anchor.web3.PublicKey;
+}
+
+export interface TransferPaymentNativeArgs {
+  amount: anchor.BN;
+  updateTarget: UpdateTargetSelection;
+}
+
 export interface BurnPaymentAccounts {
   avatar: anchor.web3.PublicKey;
   paymentMint: anchor.web3.PublicKey;
@@ -349,6 +448,54 @@ export interface MigrateAvatarAccountAccounts {
   avatar: anchor.web3.PublicKey;
 }
 
+export interface MigrateTraitAccountAccounts {
+  traitAccount: anchor.web3.PublicKey;
+}
+
+export interface MigrateUpdateStateAccounts {
+  updateState: anchor.web3.PublicKey;
+  payer: anchor.web3.PublicKey;
+}
+
+export interface CreateTraitRentalAccounts {
+  avatarClass: anchor.web3.PublicKey;
+  traitMint: anchor.web3.PublicKey;
+  lender: anchor.web3.PublicKey;
+}
+
+export interface CreateTraitRentalArgs {
+  duration: anchor.BN;
+  fee: anchor.BN;
+}
+
+export interface CancelTraitRentalAccounts {
+  avatarClass: anchor.web3.PublicKey;
+  traitMint: anchor.web3.PublicKey;
+  lender: anchor.web3.PublicKey;
+}
+
+export interface RentTraitAccounts {
+  avatar: anchor.web3.PublicKey;
+  traitMint: anchor.web3.PublicKey;
+  lender: anchor.web3.PublicKey;
+  renter: anchor.web3.PublicKey;
+}
+
+export interface ReclaimTraitRentalAccounts {
+  traitRental: anchor.web3.PublicKey;
+  payer: anchor.web3.PublicKey;
+}
+
+export interface TraitRental {
+  avatarClass: anchor.web3.PublicKey;
+  traitAccount: anchor.web3.PublicKey;
+  lender: anchor.web3.PublicKey;
+  duration: anchor.BN;
+  fee: anchor.BN;
+  avatar: anchor.web3.PublicKey | null;
+  expiry: anchor.BN | null;
+}
+
 export interface Attribute {
   id: number;
   trait: anchor.web3.PublicKey | null;
@@ -395,25 +542,38 @@ export interface Variant {
   optionId: string;
 }
 
+export interface VariantOptionAvailability {
+  startTime?: anchor.BN | null;
+  endTime?: anchor.BN | null;
+  maxSelections?: anchor.BN | null;
+}
+
 export class VariantOption {
   name?: string; // not written to chain
   variantId: string;
   optionId: string;
   paymentDetails: PaymentDetails | null;
   traitGate: TraitGate | null;
+  startTime: anchor.BN | null;
+  endTime: anchor.BN | null;
+  maxSelections: anchor.BN | null;
 
   constructor(
     variantId: string,
     optionId: string,
     paymentDetails?: PaymentDetails,
     traitGate?: TraitGate,
-    name?: string
+    name?: string,
+    availability?: VariantOptionAvailability
   ) {
     this.name = name;
     this.variantId = variantId;
     this.optionId = optionId;
     this.paymentDetails = null;
     this.traitGate = null;
+    this.startTime = null;
+    this.endTime = null;
+    this.maxSelections = null;
 
     if (paymentDetails) {
       this.paymentDetails = paymentDetails;
@@ -422,6 +582,17 @@ export class VariantOption {
     if (traitGate) {
       this.traitGate = traitGate;
     }
+
+    if (availability) {
+      this.startTime = availability.startTime ?? null;
+      this.endTime = availability.endTime ?? null;
+      this.maxSelections = availability.maxSelections ?? null;
+    }
+  }
+
+  // supply capped options require a variant option counter account
+  hasSupplyCap(): boolean {
+    return this.maxSelections !== null;
   }
 
   formatForIx(): any {
@@ -433,8 +604,11 @@ export class VariantOption {
     return {
       variantId: this.variantId,
       optionId: this.optionId,
-      paymentDetails: this.paymentDetails,
+      paymentDetails: formatPaymentDetailsForIx(this.paymentDetails),
       traitGate: tg,
+      startTime: this.startTime,
+      endTime: this.endTime,
+      maxSelections: this.maxSelections,
     };
   }
 }
@@ -443,6 +617,40 @@ export interface PaymentDetails {
   paymentMethod: anchor.web3.PublicKey;
   amount: anchor.BN;
   uiAmount?: number;
+  alternatives?: PaymentAlternative[];
+}
+
+// an alternative payment method which can be used instead of the primary payment method
+export interface PaymentAlternative {
+  paymentMethod: anchor.web3.PublicKey;
+  amount: anchor.BN;
+}
+
+export function formatPaymentDetailsForIx(
+  paymentDetails: PaymentDetails | null | undefined
+): any | null {
+  if (!paymentDetails) {
+    return null;
+  }
+
+  return {
+    paymentMethod: paymentDetails.paymentMethod,
+    amount: paymentDetails.amount,
+    alternatives: paymentDetails.alternatives
+      ? paymentDetails.alternatives
+      : [],
+  };
+}
+
+export function parsePaymentAlternatives(data: any): PaymentAlternative[] {
+  if (!data) {
+    return [];
+  }
+
+  return data.map((alternative: any) => ({
+    paymentMethod: new anchor.web3.PublicKey(alternative.paymentMethod),
+    amount: new anchor.BN(alternative.amount, "hex"),
+  }));
 }
 
 export interface PaymentDetailsExpanded {
@@ -456,15 +664,36 @@ export class PaymentState {
   readonly paymentMethod: anchor.web3.PublicKey;
   readonly currentAmount: anchor.BN;
   readonly requiredAmount: anchor.BN;
+  readonly alternatives: PaymentAlternative[];
 
   constructor(
     paymentMethod: anchor.web3.PublicKey,
     currentAmount: anchor.BN,
-    requiredAmount: anchor.BN
+    requiredAmount: anchor.BN,
+    alternatives?: PaymentAlternative[]
   ) {
     this.paymentMethod = paymentMethod;
     this.currentAmount = currentAmount;
     this.requiredAmount = requiredAmount;
+    this.alternatives = alternatives ? alternatives : [];
+  }
+
+  // an alternative can only be chosen before any payment has been made
+  acceptsPaymentMethod(paymentMethod: anchor.web3.PublicKey): boolean {
+    if (this.isPaid()) {
+      return false;
+    }
+
+    if (this.paymentMethod.equals(paymentMethod)) {
+      return true;
+    }
+
+    return (
+      this.currentAmount.isZero() &&
+      this.alternatives.some((alternative) =>
+        alternative.paymentMethod.equals(paymentMethod)
+      )
+    );
   }
 
   isPaid(): boolean {
@@ -532,6 +761,8 @@ export interface TraitData {
   traitAddress: anchor.web3.PublicKey;
   variantSelection: VariantOption[];
   traitGate: TraitGate | null;
+  rental: anchor.web3.PublicKey | null;
+  statModifiers: StatModifier[];
 }
 
 export interface TraitStatus {
@@ -552,6 +783,8 @@ export class AvatarClass {
   readonly attributeMetadata: AttributeMetadata[];
   readonly variantMetadata: VariantMetadata[];
   readonly globalRenderingConfigUri: string;
+  readonly statTemplates: StatTemplate[];
+  readonly namespaces: NamespaceAndIndex[];
 
   constructor(
     mint: anchor.web3.PublicKey,
@@ -559,7 +792,9 @@ export class AvatarClass {
     paymentIndex: anchor.BN,
     attributeMetadata: AttributeMetadata[],
     variantMetadata: VariantMetadata[],
-    globalRenderingConfigUri: string
+    globalRenderingConfigUri: string,
+    statTemplates: StatTemplate[] = [],
+    namespaces: NamespaceAndIndex[] = []
   ) {
     this.mint = mint;
     this.traitIndex = traitIndex;
@@ -567,6 +802,8 @@ export class AvatarClass {
     this.attributeMetadata = attributeMetadata;
     this.variantMetadata = variantMetadata;
     this.globalRenderingConfigUri = globalRenderingConfigUri;
+    this.statTemplates = statTemplates;
+    this.namespaces = namespaces;
   }
 
   findVariantOption(variantId: string, optionId: string): VariantOption {
@@ -595,6 +832,8 @@ export class Avatar {
   readonly imageUri: string;
   public traits: TraitData[];
   readonly variants: VariantOption[];
+  readonly stats: Stat[];
+  readonly namespaces: NamespaceAndIndex[];
   public updateStates: UpdateState[] = [];
 
   constructor(
@@ -603,7 +842,9 @@ export class Avatar {
     mint: anchor.web3.PublicKey,
     imageUri: string,
     traits: TraitData[],
-    variants: VariantOption[]
+    variants: VariantOption[],
+    stats: Stat[] = [],
+    namespaces: NamespaceAndIndex[] = []
   ) {
     this.address = address;
     this.avatarClass = avatarClass;
@@ -611,6 +852,18 @@ export class Avatar {
     this.imageUri = imageUri;
     this.traits = traits;
     this.variants = variants;
+    this.stats = stats;
+    this.namespaces = namespaces;
+  }
+
+  // cached value of the stat, null if the avatar class doesn't define it
+  getStat(statId: number): anchor.BN | null {
+    const stat = this.stats.find((stat) => stat.id === statId);
+    if (stat === undefined) {
+      return null;
+    }
+
+    return stat.value;
   }
 
   // true if the provided attribute ids are not currently occupied
@@ -693,6 +946,7 @@ export class Trait {
   readonly equipPaymentDetails: PaymentDetailsExpanded | null;
   readonly removePaymentDetails: PaymentDetailsExpanded | null;
   readonly traitGate: TraitGate | null;
+  readonly statModifiers: StatModifier[];
 
   constructor(
     id: number,
@@ -705,7 +959,8 @@ export class Trait {
     variantMetadata: VariantMetadata[],
     equipPaymentDetails: PaymentDetailsExpanded | null,
     removePaymentDetails: PaymentDetailsExpanded | null,
-    traitGate: TraitGate
+    traitGate: TraitGate,
+    statModifiers: StatModifier[] = []
   ) {
     this.id = id;
     this.traitAddress = traitAddress;
@@ -718,6 +973,25 @@ export class Trait {
     this.equipPaymentDetails = equipPaymentDetails;
     this.removePaymentDetails = removePaymentDetails;
     this.traitGate = traitGate;
+    this.statModifiers = statModifiers;
+  }
+
+  findVariantOption(variantId: string, optionId: string): VariantOption {
+    const variantMetadata = this.variantMetadata.find(
+      (vm) => vm.id === variantId
+    );
+    if (variantMetadata === undefined) {
+      throw new Error(`variantId ${variantId} not found`);
+    }
+
+    const option = variantMetadata.options.find(
+      (opt) => opt.optionId === optionId
+    );
+    if (option === undefined) {
+      throw new Error(`optionId ${optionId} not found`);
+    }
+
+    return option;
   }
 
   isValidVariant(variantId: string, optionId: string): boolean {
@@ -751,6 +1025,10 @@ export class PaymentMethod {
   }
 }
 
+// mirrors UpdateState::LEGACY_VERSION, the leading initialized flag
+// of update states begun before the version byte
+export const UPDATE_STATE_LEGACY_VERSION = 1;
+
 export class UpdateState {
   initialized: boolean;
   avatar: anchor.web3.PublicKey;
@@ -811,14 +1089,19 @@ export type UpdateTarget =
   | UpdateTargetRemoveTrait
   | UpdateTargetSwapTrait;
 
-export function parseUpdateTarget(data: any): UpdateTarget {
+// payment alternatives are stored after the update target on the update state
+export function parseUpdateTarget(
+  data: any,
+  paymentAlternatives?: any
+): UpdateTarget {
   if ("classVariant" in data) {
     let paymentState: PaymentState | undefined = undefined;
     if (data.classVariant.paymentState) {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.classVariant.paymentState.paymentMethod),
         new anchor.BN(data.classVariant.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.classVariant.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.classVariant.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(paymentAlternatives?.payment)
       );
     }
     return new UpdateTargetClassVariant(
@@ -834,7 +1117,8 @@ export function parseUpdateTarget(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.traitVariant.paymentState.paymentMethod),
         new anchor.BN(data.traitVariant.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.traitVariant.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.traitVariant.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(paymentAlternatives?.payment)
       );
     }
     return new UpdateTargetTraitVariant(
@@ -851,7 +1135,8 @@ export function parseUpdateTarget(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.equipTrait.paymentState.paymentMethod),
         new anchor.BN(data.equipTrait.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.equipTrait.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.equipTrait.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(paymentAlternatives?.payment)
       );
     }
     return new UpdateTargetEquipTrait(
@@ -866,7 +1151,8 @@ export function parseUpdateTarget(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.removeTrait.paymentState.paymentMethod),
         new anchor.BN(data.removeTrait.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.removeTrait.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.removeTrait.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(paymentAlternatives?.payment)
       );
     }
     return new UpdateTargetRemoveTrait(
@@ -883,7 +1169,8 @@ export function parseUpdateTarget(data: any): UpdateTarget {
           data.swapTrait.equipPaymentState.paymentMethod
         ),
         new anchor.BN(data.swapTrait.equipPaymentState.currentAmount, "hex"),
-        new anchor.BN(data.swapTrait.equipPaymentState.requiredAmount, "hex")
+        new anchor.BN(data.swapTrait.equipPaymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(paymentAlternatives?.payment)
       );
     }
 
@@ -894,7 +1181,8 @@ export function parseUpdateTarget(data: any): UpdateTarget {
           data.swapTrait.removePaymentState.paymentMethod
         ),
         new anchor.BN(data.swapTrait.removePaymentState.currentAmount, "hex"),
-        new anchor.BN(data.swapTrait.removePaymentState.requiredAmount, "hex")
+        new anchor.BN(data.swapTrait.removePaymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(paymentAlternatives?.removePayment)
       );
     }
     return new UpdateTargetSwapTrait(
@@ -913,7 +1201,8 @@ export function parseUpdateTargetByKind(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.paymentState.paymentMethod),
         new anchor.BN(data.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(data.paymentState.alternatives)
       );
     }
     return new UpdateTargetClassVariant(
@@ -929,7 +1218,8 @@ export function parseUpdateTargetByKind(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.traitVariant.paymentState.paymentMethod),
         new anchor.BN(data.traitVariant.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.traitVariant.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.traitVariant.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(data.traitVariant.paymentState.alternatives)
       );
     }
     return new UpdateTargetTraitVariant(
@@ -946,7 +1236,8 @@ export function parseUpdateTargetByKind(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.equipTrait.paymentState.paymentMethod),
         new anchor.BN(data.equipTrait.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.equipTrait.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.equipTrait.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(data.equipTrait.paymentState.alternatives)
       );
     }
     return new UpdateTargetEquipTrait(
@@ -961,7 +1252,8 @@ export function parseUpdateTargetByKind(data: any): UpdateTarget {
       paymentState = new PaymentState(
         new anchor.web3.PublicKey(data.paymentState.paymentMethod),
         new anchor.BN(data.paymentState.currentAmount, "hex"),
-        new anchor.BN(data.paymentState.requiredAmount, "hex")
+        new anchor.BN(data.paymentState.requiredAmount, "hex"),
+        parsePaymentAlternatives(data.paymentState.alternatives)
       );
     }
     return new UpdateTargetRemoveTrait(
@@ -1315,7 +1607,8 @@ export class UpdateTargetSelectionSwapTrait {
 
 export type PaymentAssetClass =
   | FungiblePaymentAssetClass
-  | NonFungiblePaymentAssetClass;
+  | NonFungiblePaymentAssetClass
+  | NativePaymentAssetClass;
 
 export function parsePaymentAssetClass(jsonPayload: any): PaymentAssetClass {
   if ("fungible" in jsonPayload) {
@@ -1329,6 +1622,10 @@ export function parsePaymentAssetClass(jsonPayload: any): PaymentAssetClass {
       new anchor.web3.PublicKey(jsonPayload.nonFungible.mints)
     );
   }
+
+  if ("native" in jsonPayload) {
+    return new NativePaymentAssetClass();
+  }
 }
 
 export function parsePaymentAction(jsonPayload: any): PaymentAction {
@@ -1341,6 +1638,15 @@ export function parsePaymentAction(jsonPayload: any): PaymentAction {
   if ("burn" in jsonPayload) {
     return new BurnPaymentAction();
   }
+
+  if ("split" in jsonPayload) {
+    return new SplitPaymentAction(
+      jsonPayload.split.recipients.map((recipient: any) => ({
+        treasury: new anchor.web3.PublicKey(recipient.treasury),
+        basisPoints: recipient.basisPoints,
+      }))
+    );
+  }
 }
 
 export class FungiblePaymentAssetClass {
@@ -1365,7 +1671,16 @@ export class NonFungiblePaymentAssetClass {
   }
 }
 
-export type PaymentAction = BurnPaymentAction | TransferPaymentAction;
+export class NativePaymentAssetClass {
+  format(): any {
+    return { native: {} };
+  }
+}
+
+export type PaymentAction =
+  | BurnPaymentAction
+  | TransferPaymentAction
+  | SplitPaymentAction;
 
 export class BurnPaymentAction {
   format(): any {
@@ -1385,6 +1700,24 @@ export class TransferPaymentAction {
   }
 }
 
+// share of a payment in basis points, all recipients must add up to 10000
+export interface PaymentSplit {
+  treasury: anchor.web3.PublicKey;
+  basisPoints: number;
+}
+
+export class SplitPaymentAction {
+  readonly recipients: PaymentSplit[];
+
+  constructor(recipients: PaymentSplit[]) {
+    this.recipients = recipients;
+  }
+
+  format(): any {
+    return { split: { recipients: this.recipients } };
+  }
+}
+
 export interface TraitConflicts {
   avatarClass: anchor.web3.PublicKey;
   traitAccount: anchor.web3.PublicKey;
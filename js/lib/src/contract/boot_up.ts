@@ -397,6 +397,8 @@ export class BootUp {
           })),
           equipValidation: null,
           addToPackValidation: null,
+          backpack: null,
+          equipmentSets: null,
         },
       },
       metadataUpdateAuthority: null,
@@ -676,6 +678,8 @@ export class BootUp {
           componentRoot: null,
           usages: [],
           components: [],
+          weight: null,
+          propagationVersion: null,
         },
       },
       metadataUpdateAuthority: null,
@@ -974,6 +978,8 @@ export class BootUp {
                 },
               ],
               components: [],
+              weight: null,
+              propagationVersion: null,
             },
           },
           parent: {
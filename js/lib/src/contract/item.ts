@@ -88,6 +88,24 @@ export class ItemProgram extends Program.Program {
     return this.sendWithRetry(instruction, [], options);
   }
 
+  async propagateItemClassInheritance(
+    accounts: ItemInstruction.PropagateItemClassInheritanceAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.propagateItemClassInheritance(
+      accounts
+    );
+    return this.sendWithRetry(instruction, [], options);
+  }
+
+  async migrateItemClassData(
+    accounts: ItemInstruction.MigrateItemClassDataAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.migrateItemClassData(accounts);
+    return this.sendWithRetry(instruction, [], options);
+  }
+
   async createItemEscrow(
     args: ItemInstruction.CreateItemEscrowArgs,
     accounts: ItemInstruction.CreateItemEscrowAccounts,
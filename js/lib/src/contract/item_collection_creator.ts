@@ -315,6 +315,8 @@ export class ItemCollectionCreator {
           componentRoot: null,
           usages: [],
           components: [],
+          weight: null,
+          propagationVersion: null,
         },
       },
       metadataUpdateAuthority: null,
@@ -600,6 +602,8 @@ export class ItemCollectionCreator {
                 },
               ],
               components: [],
+              weight: null,
+              propagationVersion: null,
             },
           },
           parent: {
@@ -227,6 +227,26 @@ export class ItemProgramV2 extends Program.Program {
     return await this.sendWithRetry(ixns, [], options);
   }
 
+  async importItemClassV1(
+    accounts: ItemInstruction.ImportItemClassV1Accounts,
+    args: ItemInstruction.CreateItemClassArgs,
+    options?: SendOptions
+  ): Promise<[web3.PublicKey, Transaction.SendTransactionResult]> {
+    const [itemClass, signers, instructions] =
+      await this.instruction.importItemClassV1(accounts, args);
+    const result = await this.sendWithRetry(instructions, signers, options);
+    return [itemClass, result];
+  }
+
+  async importRecipeV1(
+    accounts: ItemInstruction.ImportRecipeV1Accounts,
+    args: ItemInstruction.ImportRecipeV1Args,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const ix = await this.instruction.importRecipeV1(accounts, args);
+    return await this.sendWithRetry([ix], [], options);
+  }
+
   async migrateItemClassAccount(
     itemClass: web3.PublicKey,
     options?: SendOptions
@@ -315,6 +335,7 @@ export class ItemProgramV2 extends Program.Program {
       recipeIndex: recipeIndex,
       recipes: recipes,
       mode: mode,
+      namespaces: (itemClassData.namespaces as any[]) ?? [],
     };
 
     return data;
@@ -124,4 +124,39 @@ export class MatchesProgram extends Program.Program {
 
     return await this.sendWithRetry(ix.instructions, ix.signers, options);
   }
+
+  async updateMatchEconomics(
+    args: MatchesInstruction.UpdateMatchEconomicsArgs,
+    accounts: MatchesInstruction.UpdateMatchEconomicsAccounts,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const ix = await this.instruction.updateMatchEconomics(args, accounts, {});
+
+    return await this.sendWithRetry(ix.instructions, ix.signers, options);
+  }
+
+  async disbursePrizePool(
+    args: MatchesInstruction.DisbursePrizePoolArgs,
+    accounts: MatchesInstruction.DisbursePrizePoolAccounts,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const ix = await this.instruction.disbursePrizePool(args, accounts, {});
+
+    return await this.sendWithRetry(ix.instructions, ix.signers, options);
+  }
+
+  async disburseTokensByOracleBatch(
+    args: MatchesInstruction.DisburseTokensByOracleBatchArgs,
+    accounts: MatchesInstruction.DisburseTokensByOracleAccounts,
+    additionalArgs: MatchesInstruction.DisburseTokensByOracleBatchAdditionalArgs,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const ix = await this.instruction.disburseTokensByOracleBatch(
+      args,
+      accounts,
+      additionalArgs
+    );
+
+    return await this.sendWithRetry(ix.instructions, ix.signers, options);
+  }
 }
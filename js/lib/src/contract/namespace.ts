@@ -103,9 +103,10 @@ export class NamespaceProgram extends Program.Program {
 
   async joinNamespace(
     accounts: NamespaceInstruction.JoinNamespaceAccounts,
+    args?: NamespaceInstruction.JoinNamespaceArgs,
     options?: SendOptions
   ): Promise<SendTransactionResult> {
-    const instruction = await this.instruction.joinNamespace(accounts);
+    const instruction = await this.instruction.joinNamespace(accounts, args);
 
     return this.sendWithRetry(instruction, [], options);
   }
@@ -119,11 +120,51 @@ export class NamespaceProgram extends Program.Program {
     return this.sendWithRetry(instruction, [], options);
   }
 
+  async initializeProtocolFeeConfig(
+    args: NamespaceInstruction.InitializeProtocolFeeConfigArgs,
+    accounts: NamespaceInstruction.InitializeProtocolFeeConfigAccounts,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const instruction = await this.instruction.initializeProtocolFeeConfig(
+      args,
+      accounts
+    );
+
+    return this.sendWithRetry(instruction, [], options);
+  }
+
+  async updateProtocolFeeConfig(
+    args: NamespaceInstruction.UpdateProtocolFeeConfigArgs,
+    accounts: NamespaceInstruction.UpdateProtocolFeeConfigAccounts,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const instruction = await this.instruction.updateProtocolFeeConfig(
+      args,
+      accounts
+    );
+
+    return this.sendWithRetry(instruction, [], options);
+  }
+
+  async setNamespaceProtocolFeeExempt(
+    exempt: boolean,
+    accounts: NamespaceInstruction.SetNamespaceProtocolFeeExemptAccounts,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const instruction = await this.instruction.setNamespaceProtocolFeeExempt(
+      exempt,
+      accounts
+    );
+
+    return this.sendWithRetry(instruction, [], options);
+  }
+
   async cacheArtifact(
     accounts: NamespaceInstruction.CacheArtifactAccounts,
+    args?: NamespaceInstruction.CacheArtifactArgs,
     options?: SendOptions
   ): Promise<SendTransactionResult> {
-    const instruction = await this.instruction.cacheArtifact(accounts);
+    const instruction = await this.instruction.cacheArtifact(accounts, args);
 
     return this.sendWithRetry(instruction, [], options);
   }
@@ -137,4 +178,14 @@ export class NamespaceProgram extends Program.Program {
 
     return this.sendWithRetry(instruction, [], options);
   }
+
+  async compactIndex(
+    args: NamespaceInstruction.CompactIndexArgs,
+    accounts: NamespaceInstruction.CompactIndexAccounts,
+    options?: SendOptions
+  ): Promise<SendTransactionResult> {
+    const instruction = await this.instruction.compactIndex(args, accounts);
+
+    return this.sendWithRetry(instruction, [], options);
+  }
 }
@@ -673,6 +673,32 @@ export class PlayerProgram extends Program.Program {
       rpc: () => this.sendWithRetry(instructions, signers, options),
     };
   }
+
+  async expireItemEffects(
+    _args = {},
+    accounts: Partial<PlayerInstruction.ExpireItemEffectsAccounts>,
+    options?: { commitment: web3.Commitment; timeout?: number }
+  ): Promise<{
+    rpc: () => Promise<{ txid: string; slot: number }>;
+    instructions: TransactionInstruction[];
+    signers: Signer[];
+  }> {
+    if (!accounts.playerClass) {
+      const player = await this.client.account.player.fetch(accounts.player);
+      accounts.playerClass = player.parent as PublicKey;
+    }
+
+    const { instructions, signers } = await this.instruction.expireItemEffects(
+      _args,
+      accounts as PlayerInstruction.ExpireItemEffectsAccounts
+    );
+
+    return {
+      instructions,
+      signers,
+      rpc: () => this.sendWithRetry(instructions, signers, options),
+    };
+  }
 }
 
 export async function getPlayerProgram(
@@ -69,4 +69,57 @@ export class StakingProgram extends Program.Program {
 
     return await this.sendWithRetry(instruction, [], options);
   }
+
+  async createRewardPool(
+    args: StakingInstruction.CreateRewardPoolArgs,
+    accounts: StakingInstruction.CreateRewardPoolAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.createRewardPool(args, accounts);
+
+    return await this.sendWithRetry(instruction, [], options);
+  }
+
+  async topUpRewardPool(
+    args: StakingInstruction.TopUpRewardPoolArgs,
+    accounts: StakingInstruction.TopUpRewardPoolAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.topUpRewardPool(args, accounts);
+
+    return await this.sendWithRetry(
+      instruction,
+      [accounts.fundingTransferAuthority],
+      options
+    );
+  }
+
+  async closeRewardPool(
+    accounts: StakingInstruction.CloseRewardPoolAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.closeRewardPool(accounts);
+
+    return await this.sendWithRetry(instruction, [], options);
+  }
+
+  async claimRewards(
+    args: StakingInstruction.ClaimRewardsArgs,
+    accounts: StakingInstruction.ClaimRewardsAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.claimRewards(args, accounts);
+
+    return await this.sendWithRetry(instruction, [], options);
+  }
+
+  async setLockupTiers(
+    args: StakingInstruction.SetLockupTiersArgs,
+    accounts: StakingInstruction.SetLockupTiersAccounts,
+    options?: SendOptions
+  ): Promise<Transaction.SendTransactionResult> {
+    const instruction = await this.instruction.setLockupTiers(args, accounts);
+
+    return await this.sendWithRetry(instruction, [], options);
+  }
 }
@@ -1577,6 +1577,231 @@ export type Itemv2 = {
         }
       ],
       "args": []
+    },
+    {
+      "name": "itemClassJoinNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "itemClassLeaveNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "itemClassCacheNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "page",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "itemClassUncacheNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "importItemClassV1",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassAuthorityMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassAuthorityMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassV1Import",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassV1",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassV1MintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ImportItemClassV1Args"
+          }
+        }
+      ]
+    },
+    {
+      "name": "importRecipeV1",
+      "accounts": [
+        {
+          "name": "recipe",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassAuthorityMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassAuthorityMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassV1Import",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassV1",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ImportRecipeV1Args"
+          }
+        }
+      ]
     }
   ],
   "accounts": [
@@ -1690,6 +1915,14 @@ export type Itemv2 = {
             "type": {
               "defined": "ItemClassMode"
             }
+          },
+          {
+            "name": "namespaces",
+            "type": {
+              "vec": {
+                "defined": "NamespaceAndIndex"
+              }
+            }
           }
         ]
       }
@@ -1886,6 +2119,36 @@ export type Itemv2 = {
           }
         ]
       }
+    },
+    {
+      "name": "itemClassV1Import",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemClassV1",
+            "type": "publicKey"
+          },
+          {
+            "name": "itemClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "importedScopes",
+            "type": {
+              "vec": "string"
+            }
+          },
+          {
+            "name": "issues",
+            "type": {
+              "vec": {
+                "defined": "ImportIssue"
+              }
+            }
+          }
+        ]
+      }
     }
   ],
   "types": [
@@ -2069,6 +2332,40 @@ export type Itemv2 = {
         ]
       }
     },
+    {
+      "name": "ImportItemClassV1Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemClassName",
+            "type": "string"
+          },
+          {
+            "name": "mode",
+            "type": {
+              "defined": "ItemClassModeSelection"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "ImportRecipeV1Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "componentScope",
+            "type": "string"
+          },
+          {
+            "name": "buildEnabled",
+            "type": "bool"
+          }
+        ]
+      }
+    },
     {
       "name": "MintAuthorityTokensArgs",
       "type": {
@@ -2589,17 +2886,72 @@ export type Itemv2 = {
             "name": "Fungible"
           },
           {
-            "name": "NonFungible",
+            "name": "NonFungible",
+            "fields": [
+              {
+                "name": "durability",
+                "type": "u64"
+              },
+              {
+                "name": "cooldown",
+                "type": {
+                  "option": "i64"
+                }
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "ImportIssue",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "UsagesNotImported"
+          },
+          {
+            "name": "StakingNotImported"
+          },
+          {
+            "name": "BuildPermissivenessNotImported"
+          },
+          {
+            "name": "ComponentsOnlyInRoot"
+          },
+          {
+            "name": "TimeToBuildDropped",
+            "fields": [
+              {
+                "name": "component_scope",
+                "type": "string"
+              }
+            ]
+          },
+          {
+            "name": "AbsenceComponentSkipped",
+            "fields": [
+              {
+                "name": "component_scope",
+                "type": "string"
+              },
+              {
+                "name": "step",
+                "type": "u64"
+              }
+            ]
+          },
+          {
+            "name": "CooldownDurationMissing",
             "fields": [
               {
-                "name": "durability",
-                "type": "u64"
+                "name": "component_scope",
+                "type": "string"
               },
               {
-                "name": "cooldown",
-                "type": {
-                  "option": "i64"
-                }
+                "name": "step",
+                "type": "u64"
               }
             ]
           }
@@ -2622,6 +2974,47 @@ export type Itemv2 = {
           }
         ]
       }
+    },
+    {
+      "name": "NamespaceAndIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "namespace",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "InheritanceState",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NotInherited"
+          },
+          {
+            "name": "Inherited"
+          },
+          {
+            "name": "Overridden"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -2739,6 +3132,46 @@ export type Itemv2 = {
       "code": 6022,
       "name": "MigrationError",
       "msg": "Error Migrating Account"
+    },
+    {
+      "code": 6023,
+      "name": "ComponentScopeNotFound",
+      "msg": "Component Scope Not Found"
+    },
+    {
+      "code": 6024,
+      "name": "ComponentScopeAlreadyImported",
+      "msg": "Component Scope Already Imported"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidImportAccounts",
+      "msg": "Invalid Import Accounts"
+    },
+    {
+      "code": 6026,
+      "name": "UnauthorizedCaller",
+      "msg": "Only the Namespace Program Can Call This"
+    },
+    {
+      "code": 6027,
+      "name": "FailedToJoinNamespace",
+      "msg": "Failed to Join Namespace"
+    },
+    {
+      "code": 6028,
+      "name": "FailedToLeaveNamespace",
+      "msg": "Failed to Leave Namespace"
+    },
+    {
+      "code": 6029,
+      "name": "FailedToCache",
+      "msg": "Failed to Cache"
+    },
+    {
+      "code": 6030,
+      "name": "FailedToUncache",
+      "msg": "Failed to Uncache"
     }
   ]
 };
@@ -4189,38 +4622,255 @@ export const IDL: Itemv2 = {
           "isSigner": false
         },
         {
-          "name": "authRules",
+          "name": "authRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemSource",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemSourceTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemDestinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "itemClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassAuthorityMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "itemClassAuthorityMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateBuildAccount",
+      "accounts": [
+        {
+          "name": "build",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipe",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateItemClassAccount",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "itemClassJoinNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "itemClassLeaveNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "itemClassCacheNamespace",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "itemSource",
-          "isMut": true,
+          "name": "instructions",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "itemSourceTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "page",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "itemClassUncacheNamespace",
+      "accounts": [
         {
-          "name": "itemDestination",
+          "name": "itemClass",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destinationAuthority",
+          "name": "namespace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "itemDestinationTokenRecord",
-          "isMut": true,
+          "name": "instructions",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "importItemClassV1",
+      "accounts": [
         {
           "name": "itemClass",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -4234,94 +4884,102 @@ export const IDL: Itemv2 = {
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "itemClassV1Import",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
+          "name": "itemClassV1",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "itemClassV1MintAta",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "tokenMetadata",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authRulesProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ImportItemClassV1Args"
+          }
+        }
+      ]
     },
     {
-      "name": "migrateBuildAccount",
+      "name": "importRecipeV1",
       "accounts": [
         {
-          "name": "build",
+          "name": "recipe",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "recipe",
-          "isMut": false,
+          "name": "itemClass",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "itemClassAuthorityMint",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "itemClassAuthorityMintAta",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "migrateItemClassAccount",
-      "accounts": [
+        },
         {
-          "name": "itemClass",
+          "name": "itemClassV1Import",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "itemClassV1",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ImportRecipeV1Args"
+          }
+        }
+      ]
     }
   ],
   "accounts": [
@@ -4435,6 +5093,14 @@ export const IDL: Itemv2 = {
             "type": {
               "defined": "ItemClassMode"
             }
+          },
+          {
+            "name": "namespaces",
+            "type": {
+              "vec": {
+                "defined": "NamespaceAndIndex"
+              }
+            }
           }
         ]
       }
@@ -4631,6 +5297,36 @@ export const IDL: Itemv2 = {
           }
         ]
       }
+    },
+    {
+      "name": "itemClassV1Import",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemClassV1",
+            "type": "publicKey"
+          },
+          {
+            "name": "itemClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "importedScopes",
+            "type": {
+              "vec": "string"
+            }
+          },
+          {
+            "name": "issues",
+            "type": {
+              "vec": {
+                "defined": "ImportIssue"
+              }
+            }
+          }
+        ]
+      }
     }
   ],
   "types": [
@@ -4814,6 +5510,40 @@ export const IDL: Itemv2 = {
         ]
       }
     },
+    {
+      "name": "ImportItemClassV1Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemClassName",
+            "type": "string"
+          },
+          {
+            "name": "mode",
+            "type": {
+              "defined": "ItemClassModeSelection"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "ImportRecipeV1Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "componentScope",
+            "type": "string"
+          },
+          {
+            "name": "buildEnabled",
+            "type": "bool"
+          }
+        ]
+      }
+    },
     {
       "name": "MintAuthorityTokensArgs",
       "type": {
@@ -5351,6 +6081,61 @@ export const IDL: Itemv2 = {
         ]
       }
     },
+    {
+      "name": "ImportIssue",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "UsagesNotImported"
+          },
+          {
+            "name": "StakingNotImported"
+          },
+          {
+            "name": "BuildPermissivenessNotImported"
+          },
+          {
+            "name": "ComponentsOnlyInRoot"
+          },
+          {
+            "name": "TimeToBuildDropped",
+            "fields": [
+              {
+                "name": "component_scope",
+                "type": "string"
+              }
+            ]
+          },
+          {
+            "name": "AbsenceComponentSkipped",
+            "fields": [
+              {
+                "name": "component_scope",
+                "type": "string"
+              },
+              {
+                "name": "step",
+                "type": "u64"
+              }
+            ]
+          },
+          {
+            "name": "CooldownDurationMissing",
+            "fields": [
+              {
+                "name": "component_scope",
+                "type": "string"
+              },
+              {
+                "name": "step",
+                "type": "u64"
+              }
+            ]
+          }
+        ]
+      }
+    },
     {
       "name": "PaymentStatus",
       "type": {
@@ -5367,6 +6152,47 @@ export const IDL: Itemv2 = {
           }
         ]
       }
+    },
+    {
+      "name": "NamespaceAndIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "namespace",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "InheritanceState",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NotInherited"
+          },
+          {
+            "name": "Inherited"
+          },
+          {
+            "name": "Overridden"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -5484,6 +6310,46 @@ export const IDL: Itemv2 = {
       "code": 6022,
       "name": "MigrationError",
       "msg": "Error Migrating Account"
+    },
+    {
+      "code": 6023,
+      "name": "ComponentScopeNotFound",
+      "msg": "Component Scope Not Found"
+    },
+    {
+      "code": 6024,
+      "name": "ComponentScopeAlreadyImported",
+      "msg": "Component Scope Already Imported"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidImportAccounts",
+      "msg": "Invalid Import Accounts"
+    },
+    {
+      "code": 6026,
+      "name": "UnauthorizedCaller",
+      "msg": "Only the Namespace Program Can Call This"
+    },
+    {
+      "code": 6027,
+      "name": "FailedToJoinNamespace",
+      "msg": "Failed to Join Namespace"
+    },
+    {
+      "code": 6028,
+      "name": "FailedToLeaveNamespace",
+      "msg": "Failed to Leave Namespace"
+    },
+    {
+      "code": 6029,
+      "name": "FailedToCache",
+      "msg": "Failed to Cache"
+    },
+    {
+      "code": 6030,
+      "name": "FailedToUncache",
+      "msg": "Failed to Uncache"
     }
   ]
 };
@@ -596,6 +596,12 @@ export type RaindropsAvatar = {
           "isSigner": false,
           "isOptional": true
         },
+        {
+          "name": "variantOptionCounter",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "avatarAuthority",
           "isMut": true,
@@ -828,6 +834,12 @@ export type RaindropsAvatar = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "variantOptionCounter",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "authority",
           "isMut": true,
@@ -1086,6 +1098,12 @@ export type RaindropsAvatar = {
           "isSigner": false,
           "isOptional": true
         },
+        {
+          "name": "variantOptionCounter",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "avatarAuthority",
           "isMut": true,
@@ -1130,7 +1148,8 @@ export type RaindropsAvatar = {
         {
           "name": "paymentDestination",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "authority",
@@ -1162,6 +1181,45 @@ export type RaindropsAvatar = {
         }
       ]
     },
+    {
+      "name": "transferPaymentNative",
+      "accounts": [
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMethod",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentDestination",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "TransferPaymentNativeArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "burnPayment",
       "accounts": [
@@ -1507,18 +1565,116 @@ export type RaindropsAvatar = {
       ]
     },
     {
-      "name": "migrateAvatarClassAccount",
+      "name": "createTraitRental",
       "accounts": [
         {
-          "name": "avatarClass",
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitRental",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "traitSource",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lender",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreateTraitRentalArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "cancelTraitRental",
+      "accounts": [
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitRental",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lender",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1528,18 +1684,78 @@ export type RaindropsAvatar = {
       "args": []
     },
     {
-      "name": "migrateAvatarAccount",
+      "name": "rentTrait",
       "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "avatar",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitConflicts",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitRental",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lender",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "renter",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1547,55 +1763,421 @@ export type RaindropsAvatar = {
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "oldAvatar",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "avatarClass",
-            "type": "publicKey"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "imageUri",
-            "type": "string"
-          },
-          {
-            "name": "traits",
-            "type": {
-              "vec": {
-                "defined": "OldTraitData"
-              }
-            }
-          },
-          {
-            "name": "variants",
-            "type": {
-              "vec": {
-                "defined": "VariantOption"
-              }
-            }
-          }
-        ]
-      }
     },
     {
-      "name": "oldAvatarClass",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
+      "name": "reclaimTraitRental",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatarAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitRental",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lender",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateStatTemplate",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateStatTemplateArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeStatTemplate",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "RemoveStatTemplateArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "refreshAvatarStats",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "avatarArtifactJoinNamespace",
+      "accounts": [
+        {
+          "name": "avatarArtifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "avatarArtifactLeaveNamespace",
+      "accounts": [
+        {
+          "name": "avatarArtifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "avatarArtifactCacheNamespace",
+      "accounts": [
+        {
+          "name": "avatarArtifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "page",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "avatarArtifactUncacheNamespace",
+      "accounts": [
+        {
+          "name": "avatarArtifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateAvatarClassAccount",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateAvatarAccount",
+      "accounts": [
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateTraitAccount",
+      "accounts": [
+        {
+          "name": "traitAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateUpdateState",
+      "accounts": [
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "oldAvatar",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "avatarClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "imageUri",
+            "type": "string"
+          },
+          {
+            "name": "traits",
+            "type": {
+              "vec": {
+                "defined": "OldTraitData"
+              }
+            }
+          },
+          {
+            "name": "variants",
+            "type": {
+              "vec": {
+                "defined": "OldVariantOption"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oldAvatarClass",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
             "name": "traitIndex",
             "type": "u16"
           },
@@ -1607,7 +2189,7 @@ export type RaindropsAvatar = {
             "name": "attributeMetadata",
             "type": {
               "vec": {
-                "defined": "OldAttributeMetadata"
+                "defined": "AttributeMetadata"
               }
             }
           },
@@ -1615,7 +2197,7 @@ export type RaindropsAvatar = {
             "name": "variantMetadata",
             "type": {
               "vec": {
-                "defined": "VariantMetadata"
+                "defined": "OldVariantMetadata"
               }
             }
           },
@@ -1626,6 +2208,96 @@ export type RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "oldTrait",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u16"
+          },
+          {
+            "name": "avatarClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "traitMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "attributeIds",
+            "type": {
+              "vec": "u16"
+            }
+          },
+          {
+            "name": "componentUri",
+            "type": "string"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": "TraitStatus"
+            }
+          },
+          {
+            "name": "variantMetadata",
+            "type": {
+              "vec": {
+                "defined": "OldVariantMetadata"
+              }
+            }
+          },
+          {
+            "name": "equipPaymentDetails",
+            "type": {
+              "option": {
+                "defined": "OldPaymentDetails"
+              }
+            }
+          },
+          {
+            "name": "removePaymentDetails",
+            "type": {
+              "option": {
+                "defined": "OldPaymentDetails"
+              }
+            }
+          },
+          {
+            "name": "traitGate",
+            "type": {
+              "option": {
+                "defined": "TraitGate"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oldUpdateState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "initialized",
+            "type": "bool"
+          },
+          {
+            "name": "avatar",
+            "type": "publicKey"
+          },
+          {
+            "name": "target",
+            "type": {
+              "defined": "UpdateTarget"
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "avatarClass",
       "type": {
@@ -1662,6 +2334,22 @@ export type RaindropsAvatar = {
           {
             "name": "globalRenderingConfigUri",
             "type": "string"
+          },
+          {
+            "name": "statTemplates",
+            "type": {
+              "vec": {
+                "defined": "StatTemplate"
+              }
+            }
+          },
+          {
+            "name": "namespaces",
+            "type": {
+              "vec": {
+                "defined": "NamespaceAndIndex"
+              }
+            }
           }
         ]
       }
@@ -1698,6 +2386,22 @@ export type RaindropsAvatar = {
                 "defined": "VariantOption"
               }
             }
+          },
+          {
+            "name": "stats",
+            "type": {
+              "vec": {
+                "defined": "Stat"
+              }
+            }
+          },
+          {
+            "name": "namespaces",
+            "type": {
+              "vec": {
+                "defined": "NamespaceAndIndex"
+              }
+            }
           }
         ]
       }
@@ -1766,6 +2470,14 @@ export type RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "vec": {
+                "defined": "StatModifier"
+              }
+            }
           }
         ]
       }
@@ -1807,6 +2519,10 @@ export type RaindropsAvatar = {
       "type": {
         "kind": "struct",
         "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
           {
             "name": "initialized",
             "type": "bool"
@@ -1820,6 +2536,68 @@ export type RaindropsAvatar = {
             "type": {
               "defined": "UpdateTarget"
             }
+          },
+          {
+            "name": "paymentAlternatives",
+            "type": {
+              "defined": "UpdatePaymentAlternatives"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "variantOptionCounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "avatarClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "selectionCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "traitRental",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "avatarClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "traitAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "lender",
+            "type": "publicKey"
+          },
+          {
+            "name": "duration",
+            "type": "i64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "avatar",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
           }
         ]
       }
@@ -1987,13 +2765,21 @@ export type RaindropsAvatar = {
             "name": "variantMetadata",
             "type": {
               "vec": {
-                "defined": "VariantMetadata"
+                "defined": "VariantMetadata"
+              }
+            }
+          },
+          {
+            "name": "globalRenderingConfigUri",
+            "type": "string"
+          },
+          {
+            "name": "statTemplates",
+            "type": {
+              "vec": {
+                "defined": "StatTemplate"
               }
             }
-          },
-          {
-            "name": "globalRenderingConfigUri",
-            "type": "string"
           }
         ]
       }
@@ -2050,6 +2836,22 @@ export type RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "CreateTraitRentalArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duration",
+            "type": "i64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "CreateTraitArgs",
       "type": {
@@ -2102,6 +2904,14 @@ export type RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "vec": {
+                "defined": "StatModifier"
+              }
+            }
           }
         ]
       }
@@ -2129,7 +2939,15 @@ export type RaindropsAvatar = {
             "name": "variantSelection",
             "type": {
               "vec": {
-                "defined": "VariantOption"
+                "defined": "OldVariantOption"
+              }
+            }
+          },
+          {
+            "name": "traitGate",
+            "type": {
+              "option": {
+                "defined": "TraitGate"
               }
             }
           }
@@ -2137,35 +2955,91 @@ export type RaindropsAvatar = {
       }
     },
     {
-      "name": "OldAttributeMetadata",
+      "name": "OldVariantMetadata",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "id",
-            "type": "u16"
+            "name": "name",
+            "type": "string"
           },
           {
-            "name": "name",
+            "name": "id",
             "type": "string"
           },
           {
             "name": "status",
             "type": {
-              "defined": "OldAttributeStatus"
+              "defined": "VariantStatus"
+            }
+          },
+          {
+            "name": "options",
+            "type": {
+              "vec": {
+                "defined": "OldVariantOption"
+              }
             }
           }
         ]
       }
     },
     {
-      "name": "OldAttributeStatus",
+      "name": "OldVariantOption",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "mutable",
-            "type": "bool"
+            "name": "variantId",
+            "type": "string"
+          },
+          {
+            "name": "optionId",
+            "type": "string"
+          },
+          {
+            "name": "paymentDetails",
+            "type": {
+              "option": {
+                "defined": "OldPaymentDetails"
+              }
+            }
+          },
+          {
+            "name": "traitGate",
+            "type": {
+              "option": {
+                "defined": "TraitGate"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "OldPaymentDetails",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentMethod",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TransferPaymentNativeArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
@@ -2238,6 +3112,32 @@ export type RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "UpdateStatTemplateArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statTemplate",
+            "type": {
+              "defined": "StatTemplate"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RemoveStatTemplateArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statId",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
       "name": "UpdateTraitVariantAuthorityArgs",
       "type": {
@@ -2298,6 +3198,16 @@ export type RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "StatModifier"
+                }
+              }
+            }
           }
         ]
       }
@@ -2366,6 +3276,70 @@ export type RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "StatTemplate",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u16"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "baseValue",
+            "type": "i64"
+          },
+          {
+            "name": "minValue",
+            "type": "i64"
+          },
+          {
+            "name": "maxValue",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StatModifier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statId",
+            "type": "u16"
+          },
+          {
+            "name": "add",
+            "type": "i64"
+          },
+          {
+            "name": "multiply",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Stat",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u16"
+          },
+          {
+            "name": "value",
+            "type": "i64"
+          }
+        ]
+      }
+    },
     {
       "name": "VariantMetadata",
       "type": {
@@ -2424,12 +3398,54 @@ export type RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "startTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "endTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "maxSelections",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
     },
     {
       "name": "PaymentDetails",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentMethod",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "alternatives",
+            "type": {
+              "vec": {
+                "defined": "PaymentAlternative"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PaymentAlternative",
       "type": {
         "kind": "struct",
         "fields": [
@@ -2498,6 +3514,20 @@ export type RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "rental",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "vec": {
+                "defined": "StatModifier"
+              }
+            }
           }
         ]
       }
@@ -2514,6 +3544,22 @@ export type RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "PaymentSplit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "basisPoints",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
       "name": "PaymentState",
       "type": {
@@ -2534,6 +3580,30 @@ export type RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "UpdatePaymentAlternatives",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payment",
+            "type": {
+              "vec": {
+                "defined": "PaymentAlternative"
+              }
+            }
+          },
+          {
+            "name": "removePayment",
+            "type": {
+              "vec": {
+                "defined": "PaymentAlternative"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "VariantStatus",
       "type": {
@@ -2600,6 +3670,9 @@ export type RaindropsAvatar = {
                 "type": "publicKey"
               }
             ]
+          },
+          {
+            "name": "Native"
           }
         ]
       }
@@ -2620,6 +3693,19 @@ export type RaindropsAvatar = {
           },
           {
             "name": "Burn"
+          },
+          {
+            "name": "Split",
+            "fields": [
+              {
+                "name": "recipients",
+                "type": {
+                  "vec": {
+                    "defined": "PaymentSplit"
+                  }
+                }
+              }
+            ]
           }
         ]
       }
@@ -2823,6 +3909,47 @@ export type RaindropsAvatar = {
           }
         ]
       }
+    },
+    {
+      "name": "NamespaceAndIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "namespace",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "InheritanceState",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NotInherited"
+          },
+          {
+            "name": "Inherited"
+          },
+          {
+            "name": "Overridden"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -2887,67 +4014,790 @@ export type RaindropsAvatar = {
       "msg": "Incorrect Asset Class for Instruction"
     },
     {
-      "code": 6012,
-      "name": "InvalidTrait",
-      "msg": "Invalid Trait Account for Update"
+      "code": 6012,
+      "name": "InvalidTrait",
+      "msg": "Invalid Trait Account for Update"
+    },
+    {
+      "code": 6013,
+      "name": "TraitDisabled",
+      "msg": "Trait Is Disabled"
+    },
+    {
+      "code": 6014,
+      "name": "VariantDisabled",
+      "msg": "Variant Is Disabled"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidUpdateTarget",
+      "msg": "Invalid UpdateTarget"
+    },
+    {
+      "code": 6016,
+      "name": "TokenDelegateNotAllowed",
+      "msg": "Token Account Delegate Not Allowed"
+    },
+    {
+      "code": 6017,
+      "name": "MissingEssentialAttribute",
+      "msg": "Missing Essential Attribute Relacement"
+    },
+    {
+      "code": 6018,
+      "name": "MigrationError",
+      "msg": "Migration Error"
+    },
+    {
+      "code": 6019,
+      "name": "TraitGateFailure",
+      "msg": "Failed to Validate Trait Gate"
+    },
+    {
+      "code": 6020,
+      "name": "VariantUnavailable",
+      "msg": "Variant Option is Outside its Availability Window"
+    },
+    {
+      "code": 6021,
+      "name": "VariantSupplyExhausted",
+      "msg": "Variant Option Supply Exhausted"
+    },
+    {
+      "code": 6022,
+      "name": "MissingVariantOptionCounter",
+      "msg": "Variant Option Counter Required"
+    },
+    {
+      "code": 6023,
+      "name": "TooManyPaymentAlternatives",
+      "msg": "Too Many Payment Alternatives"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidPaymentSplit",
+      "msg": "Invalid Payment Split"
+    },
+    {
+      "code": 6025,
+      "name": "TraitRented",
+      "msg": "Trait Is Rented"
+    },
+    {
+      "code": 6026,
+      "name": "TraitRentalUnavailable",
+      "msg": "Trait Rental Unavailable"
+    },
+    {
+      "code": 6027,
+      "name": "TraitRentalNotExpired",
+      "msg": "Trait Rental Not Expired"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidTraitRentalDuration",
+      "msg": "Invalid Trait Rental Duration"
+    },
+    {
+      "code": 6029,
+      "name": "TraitRentalNotAllowed",
+      "msg": "Trait Can Not Be Rented"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidStatTemplate",
+      "msg": "Invalid Stat Template"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidStatId",
+      "msg": "Invalid Stat Id"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidVariantOption",
+      "msg": "Invalid Variant Option"
+    },
+    {
+      "code": 6033,
+      "name": "UnauthorizedCaller",
+      "msg": "Only the Namespace Program Can Call This"
+    },
+    {
+      "code": 6034,
+      "name": "FailedToJoinNamespace",
+      "msg": "Failed to Join Namespace"
+    },
+    {
+      "code": 6035,
+      "name": "FailedToLeaveNamespace",
+      "msg": "Failed to Leave Namespace"
+    },
+    {
+      "code": 6036,
+      "name": "FailedToCache",
+      "msg": "Failed to Cache"
+    },
+    {
+      "code": 6037,
+      "name": "FailedToUncache",
+      "msg": "Failed to Uncache"
+    },
+    {
+      "code": 6038,
+      "name": "NumericalOverflowError",
+      "msg": "Numerical Overflow"
+    },
+    {
+      "code": 6039,
+      "name": "UpdateStateNotMigrated",
+      "msg": "Update State Must Be Migrated"
+    }
+  ]
+};
+
+export const IDL: RaindropsAvatar = {
+  "version": "0.1.0",
+  "name": "raindrops_avatar",
+  "instructions": [
+    {
+      "name": "createAvatarClass",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreateAvatarClassArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createAvatar",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rainVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRainAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreateAvatarArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createTrait",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitConflicts",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rainVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRainAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreateTraitArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createPaymentMethod",
+      "accounts": [
+        {
+          "name": "paymentMethod",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMints",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountCompression",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "logWrapper",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreatePaymentMethodArgs"
+          }
+        }
+      ]
     },
     {
-      "code": 6013,
-      "name": "TraitDisabled",
-      "msg": "Trait Is Disabled"
+      "name": "equipTrait",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitConflicts",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitSource",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6014,
-      "name": "VariantDisabled",
-      "msg": "Variant Is Disabled"
+      "name": "equipTraitAuthority",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitSource",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6015,
-      "name": "InvalidUpdateTarget",
-      "msg": "Invalid UpdateTarget"
+      "name": "removeTrait",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6016,
-      "name": "TokenDelegateNotAllowed",
-      "msg": "Token Account Delegate Not Allowed"
+      "name": "removeTraitAuthority",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6017,
-      "name": "MissingEssentialAttribute",
-      "msg": "Missing Essential Attribute Relacement"
+      "name": "swapTrait",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "equipTraitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "removeTraitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "equipTraitConflicts",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "equipTraitSource",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "removeTraitDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarEquipTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarRemoveTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6018,
-      "name": "MigrationError",
-      "msg": "Migration Error"
+      "name": "updateTraitVariant",
+      "accounts": [
+        {
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "variantOptionCounter",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "avatarAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6019,
-      "name": "TraitGateFailure",
-      "msg": "Failed to Validate Trait Gate"
-    }
-  ]
-};
-
-export const IDL: RaindropsAvatar = {
-  "version": "0.1.0",
-  "name": "raindrops_avatar",
-  "instructions": [
-    {
-      "name": "createAvatarClass",
+      "name": "updateTraitVariantAuthority",
       "accounts": [
         {
-          "name": "avatarClass",
+          "name": "avatar",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClassMint",
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatarClassMintAta",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "traitAccount",
           "isMut": false,
           "isSigner": false
         },
@@ -2956,11 +4806,6 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -2971,13 +4816,13 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "CreateAvatarClassArgs"
+            "defined": "UpdateTraitVariantAuthorityArgs"
           }
         }
       ]
     },
     {
-      "name": "createAvatar",
+      "name": "updateClassVariantAuthority",
       "accounts": [
         {
           "name": "avatarClass",
@@ -2994,41 +4839,11 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "avatarMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rainVault",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "authorityRainAta",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "authority",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -3039,17 +4854,17 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "CreateAvatarArgs"
+            "defined": "UpdateClassVariantAuthorityArgs"
           }
         }
       ]
     },
     {
-      "name": "createTrait",
+      "name": "updateTrait",
       "accounts": [
         {
           "name": "avatarClass",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -3063,23 +4878,36 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         },
         {
-          "name": "traitMint",
-          "isMut": false,
-          "isSigner": false
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "traitConflicts",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "rainVault",
+          "name": "args",
+          "type": {
+            "defined": "UpdateTraitArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateClassVariantMetadata",
+      "accounts": [
+        {
+          "name": "avatarClass",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authorityRainAta",
-          "isMut": true,
+          "name": "avatarClassMintAta",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -3087,39 +4915,24 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "args",
           "type": {
-            "defined": "CreateTraitArgs"
+            "defined": "UpdateClassVariantMetadataArgs"
           }
         }
       ]
     },
     {
-      "name": "createPaymentMethod",
+      "name": "updateAttributeMetadata",
       "accounts": [
-        {
-          "name": "paymentMethod",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "avatarClass",
           "isMut": true,
@@ -3130,104 +4943,68 @@ export const IDL: RaindropsAvatar = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "paymentMints",
-          "isMut": true,
-          "isSigner": false,
-          "isOptional": true
-        },
         {
           "name": "authority",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "accountCompression",
-          "isMut": false,
-          "isSigner": false,
-          "isOptional": true
-        },
-        {
-          "name": "logWrapper",
-          "isMut": false,
-          "isSigner": false,
-          "isOptional": true
         }
       ],
       "args": [
         {
           "name": "args",
           "type": {
-            "defined": "CreatePaymentMethodArgs"
+            "defined": "UpdateAttributeMetadataArgs"
           }
         }
       ]
     },
     {
-      "name": "equipTrait",
+      "name": "beginVariantUpdate",
       "accounts": [
         {
-          "name": "avatarClass",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "avatar",
+          "name": "updateState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarMintAta",
+          "name": "avatarClass",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "updateState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "traitAccount",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "traitConflicts",
+          "name": "avatar",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitSource",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "avatarTraitAta",
-          "isMut": true,
+          "name": "avatarMintAta",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarAuthority",
+          "name": "variantOptionCounter",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "payer",
+          "name": "authority",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
@@ -3237,34 +5014,46 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "BeginVariantUpdateArgs"
+          }
+        }
+      ]
     },
     {
-      "name": "equipTraitAuthority",
+      "name": "beginTraitUpdate",
       "accounts": [
+        {
+          "name": "updateState",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "avatarClass",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "traitAccount",
           "isMut": false,
           "isSigner": false
         },
         {
           "name": "avatar",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitAccount",
+          "name": "avatarMintAta",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitSource",
-          "isMut": true,
+          "name": "traitMint",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -3278,7 +5067,7 @@ export const IDL: RaindropsAvatar = {
           "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
@@ -3286,106 +5075,77 @@ export const IDL: RaindropsAvatar = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "removeTrait",
-      "accounts": [
-        {
-          "name": "avatarClass",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "avatar",
-          "isMut": true,
-          "isSigner": false
         },
         {
-          "name": "avatarMintAta",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "updateState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "traitAccount",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "traitDestination",
-          "isMut": true,
-          "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "avatarTraitAta",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "args",
+          "type": {
+            "defined": "BeginTraitUpdateArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "beginTraitSwapUpdate",
+      "accounts": [
         {
-          "name": "avatarAuthority",
+          "name": "updateState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "tokenProgram",
+          "name": "avatarClass",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "equipTraitAccount",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "removeTraitAuthority",
-      "accounts": [
+        },
         {
-          "name": "avatarClass",
+          "name": "removeTraitAccount",
           "isMut": false,
           "isSigner": false
         },
         {
           "name": "avatar",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "avatarMintAta",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitAccount",
+          "name": "equipTraitMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitMint",
+          "name": "removeTraitMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitDestination",
+          "name": "avatarEquipTraitAta",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarTraitAta",
+          "name": "avatarAuthorityRemoveTraitAta",
           "isMut": true,
           "isSigner": false
         },
@@ -3400,34 +5160,41 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "BeginTraitSwapUpdateArgs"
+          }
+        }
+      ]
     },
     {
-      "name": "swapTrait",
+      "name": "cancelUpdate",
       "accounts": [
         {
-          "name": "avatarClass",
-          "isMut": false,
+          "name": "updateState",
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "avatar",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -3436,44 +5203,64 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         },
         {
-          "name": "updateState",
+          "name": "authority",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "equipTraitAccount",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "removeTraitAccount",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "equipTraitConflicts",
+          "name": "args",
+          "type": {
+            "defined": "CancelUpdateArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateVariant",
+      "accounts": [
+        {
+          "name": "avatarClass",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "equipTraitSource",
+          "name": "avatar",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "removeTraitDestination",
-          "isMut": true,
+          "name": "avatarMintAta",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarEquipTraitAta",
+          "name": "updateState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarRemoveTraitAta",
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "variantOptionCounter",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "avatarAuthority",
@@ -3485,11 +5272,6 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -3499,43 +5281,48 @@ export const IDL: RaindropsAvatar = {
       "args": []
     },
     {
-      "name": "updateTraitVariant",
+      "name": "transferPayment",
       "accounts": [
         {
-          "name": "avatarClass",
-          "isMut": false,
+          "name": "updateState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatar",
-          "isMut": true,
+          "name": "paymentMethod",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarMintAta",
+          "name": "paymentMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "updateState",
+          "name": "paymentSource",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "traitAccount",
-          "isMut": false,
+          "name": "paymentDestination",
+          "isMut": true,
           "isSigner": false,
           "isOptional": true
         },
         {
-          "name": "avatarAuthority",
+          "name": "authority",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         },
         {
           "name": "systemProgram",
@@ -3543,30 +5330,33 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "TransferPaymentArgs"
+          }
+        }
+      ]
     },
     {
-      "name": "updateTraitVariantAuthority",
+      "name": "transferPaymentNative",
       "accounts": [
         {
-          "name": "avatar",
+          "name": "updateState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClass",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "avatarClassMintAta",
+          "name": "paymentMethod",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitAccount",
-          "isMut": false,
-          "isSigner": false
+          "name": "paymentDestination",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "authority",
@@ -3583,26 +5373,31 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "UpdateTraitVariantAuthorityArgs"
+            "defined": "TransferPaymentNativeArgs"
           }
         }
       ]
     },
     {
-      "name": "updateClassVariantAuthority",
+      "name": "burnPayment",
       "accounts": [
         {
-          "name": "avatarClass",
-          "isMut": false,
+          "name": "updateState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "paymentMethod",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatar",
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentSource",
           "isMut": true,
           "isSigner": false
         },
@@ -3611,6 +5406,16 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -3621,26 +5426,36 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "UpdateClassVariantAuthorityArgs"
+            "defined": "BurnPaymentArgs"
           }
         }
       ]
     },
     {
-      "name": "updateTrait",
+      "name": "burnPaymentTree",
       "accounts": [
         {
-          "name": "avatarClass",
-          "isMut": false,
+          "name": "updateState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "paymentMethod",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitAccount",
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "verifiedPaymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentSource",
           "isMut": true,
           "isSigner": false
         },
@@ -3649,6 +5464,16 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -3659,29 +5484,59 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "UpdateTraitArgs"
+            "defined": "BurnPaymentTreeArgs"
           }
         }
       ]
     },
     {
-      "name": "updateClassVariantMetadata",
+      "name": "transferPaymentTree",
       "accounts": [
         {
-          "name": "avatarClass",
+          "name": "updateState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "paymentMethod",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "verifiedPaymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentSource",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentDestination",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "authority",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -3692,14 +5547,29 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "UpdateClassVariantMetadataArgs"
+            "defined": "TransferPaymentTreeArgs"
           }
         }
       ]
     },
     {
-      "name": "updateAttributeMetadata",
+      "name": "addPaymentMintPaymentMethod",
       "accounts": [
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMethod",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMints",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "avatarClass",
           "isMut": true,
@@ -3716,47 +5586,34 @@ export const IDL: RaindropsAvatar = {
           "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "logWrapper",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "args",
-          "type": {
-            "defined": "UpdateAttributeMetadataArgs"
-          }
+          "name": "accountCompression",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "beginVariantUpdate",
+      "name": "addTraitConflicts",
       "accounts": [
-        {
-          "name": "updateState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "avatarClass",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitAccount",
-          "isMut": false,
-          "isSigner": false,
-          "isOptional": true
-        },
-        {
-          "name": "avatar",
+          "name": "avatarClassMintAta",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarMintAta",
-          "isMut": false,
+          "name": "traitConflicts",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -3764,11 +5621,6 @@ export const IDL: RaindropsAvatar = {
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -3779,51 +5631,36 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "BeginVariantUpdateArgs"
+            "defined": "AddTraitConflictsArgs"
           }
         }
       ]
     },
     {
-      "name": "beginTraitUpdate",
+      "name": "verifyPaymentMint",
       "accounts": [
         {
-          "name": "updateState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "avatarClass",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "traitAccount",
+          "name": "paymentMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatar",
-          "isMut": false,
+          "name": "verifiedPaymentMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarMintAta",
+          "name": "paymentMethod",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitMint",
+          "name": "paymentMints",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarTraitAta",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "authority",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
@@ -3838,12 +5675,7 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
+          "name": "accountCompression",
           "isMut": false,
           "isSigner": false
         }
@@ -3852,66 +5684,74 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "BeginTraitUpdateArgs"
+            "defined": "VerifyPaymentMintArgs"
           }
         }
       ]
     },
     {
-      "name": "beginTraitSwapUpdate",
+      "name": "verifyPaymentMintTest",
       "accounts": [
         {
-          "name": "updateState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "avatarClass",
+          "name": "paymentMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "equipTraitAccount",
+          "name": "paymentMints",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "removeTraitAccount",
-          "isMut": false,
-          "isSigner": false
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "avatar",
+          "name": "accountCompression",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "avatarMintAta",
+          "name": "args",
+          "type": {
+            "defined": "VerifyPaymentMintTestArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createTraitRental",
+      "accounts": [
+        {
+          "name": "traitAccount",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "equipTraitMint",
+          "name": "traitMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "removeTraitMint",
-          "isMut": false,
+          "name": "traitRental",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarEquipTraitAta",
+          "name": "traitSource",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarAuthorityRemoveTraitAta",
+          "name": "traitEscrow",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "lender",
           "isMut": true,
           "isSigner": true
         },
@@ -3921,17 +5761,17 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -3940,31 +5780,41 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "BeginTraitSwapUpdateArgs"
+            "defined": "CreateTraitRentalArgs"
           }
         }
       ]
     },
     {
-      "name": "cancelUpdate",
+      "name": "cancelTraitRental",
       "accounts": [
         {
-          "name": "updateState",
-          "isMut": true,
+          "name": "traitAccount",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatar",
+          "name": "traitMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "avatarMintAta",
-          "isMut": false,
+          "name": "traitRental",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "traitEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lender",
           "isMut": true,
           "isSigner": true
         },
@@ -3973,23 +5823,26 @@ export const IDL: RaindropsAvatar = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "CancelUpdateArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "updateVariant",
+      "name": "rentTrait",
       "accounts": [
         {
           "name": "avatarClass",
@@ -4006,65 +5859,43 @@ export const IDL: RaindropsAvatar = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "updateState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "traitAccount",
           "isMut": false,
-          "isSigner": false,
-          "isOptional": true
-        },
-        {
-          "name": "avatarAuthority",
-          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
+          "name": "traitMint",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "transferPayment",
-      "accounts": [
+        },
         {
-          "name": "updateState",
-          "isMut": true,
+          "name": "traitConflicts",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "paymentMethod",
-          "isMut": false,
+          "name": "traitRental",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMint",
-          "isMut": false,
+          "name": "traitEscrow",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentSource",
+          "name": "avatarTraitAta",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentDestination",
+          "name": "lender",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "renter",
           "isMut": true,
           "isSigner": true
         },
@@ -4078,46 +5909,74 @@ export const IDL: RaindropsAvatar = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "TransferPaymentArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "burnPayment",
+      "name": "reclaimTraitRental",
       "accounts": [
         {
-          "name": "updateState",
+          "name": "avatarClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatar",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMethod",
+          "name": "avatarMintAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "avatarAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "paymentMint",
+          "name": "traitRental",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "avatarTraitAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitDestination",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentSource",
+          "name": "lender",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
@@ -4131,64 +5990,37 @@ export const IDL: RaindropsAvatar = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "BurnPaymentArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "burnPaymentTree",
+      "name": "updateStatTemplate",
       "accounts": [
         {
-          "name": "updateState",
+          "name": "avatarClass",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMethod",
+          "name": "avatarClassMintAta",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "paymentMint",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "verifiedPaymentMint",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "paymentSource",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "authority",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -4199,59 +6031,29 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "BurnPaymentTreeArgs"
+            "defined": "UpdateStatTemplateArgs"
           }
         }
       ]
     },
     {
-      "name": "transferPaymentTree",
+      "name": "removeStatTemplate",
       "accounts": [
         {
-          "name": "updateState",
+          "name": "avatarClass",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMethod",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "paymentMint",
+          "name": "avatarClassMintAta",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "verifiedPaymentMint",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "paymentSource",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "paymentDestination",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "authority",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -4262,51 +6064,62 @@ export const IDL: RaindropsAvatar = {
         {
           "name": "args",
           "type": {
-            "defined": "TransferPaymentTreeArgs"
+            "defined": "RemoveStatTemplateArgs"
           }
         }
       ]
     },
     {
-      "name": "addPaymentMintPaymentMethod",
+      "name": "refreshAvatarStats",
       "accounts": [
         {
-          "name": "paymentMint",
+          "name": "avatarClass",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "paymentMethod",
-          "isMut": false,
+          "name": "avatar",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMints",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "avatarClass",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "avatarArtifactJoinNamespace",
+      "accounts": [
+        {
+          "name": "avatarArtifact",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "namespace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "logWrapper",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountCompression",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -4314,27 +6127,27 @@ export const IDL: RaindropsAvatar = {
       "args": []
     },
     {
-      "name": "addTraitConflicts",
+      "name": "avatarArtifactLeaveNamespace",
       "accounts": [
         {
-          "name": "avatarClass",
-          "isMut": false,
+          "name": "avatarArtifact",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "avatarClassMintAta",
+          "name": "namespace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "traitConflicts",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
         },
         {
           "name": "systemProgram",
@@ -4342,106 +6155,102 @@ export const IDL: RaindropsAvatar = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "AddTraitConflictsArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "verifyPaymentMint",
+      "name": "avatarArtifactCacheNamespace",
       "accounts": [
         {
-          "name": "paymentMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "verifiedPaymentMint",
+          "name": "avatarArtifact",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMethod",
+          "name": "namespace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "paymentMints",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
+          "name": "page",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "avatarArtifactUncacheNamespace",
+      "accounts": [
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "avatarArtifact",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "namespace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountCompression",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "VerifyPaymentMintArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "verifyPaymentMintTest",
+      "name": "migrateAvatarClassAccount",
       "accounts": [
         {
-          "name": "paymentMint",
-          "isMut": false,
+          "name": "avatarClass",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMints",
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateAvatarAccount",
+      "accounts": [
+        {
+          "name": "avatar",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "authority",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "accountCompression",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "VerifyPaymentMintTestArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "migrateAvatarClassAccount",
+      "name": "migrateTraitAccount",
       "accounts": [
         {
-          "name": "avatarClass",
+          "name": "traitAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -4459,15 +6268,15 @@ export const IDL: RaindropsAvatar = {
       "args": []
     },
     {
-      "name": "migrateAvatarAccount",
+      "name": "migrateUpdateState",
       "accounts": [
         {
-          "name": "avatar",
+          "name": "updateState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
@@ -4495,64 +6304,154 @@ export const IDL: RaindropsAvatar = {
             "type": "publicKey"
           },
           {
-            "name": "imageUri",
-            "type": "string"
+            "name": "imageUri",
+            "type": "string"
+          },
+          {
+            "name": "traits",
+            "type": {
+              "vec": {
+                "defined": "OldTraitData"
+              }
+            }
+          },
+          {
+            "name": "variants",
+            "type": {
+              "vec": {
+                "defined": "OldVariantOption"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oldAvatarClass",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "traitIndex",
+            "type": "u16"
+          },
+          {
+            "name": "paymentIndex",
+            "type": "u64"
           },
           {
-            "name": "traits",
+            "name": "attributeMetadata",
             "type": {
               "vec": {
-                "defined": "OldTraitData"
+                "defined": "AttributeMetadata"
               }
             }
           },
           {
-            "name": "variants",
+            "name": "variantMetadata",
             "type": {
               "vec": {
-                "defined": "VariantOption"
+                "defined": "OldVariantMetadata"
               }
             }
+          },
+          {
+            "name": "globalRenderingConfigUri",
+            "type": "string"
           }
         ]
       }
     },
     {
-      "name": "oldAvatarClass",
+      "name": "oldTrait",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "mint",
+            "name": "id",
+            "type": "u16"
+          },
+          {
+            "name": "avatarClass",
             "type": "publicKey"
           },
           {
-            "name": "traitIndex",
-            "type": "u16"
+            "name": "traitMint",
+            "type": "publicKey"
           },
           {
-            "name": "paymentIndex",
-            "type": "u64"
+            "name": "attributeIds",
+            "type": {
+              "vec": "u16"
+            }
           },
           {
-            "name": "attributeMetadata",
+            "name": "componentUri",
+            "type": "string"
+          },
+          {
+            "name": "status",
             "type": {
-              "vec": {
-                "defined": "OldAttributeMetadata"
-              }
+              "defined": "TraitStatus"
             }
           },
           {
             "name": "variantMetadata",
             "type": {
               "vec": {
-                "defined": "VariantMetadata"
+                "defined": "OldVariantMetadata"
               }
             }
           },
           {
-            "name": "globalRenderingConfigUri",
-            "type": "string"
+            "name": "equipPaymentDetails",
+            "type": {
+              "option": {
+                "defined": "OldPaymentDetails"
+              }
+            }
+          },
+          {
+            "name": "removePaymentDetails",
+            "type": {
+              "option": {
+                "defined": "OldPaymentDetails"
+              }
+            }
+          },
+          {
+            "name": "traitGate",
+            "type": {
+              "option": {
+                "defined": "TraitGate"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oldUpdateState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "initialized",
+            "type": "bool"
+          },
+          {
+            "name": "avatar",
+            "type": "publicKey"
+          },
+          {
+            "name": "target",
+            "type": {
+              "defined": "UpdateTarget"
+            }
           }
         ]
       }
@@ -4593,6 +6492,22 @@ export const IDL: RaindropsAvatar = {
           {
             "name": "globalRenderingConfigUri",
             "type": "string"
+          },
+          {
+            "name": "statTemplates",
+            "type": {
+              "vec": {
+                "defined": "StatTemplate"
+              }
+            }
+          },
+          {
+            "name": "namespaces",
+            "type": {
+              "vec": {
+                "defined": "NamespaceAndIndex"
+              }
+            }
           }
         ]
       }
@@ -4629,6 +6544,22 @@ export const IDL: RaindropsAvatar = {
                 "defined": "VariantOption"
               }
             }
+          },
+          {
+            "name": "stats",
+            "type": {
+              "vec": {
+                "defined": "Stat"
+              }
+            }
+          },
+          {
+            "name": "namespaces",
+            "type": {
+              "vec": {
+                "defined": "NamespaceAndIndex"
+              }
+            }
           }
         ]
       }
@@ -4697,6 +6628,14 @@ export const IDL: RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "vec": {
+                "defined": "StatModifier"
+              }
+            }
           }
         ]
       }
@@ -4738,6 +6677,10 @@ export const IDL: RaindropsAvatar = {
       "type": {
         "kind": "struct",
         "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
           {
             "name": "initialized",
             "type": "bool"
@@ -4751,6 +6694,68 @@ export const IDL: RaindropsAvatar = {
             "type": {
               "defined": "UpdateTarget"
             }
+          },
+          {
+            "name": "paymentAlternatives",
+            "type": {
+              "defined": "UpdatePaymentAlternatives"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "variantOptionCounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "avatarClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "selectionCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "traitRental",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "avatarClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "traitAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "lender",
+            "type": "publicKey"
+          },
+          {
+            "name": "duration",
+            "type": "i64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "avatar",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
           }
         ]
       }
@@ -4925,6 +6930,14 @@ export const IDL: RaindropsAvatar = {
           {
             "name": "globalRenderingConfigUri",
             "type": "string"
+          },
+          {
+            "name": "statTemplates",
+            "type": {
+              "vec": {
+                "defined": "StatTemplate"
+              }
+            }
           }
         ]
       }
@@ -4981,6 +6994,22 @@ export const IDL: RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "CreateTraitRentalArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duration",
+            "type": "i64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "CreateTraitArgs",
       "type": {
@@ -5033,6 +7062,14 @@ export const IDL: RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "vec": {
+                "defined": "StatModifier"
+              }
+            }
           }
         ]
       }
@@ -5059,8 +7096,78 @@ export const IDL: RaindropsAvatar = {
           {
             "name": "variantSelection",
             "type": {
-              "vec": {
-                "defined": "VariantOption"
+              "vec": {
+                "defined": "OldVariantOption"
+              }
+            }
+          },
+          {
+            "name": "traitGate",
+            "type": {
+              "option": {
+                "defined": "TraitGate"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "OldVariantMetadata",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "id",
+            "type": "string"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": "VariantStatus"
+            }
+          },
+          {
+            "name": "options",
+            "type": {
+              "vec": {
+                "defined": "OldVariantOption"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "OldVariantOption",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "variantId",
+            "type": "string"
+          },
+          {
+            "name": "optionId",
+            "type": "string"
+          },
+          {
+            "name": "paymentDetails",
+            "type": {
+              "option": {
+                "defined": "OldPaymentDetails"
+              }
+            }
+          },
+          {
+            "name": "traitGate",
+            "type": {
+              "option": {
+                "defined": "TraitGate"
               }
             }
           }
@@ -5068,35 +7175,29 @@ export const IDL: RaindropsAvatar = {
       }
     },
     {
-      "name": "OldAttributeMetadata",
+      "name": "OldPaymentDetails",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "id",
-            "type": "u16"
-          },
-          {
-            "name": "name",
-            "type": "string"
+            "name": "paymentMethod",
+            "type": "publicKey"
           },
           {
-            "name": "status",
-            "type": {
-              "defined": "OldAttributeStatus"
-            }
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "OldAttributeStatus",
+      "name": "TransferPaymentNativeArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "mutable",
-            "type": "bool"
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
@@ -5169,6 +7270,32 @@ export const IDL: RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "UpdateStatTemplateArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statTemplate",
+            "type": {
+              "defined": "StatTemplate"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RemoveStatTemplateArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statId",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
       "name": "UpdateTraitVariantAuthorityArgs",
       "type": {
@@ -5229,6 +7356,16 @@ export const IDL: RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "StatModifier"
+                }
+              }
+            }
           }
         ]
       }
@@ -5297,6 +7434,70 @@ export const IDL: RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "StatTemplate",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u16"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "baseValue",
+            "type": "i64"
+          },
+          {
+            "name": "minValue",
+            "type": "i64"
+          },
+          {
+            "name": "maxValue",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StatModifier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statId",
+            "type": "u16"
+          },
+          {
+            "name": "add",
+            "type": "i64"
+          },
+          {
+            "name": "multiply",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Stat",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u16"
+          },
+          {
+            "name": "value",
+            "type": "i64"
+          }
+        ]
+      }
+    },
     {
       "name": "VariantMetadata",
       "type": {
@@ -5355,12 +7556,54 @@ export const IDL: RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "startTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "endTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "maxSelections",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
     },
     {
       "name": "PaymentDetails",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentMethod",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "alternatives",
+            "type": {
+              "vec": {
+                "defined": "PaymentAlternative"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PaymentAlternative",
       "type": {
         "kind": "struct",
         "fields": [
@@ -5429,6 +7672,20 @@ export const IDL: RaindropsAvatar = {
                 "defined": "TraitGate"
               }
             }
+          },
+          {
+            "name": "rental",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "statModifiers",
+            "type": {
+              "vec": {
+                "defined": "StatModifier"
+              }
+            }
           }
         ]
       }
@@ -5445,6 +7702,22 @@ export const IDL: RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "PaymentSplit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "basisPoints",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
       "name": "PaymentState",
       "type": {
@@ -5465,6 +7738,30 @@ export const IDL: RaindropsAvatar = {
         ]
       }
     },
+    {
+      "name": "UpdatePaymentAlternatives",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payment",
+            "type": {
+              "vec": {
+                "defined": "PaymentAlternative"
+              }
+            }
+          },
+          {
+            "name": "removePayment",
+            "type": {
+              "vec": {
+                "defined": "PaymentAlternative"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "VariantStatus",
       "type": {
@@ -5531,6 +7828,9 @@ export const IDL: RaindropsAvatar = {
                 "type": "publicKey"
               }
             ]
+          },
+          {
+            "name": "Native"
           }
         ]
       }
@@ -5551,6 +7851,19 @@ export const IDL: RaindropsAvatar = {
           },
           {
             "name": "Burn"
+          },
+          {
+            "name": "Split",
+            "fields": [
+              {
+                "name": "recipients",
+                "type": {
+                  "vec": {
+                    "defined": "PaymentSplit"
+                  }
+                }
+              }
+            ]
           }
         ]
       }
@@ -5754,6 +8067,47 @@ export const IDL: RaindropsAvatar = {
           }
         ]
       }
+    },
+    {
+      "name": "NamespaceAndIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "namespace",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "InheritanceState",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NotInherited"
+          },
+          {
+            "name": "Inherited"
+          },
+          {
+            "name": "Overridden"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -5856,6 +8210,106 @@ export const IDL: RaindropsAvatar = {
       "code": 6019,
       "name": "TraitGateFailure",
       "msg": "Failed to Validate Trait Gate"
+    },
+    {
+      "code": 6020,
+      "name": "VariantUnavailable",
+      "msg": "Variant Option is Outside its Availability Window"
+    },
+    {
+      "code": 6021,
+      "name": "VariantSupplyExhausted",
+      "msg": "Variant Option Supply Exhausted"
+    },
+    {
+      "code": 6022,
+      "name": "MissingVariantOptionCounter",
+      "msg": "Variant Option Counter Required"
+    },
+    {
+      "code": 6023,
+      "name": "TooManyPaymentAlternatives",
+      "msg": "Too Many Payment Alternatives"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidPaymentSplit",
+      "msg": "Invalid Payment Split"
+    },
+    {
+      "code": 6025,
+      "name": "TraitRented",
+      "msg": "Trait Is Rented"
+    },
+    {
+      "code": 6026,
+      "name": "TraitRentalUnavailable",
+      "msg": "Trait Rental Unavailable"
+    },
+    {
+      "code": 6027,
+      "name": "TraitRentalNotExpired",
+      "msg": "Trait Rental Not Expired"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidTraitRentalDuration",
+      "msg": "Invalid Trait Rental Duration"
+    },
+    {
+      "code": 6029,
+      "name": "TraitRentalNotAllowed",
+      "msg": "Trait Can Not Be Rented"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidStatTemplate",
+      "msg": "Invalid Stat Template"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidStatId",
+      "msg": "Invalid Stat Id"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidVariantOption",
+      "msg": "Invalid Variant Option"
+    },
+    {
+      "code": 6033,
+      "name": "UnauthorizedCaller",
+      "msg": "Only the Namespace Program Can Call This"
+    },
+    {
+      "code": 6034,
+      "name": "FailedToJoinNamespace",
+      "msg": "Failed to Join Namespace"
+    },
+    {
+      "code": 6035,
+      "name": "FailedToLeaveNamespace",
+      "msg": "Failed to Leave Namespace"
+    },
+    {
+      "code": 6036,
+      "name": "FailedToCache",
+      "msg": "Failed to Cache"
+    },
+    {
+      "code": 6037,
+      "name": "FailedToUncache",
+      "msg": "Failed to Uncache"
+    },
+    {
+      "code": 6038,
+      "name": "NumericalOverflowError",
+      "msg": "Numerical Overflow"
+    },
+    {
+      "code": 6039,
+      "name": "UpdateStateNotMigrated",
+      "msg": "Update State Must Be Migrated"
     }
   ]
 };
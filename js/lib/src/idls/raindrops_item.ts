@@ -890,6 +890,38 @@ export type RaindropsItem = {
         }
       ]
     },
+    {
+      "name": "propagateItemClassInheritance",
+      "accounts": [
+        {
+          "name": "parent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateItemClassData",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "createItemClassV1",
       "accounts": [
@@ -2180,6 +2212,10 @@ export type RaindropsItem = {
             "type": {
               "defined": "ItemData"
             }
+          },
+          {
+            "name": "weightedTokensStaked",
+            "type": "u64"
           }
         ]
       }
@@ -3451,6 +3487,10 @@ export type RaindropsItem = {
           {
             "name": "amount",
             "type": "u64"
+          },
+          {
+            "name": "weightedAmount",
+            "type": "u64"
           }
         ]
       }
@@ -3619,6 +3659,230 @@ export type RaindropsItem = {
             "type": {
               "option": "u64"
             }
+          },
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "option": {
+                "defined": "StackingPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyBasicItemEffect",
+      "docs": [
+        "`BasicItemEffect` as stored before clamps and stacking policies were added,",
+        "used to read data written in that layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "stat",
+            "type": "string"
+          },
+          {
+            "name": "itemEffectType",
+            "type": {
+              "defined": "BasicItemEffectType"
+            }
+          },
+          {
+            "name": "activeDuration",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUses",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyItemUsage",
+      "docs": [
+        "`ItemUsage` with its basic item effects in the legacy layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "index",
+            "type": "u16"
+          },
+          {
+            "name": "basicItemEffects",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "LegacyBasicItemEffect"
+                }
+              }
+            }
+          },
+          {
+            "name": "usagePermissiveness",
+            "type": {
+              "vec": {
+                "defined": "PermissivenessType"
+              }
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          },
+          {
+            "name": "itemClassType",
+            "type": {
+              "defined": "ItemClassType"
+            }
+          },
+          {
+            "name": "callback",
+            "type": {
+              "option": {
+                "defined": "Callback"
+              }
+            }
+          },
+          {
+            "name": "validation",
+            "type": {
+              "option": {
+                "defined": "Callback"
+              }
+            }
+          },
+          {
+            "name": "doNotPairWithSelf",
+            "type": "bool"
+          },
+          {
+            "name": "dnp",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "DNPItem"
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyItemClassConfig",
+      "docs": [
+        "`ItemClassConfig` with its usages in the legacy layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "usageRoot",
+            "type": {
+              "option": {
+                "defined": "Root"
+              }
+            }
+          },
+          {
+            "name": "usageStateRoot",
+            "type": {
+              "option": {
+                "defined": "Root"
+              }
+            }
+          },
+          {
+            "name": "componentRoot",
+            "type": {
+              "option": {
+                "defined": "Root"
+              }
+            }
+          },
+          {
+            "name": "usages",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "LegacyItemUsage"
+                }
+              }
+            }
+          },
+          {
+            "name": "components",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "Component"
+                }
+              }
+            }
+          },
+          {
+            "name": "weight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "propagationVersion",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -3938,6 +4202,18 @@ export type RaindropsItem = {
                 }
               }
             }
+          },
+          {
+            "name": "weight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "propagationVersion",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -3963,16 +4239,44 @@ export type RaindropsItem = {
       }
     },
     {
-      "name": "ItemActivationMarkerProofCounter",
+      "name": "ItemClassDataLayout",
+      "docs": [
+        "Written before the settings and config in the versioned layout, so either",
+        "section can be found without deserializing the other."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "statesProven",
-            "type": "u16"
+            "name": "marker",
+            "type": "u8"
           },
           {
-            "name": "statesRequired",
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "settingsLen",
+            "type": "u32"
+          },
+          {
+            "name": "configLen",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ItemActivationMarkerProofCounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statesProven",
+            "type": "u16"
+          },
+          {
+            "name": "statesRequired",
             "type": "u16"
           },
           {
@@ -4329,6 +4633,29 @@ export type RaindropsItem = {
           },
           {
             "name": "DecrementPercentFromBase"
+          },
+          {
+            "name": "Set"
+          },
+          {
+            "name": "Clamp"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StackingPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Stack"
+          },
+          {
+            "name": "RefreshDuration"
+          },
+          {
+            "name": "HighestWins"
           }
         ]
       }
@@ -5388,6 +5715,38 @@ export const IDL: RaindropsItem = {
         }
       ]
     },
+    {
+      "name": "propagateItemClassInheritance",
+      "accounts": [
+        {
+          "name": "parent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateItemClassData",
+      "accounts": [
+        {
+          "name": "itemClass",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "createItemClassV1",
       "accounts": [
@@ -6678,6 +7037,10 @@ export const IDL: RaindropsItem = {
             "type": {
               "defined": "ItemData"
             }
+          },
+          {
+            "name": "weightedTokensStaked",
+            "type": "u64"
           }
         ]
       }
@@ -7949,6 +8312,10 @@ export const IDL: RaindropsItem = {
           {
             "name": "amount",
             "type": "u64"
+          },
+          {
+            "name": "weightedAmount",
+            "type": "u64"
           }
         ]
       }
@@ -8117,6 +8484,230 @@ export const IDL: RaindropsItem = {
             "type": {
               "option": "u64"
             }
+          },
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "option": {
+                "defined": "StackingPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyBasicItemEffect",
+      "docs": [
+        "`BasicItemEffect` as stored before clamps and stacking policies were added,",
+        "used to read data written in that layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "stat",
+            "type": "string"
+          },
+          {
+            "name": "itemEffectType",
+            "type": {
+              "defined": "BasicItemEffectType"
+            }
+          },
+          {
+            "name": "activeDuration",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUses",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyItemUsage",
+      "docs": [
+        "`ItemUsage` with its basic item effects in the legacy layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "index",
+            "type": "u16"
+          },
+          {
+            "name": "basicItemEffects",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "LegacyBasicItemEffect"
+                }
+              }
+            }
+          },
+          {
+            "name": "usagePermissiveness",
+            "type": {
+              "vec": {
+                "defined": "PermissivenessType"
+              }
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          },
+          {
+            "name": "itemClassType",
+            "type": {
+              "defined": "ItemClassType"
+            }
+          },
+          {
+            "name": "callback",
+            "type": {
+              "option": {
+                "defined": "Callback"
+              }
+            }
+          },
+          {
+            "name": "validation",
+            "type": {
+              "option": {
+                "defined": "Callback"
+              }
+            }
+          },
+          {
+            "name": "doNotPairWithSelf",
+            "type": "bool"
+          },
+          {
+            "name": "dnp",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "DNPItem"
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyItemClassConfig",
+      "docs": [
+        "`ItemClassConfig` with its usages in the legacy layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "usageRoot",
+            "type": {
+              "option": {
+                "defined": "Root"
+              }
+            }
+          },
+          {
+            "name": "usageStateRoot",
+            "type": {
+              "option": {
+                "defined": "Root"
+              }
+            }
+          },
+          {
+            "name": "componentRoot",
+            "type": {
+              "option": {
+                "defined": "Root"
+              }
+            }
+          },
+          {
+            "name": "usages",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "LegacyItemUsage"
+                }
+              }
+            }
+          },
+          {
+            "name": "components",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "Component"
+                }
+              }
+            }
+          },
+          {
+            "name": "weight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "propagationVersion",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -8436,6 +9027,18 @@ export const IDL: RaindropsItem = {
                 }
               }
             }
+          },
+          {
+            "name": "weight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "propagationVersion",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -8460,6 +9063,34 @@ export const IDL: RaindropsItem = {
         ]
       }
     },
+    {
+      "name": "ItemClassDataLayout",
+      "docs": [
+        "Written before the settings and config in the versioned layout, so either",
+        "section can be found without deserializing the other."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marker",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "settingsLen",
+            "type": "u32"
+          },
+          {
+            "name": "configLen",
+            "type": "u32"
+          }
+        ]
+      }
+    },
     {
       "name": "ItemActivationMarkerProofCounter",
       "type": {
@@ -8827,6 +9458,29 @@ export const IDL: RaindropsItem = {
           },
           {
             "name": "DecrementPercentFromBase"
+          },
+          {
+            "name": "Set"
+          },
+          {
+            "name": "Clamp"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StackingPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Stack"
+          },
+          {
+            "name": "RefreshDuration"
+          },
+          {
+            "name": "HighestWins"
           }
         ]
       }
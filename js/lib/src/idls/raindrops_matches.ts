@@ -96,6 +96,59 @@ export type RaindropsMatches = {
         }
       ]
     },
+    {
+      "name": "updateMatchEconomics",
+      "accounts": [
+        {
+          "name": "matchInstance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "prizePoolEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "entryFeeMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateMatchEconomicsArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "updateMatchFromOracle",
       "accounts": [
@@ -247,6 +300,11 @@ export type RaindropsMatches = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -272,6 +330,82 @@ export type RaindropsMatches = {
         }
       ]
     },
+    {
+      "name": "disburseTokensByOracleBatch",
+      "accounts": [
+        {
+          "name": "matchInstance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "winOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DisburseTokensByOracleBatchArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "disbursePrizePool",
+      "accounts": [
+        {
+          "name": "matchInstance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "winOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "prizePoolEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DisbursePrizePoolArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "joinMatch",
       "accounts": [
@@ -483,10 +617,10 @@ export type RaindropsMatches = {
             "type": "u8"
           },
           {
-            "name": "currentTokenTransferIndex",
+            "name": "tokenTransfersCompleted",
             "docs": [
-              "Increased by 1 every time the next token transfer",
-              "in the win oracle is completed."
+              "Increased by 1 every time a token transfer",
+              "in the win oracle is completed, in any order."
             ],
             "type": "u64"
           },
@@ -519,6 +653,63 @@ export type RaindropsMatches = {
           {
             "name": "joinAllowedDuringStart",
             "type": "bool"
+          },
+          {
+            "name": "tokenTransfersClaimed",
+            "docs": [
+              "One bit per win oracle token transfer, set once it is disbursed."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "entryFee",
+            "docs": [
+              "Paid into the prize pool on every join when present."
+            ],
+            "type": {
+              "option": {
+                "defined": "EntryFee"
+              }
+            }
+          },
+          {
+            "name": "rake",
+            "type": {
+              "option": {
+                "defined": "Rake"
+              }
+            }
+          },
+          {
+            "name": "payoutSchedule",
+            "type": {
+              "defined": "PayoutSchedule"
+            }
+          },
+          {
+            "name": "prizePool",
+            "docs": [
+              "Entry fees collected, net of refunds to entrants who left."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "prizePoolPaid",
+            "docs": [
+              "Paid out of the prize pool to ranked players and the treasury."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rakePaid",
+            "type": "bool"
+          },
+          {
+            "name": "prizesClaimed",
+            "docs": [
+              "One bit per payout schedule place, set once it is paid."
+            ],
+            "type": "bytes"
           }
         ]
       }
@@ -561,6 +752,14 @@ export type RaindropsMatches = {
                 }
               }
             }
+          },
+          {
+            "name": "rankings",
+            "type": {
+              "option": {
+                "vec": "publicKey"
+              }
+            }
           }
         ]
       }
@@ -601,6 +800,14 @@ export type RaindropsMatches = {
           {
             "name": "finalized",
             "type": "bool"
+          },
+          {
+            "name": "rankings",
+            "type": {
+              "option": {
+                "vec": "publicKey"
+              }
+            }
           }
         ]
       }
@@ -737,6 +944,32 @@ export type RaindropsMatches = {
         ]
       }
     },
+    {
+      "name": "UpdateMatchEconomicsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "entryFeeAmount",
+            "type": "u64"
+          },
+          {
+            "name": "rake",
+            "type": {
+              "option": {
+                "defined": "Rake"
+              }
+            }
+          },
+          {
+            "name": "payoutSchedule",
+            "type": {
+              "defined": "PayoutSchedule"
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "JoinMatchArgs",
       "type": {
@@ -794,6 +1027,40 @@ export type RaindropsMatches = {
                 "defined": "TokenDeltaProofInfo"
               }
             }
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DisburseTokensByOracleBatchArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenDeltas",
+            "type": {
+              "vec": {
+                "defined": "DisburseTokensByOracleArgs"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "DisbursePrizePoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rank",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -922,6 +1189,38 @@ export type RaindropsMatches = {
         ]
       }
     },
+    {
+      "name": "EntryFee",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Rake",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "basisPoints",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
       "name": "TokenDelta",
       "type": {
@@ -1009,6 +1308,31 @@ export type RaindropsMatches = {
         ]
       }
     },
+    {
+      "name": "PayoutSchedule",
+      "docs": [
+        "How the prize pool, less the rake, is split across the oracle rankings."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "WinnerTakeAll"
+          },
+          {
+            "name": "TopN",
+            "fields": [
+              {
+                "name": "basis_points",
+                "type": {
+                  "vec": "u16"
+                }
+              }
+            ]
+          }
+        ]
+      }
+    },
     {
       "name": "PermissivenessType",
       "type": {
@@ -1342,6 +1666,81 @@ export type RaindropsMatches = {
       "code": 6043,
       "name": "NotCached",
       "msg": "Not cached"
+    },
+    {
+      "code": 6044,
+      "name": "TokenTransferAlreadyClaimed",
+      "msg": "This token transfer has already been disbursed"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidTokenTransferIndex",
+      "msg": "Token transfer index is out of range for this oracle"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidRemainingAccounts",
+      "msg": "Remaining accounts must hold escrow, mint, destination and original sender per delta"
+    },
+    {
+      "code": 6047,
+      "name": "CannotSetEconomics",
+      "msg": "Match economics can only be set once, while the match is in draft"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidPayoutSchedule",
+      "msg": "Payout schedule must have between 1 and 10 places adding up to 10000 basis points"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidRake",
+      "msg": "Rake cannot be more than 10000 basis points"
+    },
+    {
+      "code": 6050,
+      "name": "MissingPrizePoolAccounts",
+      "msg": "Remaining accounts must hold the prize pool escrow and the entry fee token account"
+    },
+    {
+      "code": 6051,
+      "name": "EntryFeeNotSet",
+      "msg": "This match has no entry fee"
+    },
+    {
+      "code": 6052,
+      "name": "RakeNotSet",
+      "msg": "This match has no rake"
+    },
+    {
+      "code": 6053,
+      "name": "NoRankingsFound",
+      "msg": "This oracle lacks any rankings"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidRank",
+      "msg": "Rank is not paid by this payout schedule"
+    },
+    {
+      "code": 6055,
+      "name": "PrizeAlreadyClaimed",
+      "msg": "This prize has already been disbursed"
+    },
+    {
+      "code": 6056,
+      "name": "PrizePoolNotPaidOut",
+      "msg": "Prize pool must be paid out first"
+    },
+    {
+      "code": 6057,
+      "name": "TooManyRankings",
+      "msg": "Oracles can rank at most 10 players"
+    },
+    {
+      "code": 6058,
+      "name": "EntryFeeNeedsTokens",
+      "msg": "Matches with an entry fee must be joined with tokens"
     }
   ]
 };
@@ -1430,16 +1829,69 @@ export const IDL: RaindropsMatches = {
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateMatchArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateMatchEconomics",
+      "accounts": [
+        {
+          "name": "matchInstance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "prizePoolEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "entryFeeMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "args",
           "type": {
-            "defined": "UpdateMatchArgs"
+            "defined": "UpdateMatchEconomicsArgs"
           }
         }
       ]
@@ -1595,6 +2047,11 @@ export const IDL: RaindropsMatches = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1620,6 +2077,82 @@ export const IDL: RaindropsMatches = {
         }
       ]
     },
+    {
+      "name": "disburseTokensByOracleBatch",
+      "accounts": [
+        {
+          "name": "matchInstance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "winOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DisburseTokensByOracleBatchArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "disbursePrizePool",
+      "accounts": [
+        {
+          "name": "matchInstance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "winOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "prizePoolEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DisbursePrizePoolArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "joinMatch",
       "accounts": [
@@ -1831,10 +2364,10 @@ export const IDL: RaindropsMatches = {
             "type": "u8"
           },
           {
-            "name": "currentTokenTransferIndex",
+            "name": "tokenTransfersCompleted",
             "docs": [
-              "Increased by 1 every time the next token transfer",
-              "in the win oracle is completed."
+              "Increased by 1 every time a token transfer",
+              "in the win oracle is completed, in any order."
             ],
             "type": "u64"
           },
@@ -1867,6 +2400,63 @@ export const IDL: RaindropsMatches = {
           {
             "name": "joinAllowedDuringStart",
             "type": "bool"
+          },
+          {
+            "name": "tokenTransfersClaimed",
+            "docs": [
+              "One bit per win oracle token transfer, set once it is disbursed."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "entryFee",
+            "docs": [
+              "Paid into the prize pool on every join when present."
+            ],
+            "type": {
+              "option": {
+                "defined": "EntryFee"
+              }
+            }
+          },
+          {
+            "name": "rake",
+            "type": {
+              "option": {
+                "defined": "Rake"
+              }
+            }
+          },
+          {
+            "name": "payoutSchedule",
+            "type": {
+              "defined": "PayoutSchedule"
+            }
+          },
+          {
+            "name": "prizePool",
+            "docs": [
+              "Entry fees collected, net of refunds to entrants who left."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "prizePoolPaid",
+            "docs": [
+              "Paid out of the prize pool to ranked players and the treasury."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rakePaid",
+            "type": "bool"
+          },
+          {
+            "name": "prizesClaimed",
+            "docs": [
+              "One bit per payout schedule place, set once it is paid."
+            ],
+            "type": "bytes"
           }
         ]
       }
@@ -1909,6 +2499,14 @@ export const IDL: RaindropsMatches = {
                 }
               }
             }
+          },
+          {
+            "name": "rankings",
+            "type": {
+              "option": {
+                "vec": "publicKey"
+              }
+            }
           }
         ]
       }
@@ -1949,6 +2547,14 @@ export const IDL: RaindropsMatches = {
           {
             "name": "finalized",
             "type": "bool"
+          },
+          {
+            "name": "rankings",
+            "type": {
+              "option": {
+                "vec": "publicKey"
+              }
+            }
           }
         ]
       }
@@ -2085,6 +2691,32 @@ export const IDL: RaindropsMatches = {
         ]
       }
     },
+    {
+      "name": "UpdateMatchEconomicsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "entryFeeAmount",
+            "type": "u64"
+          },
+          {
+            "name": "rake",
+            "type": {
+              "option": {
+                "defined": "Rake"
+              }
+            }
+          },
+          {
+            "name": "payoutSchedule",
+            "type": {
+              "defined": "PayoutSchedule"
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "JoinMatchArgs",
       "type": {
@@ -2142,6 +2774,40 @@ export const IDL: RaindropsMatches = {
                 "defined": "TokenDeltaProofInfo"
               }
             }
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DisburseTokensByOracleBatchArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenDeltas",
+            "type": {
+              "vec": {
+                "defined": "DisburseTokensByOracleArgs"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "DisbursePrizePoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rank",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -2270,6 +2936,38 @@ export const IDL: RaindropsMatches = {
         ]
       }
     },
+    {
+      "name": "EntryFee",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Rake",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "basisPoints",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
       "name": "TokenDelta",
       "type": {
@@ -2357,6 +3055,31 @@ export const IDL: RaindropsMatches = {
         ]
       }
     },
+    {
+      "name": "PayoutSchedule",
+      "docs": [
+        "How the prize pool, less the rake, is split across the oracle rankings."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "WinnerTakeAll"
+          },
+          {
+            "name": "TopN",
+            "fields": [
+              {
+                "name": "basis_points",
+                "type": {
+                  "vec": "u16"
+                }
+              }
+            ]
+          }
+        ]
+      }
+    },
     {
       "name": "PermissivenessType",
       "type": {
@@ -2690,6 +3413,81 @@ export const IDL: RaindropsMatches = {
       "code": 6043,
       "name": "NotCached",
       "msg": "Not cached"
+    },
+    {
+      "code": 6044,
+      "name": "TokenTransferAlreadyClaimed",
+      "msg": "This token transfer has already been disbursed"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidTokenTransferIndex",
+      "msg": "Token transfer index is out of range for this oracle"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidRemainingAccounts",
+      "msg": "Remaining accounts must hold escrow, mint, destination and original sender per delta"
+    },
+    {
+      "code": 6047,
+      "name": "CannotSetEconomics",
+      "msg": "Match economics can only be set once, while the match is in draft"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidPayoutSchedule",
+      "msg": "Payout schedule must have between 1 and 10 places adding up to 10000 basis points"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidRake",
+      "msg": "Rake cannot be more than 10000 basis points"
+    },
+    {
+      "code": 6050,
+      "name": "MissingPrizePoolAccounts",
+      "msg": "Remaining accounts must hold the prize pool escrow and the entry fee token account"
+    },
+    {
+      "code": 6051,
+      "name": "EntryFeeNotSet",
+      "msg": "This match has no entry fee"
+    },
+    {
+      "code": 6052,
+      "name": "RakeNotSet",
+      "msg": "This match has no rake"
+    },
+    {
+      "code": 6053,
+      "name": "NoRankingsFound",
+      "msg": "This oracle lacks any rankings"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidRank",
+      "msg": "Rank is not paid by this payout schedule"
+    },
+    {
+      "code": 6055,
+      "name": "PrizeAlreadyClaimed",
+      "msg": "This prize has already been disbursed"
+    },
+    {
+      "code": 6056,
+      "name": "PrizePoolNotPaidOut",
+      "msg": "Prize pool must be paid out first"
+    },
+    {
+      "code": 6057,
+      "name": "TooManyRankings",
+      "msg": "Oracles can rank at most 10 players"
+    },
+    {
+      "code": 6058,
+      "name": "EntryFeeNeedsTokens",
+      "msg": "Matches with an entry fee must be joined with tokens"
     }
   ]
 };
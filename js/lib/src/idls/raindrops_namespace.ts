@@ -83,6 +83,123 @@ export type RaindropsNamespace = {
         }
       ]
     },
+    {
+      "name": "initializeProtocolFeeConfig",
+      "accounts": [
+        {
+          "name": "protocolFeeConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "program",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programData",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "InitializeProtocolFeeConfigArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateProtocolFeeConfig",
+      "accounts": [
+        {
+          "name": "protocolFeeConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateProtocolFeeConfigArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setNamespaceProtocolFeeExempt",
+      "accounts": [
+        {
+          "name": "protocolFeeConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "exempt",
+          "type": "bool"
+        }
+      ]
+    },
     {
       "name": "cacheArtifact",
       "accounts": [
@@ -199,6 +316,54 @@ export type RaindropsNamespace = {
         }
       ]
     },
+    {
+      "name": "compactIndex",
+      "accounts": [
+        {
+          "name": "namespace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespaceToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "fromIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "toIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenHolder",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CompactIndexArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "createNamespaceGatekeeper",
       "accounts": [
@@ -262,6 +427,16 @@ export type RaindropsNamespace = {
           "name": "tokenHolder",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -295,6 +470,16 @@ export type RaindropsNamespace = {
           "name": "tokenHolder",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -331,9 +516,14 @@ export type RaindropsNamespace = {
         },
         {
           "name": "tokenHolder",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "membershipPayment",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "instructions",
           "isMut": false,
@@ -343,6 +533,16 @@ export type RaindropsNamespace = {
           "name": "raindropsProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
@@ -376,17 +576,22 @@ export type RaindropsNamespace = {
           "isSigner": true
         },
         {
-          "name": "rainTokenMint",
+          "name": "membershipPayment",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "protocolFeeConfig",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rainTokenVault",
+          "name": "protocolFeeVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rainPayerAta",
+          "name": "protocolFeePayerAta",
           "isMut": true,
           "isSigner": false
         },
@@ -404,9 +609,21 @@ export type RaindropsNamespace = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "JoinNamespaceArgs"
+          }
+        }
+      ]
     },
     {
       "name": "itemValidation",
@@ -580,6 +797,64 @@ export type RaindropsNamespace = {
             "type": {
               "option": "u64"
             }
+          },
+          {
+            "name": "protocolFeeExempt",
+            "type": "bool"
+          },
+          {
+            "name": "refundPaymentOnLeave",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolFeeConfig",
+      "docs": [
+        "seed ['namespace', 'protocol_fee']"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "membershipPayment",
+      "docs": [
+        "seed ['namespace', namespace, artifact, 'membership_payment']"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
@@ -609,6 +884,12 @@ export type RaindropsNamespace = {
             "type": {
               "vec": "publicKey"
             }
+          },
+          {
+            "name": "sortKeys",
+            "type": {
+              "vec": "u64"
+            }
           }
         ]
       }
@@ -679,29 +960,110 @@ export type RaindropsNamespace = {
                 "vec": "publicKey"
               }
             }
+          },
+          {
+            "name": "refundPaymentOnLeave",
+            "type": {
+              "option": "bool"
+            }
           }
         ]
       }
     },
     {
-      "name": "CacheArtifactArgs",
+      "name": "InitializeProtocolFeeConfigArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "page",
+            "name": "feeAmount",
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "UncacheArtifactArgs",
+      "name": "UpdateProtocolFeeConfigArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "page",
+            "name": "admin",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "feeAmount",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "JoinNamespaceArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allowlistProofs",
+            "type": {
+              "vec": {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "CacheArtifactArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "page",
+            "type": "u64"
+          },
+          {
+            "name": "sortKey",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "UncacheArtifactArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "page",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CompactIndexArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fromPage",
+            "type": "u64"
+          },
+          {
+            "name": "toPage",
             "type": "u64"
           }
         ]
@@ -977,6 +1339,42 @@ export type RaindropsNamespace = {
                 }
               }
             ]
+          },
+          {
+            "name": "VerifiedCollection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "MerkleAllowlist",
+            "fields": [
+              {
+                "name": "root",
+                "type": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            ]
+          },
+          {
+            "name": "TokenBalance",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "minimumAmount",
+                "type": "u64"
+              }
+            ]
           }
         ]
       }
@@ -1038,6 +1436,26 @@ export type RaindropsNamespace = {
           }
         ]
       }
+    },
+    {
+      "name": "PermissivenessType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "TokenHolder"
+          },
+          {
+            "name": "ParentTokenHolder"
+          },
+          {
+            "name": "UpdateAuthority"
+          },
+          {
+            "name": "Anybody"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1170,6 +1588,46 @@ export type RaindropsNamespace = {
       "code": 6025,
       "name": "InvalidRemainingAccounts",
       "msg": "Invalid Remaining Accounts"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidProtocolFeeAccount",
+      "msg": "Protocol fee account does not match the protocol fee config"
+    },
+    {
+      "code": 6027,
+      "name": "NotUpgradeAuthority",
+      "msg": "Only the program upgrade authority can do this"
+    },
+    {
+      "code": 6028,
+      "name": "PaymentsNotConfigured",
+      "msg": "Namespace payments are not configured"
+    },
+    {
+      "code": 6029,
+      "name": "PaymentVaultNotOwnedByNamespace",
+      "msg": "Payment vault must be owned by the namespace to refund payments"
+    },
+    {
+      "code": 6030,
+      "name": "HolderFilterCannotBlacklist",
+      "msg": "Holder filters can only whitelist, a holder can always withhold their proof"
+    },
+    {
+      "code": 6031,
+      "name": "MissingAllowlistProof",
+      "msg": "Missing merkle allowlist proof"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidCompaction",
+      "msg": "Index entries can only be compacted into an earlier page"
+    },
+    {
+      "code": 6033,
+      "name": "ArtifactNotInIndex",
+      "msg": "Artifact is not cached in this index"
     }
   ]
 };
@@ -1247,20 +1705,195 @@ export const IDL: RaindropsNamespace = {
         {
           "name": "tokenHolder",
           "isMut": false,
-          "isSigner": true
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateNamespaceArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializeProtocolFeeConfig",
+      "accounts": [
+        {
+          "name": "protocolFeeConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "program",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programData",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "InitializeProtocolFeeConfigArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateProtocolFeeConfig",
+      "accounts": [
+        {
+          "name": "protocolFeeConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateProtocolFeeConfigArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setNamespaceProtocolFeeExempt",
+      "accounts": [
+        {
+          "name": "protocolFeeConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "exempt",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "cacheArtifact",
+      "accounts": [
+        {
+          "name": "namespace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "namespaceToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "index",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenHolder",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "raindropsProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "args",
           "type": {
-            "defined": "UpdateNamespaceArgs"
+            "defined": "CacheArtifactArgs"
           }
         }
       ]
     },
     {
-      "name": "cacheArtifact",
+      "name": "uncacheArtifact",
       "accounts": [
         {
           "name": "namespace",
@@ -1288,17 +1921,17 @@ export const IDL: RaindropsNamespace = {
           "isSigner": true
         },
         {
-          "name": "instructions",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
         },
@@ -1312,13 +1945,13 @@ export const IDL: RaindropsNamespace = {
         {
           "name": "args",
           "type": {
-            "defined": "CacheArtifactArgs"
+            "defined": "UncacheArtifactArgs"
           }
         }
       ]
     },
     {
-      "name": "uncacheArtifact",
+      "name": "compactIndex",
       "accounts": [
         {
           "name": "namespace",
@@ -1331,12 +1964,12 @@ export const IDL: RaindropsNamespace = {
           "isSigner": false
         },
         {
-          "name": "index",
+          "name": "fromIndex",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "artifact",
+          "name": "toIndex",
           "isMut": true,
           "isSigner": false
         },
@@ -1345,23 +1978,13 @@ export const IDL: RaindropsNamespace = {
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "instructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "raindropsProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -1370,7 +1993,7 @@ export const IDL: RaindropsNamespace = {
         {
           "name": "args",
           "type": {
-            "defined": "UncacheArtifactArgs"
+            "defined": "CompactIndexArgs"
           }
         }
       ]
@@ -1438,6 +2061,16 @@ export const IDL: RaindropsNamespace = {
           "name": "tokenHolder",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -1471,6 +2104,16 @@ export const IDL: RaindropsNamespace = {
           "name": "tokenHolder",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -1507,9 +2150,14 @@ export const IDL: RaindropsNamespace = {
         },
         {
           "name": "tokenHolder",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "membershipPayment",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "instructions",
           "isMut": false,
@@ -1519,6 +2167,16 @@ export const IDL: RaindropsNamespace = {
           "name": "raindropsProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
@@ -1552,17 +2210,22 @@ export const IDL: RaindropsNamespace = {
           "isSigner": true
         },
         {
-          "name": "rainTokenMint",
+          "name": "membershipPayment",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "protocolFeeConfig",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rainTokenVault",
+          "name": "protocolFeeVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rainPayerAta",
+          "name": "protocolFeePayerAta",
           "isMut": true,
           "isSigner": false
         },
@@ -1580,9 +2243,21 @@ export const IDL: RaindropsNamespace = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "JoinNamespaceArgs"
+          }
+        }
+      ]
     },
     {
       "name": "itemValidation",
@@ -1756,6 +2431,64 @@ export const IDL: RaindropsNamespace = {
             "type": {
               "option": "u64"
             }
+          },
+          {
+            "name": "protocolFeeExempt",
+            "type": "bool"
+          },
+          {
+            "name": "refundPaymentOnLeave",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolFeeConfig",
+      "docs": [
+        "seed ['namespace', 'protocol_fee']"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "membershipPayment",
+      "docs": [
+        "seed ['namespace', namespace, artifact, 'membership_payment']"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
@@ -1785,6 +2518,12 @@ export const IDL: RaindropsNamespace = {
             "type": {
               "vec": "publicKey"
             }
+          },
+          {
+            "name": "sortKeys",
+            "type": {
+              "vec": "u64"
+            }
           }
         ]
       }
@@ -1855,6 +2594,65 @@ export const IDL: RaindropsNamespace = {
                 "vec": "publicKey"
               }
             }
+          },
+          {
+            "name": "refundPaymentOnLeave",
+            "type": {
+              "option": "bool"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "InitializeProtocolFeeConfigArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "feeAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UpdateProtocolFeeConfigArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "feeAmount",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "JoinNamespaceArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allowlistProofs",
+            "type": {
+              "vec": {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            }
           }
         ]
       }
@@ -1867,6 +2665,12 @@ export const IDL: RaindropsNamespace = {
           {
             "name": "page",
             "type": "u64"
+          },
+          {
+            "name": "sortKey",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -1883,6 +2687,22 @@ export const IDL: RaindropsNamespace = {
         ]
       }
     },
+    {
+      "name": "CompactIndexArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fromPage",
+            "type": "u64"
+          },
+          {
+            "name": "toPage",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "ArtifactFilter",
       "type": {
@@ -2153,6 +2973,42 @@ export const IDL: RaindropsNamespace = {
                 }
               }
             ]
+          },
+          {
+            "name": "VerifiedCollection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "MerkleAllowlist",
+            "fields": [
+              {
+                "name": "root",
+                "type": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            ]
+          },
+          {
+            "name": "TokenBalance",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "minimumAmount",
+                "type": "u64"
+              }
+            ]
           }
         ]
       }
@@ -2214,6 +3070,26 @@ export const IDL: RaindropsNamespace = {
           }
         ]
       }
+    },
+    {
+      "name": "PermissivenessType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "TokenHolder"
+          },
+          {
+            "name": "ParentTokenHolder"
+          },
+          {
+            "name": "UpdateAuthority"
+          },
+          {
+            "name": "Anybody"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -2346,6 +3222,46 @@ export const IDL: RaindropsNamespace = {
       "code": 6025,
       "name": "InvalidRemainingAccounts",
       "msg": "Invalid Remaining Accounts"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidProtocolFeeAccount",
+      "msg": "Protocol fee account does not match the protocol fee config"
+    },
+    {
+      "code": 6027,
+      "name": "NotUpgradeAuthority",
+      "msg": "Only the program upgrade authority can do this"
+    },
+    {
+      "code": 6028,
+      "name": "PaymentsNotConfigured",
+      "msg": "Namespace payments are not configured"
+    },
+    {
+      "code": 6029,
+      "name": "PaymentVaultNotOwnedByNamespace",
+      "msg": "Payment vault must be owned by the namespace to refund payments"
+    },
+    {
+      "code": 6030,
+      "name": "HolderFilterCannotBlacklist",
+      "msg": "Holder filters can only whitelist, a holder can always withhold their proof"
+    },
+    {
+      "code": 6031,
+      "name": "MissingAllowlistProof",
+      "msg": "Missing merkle allowlist proof"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidCompaction",
+      "msg": "Index entries can only be compacted into an earlier page"
+    },
+    {
+      "code": 6033,
+      "name": "ArtifactNotInIndex",
+      "msg": "Artifact is not cached in this index"
     }
   ]
 };
@@ -593,6 +593,27 @@ export type RaindropsPlayer = {
       ],
       "args": []
     },
+    {
+      "name": "expireItemEffects",
+      "accounts": [
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "addItemEffect",
       "accounts": [
@@ -737,6 +758,29 @@ export type RaindropsPlayer = {
         }
       ]
     },
+    {
+      "name": "updateTokensStaked",
+      "accounts": [
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructionSysvarAccount",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateTokensStakedArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "playerArtifactJoinNamespace",
       "accounts": [
@@ -980,6 +1024,38 @@ export type RaindropsPlayer = {
           {
             "name": "tokensPaidIn",
             "type": "u64"
+          },
+          {
+            "name": "activeStatEffects",
+            "type": {
+              "vec": {
+                "defined": "ActiveStatEffect"
+              }
+            }
+          },
+          {
+            "name": "backpackWeight",
+            "type": "u64"
+          },
+          {
+            "name": "equippedItemClasses",
+            "type": {
+              "vec": {
+                "defined": "EquippedItemClass"
+              }
+            }
+          },
+          {
+            "name": "weightedTokensStaked",
+            "type": "u64"
+          },
+          {
+            "name": "backpackItems",
+            "type": {
+              "vec": {
+                "defined": "BackpackItem"
+              }
+            }
           }
         ]
       }
@@ -1010,7 +1086,7 @@ export type RaindropsPlayer = {
             "type": {
               "option": {
                 "vec": {
-                  "defined": "BasicItemEffect"
+                  "defined": "LegacyBasicItemEffect"
                 }
               }
             }
@@ -1032,6 +1108,12 @@ export type RaindropsPlayer = {
           {
             "name": "activeItemCounter",
             "type": "u64"
+          },
+          {
+            "name": "additions",
+            "type": {
+              "defined": "PlayerItemActivationMarkerAdditions"
+            }
           }
         ]
       }
@@ -1746,6 +1828,34 @@ export type RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "UpdateTokensStakedArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "playerMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "staked",
+            "type": "bool"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "weightedAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "EquippedItem",
       "type": {
@@ -1766,6 +1876,42 @@ export type RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "EquippedItemClass",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BackpackItem",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "item",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "NamespaceAndIndex",
       "type": {
@@ -1878,6 +2024,123 @@ export type RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "Backpack",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxItems",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxWeight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "statBonuses",
+            "type": {
+              "vec": {
+                "defined": "BackpackCapacityBonus"
+              }
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BackpackCapacityBonus",
+      "docs": [
+        "Extra backpack capacity given by each point of an integer stat."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statIndex",
+            "type": "u16"
+          },
+          {
+            "name": "itemsPerPoint",
+            "type": "u64"
+          },
+          {
+            "name": "weightPerPoint",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EquipmentSet",
+      "docs": [
+        "Item classes that give extra effects when enough different ones of them are",
+        "equipped at the same time."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "index",
+            "type": "u16"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "itemClasses",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "bonuses",
+            "type": {
+              "vec": {
+                "defined": "EquipmentSetBonus"
+              }
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "EquipmentSetBonus",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pieces",
+            "type": "u16"
+          },
+          {
+            "name": "basicItemEffects",
+            "type": {
+              "vec": {
+                "defined": "BasicItemEffect"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "PlayerClassData",
       "type": {
@@ -2138,6 +2401,24 @@ export type RaindropsPlayer = {
                 "defined": "Callback"
               }
             }
+          },
+          {
+            "name": "backpack",
+            "type": {
+              "option": {
+                "defined": "Backpack"
+              }
+            }
+          },
+          {
+            "name": "equipmentSets",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "EquipmentSet"
+                }
+              }
+            }
           }
         ]
       }
@@ -2308,28 +2589,110 @@ export type RaindropsPlayer = {
             "type": {
               "option": "u64"
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "PermissivenessType",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "TokenHolder"
           },
           {
-            "name": "ParentTokenHolder"
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
           },
           {
-            "name": "UpdateAuthority"
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
           },
           {
-            "name": "Anybody"
-          }
-        ]
+            "name": "stackingPolicy",
+            "type": {
+              "option": {
+                "defined": "StackingPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyBasicItemEffect",
+      "docs": [
+        "`BasicItemEffect` as stored before clamps and stacking policies were added,",
+        "used to read data written in that layout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "stat",
+            "type": "string"
+          },
+          {
+            "name": "itemEffectType",
+            "type": {
+              "defined": "BasicItemEffectType"
+            }
+          },
+          {
+            "name": "activeDuration",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUses",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PermissivenessType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "TokenHolder"
+          },
+          {
+            "name": "ParentTokenHolder"
+          },
+          {
+            "name": "UpdateAuthority"
+          },
+          {
+            "name": "Anybody"
+          }
+        ]
       }
     },
     {
@@ -2398,6 +2761,12 @@ export type RaindropsPlayer = {
           },
           {
             "name": "StatsUri"
+          },
+          {
+            "name": "Backpack"
+          },
+          {
+            "name": "EquipmentSets"
           }
         ]
       }
@@ -2547,6 +2916,54 @@ export type RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "ActiveStatEffect",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statIndex",
+            "type": "u16"
+          },
+          {
+            "name": "itemClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "itemEffectType",
+            "type": {
+              "defined": "BasicItemEffectType"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "defined": "StackingPolicy"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "i64"
+          },
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "StatDiffType",
       "type": {
@@ -2583,6 +3000,79 @@ export type RaindropsPlayer = {
           },
           {
             "name": "DecrementPercentFromBase"
+          },
+          {
+            "name": "Set"
+          },
+          {
+            "name": "Clamp"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PlayerItemActivationMarkerAdditions",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payer",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "effectPolicies",
+            "type": {
+              "vec": {
+                "defined": "BasicItemEffectPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BasicItemEffectPolicy",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "option": {
+                "defined": "StackingPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "StackingPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Stack"
+          },
+          {
+            "name": "RefreshDuration"
+          },
+          {
+            "name": "HighestWins"
           }
         ]
       }
@@ -2838,6 +3328,46 @@ export type RaindropsPlayer = {
       "code": 6049,
       "name": "AmountMustBeGreaterThanZero",
       "msg": "Amount must be greater than zero"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidExpiryAccounts",
+      "msg": "Markers must be passed as marker, item and payer account triples"
+    },
+    {
+      "code": 6051,
+      "name": "MarkerDoesNotBelongToPlayer",
+      "msg": "Activation marker belongs to a different player"
+    },
+    {
+      "code": 6052,
+      "name": "BackpackFull",
+      "msg": "Backpack cannot hold this many items"
+    },
+    {
+      "code": 6053,
+      "name": "BackpackTooHeavy",
+      "msg": "Backpack cannot hold this much weight"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidEquipmentSetBonus",
+      "msg": "Equipment set bonuses need between one and all of the set's item classes"
+    },
+    {
+      "code": 6055,
+      "name": "DuplicateEquipmentSetItemClass",
+      "msg": "An item class can only appear once in an equipment set"
+    },
+    {
+      "code": 6056,
+      "name": "MustBeCalledByStakingProgram",
+      "msg": "Must be called by staking program"
+    },
+    {
+      "code": 6057,
+      "name": "PlayerTokenHolderMustSign",
+      "msg": "Markers created before the payer was recorded refund the player's token holder, who must sign"
     }
   ]
 };
@@ -3437,6 +3967,27 @@ export const IDL: RaindropsPlayer = {
       ],
       "args": []
     },
+    {
+      "name": "expireItemEffects",
+      "accounts": [
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "addItemEffect",
       "accounts": [
@@ -3581,6 +4132,29 @@ export const IDL: RaindropsPlayer = {
         }
       ]
     },
+    {
+      "name": "updateTokensStaked",
+      "accounts": [
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructionSysvarAccount",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateTokensStakedArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "playerArtifactJoinNamespace",
       "accounts": [
@@ -3824,22 +4398,54 @@ export const IDL: RaindropsPlayer = {
           {
             "name": "tokensPaidIn",
             "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "playerItemActivationMarker",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "bump",
-            "type": "u8"
           },
           {
-            "name": "player",
-            "type": "publicKey"
+            "name": "activeStatEffects",
+            "type": {
+              "vec": {
+                "defined": "ActiveStatEffect"
+              }
+            }
+          },
+          {
+            "name": "backpackWeight",
+            "type": "u64"
+          },
+          {
+            "name": "equippedItemClasses",
+            "type": {
+              "vec": {
+                "defined": "EquippedItemClass"
+              }
+            }
+          },
+          {
+            "name": "weightedTokensStaked",
+            "type": "u64"
+          },
+          {
+            "name": "backpackItems",
+            "type": {
+              "vec": {
+                "defined": "BackpackItem"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "playerItemActivationMarker",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "player",
+            "type": "publicKey"
           },
           {
             "name": "item",
@@ -3854,7 +4460,7 @@ export const IDL: RaindropsPlayer = {
             "type": {
               "option": {
                 "vec": {
-                  "defined": "BasicItemEffect"
+                  "defined": "LegacyBasicItemEffect"
                 }
               }
             }
@@ -3876,6 +4482,12 @@ export const IDL: RaindropsPlayer = {
           {
             "name": "activeItemCounter",
             "type": "u64"
+          },
+          {
+            "name": "additions",
+            "type": {
+              "defined": "PlayerItemActivationMarkerAdditions"
+            }
           }
         ]
       }
@@ -4590,6 +5202,34 @@ export const IDL: RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "UpdateTokensStakedArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "playerMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "staked",
+            "type": "bool"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "weightedAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "EquippedItem",
       "type": {
@@ -4610,6 +5250,42 @@ export const IDL: RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "EquippedItemClass",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BackpackItem",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "item",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "NamespaceAndIndex",
       "type": {
@@ -4722,6 +5398,123 @@ export const IDL: RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "Backpack",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxItems",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxWeight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "statBonuses",
+            "type": {
+              "vec": {
+                "defined": "BackpackCapacityBonus"
+              }
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BackpackCapacityBonus",
+      "docs": [
+        "Extra backpack capacity given by each point of an integer stat."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statIndex",
+            "type": "u16"
+          },
+          {
+            "name": "itemsPerPoint",
+            "type": "u64"
+          },
+          {
+            "name": "weightPerPoint",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EquipmentSet",
+      "docs": [
+        "Item classes that give extra effects when enough different ones of them are",
+        "equipped at the same time."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "index",
+            "type": "u16"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "itemClasses",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "bonuses",
+            "type": {
+              "vec": {
+                "defined": "EquipmentSetBonus"
+              }
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "EquipmentSetBonus",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pieces",
+            "type": "u16"
+          },
+          {
+            "name": "basicItemEffects",
+            "type": {
+              "vec": {
+                "defined": "BasicItemEffect"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "PlayerClassData",
       "type": {
@@ -4982,6 +5775,24 @@ export const IDL: RaindropsPlayer = {
                 "defined": "Callback"
               }
             }
+          },
+          {
+            "name": "backpack",
+            "type": {
+              "option": {
+                "defined": "Backpack"
+              }
+            }
+          },
+          {
+            "name": "equipmentSets",
+            "type": {
+              "option": {
+                "vec": {
+                  "defined": "EquipmentSet"
+                }
+              }
+            }
           }
         ]
       }
@@ -5100,6 +5911,88 @@ export const IDL: RaindropsPlayer = {
     },
     {
       "name": "BasicItemEffect",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "stat",
+            "type": "string"
+          },
+          {
+            "name": "itemEffectType",
+            "type": {
+              "defined": "BasicItemEffectType"
+            }
+          },
+          {
+            "name": "activeDuration",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingAmountDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationNumerator",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "stakingDurationDivisor",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUses",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "option": {
+                "defined": "StackingPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyBasicItemEffect",
+      "docs": [
+        "`BasicItemEffect` as stored before clamps and stacking policies were added,",
+        "used to read data written in that layout."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -5242,6 +6135,12 @@ export const IDL: RaindropsPlayer = {
           },
           {
             "name": "StatsUri"
+          },
+          {
+            "name": "Backpack"
+          },
+          {
+            "name": "EquipmentSets"
           }
         ]
       }
@@ -5391,6 +6290,54 @@ export const IDL: RaindropsPlayer = {
         ]
       }
     },
+    {
+      "name": "ActiveStatEffect",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statIndex",
+            "type": "u16"
+          },
+          {
+            "name": "itemClass",
+            "type": "publicKey"
+          },
+          {
+            "name": "itemEffectType",
+            "type": {
+              "defined": "BasicItemEffectType"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "defined": "StackingPolicy"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "i64"
+          },
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "StatDiffType",
       "type": {
@@ -5427,6 +6374,79 @@ export const IDL: RaindropsPlayer = {
           },
           {
             "name": "DecrementPercentFromBase"
+          },
+          {
+            "name": "Set"
+          },
+          {
+            "name": "Clamp"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PlayerItemActivationMarkerAdditions",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payer",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "effectPolicies",
+            "type": {
+              "vec": {
+                "defined": "BasicItemEffectPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BasicItemEffectPolicy",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "clampMin",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "clampMax",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "stackingPolicy",
+            "type": {
+              "option": {
+                "defined": "StackingPolicy"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "StackingPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Stack"
+          },
+          {
+            "name": "RefreshDuration"
+          },
+          {
+            "name": "HighestWins"
           }
         ]
       }
@@ -5682,6 +6702,46 @@ export const IDL: RaindropsPlayer = {
       "code": 6049,
       "name": "AmountMustBeGreaterThanZero",
       "msg": "Amount must be greater than zero"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidExpiryAccounts",
+      "msg": "Markers must be passed as marker, item and payer account triples"
+    },
+    {
+      "code": 6051,
+      "name": "MarkerDoesNotBelongToPlayer",
+      "msg": "Activation marker belongs to a different player"
+    },
+    {
+      "code": 6052,
+      "name": "BackpackFull",
+      "msg": "Backpack cannot hold this many items"
+    },
+    {
+      "code": 6053,
+      "name": "BackpackTooHeavy",
+      "msg": "Backpack cannot hold this much weight"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidEquipmentSetBonus",
+      "msg": "Equipment set bonuses need between one and all of the set's item classes"
+    },
+    {
+      "code": 6055,
+      "name": "DuplicateEquipmentSetItemClass",
+      "msg": "An item class can only appear once in an equipment set"
+    },
+    {
+      "code": 6056,
+      "name": "MustBeCalledByStakingProgram",
+      "msg": "Must be called by staking program"
+    },
+    {
+      "code": 6057,
+      "name": "PlayerTokenHolderMustSign",
+      "msg": "Markers created before the payer was recorded refund the player's token holder, who must sign"
     }
   ]
 };
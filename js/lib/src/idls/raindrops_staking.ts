@@ -40,6 +40,11 @@ export type RaindropsStaking = {
           "isMut": false,
           "isSigner": true
         },
+        {
+          "name": "lockupTiers",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "namespace",
           "isMut": false,
@@ -108,6 +113,11 @@ export type RaindropsStaking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "stakePositions",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "stakingMint",
           "isMut": false,
@@ -191,6 +201,11 @@ export type RaindropsStaking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "stakePositions",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "stakingAccount",
           "isMut": true,
@@ -308,6 +323,274 @@ export type RaindropsStaking = {
           }
         }
       ]
+    },
+    {
+      "name": "createRewardPool",
+      "accounts": [
+        {
+          "name": "rewardPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "scope",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakingMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreateRewardPoolArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "topUpRewardPool",
+      "accounts": [
+        {
+          "name": "rewardPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "fundingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "fundingTransferAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "TopUpRewardPoolArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeRewardPool",
+      "accounts": [
+        {
+          "name": "rewardPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "accounts": [
+        {
+          "name": "artifactClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifact",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifactMintStakingAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakingMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakeRewards",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakePositions",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ClaimRewardsArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setLockupTiers",
+      "accounts": [
+        {
+          "name": "artifactClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lockupTiers",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetLockupTiersArgs"
+          }
+        }
+      ]
     }
   ],
   "accounts": [
@@ -327,10 +610,148 @@ export type RaindropsStaking = {
           {
             "name": "eventType",
             "type": "u8"
+          },
+          {
+            "name": "lockup",
+            "type": {
+              "defined": "StakingCounterLockup"
+            }
           }
         ]
       }
-    }
+    },
+    {
+      "name": "lockupTiers",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "tiers",
+            "type": {
+              "vec": {
+                "defined": "LockupTier"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "scope",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionRate",
+            "type": "u64"
+          },
+          {
+            "name": "rewardsAvailable",
+            "type": "u64"
+          },
+          {
+            "name": "totalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerToken",
+            "type": "u128"
+          },
+          {
+            "name": "lastUpdate",
+            "type": "i64"
+          },
+          {
+            "name": "closed",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeRewards",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "rewardPool",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerTokenPaid",
+            "type": "u128"
+          },
+          {
+            "name": "pending",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakePositions",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "positions",
+            "type": {
+              "vec": {
+                "defined": "StakePosition"
+              }
+            }
+          },
+          {
+            "name": "rewardPools",
+            "type": {
+              "vec": {
+                "defined": "EnrolledRewardPool"
+              }
+            }
+          }
+        ]
+      }
+    }
   ],
   "types": [
     {
@@ -375,6 +796,12 @@ export type RaindropsStaking = {
                 "defined": "PermissivenessType"
               }
             }
+          },
+          {
+            "name": "lockupTier",
+            "type": {
+              "option": "u8"
+            }
           }
         ]
       }
@@ -445,6 +872,12 @@ export type RaindropsStaking = {
                 "defined": "PermissivenessType"
               }
             }
+          },
+          {
+            "name": "amount",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -478,64 +911,301 @@ export type RaindropsStaking = {
       }
     },
     {
-      "name": "PermissivenessType",
+      "name": "CreateRewardPoolArgs",
       "type": {
-        "kind": "enum",
-        "variants": [
+        "kind": "struct",
+        "fields": [
           {
-            "name": "TokenHolder"
+            "name": "emissionRate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TopUpRewardPoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ClaimRewardsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "classIndex",
+            "type": "u64"
           },
           {
-            "name": "ParentTokenHolder"
+            "name": "parentClassIndex",
+            "type": {
+              "option": "u64"
+            }
           },
           {
-            "name": "UpdateAuthority"
+            "name": "index",
+            "type": "u64"
           },
           {
-            "name": "Anybody"
+            "name": "artifactClassMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "artifactMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingPermissivenessToUse",
+            "type": {
+              "option": {
+                "defined": "PermissivenessType"
+              }
+            }
           }
         ]
       }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "IncorrectOwner",
-      "msg": "Account does not have correct owner!"
-    },
-    {
-      "code": 6001,
-      "name": "Uninitialized",
-      "msg": "Account is not initialized!"
-    },
-    {
-      "code": 6002,
-      "name": "MintMismatch",
-      "msg": "Mint Mismatch!"
     },
     {
-      "code": 6003,
-      "name": "TokenTransferFailed",
-      "msg": "Token transfer failed"
+      "name": "SetLockupTiersArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "classIndex",
+            "type": "u64"
+          },
+          {
+            "name": "parentClassIndex",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "artifactClassMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "updatePermissivenessToUse",
+            "type": {
+              "option": {
+                "defined": "PermissivenessType"
+              }
+            }
+          },
+          {
+            "name": "tiers",
+            "type": {
+              "vec": {
+                "defined": "LockupTier"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "NumericalOverflowError",
-      "msg": "Numerical overflow error"
+      "name": "EnrolledRewardPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rewardPool",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingMint",
+            "type": "publicKey"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "TokenMintToFailed",
-      "msg": "Token mint to failed"
+      "name": "StakingCounterLockup",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockDuration",
+            "type": "u64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "TokenBurnFailed",
-      "msg": "TokenBurnFailed"
+      "name": "LockupTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockDuration",
+            "type": "u64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
+      "name": "StakePosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "stakingMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingIndex",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "stakedAt",
+            "type": "i64"
+          },
+          {
+            "name": "unlocksAt",
+            "type": "i64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PermissivenessType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "TokenHolder"
+          },
+          {
+            "name": "ParentTokenHolder"
+          },
+          {
+            "name": "UpdateAuthority"
+          },
+          {
+            "name": "Anybody"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InheritanceState",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NotInherited"
+          },
+          {
+            "name": "Inherited"
+          },
+          {
+            "name": "Overridden"
+          }
+        ]
+      }
+    },
+    {
+      "name": "NamespaceAndIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "namespace",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Callback",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
+            "type": "publicKey"
+          },
+          {
+            "name": "code",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "IncorrectOwner",
+      "msg": "Account does not have correct owner!"
+    },
+    {
+      "code": 6001,
+      "name": "Uninitialized",
+      "msg": "Account is not initialized!"
+    },
+    {
+      "code": 6002,
+      "name": "MintMismatch",
+      "msg": "Mint Mismatch!"
+    },
+    {
+      "code": 6003,
+      "name": "TokenTransferFailed",
+      "msg": "Token transfer failed"
+    },
+    {
+      "code": 6004,
+      "name": "NumericalOverflowError",
+      "msg": "Numerical overflow error"
+    },
+    {
+      "code": 6005,
+      "name": "TokenMintToFailed",
+      "msg": "Token mint to failed"
+    },
+    {
+      "code": 6006,
+      "name": "TokenBurnFailed",
+      "msg": "TokenBurnFailed"
+    },
+    {
+      "code": 6007,
       "name": "DerivedKeyInvalid",
       "msg": "Derived key is invalid"
     },
@@ -653,6 +1323,51 @@ export type RaindropsStaking = {
       "code": 6030,
       "name": "StakingForPlayerComingSoon",
       "msg": "Staking for player coming soon"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidRewardPoolScope",
+      "msg": "Reward pools must be scoped to a namespace or an artifact class"
+    },
+    {
+      "code": 6032,
+      "name": "RewardPoolScopeMismatch",
+      "msg": "Artifact is not in this reward pool's namespace or class"
+    },
+    {
+      "code": 6033,
+      "name": "RewardPoolClosed",
+      "msg": "Reward pool is closed"
+    },
+    {
+      "code": 6034,
+      "name": "StakePositionAlreadyOpen",
+      "msg": "A stake position with this staking index is already open"
+    },
+    {
+      "code": 6035,
+      "name": "StakePositionNotFound",
+      "msg": "No open stake position for this staking mint and index"
+    },
+    {
+      "code": 6036,
+      "name": "InvalidUnstakeAmount",
+      "msg": "Unstake amount must be above zero and within the position"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidLockupTier",
+      "msg": "Lockup tier does not exist or has a multiplier below 1x"
+    },
+    {
+      "code": 6038,
+      "name": "StakeStillLocked",
+      "msg": "Stake position is still locked"
+    },
+    {
+      "code": 6039,
+      "name": "RewardPoolAccountsMissing",
+      "msg": "Every reward pool counting this stake must be passed in the remaining accounts"
     }
   ]
 };
@@ -665,27 +1380,339 @@ export const IDL: RaindropsStaking = {
       "name": "beginArtifactStakeWarmup",
       "accounts": [
         {
-          "name": "artifactClass",
+          "name": "artifactClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifact",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakingMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakingTransferAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "lockupTiers",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "namespace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "BeginArtifactStakeWarmupArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "endArtifactStakeWarmup",
+      "accounts": [
+        {
+          "name": "artifactClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactMintStakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakePositions",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "itemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "playerProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructionSysvarAccount",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "EndArtifactStakeWarmupArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "beginArtifactStakeCooldown",
+      "accounts": [
+        {
+          "name": "artifactClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifact",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactMintStakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakePositions",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "itemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "playerProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructionSysvarAccount",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "BeginArtifactStakeCooldownArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "endArtifactStakeCooldown",
+      "accounts": [
+        {
+          "name": "artifactClass",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifact",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "artifactIntermediaryStakingCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "artifact",
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "artifactIntermediaryStakingAccount",
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "EndArtifactStakeCooldownArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createRewardPool",
+      "accounts": [
+        {
+          "name": "rewardPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "artifactIntermediaryStakingCounter",
+          "name": "rewardVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakingAccount",
+          "name": "scope",
           "isMut": false,
           "isSigner": false
         },
@@ -695,14 +1722,14 @@ export const IDL: RaindropsStaking = {
           "isSigner": false
         },
         {
-          "name": "stakingTransferAuthority",
+          "name": "rewardMint",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "namespace",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "payer",
@@ -734,96 +1761,92 @@ export const IDL: RaindropsStaking = {
         {
           "name": "args",
           "type": {
-            "defined": "BeginArtifactStakeWarmupArgs"
+            "defined": "CreateRewardPoolArgs"
           }
         }
       ]
     },
     {
-      "name": "endArtifactStakeWarmup",
+      "name": "topUpRewardPool",
       "accounts": [
         {
-          "name": "artifactClass",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "artifact",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "artifactIntermediaryStakingAccount",
+          "name": "rewardPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "artifactIntermediaryStakingCounter",
+          "name": "rewardVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "artifactMintStakingAccount",
+          "name": "fundingAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakingMint",
+          "name": "fundingTransferAuthority",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "payer",
-          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "itemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "playerProgram",
+          "name": "clock",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "TopUpRewardPoolArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeRewardPool",
+      "accounts": [
+        {
+          "name": "rewardPool",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "rewardVault",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "destination",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "clock",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "instructionSysvarAccount",
+          "name": "clock",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "EndArtifactStakeWarmupArgs"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "beginArtifactStakeCooldown",
+      "name": "claimRewards",
       "accounts": [
         {
           "name": "artifactClass",
@@ -832,48 +1855,48 @@ export const IDL: RaindropsStaking = {
         },
         {
           "name": "artifact",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "artifactIntermediaryStakingAccount",
-          "isMut": true,
+          "name": "artifactMintStakingAccount",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "artifactIntermediaryStakingCounter",
-          "isMut": true,
+          "name": "stakingMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "artifactMintStakingAccount",
+          "name": "rewardPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakingAccount",
+          "name": "rewardVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakingMint",
-          "isMut": false,
+          "name": "stakeRewards",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "stakePositions",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "itemProgram",
-          "isMut": false,
+          "name": "rewardAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "playerProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
           "name": "systemProgram",
@@ -894,24 +1917,19 @@ export const IDL: RaindropsStaking = {
           "name": "clock",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "instructionSysvarAccount",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "args",
           "type": {
-            "defined": "BeginArtifactStakeCooldownArgs"
+            "defined": "ClaimRewardsArgs"
           }
         }
       ]
     },
     {
-      "name": "endArtifactStakeCooldown",
+      "name": "setLockupTiers",
       "accounts": [
         {
           "name": "artifactClass",
@@ -919,42 +1937,22 @@ export const IDL: RaindropsStaking = {
           "isSigner": false
         },
         {
-          "name": "artifact",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "artifactIntermediaryStakingAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "artifactIntermediaryStakingCounter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "stakingAccount",
+          "name": "lockupTiers",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "stakingMint",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "payer",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "clock",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         }
@@ -963,15 +1961,145 @@ export const IDL: RaindropsStaking = {
         {
           "name": "args",
           "type": {
-            "defined": "EndArtifactStakeCooldownArgs"
+            "defined": "SetLockupTiersArgs"
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "stakingCounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "eventStart",
+            "type": "i64"
+          },
+          {
+            "name": "eventType",
+            "type": "u8"
+          },
+          {
+            "name": "lockup",
+            "type": {
+              "defined": "StakingCounterLockup"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "lockupTiers",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "tiers",
+            "type": {
+              "vec": {
+                "defined": "LockupTier"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "scope",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionRate",
+            "type": "u64"
+          },
+          {
+            "name": "rewardsAvailable",
+            "type": "u64"
+          },
+          {
+            "name": "totalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerToken",
+            "type": "u128"
+          },
+          {
+            "name": "lastUpdate",
+            "type": "i64"
+          },
+          {
+            "name": "closed",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeRewards",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "rewardPool",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerTokenPaid",
+            "type": "u128"
+          },
+          {
+            "name": "pending",
+            "type": "u64"
           }
-        }
-      ]
-    }
-  ],
-  "accounts": [
+        ]
+      }
+    },
     {
-      "name": "stakingCounter",
+      "name": "stakePositions",
       "type": {
         "kind": "struct",
         "fields": [
@@ -980,12 +2108,20 @@ export const IDL: RaindropsStaking = {
             "type": "u8"
           },
           {
-            "name": "eventStart",
-            "type": "i64"
+            "name": "positions",
+            "type": {
+              "vec": {
+                "defined": "StakePosition"
+              }
+            }
           },
           {
-            "name": "eventType",
-            "type": "u8"
+            "name": "rewardPools",
+            "type": {
+              "vec": {
+                "defined": "EnrolledRewardPool"
+              }
+            }
           }
         ]
       }
@@ -1034,6 +2170,12 @@ export const IDL: RaindropsStaking = {
                 "defined": "PermissivenessType"
               }
             }
+          },
+          {
+            "name": "lockupTier",
+            "type": {
+              "option": "u8"
+            }
           }
         ]
       }
@@ -1104,6 +2246,12 @@ export const IDL: RaindropsStaking = {
                 "defined": "PermissivenessType"
               }
             }
+          },
+          {
+            "name": "amount",
+            "type": {
+              "option": "u64"
+            }
           }
         ]
       }
@@ -1136,6 +2284,186 @@ export const IDL: RaindropsStaking = {
         ]
       }
     },
+    {
+      "name": "CreateRewardPoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "emissionRate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TopUpRewardPoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ClaimRewardsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "classIndex",
+            "type": "u64"
+          },
+          {
+            "name": "parentClassIndex",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "artifactClassMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "artifactMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingPermissivenessToUse",
+            "type": {
+              "option": {
+                "defined": "PermissivenessType"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SetLockupTiersArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "classIndex",
+            "type": "u64"
+          },
+          {
+            "name": "parentClassIndex",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "artifactClassMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "updatePermissivenessToUse",
+            "type": {
+              "option": {
+                "defined": "PermissivenessType"
+              }
+            }
+          },
+          {
+            "name": "tiers",
+            "type": {
+              "vec": {
+                "defined": "LockupTier"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "EnrolledRewardPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rewardPool",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StakingCounterLockup",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockDuration",
+            "type": "u64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockupTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockDuration",
+            "type": "u64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StakePosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "stakingMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingIndex",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "stakedAt",
+            "type": "i64"
+          },
+          {
+            "name": "unlocksAt",
+            "type": "i64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "PermissivenessType",
       "type": {
@@ -1155,6 +2483,63 @@ export const IDL: RaindropsStaking = {
           }
         ]
       }
+    },
+    {
+      "name": "InheritanceState",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NotInherited"
+          },
+          {
+            "name": "Inherited"
+          },
+          {
+            "name": "Overridden"
+          }
+        ]
+      }
+    },
+    {
+      "name": "NamespaceAndIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "namespace",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "inherited",
+            "type": {
+              "defined": "InheritanceState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Callback",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
+            "type": "publicKey"
+          },
+          {
+            "name": "code",
+            "type": "u64"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1312,6 +2697,51 @@ export const IDL: RaindropsStaking = {
       "code": 6030,
       "name": "StakingForPlayerComingSoon",
       "msg": "Staking for player coming soon"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidRewardPoolScope",
+      "msg": "Reward pools must be scoped to a namespace or an artifact class"
+    },
+    {
+      "code": 6032,
+      "name": "RewardPoolScopeMismatch",
+      "msg": "Artifact is not in this reward pool's namespace or class"
+    },
+    {
+      "code": 6033,
+      "name": "RewardPoolClosed",
+      "msg": "Reward pool is closed"
+    },
+    {
+      "code": 6034,
+      "name": "StakePositionAlreadyOpen",
+      "msg": "A stake position with this staking index is already open"
+    },
+    {
+      "code": 6035,
+      "name": "StakePositionNotFound",
+      "msg": "No open stake position for this staking mint and index"
+    },
+    {
+      "code": 6036,
+      "name": "InvalidUnstakeAmount",
+      "msg": "Unstake amount must be above zero and within the position"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidLockupTier",
+      "msg": "Lockup tier does not exist or has a multiplier below 1x"
+    },
+    {
+      "code": 6038,
+      "name": "StakeStillLocked",
+      "msg": "Stake position is still locked"
+    },
+    {
+      "code": 6039,
+      "name": "RewardPoolAccountsMissing",
+      "msg": "Every reward pool counting this stake must be passed in the remaining accounts"
     }
   ]
 };
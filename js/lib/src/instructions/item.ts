@@ -43,6 +43,8 @@ const ITEM_CLASS_DATA_ARGS_CONVERT_TO_BNS = [
   "itemClassData.settings.stakingWarmUpDuration",
   "itemClassData.settings.stakingCooldownDuration",
   "itemClassData.config.components.[].timeToBuild",
+  "itemClassData.config.weight",
+  "itemClassData.config.propagationVersion",
   "itemClassData.config.usages.[].validation.code",
   "itemClassData.config.usages.[].callback.code",
   "itemClassData.config.usages.[].basicItemEffects.[].amount",
@@ -189,6 +191,42 @@ export class Instruction extends SolKitInstruction {
     ];
   }
 
+  async propagateItemClassInheritance(
+    accounts: PropagateItemClassInheritanceAccounts
+  ) {
+    const remainingAccounts: AccountMeta[] = accounts.children.map(
+      (child) => ({
+        pubkey: child,
+        isWritable: true,
+        isSigner: false,
+      })
+    );
+
+    return [
+      await this.program.client.methods
+        .propagateItemClassInheritance()
+        .accounts({
+          parent: accounts.parent,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction(),
+    ];
+  }
+
+  async migrateItemClassData(accounts: MigrateItemClassDataAccounts) {
+    return [
+      await this.program.client.methods
+        .migrateItemClassData()
+        .accounts({
+          itemClass: accounts.itemClass,
+          payer: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction(),
+    ];
+  }
+
   async createItemEscrow(
     args: CreateItemEscrowArgs,
     accounts: CreateItemEscrowAccounts,
@@ -2043,6 +2081,15 @@ export interface UpdateItemClassAdditionalArgs {
   permissionless: boolean;
 }
 
+export interface PropagateItemClassInheritanceAccounts {
+  parent: web3.PublicKey;
+  children: web3.PublicKey[];
+}
+
+export interface MigrateItemClassDataAccounts {
+  itemClass: web3.PublicKey;
+}
+
 export interface CreateItemEscrowArgs {
   classIndex: BN;
   parentClassIndex: null | BN;
@@ -19,6 +19,7 @@ import {
   OutputSelectionGroup,
   getPackPda,
   getItemClassPda,
+  getItemClassV1ImportPda,
   getRecipePda,
   Payment,
   getDeterministicIngredientPda,
@@ -44,8 +45,100 @@ export class Instruction extends SolKitInstruction {
     const ixns: web3.TransactionInstruction[] = [];
 
     // create authority mint account and mint some to the creator
-    const authorityMint = web3.Keypair.generate();
+    const [authorityMint, itemClass, destinationAta, authorityMintIxns] =
+      await this.createAuthorityMint();
     signers.push(authorityMint);
+    ixns.push(...authorityMintIxns);
+
+    let merkleTree: web3.PublicKey | null = null;
+    let logWrapper: web3.PublicKey | null = null;
+    let accountCompression: web3.PublicKey | null = null;
+
+    switch (args.mode.kind) {
+      case "MerkleTree": {
+        // create merkle tree account
+        const tree = web3.Keypair.generate();
+
+        // hardcoded to match the contract code
+        const maxDepth = 16;
+        const maxBufferSize = 64;
+
+        const treeSpace = cmp.getConcurrentMerkleTreeAccountSize(
+          maxDepth,
+          maxBufferSize
+        );
+
+        const treeLamports =
+          await this.program.client.provider.connection.getMinimumBalanceForRentExemption(
+            treeSpace
+          );
+
+        const createAccountIx = await web3.SystemProgram.createAccount({
+          fromPubkey: this.program.client.provider.publicKey!,
+          newAccountPubkey: tree.publicKey,
+          lamports: treeLamports,
+          space: treeSpace,
+          programId: cmp.PROGRAM_ID,
+        });
+        ixns.push(createAccountIx);
+
+        merkleTree = tree.publicKey;
+        logWrapper = cmp.SPL_NOOP_PROGRAM_ID;
+        accountCompression = cmp.PROGRAM_ID;
+
+        signers.push(tree);
+        break;
+      }
+      case "Collection":
+        // client side check that collectionMint is truly a mint account
+        await splToken.getMint(
+          this.program.client.provider.connection,
+          args.mode.collectionMint
+        );
+        break;
+      case "Pack":
+        break;
+      case "PresetOnly":
+        break;
+    }
+
+    const ixArgs = {
+      itemClassName: args.itemClassName,
+      mode: formatItemClassModeSelection(args.mode),
+    };
+
+    const createItemClassIx = await this.program.client.methods
+      .createItemClass(ixArgs)
+      .accounts({
+        tree: merkleTree,
+        itemClass: itemClass,
+        itemClassAuthorityMint: authorityMint.publicKey,
+        itemClassAuthorityMintAta: destinationAta,
+        authority: this.program.client.provider.publicKey!,
+        rent: web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: web3.SystemProgram.programId,
+        accountCompression: accountCompression,
+        logWrapper: logWrapper,
+      })
+      .instruction();
+    ixns.push(createItemClassIx);
+
+    return [itemClass, signers, ixns];
+  }
+
+  // creates an authority mint, mints tokens to the provider wallet and hands
+  // the mint authority to the item class pda derived from it
+  private async createAuthorityMint(): Promise<
+    [
+      web3.Keypair,
+      web3.PublicKey,
+      web3.PublicKey,
+      web3.TransactionInstruction[]
+    ]
+  > {
+    const ixns: web3.TransactionInstruction[] = [];
+
+    const authorityMint = web3.Keypair.generate();
 
     // derive item class pda
     const itemClass = getItemClassPda(authorityMint.publicKey);
@@ -142,80 +235,7 @@ export class Instruction extends SolKitInstruction {
       );
     ixns.push(transferAuthorityToItemClassIx);
 
-    let merkleTree: web3.PublicKey | null = null;
-    let logWrapper: web3.PublicKey | null = null;
-    let accountCompression: web3.PublicKey | null = null;
-
-    switch (args.mode.kind) {
-      case "MerkleTree": {
-        // create merkle tree account
-        const tree = web3.Keypair.generate();
-
-        // hardcoded to match the contract code
-        const maxDepth = 16;
-        const maxBufferSize = 64;
-
-        const treeSpace = cmp.getConcurrentMerkleTreeAccountSize(
-          maxDepth,
-          maxBufferSize
-        );
-
-        const treeLamports =
-          await this.program.client.provider.connection.getMinimumBalanceForRentExemption(
-            treeSpace
-          );
-
-        const createAccountIx = await web3.SystemProgram.createAccount({
-          fromPubkey: this.program.client.provider.publicKey!,
-          newAccountPubkey: tree.publicKey,
-          lamports: treeLamports,
-          space: treeSpace,
-          programId: cmp.PROGRAM_ID,
-        });
-        ixns.push(createAccountIx);
-
-        merkleTree = tree.publicKey;
-        logWrapper = cmp.SPL_NOOP_PROGRAM_ID;
-        accountCompression = cmp.PROGRAM_ID;
-
-        signers.push(tree);
-        break;
-      }
-      case "Collection":
-        // client side check that collectionMint is truly a mint account
-        await splToken.getMint(
-          this.program.client.provider.connection,
-          args.mode.collectionMint
-        );
-        break;
-      case "Pack":
-        break;
-      case "PresetOnly":
-        break;
-    }
-
-    const ixArgs = {
-      itemClassName: args.itemClassName,
-      mode: formatItemClassModeSelection(args.mode),
-    };
-
-    const createItemClassIx = await this.program.client.methods
-      .createItemClass(ixArgs)
-      .accounts({
-        tree: merkleTree,
-        itemClass: itemClass,
-        itemClassAuthorityMint: authorityMint.publicKey,
-        itemClassAuthorityMintAta: destinationAta,
-        authority: this.program.client.provider.publicKey!,
-        rent: web3.SYSVAR_RENT_PUBKEY,
-        systemProgram: web3.SystemProgram.programId,
-        accountCompression: accountCompression,
-        logWrapper: logWrapper,
-      })
-      .instruction();
-    ixns.push(createItemClassIx);
-
-    return [itemClass, signers, ixns];
+    return [authorityMint, itemClass, destinationAta, ixns];
   }
 
   async addItemsToItemClass(
@@ -1717,6 +1737,98 @@ export class Instruction extends SolKitInstruction {
     return ixns;
   }
 
+  async importItemClassV1(
+    accounts: ImportItemClassV1Accounts,
+    args: CreateItemClassArgs
+  ): Promise<[web3.PublicKey, web3.Keypair[], web3.TransactionInstruction[]]> {
+    if (args.mode.kind === "MerkleTree") {
+      throw new Error(`merkle tree item classes cannot be imported`);
+    }
+
+    const [authorityMint, itemClass, destinationAta, ixns] =
+      await this.createAuthorityMint();
+
+    const itemClassV1Ata = splToken.getAssociatedTokenAddressSync(
+      accounts.itemClassV1Mint,
+      this.program.client.provider.publicKey!
+    );
+
+    const ix = await this.program.client.methods
+      .importItemClassV1({
+        itemClassName: args.itemClassName,
+        mode: formatItemClassModeSelection(args.mode),
+      })
+      .accounts({
+        itemClass: itemClass,
+        itemClassAuthorityMint: authorityMint.publicKey,
+        itemClassAuthorityMintAta: destinationAta,
+        itemClassV1Import: getItemClassV1ImportPda(accounts.itemClassV1),
+        itemClassV1: accounts.itemClassV1,
+        itemClassV1MintAta: itemClassV1Ata,
+        authority: this.program.client.provider.publicKey!,
+        rent: web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .instruction();
+    ixns.push(ix);
+
+    return [itemClass, [authorityMint], ixns];
+  }
+
+  async importRecipeV1(
+    accounts: ImportRecipeV1Accounts,
+    args: ImportRecipeV1Args
+  ): Promise<web3.TransactionInstruction> {
+    const itemClassData = await this.program.client.account.itemClass.fetch(
+      accounts.itemClass
+    );
+
+    const authorityMint = new web3.PublicKey(itemClassData.authorityMint);
+    const authorityMintAta = splToken.getAssociatedTokenAddressSync(
+      authorityMint,
+      this.program.client.provider.publicKey!
+    );
+
+    let recipeIndex: BN;
+    if (itemClassData.recipeIndex === null) {
+      recipeIndex = new BN(0);
+    } else {
+      recipeIndex = new BN(itemClassData.recipeIndex as any).add(new BN(1));
+    }
+
+    // one pair per component in the scope, in component order
+    const remainingAccounts: web3.AccountMeta[] = [];
+    for (const ingredientItemClassV1 of accounts.ingredientItemClassesV1) {
+      remainingAccounts.push({
+        pubkey: ingredientItemClassV1,
+        isWritable: false,
+        isSigner: false,
+      });
+      remainingAccounts.push({
+        pubkey: getItemClassV1ImportPda(ingredientItemClassV1),
+        isWritable: false,
+        isSigner: false,
+      });
+    }
+
+    const ix = await this.program.client.methods
+      .importRecipeV1(args)
+      .accounts({
+        recipe: getRecipePda(accounts.itemClass, recipeIndex),
+        itemClass: accounts.itemClass,
+        itemClassAuthorityMint: authorityMint,
+        itemClassAuthorityMintAta: authorityMintAta,
+        itemClassV1Import: getItemClassV1ImportPda(accounts.itemClassV1),
+        itemClassV1: accounts.itemClassV1,
+        authority: this.program.client.provider.publicKey!,
+        rent: web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .instruction();
+    return ix;
+  }
+
   async migrateItemClassAccount(
     itemClass: web3.PublicKey
   ): Promise<web3.TransactionInstruction> {
@@ -1738,6 +1850,23 @@ export interface CreateItemClassArgs {
   mode: ItemClassModeSelection;
 }
 
+export interface ImportItemClassV1Accounts {
+  itemClassV1: web3.PublicKey;
+  itemClassV1Mint: web3.PublicKey;
+}
+
+export interface ImportRecipeV1Accounts {
+  itemClass: web3.PublicKey;
+  itemClassV1: web3.PublicKey;
+  // legacy item class of each component in the scope, in component order
+  ingredientItemClassesV1: web3.PublicKey[];
+}
+
+export interface ImportRecipeV1Args {
+  componentScope: string;
+  buildEnabled: boolean;
+}
+
 export interface RecipeArgs {
   buildEnabled: boolean;
   payment: Payment | null;
@@ -7,6 +7,8 @@ import { Token } from "@solana/spl-token";
 import { TOKEN_PROGRAM_ID } from "../constants/programIds";
 import {
   AnchorMatchState,
+  AnchorPayoutSchedule,
+  AnchorRake,
   AnchorTokenDelta,
   AnchorTokenEntryValidation,
   TokenType,
@@ -17,6 +19,7 @@ import {
   getAtaForMint,
   getItemPDA,
   getMatch,
+  getMatchPrizePoolEscrow,
   getMatchTokenAccountEscrow,
   getOracle,
   getPlayerPDA,
@@ -59,6 +62,21 @@ export interface LeaveMatchArgs {
 
 export interface DisburseTokensByOracleArgs {
   tokenDeltaProofInfo: null;
+  index: BN;
+}
+
+export interface DisburseTokensByOracleBatchArgs {
+  tokenDeltas: DisburseTokensByOracleArgs[];
+}
+
+export interface UpdateMatchEconomicsArgs {
+  entryFeeAmount: BN;
+  rake: AnchorRake | null;
+  payoutSchedule: AnchorPayoutSchedule;
+}
+
+export interface DisbursePrizePoolArgs {
+  rank: BN | null;
 }
 
 export interface CreateMatchAdditionalArgs {
@@ -75,6 +93,7 @@ export interface CreateOrUpdateOracleArgs {
   finalized: boolean;
   tokenTransferRoot: null;
   tokenTransfers: AnchorTokenDelta[] | null;
+  rankings?: web3.PublicKey[] | null;
 }
 
 export interface DrainMatchArgs {}
@@ -92,6 +111,15 @@ export interface UpdateMatchAccounts {
   winOracle: web3.PublicKey;
 }
 
+export interface UpdateMatchEconomicsAccounts {
+  winOracle: web3.PublicKey;
+  entryFeeMint: web3.PublicKey;
+}
+
+export interface DisbursePrizePoolAccounts {
+  winOracle: web3.PublicKey;
+}
+
 export interface DrainMatchAccounts {
   receiver: web3.PublicKey | null;
 }
@@ -134,6 +162,47 @@ export interface DisburseTokensByOracleAdditionalArgs {
   tokenDelta: AnchorTokenDelta;
 }
 
+export interface DisburseTokensByOracleBatchAdditionalArgs {
+  tokenDeltas: AnchorTokenDelta[];
+}
+
+// Wallet destinations get swapped for their ata, created up front if missing
+async function getDestinationTokenAccount(
+  provider: AnchorProvider,
+  mint: web3.PublicKey,
+  destination: web3.PublicKey,
+  instructions: web3.TransactionInstruction[]
+): Promise<web3.PublicKey> {
+  let destinationTokenAccount = destination;
+  const info = await provider.connection.getAccountInfo(
+    destinationTokenAccount
+  );
+
+  if (!info || !info.owner.equals(TOKEN_PROGRAM_ID)) {
+    const destinationTokenOwner = destinationTokenAccount;
+    destinationTokenAccount = (
+      await getAtaForMint(mint, destinationTokenAccount)
+    )[0];
+
+    const exists = await provider.connection.getAccountInfo(
+      destinationTokenAccount
+    );
+
+    if (!exists || exists.data.length == 0) {
+      instructions.unshift(
+        createAssociatedTokenAccountInstruction(
+          destinationTokenAccount,
+          provider.wallet.publicKey,
+          destinationTokenOwner,
+          mint
+        )
+      );
+    }
+  }
+
+  return destinationTokenAccount;
+}
+
 export class Instruction extends SolKitInstruction {
   constructor(args: { program: Program.Program }) {
     super(args);
@@ -177,34 +246,13 @@ export class Instruction extends SolKitInstruction {
       tfer.from
     );
 
-    let destinationTokenAccount = tfer.to;
-    const info = await (
-      this.program.client.provider as AnchorProvider
-    ).connection.getAccountInfo(destinationTokenAccount);
-
     const instructions = [];
-
-    if (!info.owner.equals(TOKEN_PROGRAM_ID)) {
-      const destinationTokenOwner = destinationTokenAccount;
-      destinationTokenAccount = (
-        await getAtaForMint(tfer.mint, destinationTokenAccount)
-      )[0];
-
-      const exists = await (
-        this.program.client.provider as AnchorProvider
-      ).connection.getAccountInfo(destinationTokenAccount);
-
-      if (!exists || exists.data.length == 0) {
-        instructions.unshift(
-          createAssociatedTokenAccountInstruction(
-            destinationTokenAccount,
-            (this.program.client.provider as AnchorProvider).wallet.publicKey,
-            destinationTokenOwner,
-            tfer.mint
-          )
-        );
-      }
-    }
+    const destinationTokenAccount = await getDestinationTokenAccount(
+      this.program.client.provider as AnchorProvider,
+      tfer.mint,
+      tfer.to,
+      instructions
+    );
 
     instructions.push(
       await this.program.client.methods
@@ -216,6 +264,8 @@ export class Instruction extends SolKitInstruction {
           originalSender: tfer.from,
           destinationTokenAccount,
           winOracle: accounts.winOracle,
+          payer: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
           rent: web3.SYSVAR_RENT_PUBKEY,
@@ -228,6 +278,117 @@ export class Instruction extends SolKitInstruction {
     };
   }
 
+  async disburseTokensByOracleBatch(
+    args: DisburseTokensByOracleBatchArgs,
+    accounts: DisburseTokensByOracleAccounts,
+    additionalArgs: DisburseTokensByOracleBatchAdditionalArgs
+  ) {
+    const match = (await getMatch(accounts.winOracle))[0];
+
+    const instructions = [];
+    const remainingAccounts: web3.AccountMeta[] = [];
+    for (const tfer of additionalArgs.tokenDeltas) {
+      const [tokenAccountEscrow, _escrowBump] =
+        await getMatchTokenAccountEscrow(
+          accounts.winOracle,
+          tfer.mint,
+          tfer.from
+        );
+
+      const destinationTokenAccount = await getDestinationTokenAccount(
+        this.program.client.provider as AnchorProvider,
+        tfer.mint,
+        tfer.to,
+        instructions
+      );
+
+      for (const pubkey of [
+        tokenAccountEscrow,
+        tfer.mint,
+        destinationTokenAccount,
+        tfer.from,
+      ]) {
+        remainingAccounts.push({
+          pubkey,
+          isWritable: true,
+          isSigner: false,
+        });
+      }
+    }
+
+    instructions.push(
+      await this.program.client.methods
+        .disburseTokensByOracleBatch(args)
+        .accounts({
+          matchInstance: match,
+          winOracle: accounts.winOracle,
+          payer: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          systemProgram: web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction()
+    );
+    return {
+      instructions,
+      signers: [],
+    };
+  }
+
+  async disbursePrizePool(
+    args: DisbursePrizePoolArgs,
+    accounts: DisbursePrizePoolAccounts,
+    _additionalArgs = {}
+  ) {
+    const match = (await getMatch(accounts.winOracle))[0];
+    const matchData = await this.program.client.account.match.fetch(match);
+    const entryFee = matchData.entryFee as any;
+
+    // a rank pays that place in the oracle rankings, no rank pays the rake
+    let recipient: web3.PublicKey;
+    if (args.rank !== null) {
+      const oracleData = await this.program.client.account.winOracle.fetch(
+        accounts.winOracle
+      );
+      recipient = (oracleData.rankings as web3.PublicKey[])[
+        args.rank.toNumber()
+      ];
+    } else {
+      recipient = (matchData.rake as any).treasury;
+    }
+
+    const [prizePoolEscrow, _prizePoolBump] = await getMatchPrizePoolEscrow(
+      accounts.winOracle,
+      entryFee.mint
+    );
+
+    const instructions = [];
+    const destinationTokenAccount = await getDestinationTokenAccount(
+      this.program.client.provider as AnchorProvider,
+      entryFee.mint,
+      recipient,
+      instructions
+    );
+
+    instructions.push(
+      await this.program.client.methods
+        .disbursePrizePool(args)
+        .accounts({
+          matchInstance: match,
+          winOracle: accounts.winOracle,
+          prizePoolEscrow,
+          destinationTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction()
+    );
+    return {
+      instructions,
+      signers: [],
+    };
+  }
+
   async drainMatch(
     _args: DrainMatchArgs,
     accounts: DrainMatchAccounts,
@@ -308,6 +469,39 @@ export class Instruction extends SolKitInstruction {
     };
   }
 
+  async updateMatchEconomics(
+    args: UpdateMatchEconomicsArgs,
+    accounts: UpdateMatchEconomicsAccounts,
+    _additionalArgs = {}
+  ) {
+    const match = (await getMatch(accounts.winOracle))[0];
+    const [prizePoolEscrow, _prizePoolBump] = await getMatchPrizePoolEscrow(
+      accounts.winOracle,
+      accounts.entryFeeMint
+    );
+
+    return {
+      instructions: [
+        await this.program.client.methods
+          .updateMatchEconomics(args)
+          .accounts({
+            matchInstance: match,
+            prizePoolEscrow,
+            entryFeeMint: accounts.entryFeeMint,
+            authority: (this.program.client.provider as AnchorProvider).wallet
+              .publicKey,
+            payer: (this.program.client.provider as AnchorProvider).wallet
+              .publicKey,
+            systemProgram: web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+          })
+          .instruction(),
+      ],
+      signers: [],
+    };
+  }
+
   async leaveMatch(
     args: LeaveMatchArgs,
     accounts: LeaveMatchAccounts,
@@ -326,22 +520,50 @@ export class Instruction extends SolKitInstruction {
     );
 
     const signers = [];
+    const instructions = [];
+
+    // entry fees are refunded from the prize pool until the match pays out
+    const remainingAccounts: web3.AccountMeta[] = [];
+    const matchData = await this.program.client.account.match.fetch(match);
+    const entryFee = matchData.entryFee as any;
+    if (entryFee && !(matchData.state as any).paidOut) {
+      const [prizePoolEscrow, _prizePoolBump] = await getMatchPrizePoolEscrow(
+        additionalArgs.winOracle,
+        entryFee.mint
+      );
+      const feeDestination = await getDestinationTokenAccount(
+        this.program.client.provider as AnchorProvider,
+        entryFee.mint,
+        (this.program.client.provider as AnchorProvider).wallet.publicKey,
+        instructions
+      );
+      for (const pubkey of [prizePoolEscrow, feeDestination]) {
+        remainingAccounts.push({
+          pubkey,
+          isWritable: true,
+          isSigner: false,
+        });
+      }
+    }
+
+    instructions.push(
+      await this.program.client.methods
+        .leaveMatch(args)
+        .accounts({
+          matchInstance: match,
+          tokenAccountEscrow,
+          tokenMint: accounts.tokenMint,
+          destinationTokenAccount: accounts.receiver,
+          receiver: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction()
+    );
 
     return {
-      instructions: [
-        await this.program.client.methods
-          .leaveMatch(args)
-          .accounts({
-            matchInstance: match,
-            tokenAccountEscrow,
-            tokenMint: accounts.tokenMint,
-            destinationTokenAccount: accounts.receiver,
-            receiver: (this.program.client.provider as AnchorProvider).wallet
-              .publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .instruction(),
-      ],
+      instructions,
       signers,
     };
   }
@@ -371,52 +593,101 @@ export class Instruction extends SolKitInstruction {
     );
 
     const signers = [transferAuthority];
-
-    return {
-      instructions: [
+    const wallet = (this.program.client.provider as AnchorProvider).wallet
+      .publicKey;
+
+    // the entry fee is pulled into the prize pool by the same delegate
+    const remainingAccounts: web3.AccountMeta[] = [];
+    const matchData = await this.program.client.account.match.fetch(match);
+    const entryFee = matchData.entryFee as any;
+    let feeSource: web3.PublicKey | null = null;
+    if (entryFee) {
+      const [prizePoolEscrow, _prizePoolBump] = await getMatchPrizePoolEscrow(
+        additionalArgs.winOracle,
+        entryFee.mint
+      );
+      feeSource = (await getAtaForMint(entryFee.mint, wallet))[0];
+      for (const pubkey of [prizePoolEscrow, feeSource]) {
+        remainingAccounts.push({
+          pubkey,
+          isWritable: true,
+          isSigner: false,
+        });
+      }
+    }
+    const feeFromSource = feeSource && feeSource.equals(sourceTokenAccount);
+    const separateFeeSource = feeSource && !feeFromSource;
+
+    const instructions = [
+      Token.createApproveInstruction(
+        TOKEN_PROGRAM_ID,
+        sourceTokenAccount,
+        transferAuthority.publicKey,
+        wallet,
+        [],
+        feeFromSource
+          ? args.amount.add(entryFee.amount).toNumber()
+          : args.amount.toNumber()
+      ),
+    ];
+    if (separateFeeSource) {
+      instructions.push(
         Token.createApproveInstruction(
           TOKEN_PROGRAM_ID,
-          sourceTokenAccount,
+          feeSource,
           transferAuthority.publicKey,
-          (this.program.client.provider as AnchorProvider).wallet.publicKey,
+          wallet,
           [],
-          args.amount.toNumber()
-        ),
-        await this.program.client.methods
-          .joinMatch(args)
-          .accounts({
-            matchInstance: match,
-            tokenTransferAuthority: transferAuthority.publicKey,
-            tokenAccountEscrow,
-            tokenMint: accounts.tokenMint,
-            sourceTokenAccount,
-            sourceItemOrPlayerPda:
-              additionalArgs.sourceType == TokenType.Any
-                ? web3.SystemProgram.programId
-                : additionalArgs.sourceType == TokenType.Item
-                ? (
-                    await getItemPDA(accounts.tokenMint, additionalArgs.index)
-                  )[0]
-                : (
-                    await getPlayerPDA(accounts.tokenMint, additionalArgs.index)
-                  )[0],
-            payer: (this.program.client.provider as AnchorProvider).wallet
-              .publicKey,
-            systemProgram: web3.SystemProgram.programId,
-            validationProgram:
-              accounts.validationProgram || web3.SystemProgram.programId,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            rent: web3.SYSVAR_RENT_PUBKEY,
-          })
-          .signers(signers)
-          .instruction(),
-        Token.createRevokeInstruction(
-          TOKEN_PROGRAM_ID,
+          entryFee.amount.toNumber()
+        )
+      );
+    }
+
+    instructions.push(
+      await this.program.client.methods
+        .joinMatch(args)
+        .accounts({
+          matchInstance: match,
+          tokenTransferAuthority: transferAuthority.publicKey,
+          tokenAccountEscrow,
+          tokenMint: accounts.tokenMint,
           sourceTokenAccount,
-          (this.program.client.provider as AnchorProvider).wallet.publicKey,
-          []
-        ),
-      ],
+          sourceItemOrPlayerPda:
+            additionalArgs.sourceType == TokenType.Any
+              ? web3.SystemProgram.programId
+              : additionalArgs.sourceType == TokenType.Item
+              ? (
+                  await getItemPDA(accounts.tokenMint, additionalArgs.index)
+                )[0]
+              : (
+                  await getPlayerPDA(accounts.tokenMint, additionalArgs.index)
+                )[0],
+          payer: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          systemProgram: web3.SystemProgram.programId,
+          validationProgram:
+            accounts.validationProgram || web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers(signers)
+        .instruction(),
+      Token.createRevokeInstruction(
+        TOKEN_PROGRAM_ID,
+        sourceTokenAccount,
+        wallet,
+        []
+      )
+    );
+    if (separateFeeSource) {
+      instructions.push(
+        Token.createRevokeInstruction(TOKEN_PROGRAM_ID, feeSource, wallet, [])
+      );
+    }
+
+    return {
+      instructions,
       signers,
     };
   }
@@ -464,6 +735,9 @@ export class Instruction extends SolKitInstruction {
           amount: new BN(t.amount),
         }))
       : null;
+    const rankings = args.rankings
+      ? args.rankings.map((r) => new web3.PublicKey(r))
+      : null;
 
     return {
       instructions: [
@@ -471,6 +745,7 @@ export class Instruction extends SolKitInstruction {
           .createOrUpdateOracle({
             ...args,
             tokenTransfers,
+            rankings,
             seed: new web3.PublicKey(args.seed),
           })
           .accounts({
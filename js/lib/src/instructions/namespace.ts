@@ -6,11 +6,19 @@ import {
 } from "@raindrop-studios/sol-kit";
 
 import {
+  getAtaForMint,
   getIndexPDA,
+  getMetadata,
   getNamespaceGatekeeperPDA,
   getNamespacePDA,
+  getMembershipPaymentPDA,
+  getProtocolFeeConfigPDA,
 } from "../utils/pda";
-import { TOKEN_PROGRAM_ID } from "../constants/programIds";
+import {
+  BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+  NAMESPACE_ID,
+  TOKEN_PROGRAM_ID,
+} from "../constants/programIds";
 import {
   ArtifactFilter,
   PermissivenessSettings,
@@ -20,7 +28,6 @@ import {
   Namespace,
 } from "../state/namespace";
 import * as splToken from "@solana/spl-token";
-import { Common } from "../constants";
 
 export interface InitializeNamespaceAccounts {
   mint: web3.PublicKey;
@@ -43,6 +50,7 @@ export interface UpdateNamespaceArgs {
   prettyName: string | null;
   permissivenessSettings: PermissivenessSettings | null;
   whitelistedStakingMints: web3.PublicKey[];
+  refundPaymentOnLeave: boolean | null;
 }
 
 export interface UpdateNamespaceAccounts {
@@ -69,10 +77,17 @@ export interface RemoveFromNamespaceGatekeeperAccounts {
   namespaceMint: web3.PublicKey;
 }
 
+export interface JoinNamespaceArgs {
+  // one proof per merkle allowlist filter, in gatekeeper order
+  allowlistProofs: number[][][];
+}
+
 export interface JoinNamespaceAccounts {
   namespaceMint: web3.PublicKey;
   artifact: web3.PublicKey;
   raindropsProgram: RaindropsProgram;
+  // one held nft per verified collection filter, in gatekeeper order
+  collectionNftMints?: web3.PublicKey[];
 }
 
 export interface LeaveNamespaceAccounts {
@@ -81,6 +96,35 @@ export interface LeaveNamespaceAccounts {
   raindropsProgram: RaindropsProgram;
 }
 
+export interface InitializeProtocolFeeConfigArgs {
+  feeAmount: BN;
+}
+
+export interface InitializeProtocolFeeConfigAccounts {
+  feeMint: web3.PublicKey;
+  feeVault: web3.PublicKey;
+}
+
+export interface UpdateProtocolFeeConfigArgs {
+  admin: web3.PublicKey | null;
+  feeAmount: BN | null;
+}
+
+export interface UpdateProtocolFeeConfigAccounts {
+  feeMint: web3.PublicKey;
+  feeVault: web3.PublicKey;
+}
+
+export interface SetNamespaceProtocolFeeExemptAccounts {
+  namespaceMint: web3.PublicKey;
+}
+
+export interface CacheArtifactArgs {
+  // orders the artifact within its index page only, pages are not sorted
+  // against each other. unkeyed artifacts sort as 0
+  sortKey: BN | null;
+}
+
 export interface CacheArtifactAccounts {
   namespaceMint: web3.PublicKey;
   artifact: web3.PublicKey;
@@ -97,6 +141,20 @@ export interface UncacheArtifactAccounts {
   raindropsProgram: RaindropsProgram;
 }
 
+export interface CompactIndexArgs {
+  fromPage: BN;
+  toPage: BN;
+}
+
+export interface CompactIndexAccounts {
+  namespaceMint: web3.PublicKey;
+  // artifacts to move from fromPage to toPage
+  artifacts: {
+    artifact: web3.PublicKey;
+    raindropsProgram: RaindropsProgram;
+  }[];
+}
+
 export class Instruction extends SolKitInstruction {
   constructor(args: { program: Program.Program }) {
     super(args);
@@ -311,6 +369,8 @@ export class Instruction extends SolKitInstruction {
           namespaceToken: nsTA,
           namespaceGatekeeper: namespaceGatekeeperPDA,
           tokenHolder: payer,
+          payer,
+          systemProgram: SystemProgram.programId,
         })
         .instruction(),
     ];
@@ -350,12 +410,17 @@ export class Instruction extends SolKitInstruction {
           namespaceToken: nsTA,
           namespaceGatekeeper: namespaceGatekeeperPDA,
           tokenHolder: payer,
+          payer,
+          systemProgram: SystemProgram.programId,
         })
         .instruction(),
     ];
   }
 
-  async joinNamespace(accounts: JoinNamespaceAccounts) {
+  async joinNamespace(
+    accounts: JoinNamespaceAccounts,
+    args: JoinNamespaceArgs = { allowlistProofs: [] }
+  ) {
     const [namespacePDA, _namespacePDABump] = await getNamespacePDA(
       accounts.namespaceMint
     );
@@ -373,18 +438,19 @@ export class Instruction extends SolKitInstruction {
       payer
     );
 
-    const rainPayerAta = await splToken.Token.getAssociatedTokenAddress(
-      splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
-      splToken.TOKEN_PROGRAM_ID,
-      Common.RAIN_TOKEN_MINT,
-      payer
-    );
+    const [protocolFeeConfigPDA, _protocolFeeConfigBump] =
+      await getProtocolFeeConfigPDA();
 
-    const rainTokenVault = await splToken.Token.getAssociatedTokenAddress(
+    const protocolFeeConfig =
+      await this.program.client.account.protocolFeeConfig.fetch(
+        protocolFeeConfigPDA
+      );
+
+    const protocolFeePayerAta = await splToken.Token.getAssociatedTokenAddress(
       splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
       splToken.TOKEN_PROGRAM_ID,
-      Common.RAIN_TOKEN_MINT,
-      Common.RAIN_TOKEN_VAULT_AUTHORITY
+      protocolFeeConfig.feeMint,
+      payer
     );
 
     const nsData = await this.program.client.account.namespace.fetch(
@@ -392,7 +458,7 @@ export class Instruction extends SolKitInstruction {
     );
     const namespace = new Namespace(namespacePDA, nsData);
 
-    let ix: web3.TransactionInstruction;
+    const remainingAccounts: web3.AccountMeta[] = [];
 
     if (nsData.paymentMint && nsData.paymentVault && nsData.paymentAmount) {
       const paymentAta = await splToken.Token.getAssociatedTokenAddress(
@@ -402,7 +468,7 @@ export class Instruction extends SolKitInstruction {
         payer
       );
 
-      const remainingAccounts = [
+      remainingAccounts.push(
         {
           pubkey: namespace.paymentMint,
           isWritable: false,
@@ -417,48 +483,68 @@ export class Instruction extends SolKitInstruction {
           pubkey: paymentAta,
           isWritable: true,
           isSigner: false,
-        },
-      ];
+        }
+      );
+    }
 
-      ix = await this.program.client.methods
-        .joinNamespace()
-        .accounts({
-          namespace: namespacePDA,
-          namespaceToken: nsTA,
-          artifact: accounts.artifact,
-          namespaceGatekeeper: namespaceGatekeeperPDA,
-          tokenHolder: payer,
-          rainPayerAta: rainPayerAta,
-          rainTokenVault: rainTokenVault,
-          rainTokenMint: Common.RAIN_TOKEN_MINT,
-          raindropsProgram: RaindropsProgram.getRaindropsProgram(
-            accounts.raindropsProgram
-          ),
-          instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
-        })
-        .remainingAccounts(remainingAccounts)
-        .instruction();
-    } else {
-      ix = await this.program.client.methods
-        .joinNamespace()
-        .accounts({
-          namespace: namespacePDA,
-          namespaceToken: nsTA,
-          artifact: accounts.artifact,
-          namespaceGatekeeper: namespaceGatekeeperPDA,
-          tokenHolder: payer,
-          rainPayer: payer,
-          rainPayerAta: rainPayerAta,
-          rainTokenVault: rainTokenVault,
-          rainTokenMint: Common.RAIN_TOKEN_MINT,
-          raindropsProgram: RaindropsProgram.getRaindropsProgram(
-            accounts.raindropsProgram
-          ),
-          instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
-        })
-        .instruction();
+    // holder filter accounts follow the payment accounts, in gatekeeper order
+    const gatekeeper =
+      await this.program.client.account.namespaceGatekeeper.fetch(
+        namespaceGatekeeperPDA
+      );
+    const collectionNftMints = [...(accounts.collectionNftMints ?? [])];
+    for (const artifactFilter of gatekeeper.artifactFilters as any[]) {
+      const filter = artifactFilter.filter;
+      if (filter.verifiedCollection) {
+        const nftMint = collectionNftMints.shift();
+        if (!nftMint) {
+          throw new Error("Missing nft mint for verified collection filter");
+        }
+        remainingAccounts.push(
+          {
+            pubkey: (await getAtaForMint(nftMint, payer))[0],
+            isWritable: false,
+            isSigner: false,
+          },
+          {
+            pubkey: await getMetadata(nftMint),
+            isWritable: false,
+            isSigner: false,
+          }
+        );
+      } else if (filter.tokenBalance) {
+        remainingAccounts.push({
+          pubkey: (await getAtaForMint(filter.tokenBalance.mint, payer))[0],
+          isWritable: false,
+          isSigner: false,
+        });
+      }
     }
 
+    const [membershipPaymentPDA, _membershipPaymentBump] =
+      await getMembershipPaymentPDA(namespacePDA, accounts.artifact);
+
+    const ix = await this.program.client.methods
+      .joinNamespace(args)
+      .accounts({
+        namespace: namespacePDA,
+        namespaceToken: nsTA,
+        artifact: accounts.artifact,
+        namespaceGatekeeper: namespaceGatekeeperPDA,
+        tokenHolder: payer,
+        membershipPayment: membershipPaymentPDA,
+        protocolFeeConfig: protocolFeeConfigPDA,
+        protocolFeeVault: protocolFeeConfig.feeVault,
+        protocolFeePayerAta,
+        raindropsProgram: RaindropsProgram.getRaindropsProgram(
+          accounts.raindropsProgram
+        ),
+        instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .instruction();
+
     return [ix];
   }
 
@@ -480,6 +566,43 @@ export class Instruction extends SolKitInstruction {
       payer
     );
 
+    const nsData = await this.program.client.account.namespace.fetch(
+      namespacePDA
+    );
+    const namespace = new Namespace(namespacePDA, nsData);
+
+    // the join payment is refunded out of the payment vault
+    const remainingAccounts: web3.AccountMeta[] = [];
+    if (namespace.refundPaymentOnLeave) {
+      const paymentAta = await splToken.Token.getAssociatedTokenAddress(
+        splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        splToken.TOKEN_PROGRAM_ID,
+        namespace.paymentMint,
+        payer
+      );
+
+      remainingAccounts.push(
+        {
+          pubkey: namespace.paymentMint,
+          isWritable: false,
+          isSigner: false,
+        },
+        {
+          pubkey: namespace.paymentVault,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: paymentAta,
+          isWritable: true,
+          isSigner: false,
+        }
+      );
+    }
+
+    const [membershipPaymentPDA, _membershipPaymentBump] =
+      await getMembershipPaymentPDA(namespacePDA, accounts.artifact);
+
     return [
       await this.program.client.methods
         .leaveNamespace()
@@ -489,16 +612,103 @@ export class Instruction extends SolKitInstruction {
           artifact: accounts.artifact,
           namespaceGatekeeper: namespaceGatekeeperPDA,
           tokenHolder: payer,
+          membershipPayment: membershipPaymentPDA,
           raindropsProgram: RaindropsProgram.getRaindropsProgram(
             accounts.raindropsProgram
           ),
           instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction(),
+    ];
+  }
+
+  async initializeProtocolFeeConfig(
+    args: InitializeProtocolFeeConfigArgs,
+    accounts: InitializeProtocolFeeConfigAccounts
+  ) {
+    const [protocolFeeConfigPDA, _protocolFeeConfigBump] =
+      await getProtocolFeeConfigPDA();
+
+    // only the upgrade authority of the namespace program may do this
+    const [programData, _programDataBump] =
+      await web3.PublicKey.findProgramAddress(
+        [NAMESPACE_ID.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+      );
+
+    const payer = (this.program.client.provider as AnchorProvider).wallet
+      .publicKey;
+
+    return [
+      await this.program.client.methods
+        .initializeProtocolFeeConfig(args)
+        .accounts({
+          protocolFeeConfig: protocolFeeConfigPDA,
+          feeMint: accounts.feeMint,
+          feeVault: accounts.feeVault,
+          admin: payer,
+          program: NAMESPACE_ID,
+          programData,
+          payer,
+          systemProgram: SystemProgram.programId,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+        })
+        .instruction(),
+    ];
+  }
+
+  async updateProtocolFeeConfig(
+    args: UpdateProtocolFeeConfigArgs,
+    accounts: UpdateProtocolFeeConfigAccounts
+  ) {
+    const [protocolFeeConfigPDA, _protocolFeeConfigBump] =
+      await getProtocolFeeConfigPDA();
+
+    return [
+      await this.program.client.methods
+        .updateProtocolFeeConfig(args)
+        .accounts({
+          protocolFeeConfig: protocolFeeConfigPDA,
+          feeMint: accounts.feeMint,
+          feeVault: accounts.feeVault,
+          admin: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+        })
+        .instruction(),
+    ];
+  }
+
+  async setNamespaceProtocolFeeExempt(
+    exempt: boolean,
+    accounts: SetNamespaceProtocolFeeExemptAccounts
+  ) {
+    const [protocolFeeConfigPDA, _protocolFeeConfigBump] =
+      await getProtocolFeeConfigPDA();
+
+    const [namespacePDA, _namespacePDABump] = await getNamespacePDA(
+      accounts.namespaceMint
+    );
+
+    return [
+      await this.program.client.methods
+        .setNamespaceProtocolFeeExempt(exempt)
+        .accounts({
+          protocolFeeConfig: protocolFeeConfigPDA,
+          namespace: namespacePDA,
+          admin: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
         })
         .instruction(),
     ];
   }
 
-  async cacheArtifact(accounts: CacheArtifactAccounts) {
+  async cacheArtifact(
+    accounts: CacheArtifactAccounts,
+    cacheArgs: CacheArtifactArgs = { sortKey: null }
+  ) {
     const [namespacePDA, _namespacePDABump] = await getNamespacePDA(
       accounts.namespaceMint
     );
@@ -531,6 +741,7 @@ export class Instruction extends SolKitInstruction {
 
     const args = {
       page: page,
+      sortKey: cacheArgs.sortKey,
     };
 
     return [
@@ -592,4 +803,59 @@ export class Instruction extends SolKitInstruction {
         .instruction(),
     ];
   }
+  async compactIndex(args: CompactIndexArgs, accounts: CompactIndexAccounts) {
+    const [namespacePDA, _namespacePDABump] = await getNamespacePDA(
+      accounts.namespaceMint
+    );
+
+    const payer = (this.program.client.provider as AnchorProvider).wallet
+      .publicKey;
+
+    const nsTA = await splToken.Token.getAssociatedTokenAddress(
+      splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+      splToken.TOKEN_PROGRAM_ID,
+      accounts.namespaceMint,
+      payer
+    );
+
+    const [fromIndex, _fromIndexBump] = await getIndexPDA(
+      namespacePDA,
+      args.fromPage
+    );
+    const [toIndex, _toIndexBump] = await getIndexPDA(
+      namespacePDA,
+      args.toPage
+    );
+
+    const remainingAccounts = accounts.artifacts.flatMap(
+      ({ artifact, raindropsProgram }) => [
+        {
+          pubkey: artifact,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: RaindropsProgram.getRaindropsProgram(raindropsProgram),
+          isWritable: false,
+          isSigner: false,
+        },
+      ]
+    );
+
+    return [
+      await this.program.client.methods
+        .compactIndex(args)
+        .accounts({
+          namespace: namespacePDA,
+          namespaceToken: nsTA,
+          fromIndex,
+          toIndex,
+          tokenHolder: payer,
+          instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction(),
+    ];
+  }
 }
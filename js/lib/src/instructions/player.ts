@@ -45,6 +45,15 @@ const PLAYER_CLASS_DATA_ARGS_CONVERT_TO_BNS = [
   "playerClassData.config.bodyParts.[].totalItemSpots",
   "playerClassData.config.equipValidation.code",
   "playerClassData.config.addToPackValidation.code",
+  "playerClassData.config.backpack.maxItems",
+  "playerClassData.config.backpack.maxWeight",
+  "playerClassData.config.backpack.statBonuses.[].itemsPerPoint",
+  "playerClassData.config.backpack.statBonuses.[].weightPerPoint",
+  "playerClassData.config.equipmentSets.[].bonuses.[].basicItemEffects.[].amount",
+  "playerClassData.config.equipmentSets.[].bonuses.[].basicItemEffects.[].stakingAmountNumerator",
+  "playerClassData.config.equipmentSets.[].bonuses.[].basicItemEffects.[].stakingAmountDivisor",
+  "playerClassData.config.equipmentSets.[].bonuses.[].basicItemEffects.[].clampMin",
+  "playerClassData.config.equipmentSets.[].bonuses.[].basicItemEffects.[].clampMax",
 ];
 
 const PLAYER_DATA_ARGS_CONVERT_TO_BNS = [
@@ -58,6 +67,7 @@ const PLAYER_DATA_ARGS_CONVERT_TO_BNS = [
 const PLAYER_CLASS_DATA_ARGS_CONVERT_TO_PUBKEYS = [
   "playerClassData.config.equipValidation.key",
   "playerClassData.config.addToPackValidation.key",
+  "playerClassData.config.equipmentSets.[].itemClasses.[]",
 ];
 export interface ToggleEquipItemArgs {
   itemIndex: BN;
@@ -247,6 +257,23 @@ export interface SubtractItemEffectAdditionalArgs {
   itemUsageIndex: number;
 }
 
+export interface ExpireItemEffectsAccounts {
+  player: PublicKey;
+  playerClass: PublicKey;
+  markers: {
+    item: PublicKey;
+    amount: BN;
+    itemUsageIndex: number;
+    // who paid for the marker, receives the rent back on close
+    // markers created before the payer was recorded refund the player's token
+    // holder, who must be the wallet
+    payer: PublicKey;
+  }[];
+  // token account holding the player, needed to close markers created before
+  // the payer was recorded
+  playerTokenAccount?: PublicKey;
+}
+
 export interface AddItemEffectAdditionalArgs {
   amount: BN;
   playerClassMint: PublicKey;
@@ -1106,6 +1133,55 @@ export class Instruction extends SolKitInstruction {
     };
   }
 
+  async expireItemEffects(_args = {}, accounts: ExpireItemEffectsAccounts) {
+    const wallet = (this.program.client.provider as AnchorProvider).wallet
+      .publicKey;
+    const remainingAccounts = [];
+    for (const marker of accounts.markers) {
+      remainingAccounts.push(
+        {
+          pubkey: (
+            await getPlayerItemActivationMarker({
+              item: marker.item,
+              player: accounts.player,
+              amount: marker.amount,
+              itemUsageIndex: new BN(marker.itemUsageIndex),
+            })
+          )[0],
+          isWritable: true,
+          isSigner: false,
+        },
+        { pubkey: marker.item, isWritable: false, isSigner: false },
+        {
+          pubkey: marker.payer,
+          isWritable: true,
+          isSigner: marker.payer.equals(wallet),
+        }
+      );
+    }
+    if (accounts.playerTokenAccount) {
+      remainingAccounts.push({
+        pubkey: accounts.playerTokenAccount,
+        isWritable: false,
+        isSigner: false,
+      });
+    }
+    return {
+      instructions: [
+        await this.program.client.methods
+          .expireItemEffects()
+          .accounts({
+            player: accounts.player,
+            playerClass: accounts.playerClass,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .remainingAccounts(remainingAccounts)
+          .instruction(),
+      ],
+      signers: [],
+    };
+  }
+
   async addItem(
     args: AddItemArgs,
     accounts: AddItemAccounts,
@@ -13,6 +13,11 @@ import {
   getArtifactIntermediaryStakingCounterForWarmup,
   getArtifactIntermediaryStakingCounterForCooldown,
   getArtifactMintStakingAccount,
+  getRewardPool,
+  getRewardVault,
+  getStakeRewards,
+  getStakePositions,
+  getLockupTiers,
 } from "../utils/pda";
 
 const { generateRemainingAccountsForGivenPermissivenessToUse } = ContractCommon;
@@ -26,6 +31,7 @@ export interface BeginArtifactStakeWarmupArgs {
   artifactMint: web3.PublicKey;
   stakingAmount: BN;
   stakingPermissivenessToUse: AnchorPermissivenessType | null;
+  lockupTier: number | null;
 }
 
 export interface BeginArtifactStakeWarmupAccounts {
@@ -52,6 +58,8 @@ export interface EndArtifactStakeWarmupAccounts {
   artifactClass: web3.PublicKey;
   artifact: web3.PublicKey;
   stakingMint: web3.PublicKey;
+  // every reward pool counting the artifact's stake of this mint
+  rewardPools?: web3.PublicKey[];
 }
 
 export interface BeginArtifactStakeCooldownArgs {
@@ -62,6 +70,7 @@ export interface BeginArtifactStakeCooldownArgs {
   artifactClassMint: web3.PublicKey;
   artifactMint: web3.PublicKey;
   stakingPermissivenessToUse: AnchorPermissivenessType | null;
+  amount: BN | null;
 }
 
 export interface BeginArtifactStakeCooldownAccounts {
@@ -73,6 +82,8 @@ export interface BeginArtifactStakeCooldownAccounts {
   parentClassMint: web3.PublicKey | null;
   parentClass: web3.PublicKey | null;
   metadataUpdateAuthority: web3.PublicKey | null;
+  // every reward pool counting the artifact's stake of this mint
+  rewardPools?: web3.PublicKey[];
 }
 
 export interface EndArtifactStakeCooldownArgs {
@@ -90,6 +101,95 @@ export interface EndArtifactStakeCooldownAccounts {
   stakingMint: web3.PublicKey;
 }
 
+export interface CreateRewardPoolArgs {
+  emissionRate: BN;
+}
+
+export interface CreateRewardPoolAccounts {
+  scope: web3.PublicKey;
+  stakingMint: web3.PublicKey;
+  rewardMint: web3.PublicKey;
+}
+
+export interface TopUpRewardPoolArgs {
+  amount: BN;
+}
+
+export interface TopUpRewardPoolAccounts {
+  rewardPool: web3.PublicKey;
+  fundingAccount: web3.PublicKey;
+  fundingTransferAuthority: web3.Keypair;
+}
+
+export interface CloseRewardPoolAccounts {
+  rewardPool: web3.PublicKey;
+  destination: web3.PublicKey;
+}
+
+export interface ClaimRewardsArgs {
+  classIndex: BN;
+  parentClassIndex: BN | null;
+  index: BN;
+  artifactClassMint: web3.PublicKey;
+  artifactMint: web3.PublicKey;
+  stakingPermissivenessToUse: AnchorPermissivenessType | null;
+}
+
+export interface LockupTier {
+  lockDuration: BN;
+  multiplierBps: BN;
+}
+
+export interface SetLockupTiersArgs {
+  classIndex: BN;
+  parentClassIndex: BN | null;
+  artifactClassMint: web3.PublicKey;
+  updatePermissivenessToUse: AnchorPermissivenessType | null;
+  tiers: LockupTier[];
+}
+
+export interface SetLockupTiersAccounts {
+  artifactClass: web3.PublicKey;
+  parentClassMint: web3.PublicKey | null;
+  parentClass: web3.PublicKey | null;
+  metadataUpdateAuthority: web3.PublicKey | null;
+}
+
+export interface ClaimRewardsAccounts {
+  artifactClass: web3.PublicKey;
+  artifact: web3.PublicKey;
+  stakingMint: web3.PublicKey;
+  rewardPool: web3.PublicKey;
+  rewardAccount: web3.PublicKey;
+  parentClassMint: web3.PublicKey | null;
+  parentClass: web3.PublicKey | null;
+  metadataUpdateAuthority: web3.PublicKey | null;
+}
+
+// The program settles stake rewards for every reward pool and artifact position
+// pair at the end of the remaining accounts. Pools already counting the stake
+// must all be passed.
+const generateRemainingAccountsForRewardPool = async (args: {
+  rewardPools: web3.PublicKey[] | undefined;
+  artifactMintStakingAccount: web3.PublicKey;
+}): Promise<web3.AccountMeta[]> => {
+  const remainingAccounts: web3.AccountMeta[] = [];
+
+  for (const rewardPool of args.rewardPools || []) {
+    const [stakeRewards, _stakeRewardsBump] = await getStakeRewards({
+      rewardPool,
+      artifactMintStakingAccount: args.artifactMintStakingAccount,
+    });
+
+    remainingAccounts.push(
+      { pubkey: rewardPool, isWritable: true, isSigner: false },
+      { pubkey: stakeRewards, isWritable: true, isSigner: false }
+    );
+  }
+
+  return remainingAccounts;
+};
+
 export class Instruction extends SolKitInstruction {
   constructor(args: { program: Program.Program }) {
     super(args);
@@ -121,6 +221,10 @@ export class Instruction extends SolKitInstruction {
       stakingIndex: args.stakingIndex,
     });
 
+    const [lockupTiers, _lockupTiersBump] = await getLockupTiers({
+      artifactClass: accounts.artifactClass,
+    });
+
     const remainingAccounts =
       await generateRemainingAccountsForGivenPermissivenessToUse({
         permissivenessToUse: args.stakingPermissivenessToUse,
@@ -152,6 +256,7 @@ export class Instruction extends SolKitInstruction {
           stakingAccount: accounts.stakingAccount,
           stakingMint: accounts.stakingMint,
           stakingTransferAuthority: accounts.stakingTransferAuthority.publicKey,
+          lockupTiers,
           namespace: accounts.namespace,
           payer: (this.program.client.provider as AnchorProvider).wallet
             .publicKey,
@@ -199,6 +304,12 @@ export class Instruction extends SolKitInstruction {
         stakingMint: accounts.stakingMint,
       });
 
+    const [stakePositions, _stakePositionsBump] = await getStakePositions({
+      artifactClassMint: args.artifactClassMint,
+      artifactMint: args.artifactMint,
+      index: args.index,
+    });
+
     return [
       await this.program.client.methods
         .endArtifactStakeWarmup(args)
@@ -208,6 +319,7 @@ export class Instruction extends SolKitInstruction {
           artifactIntermediaryStakingAccount,
           artifactIntermediaryStakingCounter,
           artifactMintStakingAccount,
+          stakePositions,
           stakingMint: accounts.stakingMint,
           payer: (this.program.client.provider as AnchorProvider).wallet
             .publicKey,
@@ -219,6 +331,12 @@ export class Instruction extends SolKitInstruction {
           clock: web3.SYSVAR_CLOCK_PUBKEY,
           instructionSysvarAccount: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
         })
+        .remainingAccounts(
+          await generateRemainingAccountsForRewardPool({
+            rewardPools: accounts.rewardPools,
+            artifactMintStakingAccount,
+          })
+        )
         .instruction(),
     ];
   }
@@ -258,6 +376,12 @@ export class Instruction extends SolKitInstruction {
         stakingMint: accounts.stakingMint,
       });
 
+    const [stakePositions, _stakePositionsBump] = await getStakePositions({
+      artifactClassMint: args.artifactClassMint,
+      artifactMint: args.artifactMint,
+      index: args.index,
+    });
+
     const remainingAccounts =
       await generateRemainingAccountsForGivenPermissivenessToUse({
         permissivenessToUse: args.stakingPermissivenessToUse,
@@ -279,6 +403,7 @@ export class Instruction extends SolKitInstruction {
           artifactIntermediaryStakingAccount,
           artifactIntermediaryStakingCounter,
           artifactMintStakingAccount,
+          stakePositions,
           stakingAccount: accounts.stakingAccount,
           stakingMint: accounts.stakingMint,
           payer: (this.program.client.provider as AnchorProvider).wallet
@@ -291,7 +416,13 @@ export class Instruction extends SolKitInstruction {
           clock: web3.SYSVAR_CLOCK_PUBKEY,
           instructionSysvarAccount: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
         })
-        .remainingAccounts(remainingAccounts)
+        .remainingAccounts([
+          ...remainingAccounts,
+          ...(await generateRemainingAccountsForRewardPool({
+            rewardPools: accounts.rewardPools,
+            artifactMintStakingAccount,
+          })),
+        ])
         .instruction(),
     ];
   }
@@ -341,4 +472,189 @@ export class Instruction extends SolKitInstruction {
         .instruction(),
     ];
   }
+
+  async createRewardPool(
+    args: CreateRewardPoolArgs,
+    accounts: CreateRewardPoolAccounts
+  ) {
+    const authority = (this.program.client.provider as AnchorProvider).wallet
+      .publicKey;
+
+    const [rewardPool, _rewardPoolBump] = await getRewardPool({
+      scope: accounts.scope,
+      stakingMint: accounts.stakingMint,
+      rewardMint: accounts.rewardMint,
+      authority,
+    });
+
+    const [rewardVault, _rewardVaultBump] = await getRewardVault(rewardPool);
+
+    return [
+      await this.program.client.methods
+        .createRewardPool(args)
+        .accounts({
+          rewardPool,
+          rewardVault,
+          scope: accounts.scope,
+          stakingMint: accounts.stakingMint,
+          rewardMint: accounts.rewardMint,
+          authority,
+          payer: authority,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+          clock: web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .instruction(),
+    ];
+  }
+
+  async topUpRewardPool(
+    args: TopUpRewardPoolArgs,
+    accounts: TopUpRewardPoolAccounts
+  ) {
+    const [rewardVault, _rewardVaultBump] = await getRewardVault(
+      accounts.rewardPool
+    );
+
+    return [
+      Token.createApproveInstruction(
+        TOKEN_PROGRAM_ID,
+        accounts.fundingAccount,
+        accounts.fundingTransferAuthority.publicKey,
+        (this.program.client.provider as AnchorProvider).wallet.publicKey,
+        [],
+        args.amount.toNumber()
+      ),
+      await this.program.client.methods
+        .topUpRewardPool(args)
+        .accounts({
+          rewardPool: accounts.rewardPool,
+          rewardVault,
+          fundingAccount: accounts.fundingAccount,
+          fundingTransferAuthority: accounts.fundingTransferAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          clock: web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .instruction(),
+    ];
+  }
+
+  async closeRewardPool(accounts: CloseRewardPoolAccounts) {
+    const [rewardVault, _rewardVaultBump] = await getRewardVault(
+      accounts.rewardPool
+    );
+
+    return [
+      await this.program.client.methods
+        .closeRewardPool()
+        .accounts({
+          rewardPool: accounts.rewardPool,
+          rewardVault,
+          destination: accounts.destination,
+          authority: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          clock: web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .instruction(),
+    ];
+  }
+
+  async claimRewards(args: ClaimRewardsArgs, accounts: ClaimRewardsAccounts) {
+    const [artifactMintStakingAccount, _artifactMintStakingAccountBump] =
+      await getArtifactMintStakingAccount({
+        artifactClassMint: args.artifactClassMint,
+        artifactMint: args.artifactMint,
+        index: args.index,
+        stakingMint: accounts.stakingMint,
+      });
+
+    const [rewardVault, _rewardVaultBump] = await getRewardVault(
+      accounts.rewardPool
+    );
+
+    const [stakeRewards, _stakeRewardsBump] = await getStakeRewards({
+      rewardPool: accounts.rewardPool,
+      artifactMintStakingAccount,
+    });
+
+    const [stakePositions, _stakePositionsBump] = await getStakePositions({
+      artifactClassMint: args.artifactClassMint,
+      artifactMint: args.artifactMint,
+      index: args.index,
+    });
+
+    const remainingAccounts =
+      await generateRemainingAccountsForGivenPermissivenessToUse({
+        permissivenessToUse: args.stakingPermissivenessToUse,
+        tokenMint: args.artifactClassMint,
+        parentClassMint: accounts.parentClassMint,
+        parentClass: accounts.parentClass,
+        metadataUpdateAuthority: accounts.metadataUpdateAuthority,
+        owner: (this.program.client.provider as AnchorProvider).wallet
+          .publicKey,
+        program: this.program.client,
+      });
+
+    return [
+      await this.program.client.methods
+        .claimRewards(args)
+        .accounts({
+          artifactClass: accounts.artifactClass,
+          artifact: accounts.artifact,
+          artifactMintStakingAccount,
+          stakingMint: accounts.stakingMint,
+          rewardPool: accounts.rewardPool,
+          rewardVault,
+          stakeRewards,
+          stakePositions,
+          rewardAccount: accounts.rewardAccount,
+          payer: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+          clock: web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction(),
+    ];
+  }
+
+  async setLockupTiers(
+    args: SetLockupTiersArgs,
+    accounts: SetLockupTiersAccounts
+  ) {
+    const [lockupTiers, _lockupTiersBump] = await getLockupTiers({
+      artifactClass: accounts.artifactClass,
+    });
+
+    const remainingAccounts =
+      await generateRemainingAccountsForGivenPermissivenessToUse({
+        permissivenessToUse: args.updatePermissivenessToUse,
+        tokenMint: args.artifactClassMint,
+        parentClassMint: accounts.parentClassMint,
+        parentClass: accounts.parentClass,
+        metadataUpdateAuthority: accounts.metadataUpdateAuthority,
+        owner: (this.program.client.provider as AnchorProvider).wallet
+          .publicKey,
+        program: this.program.client,
+      });
+
+    return [
+      await this.program.client.methods
+        .setLockupTiers(args)
+        .accounts({
+          artifactClass: accounts.artifactClass,
+          lockupTiers,
+          payer: (this.program.client.provider as AnchorProvider).wallet
+            .publicKey,
+          systemProgram: SystemProgram.programId,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction(),
+    ];
+  }
 }
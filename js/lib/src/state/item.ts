@@ -15,12 +15,57 @@ import { Payment, PaymentState } from "../instructions/item";
 
 extendBorsh();
 
+// mirrors ITEM_CLASS_DATA_LAYOUT_MARKER, ITEM_CLASS_DATA_VERSION and
+// ItemClassDataLayout::SIZE in the program
+export const ITEM_CLASS_DATA_LAYOUT_MARKER = 0xff;
+export const ITEM_CLASS_DATA_VERSION = 2;
+export const ITEM_CLASS_DATA_LAYOUT_SIZE = 10;
+// the layout version storing the legacy config
+const LEGACY_ITEM_CLASS_DATA_VERSION = 1;
+
+// offset of the item class data, right after the header
+const getItemClassDataOffset = (buffer: Buffer): number => {
+  let offset = 8;
+  if (buffer[offset] === 1) {
+    const namespaces = buffer.readUInt32LE(offset + 1);
+    offset += 5;
+    for (let i = 0; i < namespaces; i++) {
+      // namespace, index option, inherited
+      offset += 32 + (buffer[offset + 32] === 1 ? 9 : 1) + 1;
+    }
+  } else {
+    offset += 1;
+  }
+  // parent, mint, index, metadata, edition
+  for (const size of [32, 32, 8, 32, 32]) {
+    offset += buffer[offset] === 1 ? 1 + size : 1;
+  }
+  // bump, existing children
+  return offset + 1 + 8;
+};
+
+export const isVersionedItemClassData = (buffer: Buffer): boolean =>
+  buffer[getItemClassDataOffset(buffer)] === ITEM_CLASS_DATA_LAYOUT_MARKER;
+
 export const decodeItemClass = (buffer: Buffer): ItemClass => {
-  const metadata = deserializeUnchecked(
-    ITEM_SCHEMA,
-    ItemClass,
-    buffer
-  ) as ItemClass;
+  // legacy and version 1 data store basic item effects without clamps and
+  // stacking policies, and no weight or propagation version
+  let schema = LEGACY_ITEM_SCHEMA;
+  // the versioned layout puts the section lengths before the data, skip them
+  if (isVersionedItemClassData(buffer)) {
+    const offset = getItemClassDataOffset(buffer);
+    const version = buffer[offset + 1];
+    if (version === ITEM_CLASS_DATA_VERSION) {
+      schema = ITEM_SCHEMA;
+    } else if (version !== LEGACY_ITEM_CLASS_DATA_VERSION) {
+      throw new Error(`Unsupported item class data layout version ${version}`);
+    }
+    buffer = Buffer.concat([
+      buffer.subarray(0, offset),
+      buffer.subarray(offset + ITEM_CLASS_DATA_LAYOUT_SIZE),
+    ]);
+  }
+  const metadata = deserializeUnchecked(schema, ItemClass, buffer) as ItemClass;
   return metadata;
 };
 
@@ -81,6 +126,8 @@ export class ItemClassConfig {
   componentRoot: null | Root;
   usages: null | ItemUsage[];
   components: null | Component[];
+  weight: null | BN;
+  propagationVersion: null | BN;
 
   constructor(args: {
     usageRoot: null | Root;
@@ -88,12 +135,16 @@ export class ItemClassConfig {
     componentRoot: null | Root;
     usages: null | ItemUsage[];
     components: null | Component[];
+    weight: null | BN;
+    propagationVersion: null | BN;
   }) {
     this.usageRoot = args.usageRoot;
     this.usageStateRoot = args.usageStateRoot;
     this.componentRoot = args.componentRoot;
     this.usages = args.usages;
     this.components = args.components;
+    this.weight = args.weight;
+    this.propagationVersion = args.propagationVersion;
   }
 }
 
@@ -285,6 +336,9 @@ export class BasicItemEffect {
   stakingDurationNumerator: null | BN;
   stakingDurationDivisor: null | BN;
   maxUses: null | BN;
+  clampMin: null | BN;
+  clampMax: null | BN;
+  stackingPolicy: null | StackingPolicy;
 
   constructor(args: {
     amount: BN;
@@ -296,6 +350,9 @@ export class BasicItemEffect {
     stakingDurationNumerator: null | BN;
     stakingDurationDivisor: null | BN;
     maxUses: null | BN;
+    clampMin: null | BN;
+    clampMax: null | BN;
+    stackingPolicy: null | StackingPolicy;
   }) {
     this.amount = args.amount;
     this.stat = args.stat;
@@ -306,6 +363,9 @@ export class BasicItemEffect {
     this.stakingDurationNumerator = args.stakingDurationNumerator;
     this.stakingDurationDivisor = args.stakingDurationDivisor;
     this.maxUses = args.maxUses;
+    this.clampMin = args.clampMin;
+    this.clampMax = args.clampMax;
+    this.stackingPolicy = args.stackingPolicy;
   }
 }
 
@@ -316,6 +376,14 @@ export enum BasicItemEffectType {
   DecrementPercent,
   IncrementPercentFromBase,
   DecrementPercentFromBase,
+  Set,
+  Clamp,
+}
+
+export enum StackingPolicy {
+  Stack,
+  RefreshDuration,
+  HighestWins,
 }
 
 export const ITEM_SCHEMA = new Map<any, any>([
@@ -501,6 +569,9 @@ export const ITEM_SCHEMA = new Map<any, any>([
         ["stakingDurationNumerator", { kind: "option", type: "u64" }],
         ["stakingDurationDivisor", { kind: "option", type: "u64" }],
         ["maxUses", { kind: "option", type: "u64" }],
+        ["clampMin", { kind: "option", type: "i64" }],
+        ["clampMax", { kind: "option", type: "i64" }],
+        ["stackingPolicy", { kind: "option", type: "u8" }],
       ],
     },
   ],
@@ -514,6 +585,8 @@ export const ITEM_SCHEMA = new Map<any, any>([
         ["componentRoot", { kind: "option", type: Root }],
         ["usages", { kind: "option", type: [ItemUsage] }],
         ["components", { kind: "option", type: [Component] }],
+        ["weight", { kind: "option", type: "u64" }],
+        ["propagationVersion", { kind: "option", type: "u64" }],
       ],
     },
   ],
@@ -540,6 +613,31 @@ export const ITEM_SCHEMA = new Map<any, any>([
   ],
 ]);
 
+const EFFECT_POLICY_FIELDS = ["clampMin", "clampMax", "stackingPolicy"];
+const LATEST_CONFIG_FIELDS = ["weight", "propagationVersion"];
+
+const LEGACY_ITEM_SCHEMA = new Map<any, any>([
+  ...ITEM_SCHEMA,
+  [
+    BasicItemEffect,
+    {
+      kind: "struct",
+      fields: ITEM_SCHEMA.get(BasicItemEffect).fields.filter(
+        ([name]) => !EFFECT_POLICY_FIELDS.includes(name)
+      ),
+    },
+  ],
+  [
+    ItemClassConfig,
+    {
+      kind: "struct",
+      fields: ITEM_SCHEMA.get(ItemClassConfig).fields.filter(
+        ([name]) => !LATEST_CONFIG_FIELDS.includes(name)
+      ),
+    },
+  ],
+]);
+
 export interface ItemClassV1 {
   authority: web3.PublicKey;
   items: web3.PublicKey;
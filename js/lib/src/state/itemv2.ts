@@ -1,6 +1,7 @@
 import { BN, web3 } from "@project-serum/anchor";
 import { BuildOutput } from "../instructions/itemv2";
 import { sha256 } from "js-sha256";
+import { NamespaceAndIndex } from "./common";
 
 export const ITEMV2_ID = new web3.PublicKey(
   "itEm2FtqJWqUmMTYrUxoFcmFtBxEpd68VTmxJamQXA3"
@@ -12,6 +13,7 @@ export interface ItemClass {
   recipeIndex: BN | null;
   recipes: Recipe[];
   mode: ItemClassMode;
+  namespaces: NamespaceAndIndex[];
 }
 
 export type ItemClassModeSelection =
@@ -264,6 +266,18 @@ export function getItemClassPda(authorityMint: web3.PublicKey): web3.PublicKey {
   return itemClass;
 }
 
+export function getItemClassV1ImportPda(
+  itemClassV1: web3.PublicKey
+): web3.PublicKey {
+  const [itemClassV1Import, _itemClassV1ImportBump] =
+    web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("item_class_v1_import"), itemClassV1.toBuffer()],
+      ITEMV2_ID
+    );
+
+  return itemClassV1Import;
+}
+
 export function getItemPda(itemMint: web3.PublicKey): web3.PublicKey {
   const [item, _itemBump] = web3.PublicKey.findProgramAddressSync(
     [Buffer.from("item"), itemMint.toBuffer()],
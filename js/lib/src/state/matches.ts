@@ -12,12 +12,20 @@ export class Match {
   leaveAllowed: boolean;
   minimumAllowedEntryTime: number | null;
   bump: number;
-  currentTokenTransferIndex: number;
+  tokenTransfersCompleted: number;
   tokenTypesAdded: number;
   tokenTypesRemoved: number;
   tokenEntryValidation: AnchorTokenEntryValidation[] | null;
   tokenEntryValidationRoot: Root | null;
   joinAllowedDuringStart: boolean;
+  tokenTransfersClaimed: Buffer;
+  entryFee: AnchorEntryFee | null;
+  rake: AnchorRake | null;
+  payoutSchedule: AnchorPayoutSchedule;
+  prizePool: number;
+  prizePoolPaid: number;
+  rakePaid: boolean;
+  prizesClaimed: Buffer;
 
   constructor(key, data) {
     this.key = key;
@@ -30,12 +38,20 @@ export class Match {
     this.leaveAllowed = data.leaveAllowed;
     this.minimumAllowedEntryTime = data.minimumAllowedEntryTime;
     this.bump = data.bump;
-    this.currentTokenTransferIndex = data.currentTokenTransferIndex;
+    this.tokenTransfersCompleted = data.tokenTransfersCompleted;
     this.tokenTypesAdded = data.tokenTypesAdded;
     this.tokenTypesRemoved = data.tokenTypesRemoved;
     this.tokenEntryValidation = data.tokenEntryValidation;
     this.tokenEntryValidationRoot = data.tokenEntryValidationRoot;
     this.joinAllowedDuringStart = data.joinAllowedDuringStart;
+    this.tokenTransfersClaimed = data.tokenTransfersClaimed;
+    this.entryFee = data.entryFee;
+    this.rake = data.rake;
+    this.payoutSchedule = data.payoutSchedule;
+    this.prizePool = data.prizePool;
+    this.prizePoolPaid = data.prizePoolPaid;
+    this.rakePaid = data.rakePaid;
+    this.prizesClaimed = data.prizesClaimed;
   }
 }
 
@@ -44,12 +60,14 @@ export class WinOracle {
   finalized: boolean;
   tokenTransferRoot: Root | null;
   tokenTransfers: AnchorTokenDelta[] | null;
+  rankings: web3.PublicKey[] | null;
 
   constructor(key, data) {
     this.key = key;
     this.finalized = data.finalized;
     this.tokenTransferRoot = data.tokenTransferRoot;
     this.tokenTransfers = data.tokenTransfers;
+    this.rankings = data.rankings;
   }
 }
 
@@ -62,6 +80,21 @@ export interface AnchorMatchState {
   deactivated?: boolean;
 }
 
+export interface AnchorEntryFee {
+  mint: web3.PublicKey;
+  amount: BN;
+}
+
+export interface AnchorRake {
+  treasury: web3.PublicKey;
+  basisPoints: number;
+}
+
+export interface AnchorPayoutSchedule {
+  winnerTakeAll?: boolean;
+  topN?: { basis_points: number[] };
+}
+
 export interface AnchorTokenEntryValidation {
   filter: AnchorFilter;
   isBlacklist: boolean;
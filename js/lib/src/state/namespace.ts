@@ -1,6 +1,7 @@
-import { web3 } from "@project-serum/anchor";
+import { web3, BN } from "@project-serum/anchor";
 import { NamespaceAndIndex } from "./common";
 import * as pids from "../constants/programIds";
+import { ITEMV2_ID } from "./itemv2";
 
 export class PermissivenessSettings {
   namespacePermissiveness: Permissiveness;
@@ -44,10 +45,18 @@ export class Filter {
 
   constructor(
     filterType: FilterType,
-    filterData: FilterNamespaces | FilterKey
+    filterData:
+      | FilterNamespaces
+      | FilterKey
+      | FilterVerifiedCollection
+      | FilterMerkleAllowlist
+      | FilterTokenBalance
   ) {
     const filterNs = filterData as FilterNamespaces;
     const filterKeys = filterData as FilterKey;
+    const filterCollection = filterData as FilterVerifiedCollection;
+    const filterAllowlist = filterData as FilterMerkleAllowlist;
+    const filterBalance = filterData as FilterTokenBalance;
 
     switch (filterType) {
       case FilterType.FilterNamespaces:
@@ -63,6 +72,22 @@ export class Filter {
           },
         };
         break;
+      case FilterType.FilterVerifiedCollection:
+        this.filter = {
+          verifiedCollection: { collection: filterCollection.collection },
+        };
+        break;
+      case FilterType.FilterMerkleAllowlist:
+        this.filter = { merkleAllowlist: { root: filterAllowlist.root } };
+        break;
+      case FilterType.FilterTokenBalance:
+        this.filter = {
+          tokenBalance: {
+            mint: filterBalance.mint,
+            minimumAmount: filterBalance.minimumAmount,
+          },
+        };
+        break;
     }
   }
 }
@@ -71,6 +96,9 @@ export enum FilterType {
   FilterNamespaces = "NAMESPACES",
   FilterCategories = "CATEGORIES",
   FilterKey = "KEY",
+  FilterVerifiedCollection = "VERIFIED_COLLECTION",
+  FilterMerkleAllowlist = "MERKLE_ALLOWLIST",
+  FilterTokenBalance = "TOKEN_BALANCE",
 }
 
 export class FilterNamespaces {
@@ -88,6 +116,20 @@ export interface FilterKey {
   edition: web3.PublicKey | null;
 }
 
+export interface FilterVerifiedCollection {
+  collection: web3.PublicKey;
+}
+
+// leaves are keccak256(0x00 || token holder)
+export interface FilterMerkleAllowlist {
+  root: number[];
+}
+
+export interface FilterTokenBalance {
+  mint: web3.PublicKey;
+  minimumAmount: BN;
+}
+
 export enum TokenType {
   Player = "PLAYER",
   Item = "ITEM",
@@ -130,6 +172,8 @@ export class Namespace {
   paymentAmount: number | null;
   paymentMint: web3.PublicKey | null;
   paymentVault: web3.PublicKey | null;
+  protocolFeeExempt: boolean;
+  refundPaymentOnLeave: boolean;
 
   constructor(key, data) {
     this.key = key;
@@ -152,6 +196,8 @@ export class Namespace {
       : null;
     this.paymentMint = data.paymentMint;
     this.paymentVault = data.paymentVault;
+    this.protocolFeeExempt = data.protocolFeeExempt;
+    this.refundPaymentOnLeave = data.refundPaymentOnLeave;
   }
 
   print(log) {
@@ -256,6 +302,8 @@ export class Namespace {
     if (this.paymentVault) {
       log.info(`Payment Vault: ${this.paymentVault.toString()}`);
     }
+    log.info(`Refund Payment On Leave: ${this.refundPaymentOnLeave}`);
+    log.info(`Protocol Fee Exempt: ${this.protocolFeeExempt}`);
   }
 }
 
@@ -278,7 +326,9 @@ export class NamespaceIndex {
   namespace: web3.PublicKey;
   bump: number;
   page: number;
+  // sorted by sortKeys within this page only, later pages can hold lower keys
   caches: web3.PublicKey[];
+  sortKeys: BN[];
 
   constructor(address, data) {
     this.address = address;
@@ -286,6 +336,7 @@ export class NamespaceIndex {
     this.bump = data.bump;
     this.page = data.page;
     this.caches = data.caches;
+    this.sortKeys = data.sortKeys;
   }
 }
 
@@ -295,6 +346,8 @@ export enum RaindropsProgram {
   Matches,
   Staking,
   Player,
+  ItemV2,
+  Avatar,
 }
 
 export namespace RaindropsProgram {
@@ -312,6 +365,10 @@ export namespace RaindropsProgram {
         return pids.PLAYER_ID;
       case RaindropsProgram.Staking:
         return pids.STAKING_ID;
+      case RaindropsProgram.ItemV2:
+        return ITEMV2_ID;
+      case RaindropsProgram.Avatar:
+        return pids.AVATAR_ID;
       default:
         throw new Error(`Unknown RaindropsProgram: ${program}`);
     }
@@ -332,6 +389,10 @@ export namespace RaindropsProgram {
       return RaindropsProgram.Player;
     } else if (address.equals(pids.STAKING_ID)) {
       return RaindropsProgram.Staking;
+    } else if (address.equals(ITEMV2_ID)) {
+      return RaindropsProgram.ItemV2;
+    } else if (address.equals(pids.AVATAR_ID)) {
+      return RaindropsProgram.Avatar;
     }
 
     throw new Error(`Unknown RaindropsProgram: ${address}`);
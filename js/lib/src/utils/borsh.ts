@@ -1,3 +1,4 @@
+import { BN } from "@project-serum/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { BinaryReader, BinaryWriter } from "borsh";
 
@@ -13,6 +14,16 @@ export const extendBorsh = () => {
     writer.writeFixedArray(value.toBuffer());
   };
 
+  (BinaryReader.prototype as any).readI64 = function () {
+    const reader = this as unknown as BinaryReader;
+    return reader.readU64().fromTwos(64);
+  };
+
+  (BinaryWriter.prototype as any).writeI64 = function (value: BN) {
+    const writer = this as unknown as BinaryWriter;
+    writer.writeU64(value.toTwos(64));
+  };
+
   // BTreeMap<u32, u32>
   (BinaryReader.prototype as any).readMap32 = function () {
     const reader = this as unknown as BinaryReader;
@@ -10,15 +10,21 @@ import {
   STAKING_ID,
 } from "../constants/programIds";
 import { PREFIX as ITEM_PREFIX, MARKER } from "../constants/item";
-import { PREFIX as MATCHES_PREFIX } from "../constants/matches";
+import { PREFIX as MATCHES_PREFIX, PRIZE_POOL } from "../constants/matches";
 import {
   PREFIX as NAMESPACE_PREFIX,
   GATEKEEPER_PREFIX,
+  PROTOCOL_FEE_PREFIX,
+  MEMBERSHIP_PAYMENT_PREFIX,
 } from "../constants/namespace";
 import { PREFIX as PLAYER_PREFIX } from "../constants/player";
 import {
   PREFIX as STAKING_PREFIX,
   STAKING_COUNTER,
+  REWARD_POOL,
+  STAKE_REWARDS,
+  STAKE_POSITIONS,
+  LOCKUP_TIERS,
 } from "../constants/staking";
 
 export const getAtaForMint = async (
@@ -65,6 +71,21 @@ export const getMatchTokenAccountEscrow = async (
   );
 };
 
+export const getMatchPrizePoolEscrow = async (
+  oracle: web3.PublicKey,
+  entryFeeMint: web3.PublicKey
+): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(MATCHES_PREFIX),
+      oracle.toBuffer(),
+      entryFeeMint.toBuffer(),
+      Buffer.from(PRIZE_POOL),
+    ],
+    MATCHES_ID
+  );
+};
+
 export const getOracle = async (
   seed: web3.PublicKey,
   payer: web3.PublicKey
@@ -97,6 +118,30 @@ export const getNamespaceGatekeeperPDA = async (
   );
 };
 
+export const getProtocolFeeConfigPDA = async (): Promise<
+  [web3.PublicKey, number]
+> => {
+  return await web3.PublicKey.findProgramAddress(
+    [Buffer.from(NAMESPACE_PREFIX), Buffer.from(PROTOCOL_FEE_PREFIX)],
+    NAMESPACE_ID
+  );
+};
+
+export const getMembershipPaymentPDA = async (
+  namespace: web3.PublicKey,
+  artifact: web3.PublicKey
+): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(NAMESPACE_PREFIX),
+      namespace.toBuffer(),
+      artifact.toBuffer(),
+      Buffer.from(MEMBERSHIP_PAYMENT_PREFIX),
+    ],
+    NAMESPACE_ID
+  );
+};
+
 export const getIndexPDA = async (
   namespace: web3.PublicKey,
   page: BN
@@ -353,6 +398,83 @@ export const getArtifactMintStakingAccount = async (args: {
   );
 };
 
+export const getStakePositions = async (args: {
+  artifactClassMint: web3.PublicKey;
+  artifactMint: web3.PublicKey;
+  index: BN;
+}): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(STAKING_PREFIX),
+      args.artifactClassMint.toBuffer(),
+      args.artifactMint.toBuffer(),
+      args.index.toArrayLike(Buffer, "le", 8),
+      Buffer.from(STAKE_POSITIONS),
+    ],
+    STAKING_ID
+  );
+};
+
+export const getLockupTiers = async (args: {
+  artifactClass: web3.PublicKey;
+}): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(STAKING_PREFIX),
+      args.artifactClass.toBuffer(),
+      Buffer.from(LOCKUP_TIERS),
+    ],
+    STAKING_ID
+  );
+};
+
+export const getRewardPool = async (args: {
+  scope: web3.PublicKey;
+  stakingMint: web3.PublicKey;
+  rewardMint: web3.PublicKey;
+  authority: web3.PublicKey;
+}): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(STAKING_PREFIX),
+      Buffer.from(REWARD_POOL),
+      args.scope.toBuffer(),
+      args.stakingMint.toBuffer(),
+      args.rewardMint.toBuffer(),
+      args.authority.toBuffer(),
+    ],
+    STAKING_ID
+  );
+};
+
+export const getRewardVault = async (
+  rewardPool: web3.PublicKey
+): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(STAKING_PREFIX),
+      Buffer.from(REWARD_POOL),
+      rewardPool.toBuffer(),
+    ],
+    STAKING_ID
+  );
+};
+
+export const getStakeRewards = async (args: {
+  rewardPool: web3.PublicKey;
+  artifactMintStakingAccount: web3.PublicKey;
+}): Promise<[web3.PublicKey, number]> => {
+  return await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from(STAKING_PREFIX),
+      Buffer.from(STAKE_REWARDS),
+      args.rewardPool.toBuffer(),
+      args.artifactMintStakingAccount.toBuffer(),
+    ],
+    STAKING_ID
+  );
+};
+
 export const getMetadata = async (
   mint: web3.PublicKey
 ): Promise<web3.PublicKey> => {
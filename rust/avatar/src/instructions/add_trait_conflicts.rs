@@ -39,7 +39,5 @@ pub fn handler(ctx: Context<AddTraitConflicts>, args: AddTraitConflictsArgs) ->
         &trait_conflicts,
         ctx.accounts.authority.clone(),
         ctx.accounts.system_program.clone(),
-    );
-
-    Ok(())
+    )
 }
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use raindrops_common::utils::{is_namespace_program_caller, set_namespace_index};
+
+use crate::state::{
+    accounts::{Avatar, AvatarClass},
+    errors::ErrorCode,
+};
+
+#[derive(Accounts)]
+#[instruction(page: u64)]
+pub struct AvatarArtifactCacheNamespace<'info> {
+    /// CHECK: deserialized inside instruction
+    #[account(mut)]
+    pub avatar_artifact: UncheckedAccount<'info>,
+
+    /// CHECK: only the key is recorded on the artifact
+    pub namespace: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<AvatarArtifactCacheNamespace>, page: u64) -> Result<()> {
+    require!(
+        is_namespace_program_caller(&ctx.accounts.instructions.to_account_info()),
+        ErrorCode::UnauthorizedCaller
+    );
+
+    let avatar_artifact = ctx.accounts.avatar_artifact.to_account_info();
+    let namespace = ctx.accounts.namespace.key();
+
+    if let Ok(avatar_class) = &mut Account::<'_, AvatarClass>::try_from(&avatar_artifact) {
+        set_namespace_index(
+            &mut avatar_class.namespaces,
+            &namespace,
+            Some(page),
+            ErrorCode::FailedToCache,
+        )?;
+        return avatar_class.exit(&crate::id());
+    }
+
+    let avatar = &mut Account::<'_, Avatar>::try_from(&avatar_artifact)?;
+    set_namespace_index(
+        &mut avatar.namespaces,
+        &namespace,
+        Some(page),
+        ErrorCode::FailedToCache,
+    )?;
+    avatar.exit(&crate::id())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use raindrops_common::utils::is_namespace_program_caller;
+
+use crate::state::{
+    accounts::{Avatar, AvatarClass},
+    errors::ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct AvatarArtifactLeaveNamespace<'info> {
+    /// CHECK: deserialized inside instruction
+    #[account(mut)]
+    pub avatar_artifact: UncheckedAccount<'info>,
+
+    /// CHECK: only the key is recorded on the artifact
+    pub namespace: UncheckedAccount<'info>,
+
+    // refunded the rent of the removed namespace entry
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: address constraint
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AvatarArtifactLeaveNamespace>) -> Result<()> {
+    require!(
+        is_namespace_program_caller(&ctx.accounts.instructions.to_account_info()),
+        ErrorCode::UnauthorizedCaller
+    );
+
+    let avatar_artifact = ctx.accounts.avatar_artifact.to_account_info();
+    let namespace = ctx.accounts.namespace.key();
+
+    if let Ok(avatar_class) = &mut Account::<'_, AvatarClass>::try_from(&avatar_artifact) {
+        avatar_class.leave_namespace(
+            &namespace,
+            &avatar_artifact,
+            ctx.accounts.payer.clone(),
+            ctx.accounts.system_program.clone(),
+        )?;
+        return avatar_class.exit(&crate::id());
+    }
+
+    let avatar = &mut Account::<'_, Avatar>::try_from(&avatar_artifact)?;
+    avatar.leave_namespace(
+        &namespace,
+        &avatar_artifact,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+    avatar.exit(&crate::id())
+}
@@ -3,7 +3,7 @@ use anchor_spl::{associated_token, token};
 
 use crate::state::{
     accounts::{Avatar, AvatarClass, Trait, UpdateState},
-    data::{PaymentState, UpdateTarget, UpdateTargetSelection},
+    data::{PaymentState, UpdatePaymentAlternatives, UpdateTarget, UpdateTargetSelection},
     errors::ErrorCode,
 };
 
@@ -80,6 +80,8 @@ pub fn handler(ctx: Context<BeginTraitSwapUpdate>, args: BeginTraitSwapUpdateArg
         ErrorCode::TokenDelegateNotAllowed
     );
 
+    ctx.accounts.update_state.assert_migrated()?;
+
     // if the update state has already been initialized, do nothing
     // we have this check here to make this instruction idempotent incase someone is continuing their update
     // we dont want them to pay again or have their update state reset
@@ -89,26 +91,60 @@ pub fn handler(ctx: Context<BeginTraitSwapUpdate>, args: BeginTraitSwapUpdateArg
     }
 
     // get the payment details required for this update
-    let update_target: UpdateTarget = match &args.update_target {
+    let (update_target, payment_alternatives) = get_update_target(
+        &args.update_target,
+        (
+            ctx.accounts.equip_trait_account.key(),
+            &ctx.accounts.equip_trait_account,
+        ),
+        (
+            ctx.accounts.remove_trait_account.key(),
+            &ctx.accounts.remove_trait_account,
+        ),
+    )?;
+
+    // set the update state fields
+    ctx.accounts.update_state.set_inner(UpdateState {
+        version: UpdateState::VERSION,
+        initialized: true,
+        avatar: ctx.accounts.avatar.key(),
+        target: update_target,
+        payment_alternatives,
+    });
+
+    Ok(())
+}
+
+// verify both traits match the update target and get their payment requirements
+fn get_update_target(
+    update_target: &UpdateTargetSelection,
+    equip_trait_account: (Pubkey, &Trait),
+    remove_trait_account: (Pubkey, &Trait),
+) -> Result<(UpdateTarget, UpdatePaymentAlternatives)> {
+    match update_target {
         UpdateTargetSelection::SwapTrait {
-            equip_trait_account: equip_trait_account_address,
-            remove_trait_account: remove_trait_account_address,
+            equip_trait_account: equip_trait_account_target,
+            remove_trait_account: remove_trait_account_target,
         } => {
-            let equip_trait_account = ctx.accounts.equip_trait_account.clone();
-            let remove_trait_account = ctx.accounts.remove_trait_account.clone();
+            let (equip_trait_account_address, equip_trait_account) = equip_trait_account;
+            let (remove_trait_account_address, remove_trait_account) = remove_trait_account;
 
             // assert trait account matches the trait specified in the update target
             require!(
-                equip_trait_account.key().eq(equip_trait_account_address),
+                equip_trait_account_address.eq(equip_trait_account_target),
                 ErrorCode::InvalidTrait
             );
 
             require!(
-                remove_trait_account.key().eq(remove_trait_account_address),
+                remove_trait_account_address.eq(remove_trait_account_target),
                 ErrorCode::InvalidTrait
             );
 
             // set both payment states
+            let payment_alternatives = UpdatePaymentAlternatives::new(
+                equip_trait_account.equip_payment_details.as_ref(),
+                remove_trait_account.remove_payment_details.as_ref(),
+            );
             let equip_payment_state: Option<PaymentState> = equip_trait_account
                 .equip_payment_details
                 .clone()
@@ -118,22 +154,89 @@ pub fn handler(ctx: Context<BeginTraitSwapUpdate>, args: BeginTraitSwapUpdateArg
                 .clone()
                 .map(Into::into);
 
-            UpdateTarget::SwapTrait {
-                equip_trait_account: equip_trait_account.key(),
-                remove_trait_account: remove_trait_account.key(),
-                equip_payment_state,
-                remove_payment_state,
-            }
+            Ok((
+                UpdateTarget::SwapTrait {
+                    equip_trait_account: equip_trait_account_address,
+                    remove_trait_account: remove_trait_account_address,
+                    equip_payment_state,
+                    remove_payment_state,
+                },
+                payment_alternatives,
+            ))
         }
-        _ => return Err(ErrorCode::InvalidUpdateTarget.into()),
-    };
+        _ => Err(ErrorCode::InvalidUpdateTarget.into()),
+    }
+}
 
-    // set the update state fields
-    ctx.accounts.update_state.set_inner(UpdateState {
-        initialized: true,
-        avatar: ctx.accounts.avatar.key(),
-        target: update_target,
-    });
+#[cfg(test)]
+mod tests {
+    use crate::state::data::TraitStatus;
+
+    use super::*;
+
+    fn trait_account() -> Trait {
+        Trait {
+            id: 0,
+            avatar_class: Pubkey::new_unique(),
+            trait_mint: Pubkey::new_unique(),
+            attribute_ids: vec![],
+            component_uri: "".to_string(),
+            status: TraitStatus { enabled: true },
+            variant_metadata: vec![],
+            equip_payment_details: None,
+            remove_payment_details: None,
+            trait_gate: None,
+            stat_modifiers: vec![],
+        }
+    }
 
-    Ok(())
+    #[test]
+    fn test_mismatched_equip_trait_account() {
+        let (equip_trait, remove_trait) = (trait_account(), trait_account());
+        let remove_trait_address = Pubkey::new_unique();
+        let target = UpdateTargetSelection::SwapTrait {
+            equip_trait_account: Pubkey::new_unique(),
+            remove_trait_account: remove_trait_address,
+        };
+        let err = get_update_target(
+            &target,
+            (Pubkey::new_unique(), &equip_trait),
+            (remove_trait_address, &remove_trait),
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTrait.into());
+    }
+
+    #[test]
+    fn test_mismatched_remove_trait_account() {
+        let (equip_trait, remove_trait) = (trait_account(), trait_account());
+        let equip_trait_address = Pubkey::new_unique();
+        let target = UpdateTargetSelection::SwapTrait {
+            equip_trait_account: equip_trait_address,
+            remove_trait_account: Pubkey::new_unique(),
+        };
+        let err = get_update_target(
+            &target,
+            (equip_trait_address, &equip_trait),
+            (Pubkey::new_unique(), &remove_trait),
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTrait.into());
+    }
+
+    #[test]
+    fn test_invalid_update_target() {
+        let (equip_trait, remove_trait) = (trait_account(), trait_account());
+        let trait_address = Pubkey::new_unique();
+        let target = UpdateTargetSelection::EquipTrait {
+            trait_account: trait_address,
+        };
+        let err = get_update_target(
+            &target,
+            (trait_address, &equip_trait),
+            (Pubkey::new_unique(), &remove_trait),
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidUpdateTarget.into());
+    }
 }
@@ -3,7 +3,7 @@ use anchor_spl::{associated_token, token};
 
 use crate::state::{
     accounts::{Avatar, AvatarClass, Trait, UpdateState},
-    data::{PaymentState, UpdateTarget, UpdateTargetSelection},
+    data::{PaymentState, UpdatePaymentAlternatives, UpdateTarget, UpdateTargetSelection},
     errors::ErrorCode,
 };
 
@@ -67,6 +67,8 @@ pub fn handler(ctx: Context<BeginTraitUpdate>, args: BeginTraitUpdateArgs) -> Re
         ErrorCode::TokenDelegateNotAllowed
     );
 
+    ctx.accounts.update_state.assert_migrated()?;
+
     // if the update state has already been initialized, do nothing
     // we have this check here to make this instruction idempotent incase someone is continuing their update
     // we dont want them to pay again or have their update state reset
@@ -76,54 +78,178 @@ pub fn handler(ctx: Context<BeginTraitUpdate>, args: BeginTraitUpdateArgs) -> Re
     }
 
     // get the payment details required for this update
-    let update_target: UpdateTarget = match &args.update_target {
+    let (update_target, payment_alternatives) = get_update_target(
+        &args.update_target,
+        ctx.accounts.trait_account.key(),
+        &ctx.accounts.trait_account,
+    )?;
+
+    // set the update state fields
+    ctx.accounts.update_state.set_inner(UpdateState {
+        version: UpdateState::VERSION,
+        initialized: true,
+        avatar: ctx.accounts.avatar.key(),
+        target: update_target,
+        payment_alternatives,
+    });
+
+    Ok(())
+}
+
+// verify the trait matches the update target and get its payment requirements
+fn get_update_target(
+    update_target: &UpdateTargetSelection,
+    trait_account_address: Pubkey,
+    trait_account: &Trait,
+) -> Result<(UpdateTarget, UpdatePaymentAlternatives)> {
+    match update_target {
         UpdateTargetSelection::EquipTrait {
-            trait_account: trait_account_address,
+            trait_account: trait_account_target,
         } => {
-            let trait_account = ctx.accounts.trait_account.clone();
-
             // verify trait account passed into accounts array matches the UpdateTarget
             require!(
-                trait_account_address.eq(&trait_account.key()),
+                trait_account_target.eq(&trait_account_address),
                 ErrorCode::InvalidTrait
             );
 
+            let payment_alternatives =
+                UpdatePaymentAlternatives::new(trait_account.equip_payment_details.as_ref(), None);
             let payment_state: Option<PaymentState> =
                 trait_account.equip_payment_details.clone().map(Into::into);
 
-            UpdateTarget::EquipTrait {
-                trait_account: trait_account.key(),
-                payment_state: payment_state,
-            }
+            Ok((
+                UpdateTarget::EquipTrait {
+                    trait_account: trait_account_address,
+                    payment_state,
+                },
+                payment_alternatives,
+            ))
         }
         UpdateTargetSelection::RemoveTrait {
-            trait_account: trait_account_address,
+            trait_account: trait_account_target,
         } => {
-            let trait_account = ctx.accounts.trait_account.clone();
-
             // verify trait account passed into accounts array matches the UpdateTarget
             require!(
-                trait_account_address.eq(&trait_account.key()),
+                trait_account_target.eq(&trait_account_address),
                 ErrorCode::InvalidTrait
             );
 
+            let payment_alternatives =
+                UpdatePaymentAlternatives::new(trait_account.remove_payment_details.as_ref(), None);
             let payment_state: Option<PaymentState> =
-                trait_account.equip_payment_details.clone().map(Into::into);
+                trait_account.remove_payment_details.clone().map(Into::into);
+
+            Ok((
+                UpdateTarget::RemoveTrait {
+                    trait_account: trait_account_address,
+                    payment_state,
+                },
+                payment_alternatives,
+            ))
+        }
+        _ => Err(ErrorCode::InvalidUpdateTarget.into()),
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use crate::state::data::{PaymentAlternative, PaymentDetails, TraitStatus};
+
+    use super::*;
+
+    fn trait_account() -> Trait {
+        Trait {
+            id: 0,
+            avatar_class: Pubkey::new_unique(),
+            trait_mint: Pubkey::new_unique(),
+            attribute_ids: vec![],
+            component_uri: "".to_string(),
+            status: TraitStatus { enabled: true },
+            variant_metadata: vec![],
+            equip_payment_details: None,
+            remove_payment_details: None,
+            trait_gate: None,
+            stat_modifiers: vec![],
+        }
+    }
+
+    #[test]
+    fn test_equip_trait_mismatched_trait_account() {
+        let target = UpdateTargetSelection::EquipTrait {
+            trait_account: Pubkey::new_unique(),
+        };
+        let err = get_update_target(&target, Pubkey::new_unique(), &trait_account()).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTrait.into());
+    }
+
+    #[test]
+    fn test_remove_trait_mismatched_trait_account() {
+        let target = UpdateTargetSelection::RemoveTrait {
+            trait_account: Pubkey::new_unique(),
+        };
+        let err = get_update_target(&target, Pubkey::new_unique(), &trait_account()).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTrait.into());
+    }
+
+    #[test]
+    fn test_equip_trait() {
+        let trait_address = Pubkey::new_unique();
+        let target = UpdateTargetSelection::EquipTrait {
+            trait_account: trait_address,
+        };
+        let (update_target, payment_alternatives) =
+            get_update_target(&target, trait_address, &trait_account()).unwrap();
+        assert!(payment_alternatives.is_empty());
+        assert!(update_target.is_paid());
+    }
+
+    #[test]
+    fn test_remove_trait_uses_remove_payment_details() {
+        let trait_address = Pubkey::new_unique();
+        let remove_payment_method = Pubkey::new_unique();
+        let alternative = PaymentAlternative {
+            payment_method: Pubkey::new_unique(),
+            amount: 2,
+        };
+        let mut trait_data = trait_account();
+        trait_data.equip_payment_details = Some(PaymentDetails {
+            payment_method: Pubkey::new_unique(),
+            amount: 5,
+            alternatives: vec![],
+        });
+        trait_data.remove_payment_details = Some(PaymentDetails {
+            payment_method: remove_payment_method,
+            amount: 10,
+            alternatives: vec![alternative.clone()],
+        });
+
+        let target = UpdateTargetSelection::RemoveTrait {
+            trait_account: trait_address,
+        };
+        let (update_target, payment_alternatives) =
+            get_update_target(&target, trait_address, &trait_data).unwrap();
+        match update_target {
             UpdateTarget::RemoveTrait {
-                trait_account: trait_account.key(),
-                payment_state: payment_state,
+                payment_state: Some(payment_state),
+                ..
+            } => {
+                assert_eq!(payment_state.payment_method, remove_payment_method);
+                assert_eq!(payment_state.required_amount, 10);
             }
+            _ => panic!("expected a remove trait target with a payment state"),
         }
-        _ => return Err(ErrorCode::InvalidUpdateTarget.into()),
-    };
-
-    // set the update state fields
-    ctx.accounts.update_state.set_inner(UpdateState {
-        initialized: true,
-        avatar: ctx.accounts.avatar.key(),
-        target: update_target,
-    });
+        assert_eq!(payment_alternatives.payment, vec![alternative]);
+        assert!(payment_alternatives.remove_payment.is_empty());
+    }
 
-    Ok(())
+    #[test]
+    fn test_invalid_update_target() {
+        let trait_address = Pubkey::new_unique();
+        let target = UpdateTargetSelection::SwapTrait {
+            equip_trait_account: trait_address,
+            remove_trait_account: trait_address,
+        };
+        let err = get_update_target(&target, trait_address, &trait_account()).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidUpdateTarget.into());
+    }
 }
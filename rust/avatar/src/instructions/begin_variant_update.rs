@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
-use crate::state::{
-    accounts::{Avatar, AvatarClass, Trait, UpdateState},
-    data::{PaymentState, UpdateTarget, UpdateTargetSelection},
-    errors::ErrorCode,
+use crate::{
+    state::{
+        accounts::{Avatar, AvatarClass, Trait, UpdateState, VariantOptionCounter},
+        data::{PaymentState, UpdatePaymentAlternatives, UpdateTarget, UpdateTargetSelection},
+        errors::ErrorCode,
+    },
+    utils::validate_variant_option_availability,
 };
 
 #[derive(Accounts)]
@@ -31,6 +34,13 @@ pub struct BeginVariantUpdate<'info> {
         associated_token::mint = avatar.mint, associated_token::authority = authority)]
     pub avatar_mint_ata: Box<Account<'info, token::TokenAccount>>,
 
+    // required if the selected variant option has a max_selections supply cap
+    #[account(init_if_needed,
+        payer = authority,
+        space = VariantOptionCounter::SPACE,
+        seeds = [VariantOptionCounter::PREFIX.as_bytes(), avatar_class.key().as_ref(), args.update_target.hash().as_ref()], bump)]
+    pub variant_option_counter: Option<Account<'info, VariantOptionCounter>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -50,6 +60,8 @@ pub fn handler(ctx: Context<BeginVariantUpdate>, args: BeginVariantUpdateArgs) -
         ErrorCode::TokenDelegateNotAllowed
     );
 
+    ctx.accounts.update_state.assert_migrated()?;
+
     // if the update state has already been initialized, do nothing
     // we have this check here to make this instruction idempotent incase someone is continuing their update
     // we dont want them to pay again or have their update state reset
@@ -58,61 +70,252 @@ pub fn handler(ctx: Context<BeginVariantUpdate>, args: BeginVariantUpdateArgs) -
         return Ok(());
     }
 
+    // set the avatar class on the counter in case it was just initialized
+    let avatar_class_key = ctx.accounts.avatar_class.key();
+    if let Some(variant_option_counter) = &mut ctx.accounts.variant_option_counter {
+        variant_option_counter.avatar_class = avatar_class_key;
+    }
+
     // get the payment details required for this update
-    let update_target: UpdateTarget = match &args.update_target {
+    let trait_account = ctx
+        .accounts
+        .trait_account
+        .as_ref()
+        .map(|trait_account| (trait_account.key(), &**trait_account));
+    let (update_target, payment_alternatives) = get_update_target(
+        &args.update_target,
+        &ctx.accounts.avatar_class,
+        trait_account,
+        ctx.accounts.variant_option_counter.as_deref(),
+    )?;
+
+    // set the update state fields
+    ctx.accounts.update_state.set_inner(UpdateState {
+        version: UpdateState::VERSION,
+        initialized: true,
+        avatar: ctx.accounts.avatar.key(),
+        target: update_target,
+        payment_alternatives,
+    });
+
+    Ok(())
+}
+
+// resolve the selected variant option and its payment requirements
+fn get_update_target(
+    update_target: &UpdateTargetSelection,
+    avatar_class: &AvatarClass,
+    trait_account: Option<(Pubkey, &Trait)>,
+    variant_option_counter: Option<&VariantOptionCounter>,
+) -> Result<(UpdateTarget, UpdatePaymentAlternatives)> {
+    match update_target {
         UpdateTargetSelection::ClassVariant {
             variant_id,
             option_id,
         } => {
             // get variant metadata from avatar class
-            let variant_metadata = ctx.accounts.avatar_class.find_variant(variant_id);
-            let new_variant_option = variant_metadata.find_option(option_id);
+            let variant_metadata = avatar_class.find_variant(variant_id)?;
+            let new_variant_option = variant_metadata.find_option(option_id)?;
+
+            // check the variant option can be selected right now
+            validate_variant_option_availability(&new_variant_option, variant_option_counter)?;
 
+            let payment_alternatives =
+                UpdatePaymentAlternatives::new(new_variant_option.payment_details.as_ref(), None);
             let payment_state: Option<PaymentState> =
                 new_variant_option.payment_details.map(Into::into);
 
-            UpdateTarget::ClassVariant {
-                variant_id: variant_id.to_string(),
-                option_id: option_id.to_string(),
-                payment_state,
-            }
+            Ok((
+                UpdateTarget::ClassVariant {
+                    variant_id: variant_id.to_string(),
+                    option_id: option_id.to_string(),
+                    payment_state,
+                },
+                payment_alternatives,
+            ))
         }
         UpdateTargetSelection::TraitVariant {
             variant_id,
             option_id,
             trait_account: trait_account_target,
         } => {
-            let trait_account = ctx.accounts.trait_account.clone().unwrap();
+            let (trait_account_address, trait_account) =
+                trait_account.ok_or(ErrorCode::InvalidTrait)?;
 
             // assert trait account matches the trait specified in the update target
             require!(
-                trait_account.key().eq(trait_account_target),
+                trait_account_address.eq(trait_account_target),
                 ErrorCode::InvalidTrait
             );
 
             // get the variant metadata for the variant_id we want to change
-            let variant_metadata = trait_account.find_variant(variant_id);
-            let new_variant_option = variant_metadata.find_option(option_id);
+            let variant_metadata = trait_account.find_variant(variant_id)?;
+            let new_variant_option = variant_metadata.find_option(option_id)?;
 
+            // check the variant option can be selected right now
+            validate_variant_option_availability(&new_variant_option, variant_option_counter)?;
+
+            let payment_alternatives =
+                UpdatePaymentAlternatives::new(new_variant_option.payment_details.as_ref(), None);
             let payment_state: Option<PaymentState> =
                 new_variant_option.payment_details.map(Into::into);
 
-            UpdateTarget::TraitVariant {
-                variant_id: variant_id.to_string(),
-                option_id: option_id.to_string(),
-                payment_state,
-                trait_account: *trait_account_target,
-            }
+            Ok((
+                UpdateTarget::TraitVariant {
+                    variant_id: variant_id.to_string(),
+                    option_id: option_id.to_string(),
+                    payment_state,
+                    trait_account: *trait_account_target,
+                },
+                payment_alternatives,
+            ))
         }
-        _ => return Err(ErrorCode::InvalidUpdateTarget.into()),
-    };
+        _ => Err(ErrorCode::InvalidUpdateTarget.into()),
+    }
+}
 
-    // set the update state fields
-    ctx.accounts.update_state.set_inner(UpdateState {
-        initialized: true,
-        avatar: ctx.accounts.avatar.key(),
-        target: update_target,
-    });
+#[cfg(test)]
+mod tests {
+    use crate::state::data::{TraitStatus, VariantMetadata, VariantOption, VariantStatus};
 
-    Ok(())
+    use super::*;
+
+    fn variant_metadata() -> Vec<VariantMetadata> {
+        vec![VariantMetadata {
+            name: "color".to_string(),
+            id: "color".to_string(),
+            status: VariantStatus { enabled: true },
+            options: vec![VariantOption {
+                variant_id: "color".to_string(),
+                option_id: "red".to_string(),
+                payment_details: None,
+                trait_gate: None,
+                start_time: None,
+                end_time: None,
+                max_selections: None,
+            }],
+        }]
+    }
+
+    fn avatar_class() -> AvatarClass {
+        AvatarClass {
+            mint: Pubkey::new_unique(),
+            trait_index: 0,
+            payment_index: 0,
+            attribute_metadata: vec![],
+            variant_metadata: variant_metadata(),
+            global_rendering_config_uri: "".to_string(),
+            stat_templates: vec![],
+            namespaces: vec![],
+        }
+    }
+
+    fn trait_account() -> Trait {
+        Trait {
+            id: 0,
+            avatar_class: Pubkey::new_unique(),
+            trait_mint: Pubkey::new_unique(),
+            attribute_ids: vec![],
+            component_uri: "".to_string(),
+            status: TraitStatus { enabled: true },
+            variant_metadata: variant_metadata(),
+            equip_payment_details: None,
+            remove_payment_details: None,
+            trait_gate: None,
+            stat_modifiers: vec![],
+        }
+    }
+
+    fn class_variant(variant_id: &str, option_id: &str) -> UpdateTargetSelection {
+        UpdateTargetSelection::ClassVariant {
+            variant_id: variant_id.to_string(),
+            option_id: option_id.to_string(),
+        }
+    }
+
+    fn trait_variant(
+        variant_id: &str,
+        option_id: &str,
+        trait_account: Pubkey,
+    ) -> UpdateTargetSelection {
+        UpdateTargetSelection::TraitVariant {
+            variant_id: variant_id.to_string(),
+            option_id: option_id.to_string(),
+            trait_account,
+        }
+    }
+
+    #[test]
+    fn test_class_variant_invalid_variant_id() {
+        let err = get_update_target(&class_variant("colour", "red"), &avatar_class(), None, None)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariant.into());
+    }
+
+    #[test]
+    fn test_class_variant_invalid_option_id() {
+        let err = get_update_target(&class_variant("color", "blue"), &avatar_class(), None, None)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariantOption.into());
+    }
+
+    #[test]
+    fn test_trait_variant_missing_trait_account() {
+        let target = trait_variant("color", "red", Pubkey::new_unique());
+        let err = get_update_target(&target, &avatar_class(), None, None).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTrait.into());
+    }
+
+    #[test]
+    fn test_trait_variant_mismatched_trait_account() {
+        let trait_account = trait_account();
+        let target = trait_variant("color", "red", Pubkey::new_unique());
+        let err = get_update_target(
+            &target,
+            &avatar_class(),
+            Some((Pubkey::new_unique(), &trait_account)),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTrait.into());
+    }
+
+    #[test]
+    fn test_trait_variant_invalid_variant_id() {
+        let trait_account = trait_account();
+        let trait_address = Pubkey::new_unique();
+        let target = trait_variant("colour", "red", trait_address);
+        let err = get_update_target(
+            &target,
+            &avatar_class(),
+            Some((trait_address, &trait_account)),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariant.into());
+    }
+
+    #[test]
+    fn test_trait_variant_invalid_option_id() {
+        let trait_account = trait_account();
+        let trait_address = Pubkey::new_unique();
+        let target = trait_variant("color", "blue", trait_address);
+        let err = get_update_target(
+            &target,
+            &avatar_class(),
+            Some((trait_address, &trait_account)),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariantOption.into());
+    }
+
+    #[test]
+    fn test_invalid_update_target() {
+        let target = UpdateTargetSelection::EquipTrait {
+            trait_account: Pubkey::new_unique(),
+        };
+        let err = get_update_target(&target, &avatar_class(), None, None).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidUpdateTarget.into());
+    }
 }
@@ -42,7 +42,6 @@ pub fn handler(ctx: Context<BurnPayment>, args: BurnPaymentArgs) -> Result<()> {
     // update the payment state according to how much will be burned
     ctx.accounts
         .update_state
-        .target
         .update_payment_state(&ctx.accounts.payment_method.key(), args.amount)?;
 
     match ctx.accounts.payment_method.asset_class {
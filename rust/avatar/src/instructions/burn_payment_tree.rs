@@ -48,7 +48,6 @@ pub fn handler(ctx: Context<BurnPaymentTree>, args: BurnPaymentTreeArgs) -> Resu
     // update the payment state according to how much will be burned
     ctx.accounts
         .update_state
-        .target
         .update_payment_state(&ctx.accounts.payment_method.key(), args.amount)?;
 
     match ctx.accounts.payment_method.asset_class {
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token};
+
+use crate::state::{
+    accounts::{Trait, TraitRental},
+    errors::ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct CancelTraitRental<'info> {
+    #[account(
+        has_one = trait_mint,
+        seeds = [Trait::PREFIX.as_bytes(), trait_account.avatar_class.as_ref(), trait_mint.key().as_ref()], bump)]
+    pub trait_account: Box<Account<'info, Trait>>,
+
+    pub trait_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(mut,
+        close = lender,
+        has_one = lender,
+        has_one = trait_account,
+        seeds = [TraitRental::PREFIX.as_bytes(), trait_account.key().as_ref(), lender.key().as_ref()], bump)]
+    pub trait_rental: Account<'info, TraitRental>,
+
+    #[account(mut,
+        associated_token::mint = trait_mint,
+        associated_token::authority = trait_rental)]
+    pub trait_escrow: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(init_if_needed,
+        payer = lender,
+        associated_token::mint = trait_mint,
+        associated_token::authority = lender)]
+    pub trait_destination: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub token_program: Program<'info, token::Token>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelTraitRental>) -> Result<()> {
+    // rented traits are returned after the rental expires
+    require!(
+        !ctx.accounts.trait_rental.is_rented(),
+        ErrorCode::TraitRentalUnavailable
+    );
+
+    let trait_account_key = ctx.accounts.trait_account.key();
+    let lender_key = ctx.accounts.lender.key();
+    let signer_seeds: &[&[u8]] = &[
+        TraitRental::PREFIX.as_bytes(),
+        trait_account_key.as_ref(),
+        lender_key.as_ref(),
+        &[*ctx.bumps.get("trait_rental").unwrap()],
+    ];
+
+    // return the escrowed trait to the lender
+    let transfer_accounts = token::Transfer {
+        from: ctx.accounts.trait_escrow.to_account_info(),
+        to: ctx.accounts.trait_destination.to_account_info(),
+        authority: ctx.accounts.trait_rental.to_account_info(),
+    };
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            &[signer_seeds],
+        ),
+        1,
+    )?;
+
+    let close_escrow_accounts = token::CloseAccount {
+        account: ctx.accounts.trait_escrow.to_account_info(),
+        destination: ctx.accounts.lender.to_account_info(),
+        authority: ctx.accounts.trait_rental.to_account_info(),
+    };
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_escrow_accounts,
+        &[signer_seeds],
+    ))
+}
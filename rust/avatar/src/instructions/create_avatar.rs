@@ -22,7 +22,7 @@ pub struct CreateAvatar<'info> {
 
     #[account(init,
         payer = authority,
-        space = Avatar::initial_space(get_variants(&avatar_class.variant_metadata, &args.variants)),
+        space = Avatar::initial_space(get_variants(&avatar_class.variant_metadata, &args.variants), avatar_class.stat_templates.len()),
         seeds = [Avatar::PREFIX.as_bytes(), avatar_class.key().as_ref(), avatar_mint.key().as_ref()], bump)]
     pub avatar: Account<'info, Avatar>,
 
@@ -75,8 +75,17 @@ pub fn handler(ctx: Context<CreateAvatar>, args: CreateAvatarArgs) -> Result<()>
         image_uri: "".to_string(),
         traits: vec![],
         variants,
+        stats: vec![],
+        namespaces: vec![],
     });
 
+    // with no traits equipped every stat starts at its base value
+    let stats = ctx
+        .accounts
+        .avatar
+        .compute_stats(&ctx.accounts.avatar_class.stat_templates)?;
+    ctx.accounts.avatar.stats = stats;
+
     Ok(())
 }
 
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
-use crate::state::{
-    accounts::AvatarClass,
-    data::{AttributeMetadata, VariantMetadata},
+use crate::{
+    state::{
+        accounts::AvatarClass,
+        data::{AttributeMetadata, StatTemplate, VariantMetadata},
+        errors::ErrorCode,
+    },
+    utils::validate_variant_metadata_payment_details,
 };
 
 #[derive(Accounts)]
@@ -11,7 +15,7 @@ use crate::state::{
 pub struct CreateAvatarClass<'info> {
     #[account(init,
         payer = authority,
-        space = AvatarClass::space(args.attribute_metadata, args.variant_metadata, args.global_rendering_config_uri.len()),
+        space = AvatarClass::space(args.attribute_metadata, args.variant_metadata, args.global_rendering_config_uri.len(), &args.stat_templates),
         seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class_mint.key().as_ref()], bump)]
     pub avatar_class: Account<'info, AvatarClass>,
 
@@ -35,9 +39,15 @@ pub struct CreateAvatarClassArgs {
     pub attribute_metadata: Vec<AttributeMetadata>,
     pub variant_metadata: Vec<VariantMetadata>,
     pub global_rendering_config_uri: String,
+    pub stat_templates: Vec<StatTemplate>,
 }
 
 pub fn handler(ctx: Context<CreateAvatarClass>, args: CreateAvatarClassArgs) -> Result<()> {
+    validate_variant_metadata_payment_details(&args.variant_metadata)?;
+
+    let valid = AvatarClass::is_valid_stat_templates(&args.stat_templates);
+    require!(valid, ErrorCode::InvalidStatTemplate);
+
     ctx.accounts.avatar_class.set_inner(AvatarClass {
         mint: ctx.accounts.avatar_class_mint.key(),
         trait_index: 0,
@@ -45,6 +55,8 @@ pub fn handler(ctx: Context<CreateAvatarClass>, args: CreateAvatarClassArgs) ->
         attribute_metadata: args.attribute_metadata,
         variant_metadata: args.variant_metadata,
         global_rendering_config_uri: args.global_rendering_config_uri,
+        stat_templates: args.stat_templates,
+        namespaces: vec![],
     });
 
     Ok(())
@@ -13,8 +13,9 @@ use spl_account_compression::{
 };
 
 #[derive(Accounts)]
+#[instruction(args: CreatePaymentMethodArgs)]
 pub struct CreatePaymentMethod<'info> {
-    #[account(init, payer = authority, space = PaymentMethod::SPACE, seeds = [PaymentMethod::PREFIX.as_bytes(), avatar_class.key().as_ref(), &avatar_class.payment_index.to_le_bytes()], bump)]
+    #[account(init, payer = authority, space = PaymentMethod::space(&args.action), seeds = [PaymentMethod::PREFIX.as_bytes(), avatar_class.key().as_ref(), &avatar_class.payment_index.to_le_bytes()], bump)]
     pub payment_method: Account<'info, PaymentMethod>,
 
     #[account(mut, seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class.mint.key().as_ref()], bump)]
@@ -47,6 +48,14 @@ pub struct CreatePaymentMethodArgs {
 }
 
 pub fn handler(ctx: Context<CreatePaymentMethod>, args: CreatePaymentMethodArgs) -> Result<()> {
+    // native payments can't be burned and non fungible payments can't be split
+    if !args.action.is_valid(&args.asset_class) {
+        return match args.action {
+            PaymentAction::Split { .. } => Err(ErrorCode::InvalidPaymentSplit.into()),
+            _ => Err(ErrorCode::InvalidPaymentMethod.into()),
+        };
+    }
+
     ctx.accounts.payment_method.set_inner(PaymentMethod {
         uri: "".to_string(),
         index: ctx.accounts.avatar_class.payment_index,
@@ -59,8 +68,8 @@ pub fn handler(ctx: Context<CreatePaymentMethod>, args: CreatePaymentMethodArgs)
     ctx.accounts.avatar_class.payment_index += 1;
 
     match args.asset_class {
-        PaymentAssetClass::Fungible { mint: _ } => {
-            // no need to create a merkle tree for fungible assets
+        PaymentAssetClass::Fungible { mint: _ } | PaymentAssetClass::Native => {
+            // no need to create a merkle tree for fungible or native assets
         }
         PaymentAssetClass::NonFungible { mints } => {
             // if asset class is non fungible we need to create the merkle tree
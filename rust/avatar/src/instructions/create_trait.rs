@@ -4,10 +4,16 @@ use anchor_spl::token;
 use crate::{
     state::{
         accounts::{AvatarClass, Trait, TraitConflicts},
-        data::{AttributeMetadata, PaymentDetails, TraitGate, TraitStatus, VariantMetadata},
+        data::{
+            AttributeMetadata, PaymentDetails, StatModifier, TraitGate, TraitStatus,
+            VariantMetadata,
+        },
         errors::ErrorCode,
     },
-    utils::{is_rain_vault, pay_rain_fee},
+    utils::{
+        is_rain_vault, pay_rain_fee, validate_payment_details,
+        validate_variant_metadata_payment_details,
+    },
 };
 
 #[derive(Accounts)]
@@ -23,7 +29,7 @@ pub struct CreateTrait<'info> {
 
     #[account(init,
         payer = authority,
-        space = Trait::space(args.component_uri.len(), args.attribute_ids.len(), args.variant_metadata),
+        space = Trait::space(args.component_uri.len(), args.attribute_ids.len(), args.variant_metadata, &args.equip_payment_details, &args.remove_payment_details, args.stat_modifiers.len()),
         seeds = [Trait::PREFIX.as_bytes(), avatar_class.key().as_ref(), trait_mint.key().as_ref()], bump)]
     pub trait_account: Account<'info, Trait>,
 
@@ -60,6 +66,7 @@ pub struct CreateTraitArgs {
     pub equip_payment_details: Option<PaymentDetails>,
     pub remove_payment_details: Option<PaymentDetails>,
     pub trait_gate: Option<TraitGate>,
+    pub stat_modifiers: Vec<StatModifier>,
 }
 
 pub fn handler(ctx: Context<CreateTrait>, args: CreateTraitArgs) -> Result<()> {
@@ -77,6 +84,16 @@ pub fn handler(ctx: Context<CreateTrait>, args: CreateTraitArgs) -> Result<()> {
     );
     require!(valid, ErrorCode::InvalidAttributeId);
 
+    validate_variant_metadata_payment_details(&args.variant_metadata)?;
+    validate_payment_details(&args.equip_payment_details)?;
+    validate_payment_details(&args.remove_payment_details)?;
+
+    let valid = ctx
+        .accounts
+        .avatar_class
+        .is_valid_stat_modifiers(&args.stat_modifiers);
+    require!(valid, ErrorCode::InvalidStatId);
+
     ctx.accounts.trait_account.set_inner(Trait {
         id: ctx.accounts.avatar_class.trait_index,
         avatar_class: ctx.accounts.avatar_class.key(),
@@ -88,6 +105,7 @@ pub fn handler(ctx: Context<CreateTrait>, args: CreateTraitArgs) -> Result<()> {
         equip_payment_details: args.equip_payment_details,
         remove_payment_details: args.remove_payment_details,
         trait_gate: args.trait_gate,
+        stat_modifiers: args.stat_modifiers,
     });
 
     // increment trait index on the avatar class
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token};
+
+use crate::state::{
+    accounts::{Trait, TraitRental},
+    errors::ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct CreateTraitRental<'info> {
+    #[account(
+        has_one = trait_mint,
+        seeds = [Trait::PREFIX.as_bytes(), trait_account.avatar_class.as_ref(), trait_mint.key().as_ref()], bump)]
+    pub trait_account: Box<Account<'info, Trait>>,
+
+    pub trait_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(init,
+        payer = lender,
+        space = TraitRental::SPACE,
+        seeds = [TraitRental::PREFIX.as_bytes(), trait_account.key().as_ref(), lender.key().as_ref()], bump)]
+    pub trait_rental: Account<'info, TraitRental>,
+
+    #[account(mut, constraint = trait_source.mint.eq(&trait_mint.key()))]
+    pub trait_source: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(init,
+        payer = lender,
+        associated_token::mint = trait_mint,
+        associated_token::authority = trait_rental)]
+    pub trait_escrow: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub token_program: Program<'info, token::Token>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTraitRentalArgs {
+    pub duration: i64,
+    pub fee: u64,
+}
+
+pub fn handler(ctx: Context<CreateTraitRental>, args: CreateTraitRentalArgs) -> Result<()> {
+    require!(args.duration > 0, ErrorCode::InvalidTraitRentalDuration);
+
+    // the rental fee replaces the equip payment so traits which require payment can't be rented
+    require!(
+        ctx.accounts.trait_account.equip_payment_details.is_none(),
+        ErrorCode::TraitRentalNotAllowed
+    );
+
+    ctx.accounts.trait_rental.set_inner(TraitRental {
+        avatar_class: ctx.accounts.trait_account.avatar_class,
+        trait_account: ctx.accounts.trait_account.key(),
+        lender: ctx.accounts.lender.key(),
+        duration: args.duration,
+        fee: args.fee,
+        avatar: None,
+        expiry: None,
+    });
+
+    // escrow the trait token until it is rented
+    let transfer_accounts = token::Transfer {
+        from: ctx.accounts.trait_source.to_account_info(),
+        to: ctx.accounts.trait_escrow.to_account_info(),
+        authority: ctx.accounts.lender.to_account_info(),
+    };
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+        ),
+        1,
+    )
+}
@@ -4,7 +4,7 @@ use anchor_spl::token;
 use crate::{
     state::{
         accounts::{Avatar, AvatarClass, Trait, TraitConflicts, UpdateState},
-        data::UpdateTarget,
+        data::{TraitData, UpdateTarget},
         errors::ErrorCode,
     },
     utils::validate_attribute_availability,
@@ -93,16 +93,30 @@ pub fn handler(ctx: Context<EquipTrait>) -> Result<()> {
     // create trait data for newly equipped trait
     let avatar_account_info = ctx.accounts.avatar.to_account_info();
     ctx.accounts.avatar.add_trait(
-        ctx.accounts.trait_account.key(),
-        ctx.accounts.trait_account.id,
-        ctx.accounts.trait_account.attribute_ids.clone(),
-        &ctx.accounts.trait_account.variant_metadata,
-        ctx.accounts.trait_account.trait_gate.clone(),
+        TraitData::new(
+            ctx.accounts.trait_account.attribute_ids.clone(),
+            ctx.accounts.trait_account.id,
+            ctx.accounts.trait_account.key(),
+            &ctx.accounts.trait_account.variant_metadata,
+            ctx.accounts.trait_account.trait_gate.clone(),
+            None,
+            ctx.accounts.trait_account.stat_modifiers.clone(),
+        )?,
         &avatar_account_info,
         ctx.accounts.payer.clone(),
         ctx.accounts.system_program.clone(),
     );
 
+    // recompute the cached stats with the new set of traits
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
     // check trait gates still pass after changes
     let valid = ctx.accounts.avatar.validate_trait_gates();
     require!(valid, ErrorCode::TraitGateFailure);
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
-use crate::state::accounts::{Avatar, AvatarClass, Trait};
+use crate::state::{
+    accounts::{Avatar, AvatarClass, Trait},
+    data::TraitData,
+};
 
 #[derive(Accounts)]
 pub struct EquipTraitAuthority<'info> {
@@ -45,16 +48,30 @@ pub fn handler(ctx: Context<EquipTraitAuthority>) -> Result<()> {
     // create trait data for newly equipped trait
     let avatar_account_info = ctx.accounts.avatar.to_account_info();
     ctx.accounts.avatar.add_trait(
-        ctx.accounts.trait_account.key(),
-        ctx.accounts.trait_account.id,
-        ctx.accounts.trait_account.attribute_ids.clone(),
-        &ctx.accounts.trait_account.variant_metadata,
-        ctx.accounts.trait_account.trait_gate.clone(),
+        TraitData::new(
+            ctx.accounts.trait_account.attribute_ids.clone(),
+            ctx.accounts.trait_account.id,
+            ctx.accounts.trait_account.key(),
+            &ctx.accounts.trait_account.variant_metadata,
+            ctx.accounts.trait_account.trait_gate.clone(),
+            None,
+            ctx.accounts.trait_account.stat_modifiers.clone(),
+        )?,
         &avatar_account_info,
         ctx.accounts.authority.clone(),
         ctx.accounts.system_program.clone(),
     );
 
+    // recompute the cached stats with the new set of traits
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
     // transfer trait token to avatar
     let transfer_accounts = token::Transfer {
         from: ctx.accounts.trait_source.to_account_info(),
@@ -3,8 +3,9 @@ use std::vec;
 
 use anchor_lang::prelude::*;
 
+use crate::instructions::migrate_avatar_class_account::OldVariantOption;
 use crate::state::accounts::Avatar;
-use crate::state::data::{TraitData, VariantOption};
+use crate::state::data::{TraitData, TraitGate, VariantOption};
 use crate::state::errors::ErrorCode;
 use crate::utils::reallocate;
 
@@ -48,18 +49,28 @@ pub fn handler(ctx: Context<MigrateAvatarAccount>) -> Result<()> {
             attribute_ids: old_trait.attribute_ids,
             trait_id: old_trait.trait_id,
             trait_address: old_trait.trait_address,
-            variant_selection: old_trait.variant_selection,
-            trait_gate: None,
+            variant_selection: old_trait
+                .variant_selection
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            trait_gate: old_trait.trait_gate,
+            rental: None,
+            stat_modifiers: vec![],
         })
     }
 
+    let new_variants: Vec<VariantOption> = old_variants.into_iter().map(Into::into).collect();
+
     // Create new avatar data using fetched old data
     let new_avatar_data: Avatar = Avatar {
         avatar_class: old_avatar_class,
         mint: old_mint,
         image_uri: old_image_uri,
         traits: new_trait_data,
-        variants: old_variants,
+        variants: new_variants,
+        stats: vec![],
+        namespaces: vec![],
     };
 
     // Serialize the new_avatar_data
@@ -113,7 +124,7 @@ pub struct OldAvatar {
     pub mint: Pubkey,
     pub image_uri: String,
     pub traits: Vec<OldTraitData>,
-    pub variants: Vec<VariantOption>,
+    pub variants: Vec<OldVariantOption>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -121,5 +132,6 @@ pub struct OldTraitData {
     pub attribute_ids: Vec<u16>,
     pub trait_id: u16,
     pub trait_address: Pubkey,
-    pub variant_selection: Vec<VariantOption>,
+    pub variant_selection: Vec<OldVariantOption>,
+    pub trait_gate: Option<TraitGate>,
 }
@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use anchor_lang::prelude::*;
 
-use crate::state::data::{AttributeMetadata, AttributeStatus, AttributeType, VariantMetadata};
+use crate::state::data::{
+    AttributeMetadata, PaymentDetails, TraitGate, VariantMetadata, VariantOption, VariantStatus,
+};
 use crate::state::{accounts::AvatarClass, errors::ErrorCode};
 use crate::utils::reallocate;
 
@@ -52,27 +54,20 @@ pub fn handler(ctx: Context<MigrateAvatarClassAccount>) -> Result<()> {
         )
     };
 
-    // update attribute metadata with the new attribute_type field
-    let mut new_attribute_metadata: Vec<AttributeMetadata> = Vec::new();
-    for old_am in old_attribute_metadata {
-        new_attribute_metadata.push(AttributeMetadata {
-            id: old_am.id,
-            name: old_am.name,
-            status: AttributeStatus {
-                attribute_type: AttributeType::Optional,
-                mutable: old_am.status.mutable,
-            },
-        })
-    }
+    // update variant options with the new availability and payment fields
+    let new_variant_metadata: Vec<VariantMetadata> =
+        old_variant_metadata.into_iter().map(Into::into).collect();
 
     // Create new avatar_class data using fetched old data
     let new_avatar_class_data: AvatarClass = AvatarClass {
         mint: old_mint,
         trait_index: old_trait_index,
         payment_index: old_payment_index,
-        attribute_metadata: new_attribute_metadata,
-        variant_metadata: old_variant_metadata,
+        attribute_metadata: old_attribute_metadata,
+        variant_metadata: new_variant_metadata,
         global_rendering_config_uri: old_global_rendering_config_uri.clone(),
+        stat_templates: vec![],
+        namespaces: vec![],
     };
     msg!("new avatar class data created: {:?}", new_avatar_class_data);
 
@@ -127,19 +122,64 @@ pub struct OldAvatarClass {
     pub mint: Pubkey,
     pub trait_index: u16,
     pub payment_index: u64,
-    pub attribute_metadata: Vec<OldAttributeMetadata>,
-    pub variant_metadata: Vec<VariantMetadata>,
+    pub attribute_metadata: Vec<AttributeMetadata>,
+    pub variant_metadata: Vec<OldVariantMetadata>,
     pub global_rendering_config_uri: String,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct OldAttributeMetadata {
-    pub id: u16,
+pub struct OldVariantMetadata {
     pub name: String,
-    pub status: OldAttributeStatus,
+    pub id: String,
+    pub status: VariantStatus,
+    pub options: Vec<OldVariantOption>,
+}
+
+impl From<OldVariantMetadata> for VariantMetadata {
+    fn from(old: OldVariantMetadata) -> Self {
+        VariantMetadata {
+            name: old.name,
+            id: old.id,
+            status: old.status,
+            options: old.options.into_iter().map(Into::into).collect(),
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct OldAttributeStatus {
-    pub mutable: bool,
+pub struct OldVariantOption {
+    pub variant_id: String,
+    pub option_id: String,
+    pub payment_details: Option<OldPaymentDetails>,
+    pub trait_gate: Option<TraitGate>,
+}
+
+impl From<OldVariantOption> for VariantOption {
+    fn from(old: OldVariantOption) -> Self {
+        VariantOption {
+            variant_id: old.variant_id,
+            option_id: old.option_id,
+            payment_details: old.payment_details.map(Into::into),
+            trait_gate: old.trait_gate,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OldPaymentDetails {
+    pub payment_method: Pubkey,
+    pub amount: u64,
+}
+
+impl From<OldPaymentDetails> for PaymentDetails {
+    fn from(old: OldPaymentDetails) -> Self {
+        PaymentDetails {
+            payment_method: old.payment_method,
+            amount: old.amount,
+            alternatives: vec![],
+        }
+    }
 }
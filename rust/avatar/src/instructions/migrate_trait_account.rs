@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+
+use crate::instructions::migrate_avatar_class_account::{OldPaymentDetails, OldVariantMetadata};
+use crate::state::accounts::Trait;
+use crate::state::data::{TraitGate, TraitStatus, VariantMetadata};
+use crate::state::errors::ErrorCode;
+use crate::utils::reallocate;
+
+#[derive(Accounts)]
+pub struct MigrateTraitAccount<'info> {
+    /// CHECK: old trait account data
+    #[account(mut)]
+    pub trait_account: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = authority.key().eq(&Pubkey::from_str("3kkFMBB6Hg3HTR4e6c9CKaPrUUcrjA694aGTJrbVG675").unwrap()))]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateTraitAccount>) -> Result<()> {
+    msg!(
+        "migrating trait account: {}",
+        &ctx.accounts.trait_account.key()
+    );
+    let trait_account_info = &ctx.accounts.trait_account.to_account_info();
+
+    // get current space of account
+    let old_space = trait_account_info.data_len();
+    msg!("old_trait_account_space: {}", old_space);
+
+    // Extract old data within a local scope to ensure the mutable borrow is released afterwards
+    let old_trait: OldTrait = {
+        let b = &trait_account_info.try_borrow_mut_data().unwrap();
+        AnchorDeserialize::deserialize(&mut &b[8..]).unwrap()
+    };
+    let old_trait_mint = old_trait.trait_mint;
+
+    // update variant options with the new availability and payment fields
+    let new_variant_metadata: Vec<VariantMetadata> = old_trait
+        .variant_metadata
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    // Create new trait data using fetched old data
+    let new_trait_data: Trait = Trait {
+        id: old_trait.id,
+        avatar_class: old_trait.avatar_class,
+        trait_mint: old_trait.trait_mint,
+        attribute_ids: old_trait.attribute_ids,
+        component_uri: old_trait.component_uri,
+        status: old_trait.status,
+        variant_metadata: new_variant_metadata,
+        equip_payment_details: old_trait.equip_payment_details.map(Into::into),
+        remove_payment_details: old_trait.remove_payment_details.map(Into::into),
+        trait_gate: old_trait.trait_gate,
+        stat_modifiers: vec![],
+    };
+
+    // Serialize the new_trait_data
+    let new_trait_data_vec = new_trait_data.try_to_vec().unwrap();
+
+    // get the new space of the account
+    let new_space = new_trait_data.current_space();
+    msg!("new_trait_account_space: {}", new_space);
+
+    // calculate the diff in space from old and new
+    let space_diff = new_space as i64 - old_space as i64;
+    msg!("space_diff: {}", space_diff);
+
+    // reallocate the account data based on the diff
+    reallocate(
+        space_diff,
+        trait_account_info,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+    msg!("account size reallocated");
+
+    // Ensure the size fits
+    assert!(8 + new_trait_data_vec.len() <= ctx.accounts.trait_account.data_len());
+
+    // Borrow the trait account mutably and overwrite its data (excluding the first 8 bytes)
+    {
+        let mut trait_account_data = ctx.accounts.trait_account.try_borrow_mut_data()?;
+        trait_account_data[8..8 + new_trait_data_vec.len()].copy_from_slice(&new_trait_data_vec);
+    }
+    msg!("new trait data written to account");
+
+    // deserialize new account
+    let new_account_data: Account<'_, Trait> =
+        Account::try_from(&ctx.accounts.trait_account.to_account_info()).unwrap();
+
+    msg!("new account deserialized");
+
+    // simple check to make sure the new account data is correct
+    require!(
+        new_account_data.trait_mint.eq(&old_trait_mint),
+        ErrorCode::MigrationError
+    );
+
+    Ok(())
+}
+
+#[account]
+pub struct OldTrait {
+    pub id: u16,
+    pub avatar_class: Pubkey,
+    pub trait_mint: Pubkey,
+    pub attribute_ids: Vec<u16>,
+    pub component_uri: String,
+    pub status: TraitStatus,
+    pub variant_metadata: Vec<OldVariantMetadata>,
+    pub equip_payment_details: Option<OldPaymentDetails>,
+    pub remove_payment_details: Option<OldPaymentDetails>,
+    pub trait_gate: Option<TraitGate>,
+}
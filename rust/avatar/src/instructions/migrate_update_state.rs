@@ -0,0 +1,109 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::state::accounts::UpdateState;
+use crate::state::data::{UpdatePaymentAlternatives, UpdateTarget};
+use crate::state::errors::ErrorCode;
+use crate::utils::reallocate;
+
+#[derive(Accounts)]
+pub struct MigrateUpdateState<'info> {
+    /// CHECK: old update state data
+    #[account(mut, owner = crate::id())]
+    pub update_state: UncheckedAccount<'info>,
+
+    // update states are begun by their players, so anyone may pay to carry one over to the new layout
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateUpdateState>) -> Result<()> {
+    msg!(
+        "migrating update state: {}",
+        &ctx.accounts.update_state.key()
+    );
+    let update_state_info = &ctx.accounts.update_state.to_account_info();
+
+    // get current space of account
+    let old_space = update_state_info.data_len();
+    msg!("old_update_state_space: {}", old_space);
+
+    // Extract old data within a local scope to ensure the borrow is released afterwards
+    let old_update_state: OldUpdateState = {
+        let b = &update_state_info.try_borrow_data()?;
+
+        // only update states written before the version byte can be migrated
+        require!(
+            b.len() > 8
+                && b[..8] == UpdateState::DISCRIMINATOR
+                && b[8] == UpdateState::LEGACY_VERSION,
+            ErrorCode::MigrationError
+        );
+
+        AnchorDeserialize::deserialize(&mut &b[8..]).map_err(|_| ErrorCode::MigrationError)?
+    };
+    let avatar = old_update_state.avatar;
+
+    // Create new update state data using fetched old data
+    let new_update_state: UpdateState = old_update_state.into();
+
+    // Serialize the new_update_state
+    let new_update_state_vec = new_update_state.try_to_vec().unwrap();
+
+    // the version byte and the payment alternatives are new
+    let space_diff = (1 + UpdatePaymentAlternatives::SPACE) as i64;
+    msg!("space_diff: {}", space_diff);
+
+    // reallocate the account data based on the diff
+    reallocate(
+        space_diff,
+        update_state_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+    msg!("account size reallocated");
+
+    // Ensure the size fits
+    assert!(8 + new_update_state_vec.len() <= ctx.accounts.update_state.data_len());
+
+    // Borrow the update state mutably and overwrite its data (excluding the first 8 bytes)
+    {
+        let mut update_state_data = ctx.accounts.update_state.try_borrow_mut_data()?;
+        update_state_data[8..8 + new_update_state_vec.len()].copy_from_slice(&new_update_state_vec);
+    }
+    msg!("new update state data written to account");
+
+    // deserialize new account
+    let new_account_data: Account<'_, UpdateState> =
+        Account::try_from(&ctx.accounts.update_state.to_account_info())?;
+
+    msg!("new account deserialized");
+
+    // simple check to make sure the new account data is correct
+    require!(
+        new_account_data.version == UpdateState::VERSION && new_account_data.avatar.eq(&avatar),
+        ErrorCode::MigrationError
+    );
+
+    Ok(())
+}
+
+#[account]
+pub struct OldUpdateState {
+    pub initialized: bool,
+    pub avatar: Pubkey,
+    pub target: UpdateTarget,
+}
+
+impl From<OldUpdateState> for UpdateState {
+    fn from(old_update_state: OldUpdateState) -> Self {
+        UpdateState {
+            version: UpdateState::VERSION,
+            initialized: old_update_state.initialized,
+            avatar: old_update_state.avatar,
+            target: old_update_state.target,
+            payment_alternatives: UpdatePaymentAlternatives::default(),
+        }
+    }
+}
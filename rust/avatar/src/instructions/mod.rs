@@ -1,27 +1,41 @@
 pub mod add_payment_mint_to_payment_method;
 pub mod add_trait_conflicts;
+pub mod avatar_artifact_cache_namespace;
+pub mod avatar_artifact_join_namespace;
+pub mod avatar_artifact_leave_namespace;
+pub mod avatar_artifact_uncache_namespace;
 pub mod begin_trait_swap_update;
 pub mod begin_trait_update;
 pub mod begin_variant_update;
 pub mod burn_payment;
 pub mod burn_payment_tree;
+pub mod cancel_trait_rental;
 pub mod cancel_update;
 pub mod create_avatar;
 pub mod create_avatar_class;
 pub mod create_payment_method;
 pub mod create_trait;
+pub mod create_trait_rental;
 pub mod equip_trait;
 pub mod equip_trait_authority;
 pub mod migrate_avatar_account;
 pub mod migrate_avatar_class_account;
+pub mod migrate_trait_account;
+pub mod migrate_update_state;
+pub mod reclaim_trait_rental;
+pub mod refresh_avatar_stats;
+pub mod remove_stat_template;
 pub mod remove_trait;
 pub mod remove_trait_authority;
+pub mod rent_trait;
 pub mod swap_trait;
 pub mod transfer_payment;
+pub mod transfer_payment_native;
 pub mod transfer_payment_tree;
 pub mod update_attribute_metadata;
 pub mod update_class_variant_authority;
 pub mod update_class_variant_metadata;
+pub mod update_stat_template;
 pub mod update_trait;
 pub mod update_trait_variant_authority;
 pub mod update_variant;
@@ -30,28 +44,42 @@ pub mod verify_payment_mint_test;
 
 pub use add_payment_mint_to_payment_method::*;
 pub use add_trait_conflicts::*;
+pub use avatar_artifact_cache_namespace::*;
+pub use avatar_artifact_join_namespace::*;
+pub use avatar_artifact_leave_namespace::*;
+pub use avatar_artifact_uncache_namespace::*;
 pub use begin_trait_swap_update::*;
 pub use begin_trait_update::*;
 pub use begin_variant_update::*;
 pub use burn_payment::*;
 pub use burn_payment_tree::*;
+pub use cancel_trait_rental::*;
 pub use cancel_update::*;
 pub use create_avatar::*;
 pub use create_avatar_class::*;
 pub use create_payment_method::*;
 pub use create_trait::*;
+pub use create_trait_rental::*;
 pub use equip_trait::*;
 pub use equip_trait_authority::*;
 pub use migrate_avatar_account::*;
 pub use migrate_avatar_class_account::*;
+pub use migrate_trait_account::*;
+pub use migrate_update_state::*;
+pub use reclaim_trait_rental::*;
+pub use refresh_avatar_stats::*;
+pub use remove_stat_template::*;
 pub use remove_trait::*;
 pub use remove_trait_authority::*;
+pub use rent_trait::*;
 pub use swap_trait::*;
 pub use transfer_payment::*;
+pub use transfer_payment_native::*;
 pub use transfer_payment_tree::*;
 pub use update_attribute_metadata::*;
 pub use update_class_variant_authority::*;
 pub use update_class_variant_metadata::*;
+pub use update_stat_template::*;
 pub use update_trait::*;
 pub use update_trait_variant_authority::*;
 pub use update_variant::*;
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token};
+
+use crate::{
+    state::{
+        accounts::{Avatar, AvatarClass, Trait, TraitRental},
+        errors::ErrorCode,
+    },
+    utils::get_essential_attribute_ids,
+};
+
+#[derive(Accounts)]
+pub struct ReclaimTraitRental<'info> {
+    #[account(seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class.mint.key().as_ref()], bump)]
+    pub avatar_class: Box<Account<'info, AvatarClass>>,
+
+    #[account(mut,
+        has_one = avatar_class,
+        seeds = [Avatar::PREFIX.as_bytes(), avatar_class.key().as_ref(), avatar.mint.key().as_ref()], bump)]
+    pub avatar: Box<Account<'info, Avatar>>,
+
+    #[account(
+        constraint = avatar_mint_ata.amount == 1,
+        associated_token::mint = avatar.mint, associated_token::authority = avatar_authority)]
+    pub avatar_mint_ata: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(mut)]
+    pub avatar_authority: SystemAccount<'info>,
+
+    #[account(
+        has_one = avatar_class,
+        has_one = trait_mint,
+        seeds = [Trait::PREFIX.as_bytes(), avatar_class.key().as_ref(), trait_mint.key().as_ref()], bump)]
+    pub trait_account: Box<Account<'info, Trait>>,
+
+    pub trait_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(mut,
+        close = lender,
+        has_one = lender,
+        has_one = trait_account,
+        constraint = trait_rental.avatar.eq(&Some(avatar.key())) @ ErrorCode::TraitRentalUnavailable,
+        seeds = [TraitRental::PREFIX.as_bytes(), trait_account.key().as_ref(), lender.key().as_ref()], bump)]
+    pub trait_rental: Box<Account<'info, TraitRental>>,
+
+    #[account(mut,
+        associated_token::mint = trait_mint,
+        associated_token::authority = avatar)]
+    pub avatar_trait_ata: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(init_if_needed,
+        payer = payer,
+        associated_token::mint = trait_mint,
+        associated_token::authority = lender)]
+    pub trait_destination: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(mut)]
+    pub lender: SystemAccount<'info>,
+
+    // anyone can reclaim an expired rental
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub token_program: Program<'info, token::Token>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ReclaimTraitRental>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.trait_rental.is_expired(clock.unix_timestamp),
+        ErrorCode::TraitRentalNotExpired
+    );
+
+    // removing the trait must not leave an essential attribute empty
+    let essential_ids = get_essential_attribute_ids(
+        &ctx.accounts.avatar_class.attribute_metadata,
+        &ctx.accounts.trait_account.attribute_ids,
+    );
+    require!(
+        essential_ids.is_empty(),
+        ErrorCode::MissingEssentialAttribute
+    );
+
+    let avatar_signer_seeds: &[&[u8]] = &[
+        Avatar::PREFIX.as_bytes(),
+        ctx.accounts.avatar_class.to_account_info().key.as_ref(),
+        ctx.accounts.avatar.mint.as_ref(),
+        &[*ctx.bumps.get("avatar").unwrap()],
+    ];
+
+    // return the trait to the lender
+    let transfer_accounts = token::Transfer {
+        from: ctx.accounts.avatar_trait_ata.to_account_info(),
+        to: ctx.accounts.trait_destination.to_account_info(),
+        authority: ctx.accounts.avatar.to_account_info(),
+    };
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            &[avatar_signer_seeds],
+        ),
+        1,
+    )?;
+
+    // reload account data to check token account amount
+    ctx.accounts.avatar_trait_ata.reload()?;
+
+    // close token account if amount is 0
+    if ctx.accounts.avatar_trait_ata.amount == 0 {
+        let close_ata_accounts = token::CloseAccount {
+            account: ctx.accounts.avatar_trait_ata.to_account_info(),
+            destination: ctx.accounts.avatar_authority.to_account_info(),
+            authority: ctx.accounts.avatar.to_account_info(),
+        };
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            close_ata_accounts,
+            &[avatar_signer_seeds],
+        ))?;
+    }
+
+    // remove the trait data, trait gates are not checked so a renter can't block the reclaim
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    ctx.accounts.avatar.remove_trait(
+        ctx.accounts.trait_account.key(),
+        &avatar_account_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    );
+
+    // recompute the cached stats with the new set of traits
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::accounts::{Avatar, AvatarClass};
+
+#[derive(Accounts)]
+pub struct RefreshAvatarStats<'info> {
+    #[account(seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class.mint.key().as_ref()], bump)]
+    pub avatar_class: Account<'info, AvatarClass>,
+
+    #[account(mut,
+        has_one = avatar_class,
+        seeds = [Avatar::PREFIX.as_bytes(), avatar_class.key().as_ref(), avatar.mint.key().as_ref()], bump)]
+    pub avatar: Account<'info, Avatar>,
+
+    // anyone can refresh the cached stats, they are derived entirely from on-chain data
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RefreshAvatarStats>) -> Result<()> {
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
+    Ok(())
+}
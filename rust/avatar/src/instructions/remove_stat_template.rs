@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::state::accounts::AvatarClass;
+
+#[derive(Accounts)]
+#[instruction(args: RemoveStatTemplateArgs)]
+pub struct RemoveStatTemplate<'info> {
+    #[account(mut, seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class.mint.key().as_ref()], bump)]
+    pub avatar_class: Account<'info, AvatarClass>,
+
+    #[account(
+        constraint = avatar_class_mint_ata.amount >= 1,
+        associated_token::mint = avatar_class.mint, associated_token::authority = authority)]
+    pub avatar_class_mint_ata: Account<'info, token::TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveStatTemplateArgs {
+    pub stat_id: u16,
+}
+
+pub fn handler(ctx: Context<RemoveStatTemplate>, args: RemoveStatTemplateArgs) -> Result<()> {
+    // avatars keep the removed stat cached until refresh_avatar_stats is called, trait
+    // modifiers for it are ignored unless a template with the same id is added again
+    let avatar_class = &ctx.accounts.avatar_class.to_account_info();
+    ctx.accounts.avatar_class.remove_stat_template(
+        args.stat_id,
+        avatar_class,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )
+}
@@ -66,6 +66,14 @@ pub fn handler(ctx: Context<RemoveTrait>) -> Result<()> {
         _ => return Err(ErrorCode::InvalidUpdateTarget.into()),
     }
 
+    // rented traits are returned to the lender by reclaiming the rental
+    require!(
+        !ctx.accounts
+            .avatar
+            .is_trait_rented(&ctx.accounts.trait_account.key()),
+        ErrorCode::TraitRented
+    );
+
     // check trait attributes are mutable
     let mutable = ctx
         .accounts
@@ -133,6 +141,16 @@ pub fn handler(ctx: Context<RemoveTrait>) -> Result<()> {
         ctx.accounts.system_program.clone(),
     );
 
+    // recompute the cached stats with the new set of traits
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
     // check trait gates still pass after changes
     let valid = ctx.accounts.avatar.validate_trait_gates();
     require!(valid, ErrorCode::TraitGateFailure);
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token, token};
 
-use crate::state::accounts::{Avatar, AvatarClass, Trait};
+use crate::state::{
+    accounts::{Avatar, AvatarClass, Trait},
+    errors::ErrorCode,
+};
 
 #[derive(Accounts)]
 pub struct RemoveTraitAuthority<'info> {
@@ -49,6 +52,14 @@ pub struct RemoveTraitAuthority<'info> {
 }
 
 pub fn handler(ctx: Context<RemoveTraitAuthority>) -> Result<()> {
+    // rented traits are returned to the lender by reclaiming the rental
+    require!(
+        !ctx.accounts
+            .avatar
+            .is_trait_rented(&ctx.accounts.trait_account.key()),
+        ErrorCode::TraitRented
+    );
+
     // transfer trait to authority
     let transfer_accounts = token::Transfer {
         from: ctx.accounts.avatar_trait_ata.to_account_info(),
@@ -79,6 +90,16 @@ pub fn handler(ctx: Context<RemoveTraitAuthority>) -> Result<()> {
         ctx.accounts.system_program.clone(),
     );
 
+    // recompute the cached stats with the new set of traits
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
     // reload account data to check token account amount
     ctx.accounts.avatar_trait_ata.reload()?;
 
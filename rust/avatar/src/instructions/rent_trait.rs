@@ -0,0 +1,212 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{associated_token, token};
+
+use crate::{
+    state::{
+        accounts::{Avatar, AvatarClass, Trait, TraitConflicts, TraitRental},
+        data::TraitData,
+        errors::ErrorCode,
+    },
+    utils::{get_essential_attribute_ids, validate_attribute_availability},
+};
+
+#[derive(Accounts)]
+pub struct RentTrait<'info> {
+    #[account(seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class.mint.key().as_ref()], bump)]
+    pub avatar_class: Box<Account<'info, AvatarClass>>,
+
+    #[account(mut,
+        has_one = avatar_class,
+        seeds = [Avatar::PREFIX.as_bytes(), avatar_class.key().as_ref(), avatar.mint.key().as_ref()], bump)]
+    pub avatar: Box<Account<'info, Avatar>>,
+
+    #[account(
+        constraint = avatar_mint_ata.amount == 1,
+        associated_token::mint = avatar.mint, associated_token::authority = renter)]
+    pub avatar_mint_ata: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        has_one = avatar_class,
+        has_one = trait_mint,
+        seeds = [Trait::PREFIX.as_bytes(), avatar_class.key().as_ref(), trait_mint.key().as_ref()], bump)]
+    pub trait_account: Box<Account<'info, Trait>>,
+
+    pub trait_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        has_one = avatar_class,
+        has_one = trait_account,
+        seeds = [TraitConflicts::PREFIX.as_bytes(), avatar_class.key().as_ref(), trait_account.key().as_ref()], bump
+    )]
+    pub trait_conflicts: Box<Account<'info, TraitConflicts>>,
+
+    #[account(mut,
+        has_one = lender,
+        has_one = trait_account,
+        seeds = [TraitRental::PREFIX.as_bytes(), trait_account.key().as_ref(), lender.key().as_ref()], bump)]
+    pub trait_rental: Box<Account<'info, TraitRental>>,
+
+    #[account(mut,
+        associated_token::mint = trait_mint,
+        associated_token::authority = trait_rental)]
+    pub trait_escrow: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(init_if_needed,
+        payer = renter,
+        associated_token::mint = trait_mint,
+        associated_token::authority = avatar)]
+    pub avatar_trait_ata: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(mut)]
+    pub lender: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub token_program: Program<'info, token::Token>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RentTrait>) -> Result<()> {
+    require!(
+        ctx.accounts.avatar_mint_ata.delegate.is_none(),
+        ErrorCode::TokenDelegateNotAllowed
+    );
+
+    require!(
+        !ctx.accounts.trait_rental.is_rented(),
+        ErrorCode::TraitRentalUnavailable
+    );
+
+    // verify trait is enabled
+    let trait_enabled = ctx.accounts.trait_account.is_enabled();
+    require!(trait_enabled, ErrorCode::TraitDisabled);
+
+    // the trait is removed when the rental is reclaimed so it must be removable
+    let mutable = ctx
+        .accounts
+        .avatar_class
+        .is_trait_mutable(ctx.accounts.trait_account.attribute_ids.clone());
+    require!(mutable, ErrorCode::AttributeImmutable);
+
+    let essential_ids = get_essential_attribute_ids(
+        &ctx.accounts.avatar_class.attribute_metadata,
+        &ctx.accounts.trait_account.attribute_ids,
+    );
+    require!(essential_ids.is_empty(), ErrorCode::InvalidAttributeId);
+
+    // verify all attributes the trait_account requires are available
+    let valid = validate_attribute_availability(
+        &ctx.accounts.trait_account.attribute_ids,
+        &ctx.accounts.avatar.traits,
+        &ctx.accounts.avatar_class.attribute_metadata,
+    );
+    require!(valid, ErrorCode::InvalidAttributeId);
+
+    // verify there are no trait or attribute conflicts
+    let has_conflicts = ctx.accounts.trait_conflicts.has_conflicts(
+        &ctx.accounts.avatar.get_trait_ids(),
+        &ctx.accounts.avatar.get_attribute_ids(),
+    );
+    require!(!has_conflicts, ErrorCode::TraitConflict);
+
+    // create trait data for the rented trait
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    ctx.accounts.avatar.add_trait(
+        TraitData::new(
+            ctx.accounts.trait_account.attribute_ids.clone(),
+            ctx.accounts.trait_account.id,
+            ctx.accounts.trait_account.key(),
+            &ctx.accounts.trait_account.variant_metadata,
+            ctx.accounts.trait_account.trait_gate.clone(),
+            Some(ctx.accounts.trait_rental.key()),
+            ctx.accounts.trait_account.stat_modifiers.clone(),
+        )?,
+        &avatar_account_info,
+        ctx.accounts.renter.clone(),
+        ctx.accounts.system_program.clone(),
+    );
+
+    // recompute the cached stats with the new set of traits
+    let avatar_account_info = ctx.accounts.avatar.to_account_info();
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.update_stats(
+        &stat_templates,
+        &avatar_account_info,
+        ctx.accounts.renter.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
+    // check trait gates still pass after changes
+    let valid = ctx.accounts.avatar.validate_trait_gates();
+    require!(valid, ErrorCode::TraitGateFailure);
+
+    // pay the rental fee to the lender
+    if ctx.accounts.trait_rental.fee > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.renter.to_account_info(),
+                    to: ctx.accounts.lender.to_account_info(),
+                },
+            ),
+            ctx.accounts.trait_rental.fee,
+        )?;
+    }
+
+    let trait_account_key = ctx.accounts.trait_account.key();
+    let lender_key = ctx.accounts.lender.key();
+    let signer_seeds: &[&[u8]] = &[
+        TraitRental::PREFIX.as_bytes(),
+        trait_account_key.as_ref(),
+        lender_key.as_ref(),
+        &[*ctx.bumps.get("trait_rental").unwrap()],
+    ];
+
+    // transfer trait token from escrow to avatar
+    let transfer_accounts = token::Transfer {
+        from: ctx.accounts.trait_escrow.to_account_info(),
+        to: ctx.accounts.avatar_trait_ata.to_account_info(),
+        authority: ctx.accounts.trait_rental.to_account_info(),
+    };
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            &[signer_seeds],
+        ),
+        1,
+    )?;
+
+    // the escrow is empty until the trait is returned to the lender so we can close it
+    let close_escrow_accounts = token::CloseAccount {
+        account: ctx.accounts.trait_escrow.to_account_info(),
+        destination: ctx.accounts.lender.to_account_info(),
+        authority: ctx.accounts.trait_rental.to_account_info(),
+    };
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_escrow_accounts,
+        &[signer_seeds],
+    ))?;
+
+    // start the rental period
+    let clock = Clock::get()?;
+    let avatar_key = ctx.accounts.avatar.key();
+    ctx.accounts
+        .trait_rental
+        .rent(avatar_key, clock.unix_timestamp);
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use anchor_spl::token;
 use crate::{
     state::{
         accounts::{Avatar, AvatarClass, Trait, TraitConflicts, UpdateState},
-        data::UpdateTarget,
+        data::{TraitData, UpdateTarget},
         errors::ErrorCode,
     },
     utils::{validate_attribute_availability, validate_essential_attribute_updates},
@@ -90,6 +90,14 @@ pub fn handler(ctx: Context<SwapTrait>) -> Result<()> {
     let remove_trait_enabled = ctx.accounts.remove_trait_account.is_enabled();
     require!(remove_trait_enabled, ErrorCode::TraitDisabled);
 
+    // rented traits are returned to the lender by reclaiming the rental
+    require!(
+        !ctx.accounts
+            .avatar
+            .is_trait_rented(&ctx.accounts.remove_trait_account.key()),
+        ErrorCode::TraitRented
+    );
+
     // validate that each essential attribute which will be removed is replaced by an equip operation
     validate_essential_attribute_updates(
         &ctx.accounts.avatar_class.attribute_metadata,
@@ -169,13 +177,15 @@ pub fn handler(ctx: Context<SwapTrait>) -> Result<()> {
     require!(!has_conflicts, ErrorCode::TraitConflict);
 
     // create trait data for newly equipped trait
-    ctx.accounts.avatar.add_trait_data(
-        ctx.accounts.equip_trait_account.key(),
-        ctx.accounts.equip_trait_account.id,
+    ctx.accounts.avatar.add_trait_data(TraitData::new(
         ctx.accounts.equip_trait_account.attribute_ids.clone(),
+        ctx.accounts.equip_trait_account.id,
+        ctx.accounts.equip_trait_account.key(),
         &ctx.accounts.equip_trait_account.variant_metadata,
         ctx.accounts.equip_trait_account.trait_gate.clone(),
-    );
+        None,
+        ctx.accounts.equip_trait_account.stat_modifiers.clone(),
+    )?);
 
     // transfer trait token to avatar
     let transfer_accounts = token::Transfer {
@@ -192,6 +202,10 @@ pub fn handler(ctx: Context<SwapTrait>) -> Result<()> {
         1,
     )?;
 
+    // recompute the cached stats with the new set of traits
+    let stat_templates = ctx.accounts.avatar_class.stat_templates.clone();
+    ctx.accounts.avatar.stats = ctx.accounts.avatar.compute_stats(&stat_templates)?;
+
     // now that avatar account data has been modified, reallocate the account data
     let avatar_account_info = ctx.accounts.avatar.to_account_info();
     ctx.accounts.avatar.reallocate(
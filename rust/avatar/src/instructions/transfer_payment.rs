@@ -3,7 +3,7 @@ use anchor_spl::{associated_token::get_associated_token_address, token};
 
 use crate::state::{
     accounts::{PaymentMethod, UpdateState},
-    data::{PaymentAction, PaymentAssetClass},
+    data::{PaymentAction, PaymentAssetClass, PaymentSplit},
     errors::ErrorCode,
 };
 
@@ -22,8 +22,9 @@ pub struct TransferPayment<'info> {
     #[account(mut, constraint = payment_mint.key().eq(&payment_source.mint.key()))]
     pub payment_source: Box<Account<'info, token::TokenAccount>>,
 
+    // not required for split payments, the recipient token accounts are passed as remaining accounts
     #[account(mut, constraint = payment_mint.key().eq(&payment_destination.mint.key()))]
-    pub payment_destination: Box<Account<'info, token::TokenAccount>>,
+    pub payment_destination: Option<Box<Account<'info, token::TokenAccount>>>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -40,45 +41,103 @@ pub struct TransferPaymentArgs {
     pub amount: u64,
 }
 
-pub fn handler(ctx: Context<TransferPayment>, args: TransferPaymentArgs) -> Result<()> {
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, TransferPayment<'info>>,
+    args: TransferPaymentArgs,
+) -> Result<()> {
     // update the payment state according to how much will be burned
     ctx.accounts
         .update_state
-        .target
         .update_payment_state(&ctx.accounts.payment_method.key(), args.amount)?;
 
     match ctx.accounts.payment_method.asset_class {
-        PaymentAssetClass::Fungible { mint } => match ctx.accounts.payment_method.action {
-            PaymentAction::Transfer { treasury } => {
-                // check mint expected is passed in as an account
-                require!(
-                    mint.eq(&ctx.accounts.payment_mint.key()),
-                    ErrorCode::InvalidPaymentMint
-                );
-
-                // check the destination ata is defined in the payment details
-                let destination_address = get_associated_token_address(&treasury, &mint);
-                require!(
-                    destination_address.eq(&ctx.accounts.payment_destination.key()),
-                    ErrorCode::InvalidPaymentMint
-                );
-
-                let transfer_accounts = token::Transfer {
-                    from: ctx.accounts.payment_source.to_account_info(),
-                    to: ctx.accounts.payment_destination.to_account_info(),
-                    authority: ctx.accounts.authority.to_account_info(),
-                };
-
-                token::transfer(
-                    CpiContext::new(
-                        ctx.accounts.token_program.to_account_info(),
-                        transfer_accounts,
-                    ),
-                    args.amount,
-                )
+        PaymentAssetClass::Fungible { mint } => {
+            // check mint expected is passed in as an account
+            require!(
+                mint.eq(&ctx.accounts.payment_mint.key()),
+                ErrorCode::InvalidPaymentMint
+            );
+
+            match &ctx.accounts.payment_method.action {
+                PaymentAction::Transfer { treasury } => {
+                    let payment_destination = ctx
+                        .accounts
+                        .payment_destination
+                        .as_ref()
+                        .ok_or(ErrorCode::InvalidPaymentMint)?;
+
+                    // check the destination ata is defined in the payment details
+                    let destination_address = get_associated_token_address(treasury, &mint);
+                    require!(
+                        destination_address.eq(&payment_destination.key()),
+                        ErrorCode::InvalidPaymentMint
+                    );
+
+                    transfer_tokens(
+                        &ctx.accounts.payment_source.to_account_info(),
+                        &payment_destination.to_account_info(),
+                        &ctx.accounts.authority.to_account_info(),
+                        &ctx.accounts.token_program.to_account_info(),
+                        args.amount,
+                    )
+                }
+                PaymentAction::Split { recipients } => {
+                    // each recipient ata must be passed in the same order as the split
+                    require!(
+                        ctx.remaining_accounts.len() == recipients.len(),
+                        ErrorCode::InvalidPaymentSplit
+                    );
+
+                    let amounts = PaymentSplit::split_amount(recipients, args.amount);
+                    for ((recipient, amount), destination) in recipients
+                        .iter()
+                        .zip(amounts)
+                        .zip(ctx.remaining_accounts.iter())
+                    {
+                        let destination_address =
+                            get_associated_token_address(&recipient.treasury, &mint);
+                        require!(
+                            destination_address.eq(destination.key),
+                            ErrorCode::InvalidPaymentSplit
+                        );
+
+                        if amount == 0 {
+                            continue;
+                        }
+
+                        transfer_tokens(
+                            &ctx.accounts.payment_source.to_account_info(),
+                            destination,
+                            &ctx.accounts.authority.to_account_info(),
+                            &ctx.accounts.token_program.to_account_info(),
+                            amount,
+                        )?;
+                    }
+
+                    Ok(())
+                }
+                _ => Err(ErrorCode::InvalidPaymentMethod.into()),
             }
-            _ => Err(ErrorCode::InvalidPaymentMethod.into()),
-        },
+        }
         _ => Err(ErrorCode::InvalidPaymentMethod.into()),
     }
 }
+
+fn transfer_tokens<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let transfer_accounts = token::Transfer {
+        from: from.clone(),
+        to: to.clone(),
+        authority: authority.clone(),
+    };
+
+    token::transfer(
+        CpiContext::new(token_program.clone(), transfer_accounts),
+        amount,
+    )
+}
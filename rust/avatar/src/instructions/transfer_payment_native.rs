@@ -0,0 +1,122 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::state::{
+    accounts::{PaymentMethod, UpdateState},
+    data::{PaymentAction, PaymentAssetClass, PaymentSplit},
+    errors::ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct TransferPaymentNative<'info> {
+    #[account(mut,
+        seeds = [UpdateState::PREFIX.as_bytes(), update_state.avatar.key().as_ref(), update_state.target.hash().as_ref()], bump)]
+    pub update_state: Account<'info, UpdateState>,
+
+    #[account(
+        seeds = [PaymentMethod::PREFIX.as_bytes(), payment_method.avatar_class.key().as_ref(), &payment_method.index.to_le_bytes()], bump)]
+    pub payment_method: Account<'info, PaymentMethod>,
+
+    // not required for split payments, the recipient accounts are passed as remaining accounts
+    #[account(mut)]
+    pub payment_destination: Option<SystemAccount<'info>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferPaymentNativeArgs {
+    pub amount: u64,
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, TransferPaymentNative<'info>>,
+    args: TransferPaymentNativeArgs,
+) -> Result<()> {
+    // update the payment state according to how much will be transferred
+    ctx.accounts
+        .update_state
+        .update_payment_state(&ctx.accounts.payment_method.key(), args.amount)?;
+
+    match ctx.accounts.payment_method.asset_class {
+        PaymentAssetClass::Native => match &ctx.accounts.payment_method.action {
+            PaymentAction::Transfer { treasury } => {
+                let payment_destination = ctx
+                    .accounts
+                    .payment_destination
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidPaymentMethod)?;
+
+                // check the destination is the treasury defined in the payment method
+                require!(
+                    treasury.eq(&payment_destination.key()),
+                    ErrorCode::InvalidPaymentMethod
+                );
+
+                transfer_lamports(
+                    &ctx.accounts.authority.to_account_info(),
+                    &payment_destination.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    args.amount,
+                )
+            }
+            PaymentAction::Split { recipients } => {
+                // each recipient must be passed in the same order as the split
+                require!(
+                    ctx.remaining_accounts.len() == recipients.len(),
+                    ErrorCode::InvalidPaymentSplit
+                );
+
+                let amounts = PaymentSplit::split_amount(recipients, args.amount);
+                for ((recipient, amount), destination) in recipients
+                    .iter()
+                    .zip(amounts)
+                    .zip(ctx.remaining_accounts.iter())
+                {
+                    require!(
+                        recipient.treasury.eq(destination.key),
+                        ErrorCode::InvalidPaymentSplit
+                    );
+
+                    if amount == 0 {
+                        continue;
+                    }
+
+                    transfer_lamports(
+                        &ctx.accounts.authority.to_account_info(),
+                        destination,
+                        &ctx.accounts.system_program.to_account_info(),
+                        amount,
+                    )?;
+                }
+
+                Ok(())
+            }
+            _ => Err(ErrorCode::InvalidPaymentMethod.into()),
+        },
+        _ => Err(ErrorCode::InvalidPaymentMethod.into()),
+    }
+}
+
+fn transfer_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    transfer(
+        CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: from.clone(),
+                to: to.clone(),
+            },
+        ),
+        amount,
+    )
+}
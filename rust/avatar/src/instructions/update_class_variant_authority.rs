@@ -38,10 +38,10 @@ pub fn handler(
     args: UpdateClassVariantAuthorityArgs,
 ) -> Result<()> {
     // get variant metadata from avatar class
-    let variant_metadata = ctx.accounts.avatar_class.find_variant(&args.variant_id);
+    let variant_metadata = ctx.accounts.avatar_class.find_variant(&args.variant_id)?;
 
     // check avatar meets requirements to select the variant
-    let new_variant_option = variant_metadata.find_option(&args.new_variant_option_id);
+    let new_variant_option = variant_metadata.find_option(&args.new_variant_option_id)?;
     let eligible = new_variant_option.is_eligible(&ctx.accounts.avatar.get_traits());
     require!(eligible, ErrorCode::InvalidVariant);
 
@@ -52,7 +52,5 @@ pub fn handler(
         &avatar_account_info,
         ctx.accounts.authority.clone(),
         ctx.accounts.system_program.clone(),
-    );
-
-    Ok(())
+    )
 }
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
-use crate::state::{accounts::AvatarClass, data::VariantMetadata};
+use crate::{
+    state::{accounts::AvatarClass, data::VariantMetadata},
+    utils::validate_variant_metadata_payment_details,
+};
 
 #[derive(Accounts)]
 #[instruction(args: UpdateClassVariantMetadataArgs)]
@@ -29,6 +32,8 @@ pub fn handler(
     ctx: Context<UpdateClassVariantMetadata>,
     args: UpdateClassVariantMetadataArgs,
 ) -> Result<()> {
+    validate_variant_metadata_payment_details(std::slice::from_ref(&args.variant_metadata))?;
+
     let avatar_class = &ctx.accounts.avatar_class.to_account_info();
     ctx.accounts.avatar_class.update_variant_metadata(
         args.variant_metadata,
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::state::{accounts::AvatarClass, data::StatTemplate, errors::ErrorCode};
+
+#[derive(Accounts)]
+#[instruction(args: UpdateStatTemplateArgs)]
+pub struct UpdateStatTemplate<'info> {
+    #[account(mut, seeds = [AvatarClass::PREFIX.as_bytes(), avatar_class.mint.key().as_ref()], bump)]
+    pub avatar_class: Account<'info, AvatarClass>,
+
+    #[account(
+        constraint = avatar_class_mint_ata.amount >= 1,
+        associated_token::mint = avatar_class.mint, associated_token::authority = authority)]
+    pub avatar_class_mint_ata: Account<'info, token::TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateStatTemplateArgs {
+    pub stat_template: StatTemplate,
+}
+
+pub fn handler(ctx: Context<UpdateStatTemplate>, args: UpdateStatTemplateArgs) -> Result<()> {
+    require!(
+        args.stat_template.is_valid(),
+        ErrorCode::InvalidStatTemplate
+    );
+
+    // avatars keep their cached stats until refresh_avatar_stats is called
+    let avatar_class = &ctx.accounts.avatar_class.to_account_info();
+    ctx.accounts.avatar_class.update_stat_template(
+        args.stat_template,
+        avatar_class,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    );
+    Ok(())
+}
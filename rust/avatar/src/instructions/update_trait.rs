@@ -4,9 +4,10 @@ use anchor_spl::token;
 use crate::{
     state::{
         accounts::{AvatarClass, Trait},
-        data::{PaymentDetails, TraitGate, VariantMetadata, VariantOption},
+        data::{PaymentDetails, StatModifier, TraitGate, VariantMetadata, VariantOption},
+        errors::ErrorCode,
     },
-    utils::reallocate,
+    utils::{reallocate, validate_payment_details, validate_variant_metadata_payment_details},
 };
 
 #[derive(Accounts)]
@@ -38,6 +39,7 @@ pub struct UpdateTraitArgs {
     pub equip_payment_details: Option<PaymentDetails>,
     pub remove_payment_details: Option<PaymentDetails>,
     pub trait_gate: Option<TraitGate>,
+    pub stat_modifiers: Option<Vec<StatModifier>>,
 }
 
 pub fn handler(ctx: Context<UpdateTrait>, args: UpdateTraitArgs) -> Result<()> {
@@ -45,6 +47,22 @@ pub fn handler(ctx: Context<UpdateTrait>, args: UpdateTraitArgs) -> Result<()> {
 
     let old_space: i64 = ctx.accounts.trait_account.current_space() as i64;
 
+    if let Some(variant_metadata) = &args.variant_metadata {
+        validate_variant_metadata_payment_details(std::slice::from_ref(variant_metadata))?;
+    }
+    if let Some(variant_option) = &args.variant_option {
+        validate_payment_details(&variant_option.payment_details)?;
+    }
+    validate_payment_details(&args.equip_payment_details)?;
+    validate_payment_details(&args.remove_payment_details)?;
+    if let Some(stat_modifiers) = &args.stat_modifiers {
+        let valid = ctx
+            .accounts
+            .avatar_class
+            .is_valid_stat_modifiers(stat_modifiers);
+        require!(valid, ErrorCode::InvalidStatId);
+    }
+
     if let Some(variant_metadata) = args.variant_metadata {
         ctx.accounts.trait_account.update_variant_metadata(
             variant_metadata,
@@ -75,6 +93,10 @@ pub fn handler(ctx: Context<UpdateTrait>, args: UpdateTraitArgs) -> Result<()> {
         ctx.accounts.trait_account.trait_gate = Some(trait_gate);
     }
 
+    if let Some(stat_modifiers) = args.stat_modifiers {
+        ctx.accounts.trait_account.stat_modifiers = stat_modifiers;
+    }
+
     let new_space: i64 = ctx.accounts.trait_account.current_space() as i64;
 
     let diff: i64 = new_space - old_space;
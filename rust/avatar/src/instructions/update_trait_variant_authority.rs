@@ -43,26 +43,24 @@ pub fn handler(
     args: UpdateTraitVariantAuthorityArgs,
 ) -> Result<()> {
     // get the variant metadata for the variant_id we want to change
-    let variant_metadata = ctx.accounts.trait_account.find_variant(&args.variant_id);
+    let variant_metadata = ctx.accounts.trait_account.find_variant(&args.variant_id)?;
 
     // check avatar meets requirements to select the variant option
-    let new_variant_option = variant_metadata.find_option(&args.new_variant_option_id);
+    let new_variant_option = variant_metadata.find_option(&args.new_variant_option_id)?;
     let eligible = new_variant_option.is_eligible(&ctx.accounts.avatar.get_traits());
     require!(eligible, ErrorCode::InvalidVariant);
 
     // update trait data with new variant selection
     let avatar_account_info = &ctx.accounts.avatar.to_account_info();
-    let trait_data = &mut ctx
+    let trait_data = ctx
         .accounts
         .avatar
-        .find_trait_mut(&ctx.accounts.trait_account.key());
+        .find_trait_mut(&ctx.accounts.trait_account.key())?;
 
     trait_data.update_variant_selection(
         new_variant_option,
         avatar_account_info,
         ctx.accounts.authority.clone(),
         ctx.accounts.system_program.clone(),
-    );
-
-    Ok(())
+    )
 }
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
-use crate::state::{
-    accounts::{Avatar, AvatarClass, Trait, UpdateState},
-    data::UpdateTarget,
-    errors::ErrorCode,
+use crate::{
+    state::{
+        accounts::{Avatar, AvatarClass, Trait, UpdateState, VariantOptionCounter},
+        data::UpdateTarget,
+        errors::ErrorCode,
+    },
+    utils::validate_variant_option_availability,
 };
 
 #[derive(Accounts)]
@@ -32,6 +35,12 @@ pub struct UpdateVariant<'info> {
         seeds = [Trait::PREFIX.as_bytes(), trait_account.avatar_class.key().as_ref(), trait_account.trait_mint.key().as_ref()], bump)]
     pub trait_account: Option<Account<'info, Trait>>,
 
+    // required if the selected variant option has a max_selections supply cap
+    #[account(mut,
+        has_one = avatar_class,
+        seeds = [VariantOptionCounter::PREFIX.as_bytes(), avatar_class.key().as_ref(), update_state.target.hash().as_ref()], bump)]
+    pub variant_option_counter: Option<Account<'info, VariantOptionCounter>>,
+
     #[account(mut)]
     pub avatar_authority: SystemAccount<'info>,
 
@@ -54,17 +63,23 @@ pub fn handler(ctx: Context<UpdateVariant>) -> Result<()> {
             ..
         } => {
             // get variant metadata from avatar class
-            let variant_metadata = ctx.accounts.avatar_class.find_variant(variant_id);
+            let variant_metadata = ctx.accounts.avatar_class.find_variant(variant_id)?;
 
             // check variant is enabled
             let enabled = variant_metadata.is_enabled();
             require!(enabled, ErrorCode::VariantDisabled);
 
             // check avatar meets requirements to select the variant
-            let new_variant_option = variant_metadata.find_option(option_id);
+            let new_variant_option = variant_metadata.find_option(option_id)?;
             let eligible = new_variant_option.is_eligible(&ctx.accounts.avatar.get_traits());
             require!(eligible, ErrorCode::InvalidVariant);
 
+            // check the variant option can still be selected
+            validate_variant_option_availability(
+                &new_variant_option,
+                ctx.accounts.variant_option_counter.as_deref(),
+            )?;
+
             // update variant selection on the avatar
             let avatar_account_info = ctx.accounts.avatar.to_account_info();
             ctx.accounts.avatar.update_variant_selection(
@@ -72,40 +87,47 @@ pub fn handler(ctx: Context<UpdateVariant>) -> Result<()> {
                 &avatar_account_info,
                 ctx.accounts.payer.clone(),
                 ctx.accounts.system_program.clone(),
-            );
+            )?;
         }
         UpdateTarget::TraitVariant {
             variant_id,
             option_id,
             ..
         } => {
-            let trait_account = ctx.accounts.trait_account.clone().unwrap();
+            let trait_account = ctx
+                .accounts
+                .trait_account
+                .clone()
+                .ok_or(ErrorCode::InvalidTrait)?;
 
             // get the variant metadata for the variant_id we want to change
-            let variant_metadata = trait_account.find_variant(variant_id);
+            let variant_metadata = trait_account.find_variant(variant_id)?;
 
             // check variant is enabled
             let enabled = variant_metadata.is_enabled();
             require!(enabled, ErrorCode::VariantDisabled);
 
             // check avatar meets requirements to select the variant option
-            let new_variant_option = variant_metadata.find_option(option_id);
+            let new_variant_option = variant_metadata.find_option(option_id)?;
             let eligible = new_variant_option.is_eligible(&ctx.accounts.avatar.get_traits());
             require!(eligible, ErrorCode::InvalidVariant);
 
+            // check the variant option can still be selected
+            validate_variant_option_availability(
+                &new_variant_option,
+                ctx.accounts.variant_option_counter.as_deref(),
+            )?;
+
             // update trait data with new variant selection
             let avatar_account_info = &ctx.accounts.avatar.to_account_info();
-            let trait_data = &mut ctx
-                .accounts
-                .avatar
-                .find_trait_mut(&ctx.accounts.trait_account.as_ref().unwrap().key());
+            let trait_data = ctx.accounts.avatar.find_trait_mut(&trait_account.key())?;
 
             trait_data.update_variant_selection(
                 new_variant_option.clone(),
                 avatar_account_info,
                 ctx.accounts.payer.clone(),
                 ctx.accounts.system_program.clone(),
-            );
+            )?;
         }
         _ => return Err(ErrorCode::InvalidUpdateTarget.into()),
     };
@@ -116,6 +138,11 @@ pub fn handler(ctx: Context<UpdateVariant>) -> Result<()> {
         ErrorCode::PaymentNotPaid
     );
 
+    // record the selection against the variant option's supply
+    if let Some(variant_option_counter) = &mut ctx.accounts.variant_option_counter {
+        variant_option_counter.selection_count += 1;
+    }
+
     ctx.accounts
         .update_state
         .close(ctx.accounts.avatar_authority.to_account_info())
@@ -120,13 +120,20 @@ pub mod raindrops_avatar {
         instructions::update_variant::handler(ctx)
     }
 
-    pub fn transfer_payment(
-        ctx: Context<TransferPayment>,
+    pub fn transfer_payment<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, TransferPayment<'info>>,
         args: TransferPaymentArgs,
     ) -> Result<()> {
         instructions::transfer_payment::handler(ctx, args)
     }
 
+    pub fn transfer_payment_native<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, TransferPaymentNative<'info>>,
+        args: TransferPaymentNativeArgs,
+    ) -> Result<()> {
+        instructions::transfer_payment_native::handler(ctx, args)
+    }
+
     pub fn burn_payment(ctx: Context<BurnPayment>, args: BurnPaymentArgs) -> Result<()> {
         instructions::burn_payment::handler(ctx, args)
     }
@@ -172,6 +179,66 @@ pub mod raindrops_avatar {
         instructions::verify_payment_mint_test::handler(ctx, args)
     }
 
+    pub fn create_trait_rental(
+        ctx: Context<CreateTraitRental>,
+        args: CreateTraitRentalArgs,
+    ) -> Result<()> {
+        instructions::create_trait_rental::handler(ctx, args)
+    }
+
+    pub fn cancel_trait_rental(ctx: Context<CancelTraitRental>) -> Result<()> {
+        instructions::cancel_trait_rental::handler(ctx)
+    }
+
+    pub fn rent_trait(ctx: Context<RentTrait>) -> Result<()> {
+        instructions::rent_trait::handler(ctx)
+    }
+
+    pub fn reclaim_trait_rental(ctx: Context<ReclaimTraitRental>) -> Result<()> {
+        instructions::reclaim_trait_rental::handler(ctx)
+    }
+
+    pub fn update_stat_template(
+        ctx: Context<UpdateStatTemplate>,
+        args: UpdateStatTemplateArgs,
+    ) -> Result<()> {
+        instructions::update_stat_template::handler(ctx, args)
+    }
+
+    pub fn remove_stat_template(
+        ctx: Context<RemoveStatTemplate>,
+        args: RemoveStatTemplateArgs,
+    ) -> Result<()> {
+        instructions::remove_stat_template::handler(ctx, args)
+    }
+
+    pub fn refresh_avatar_stats(ctx: Context<RefreshAvatarStats>) -> Result<()> {
+        instructions::refresh_avatar_stats::handler(ctx)
+    }
+
+    pub fn avatar_artifact_join_namespace(ctx: Context<AvatarArtifactJoinNamespace>) -> Result<()> {
+        instructions::avatar_artifact_join_namespace::handler(ctx)
+    }
+
+    pub fn avatar_artifact_leave_namespace(
+        ctx: Context<AvatarArtifactLeaveNamespace>,
+    ) -> Result<()> {
+        instructions::avatar_artifact_leave_namespace::handler(ctx)
+    }
+
+    pub fn avatar_artifact_cache_namespace(
+        ctx: Context<AvatarArtifactCacheNamespace>,
+        page: u64,
+    ) -> Result<()> {
+        instructions::avatar_artifact_cache_namespace::handler(ctx, page)
+    }
+
+    pub fn avatar_artifact_uncache_namespace(
+        ctx: Context<AvatarArtifactUncacheNamespace>,
+    ) -> Result<()> {
+        instructions::avatar_artifact_uncache_namespace::handler(ctx)
+    }
+
     pub fn migrate_avatar_class_account(ctx: Context<MigrateAvatarClassAccount>) -> Result<()> {
         migrate_avatar_class_account::handler(ctx)
     }
@@ -179,4 +246,12 @@ pub mod raindrops_avatar {
     pub fn migrate_avatar_account(ctx: Context<MigrateAvatarAccount>) -> Result<()> {
         migrate_avatar_account::handler(ctx)
     }
+
+    pub fn migrate_trait_account(ctx: Context<MigrateTraitAccount>) -> Result<()> {
+        migrate_trait_account::handler(ctx)
+    }
+
+    pub fn migrate_update_state(ctx: Context<MigrateUpdateState>) -> Result<()> {
+        migrate_update_state::handler(ctx)
+    }
 }
@@ -1,13 +1,19 @@
 use std::collections::HashSet;
 
 use anchor_lang::prelude::*;
+use raindrops_common::{
+    utils::{append_namespace, remove_namespace},
+    NamespaceAndIndex,
+};
 
 use crate::utils::reallocate;
 
 use super::data::{
-    AttributeMetadata, PaymentAction, PaymentAssetClass, PaymentDetails, TraitData, TraitGate,
-    TraitStatus, UpdateTarget, UpdateTargetSelection, VariantMetadata, VariantOption,
+    AttributeMetadata, PaymentAction, PaymentAssetClass, PaymentDetails, Stat, StatModifier,
+    StatTemplate, TraitData, TraitGate, TraitStatus, UpdatePaymentAlternatives, UpdateTarget,
+    UpdateTargetSelection, VariantMetadata, VariantOption,
 };
+use super::errors::ErrorCode;
 
 // seeds = [b'avatar_class', mint.key().as_ref()]
 #[account]
@@ -19,6 +25,8 @@ pub struct AvatarClass {
     pub attribute_metadata: Vec<AttributeMetadata>,
     pub variant_metadata: Vec<VariantMetadata>,
     pub global_rendering_config_uri: String,
+    pub stat_templates: Vec<StatTemplate>,
+    pub namespaces: Vec<NamespaceAndIndex>,
 }
 
 impl AvatarClass {
@@ -27,6 +35,7 @@ impl AvatarClass {
         attribute_metadata: Vec<AttributeMetadata>,
         variant_metadata: Vec<VariantMetadata>,
         global_rendering_config_uri_bytes: usize,
+        stat_templates: &[StatTemplate],
     ) -> usize {
         8 + // anchor
         32 + // avatar_class mint
@@ -34,7 +43,9 @@ impl AvatarClass {
         8 + // payment index
         AvatarClass::attribute_metadata_space(&attribute_metadata) + // attribute metadata
         AvatarClass::variant_metadata_space(&variant_metadata) + // variant metadata
-        (4 + global_rendering_config_uri_bytes) // global rendering config uri
+        (4 + global_rendering_config_uri_bytes) + // global rendering config uri
+        AvatarClass::stat_templates_space(stat_templates) + // stat templates
+        4 // empty namespaces vector
     }
 
     pub fn current_space(&self) -> usize {
@@ -44,7 +55,127 @@ impl AvatarClass {
         8 + // payment index
         AvatarClass::attribute_metadata_space(&self.attribute_metadata) + // attribute metadata
         AvatarClass::variant_metadata_space(&self.variant_metadata) + // variant metadata
-        (4 + self.global_rendering_config_uri.len()) // global rendering config uri
+        (4 + self.global_rendering_config_uri.len()) + // global rendering config uri
+        AvatarClass::stat_templates_space(&self.stat_templates) + // stat templates
+        namespaces_space(&self.namespaces) // namespaces
+    }
+
+    pub fn stat_templates_space(stat_templates: &[StatTemplate]) -> usize {
+        let mut total_bytes: usize = 4; // inital 4 bytes required for the Vec space
+        for stat_template in stat_templates {
+            total_bytes += stat_template.space();
+        }
+
+        total_bytes
+    }
+
+    // return true if every template has a unique id and a base value inside its bounds
+    pub fn is_valid_stat_templates(stat_templates: &[StatTemplate]) -> bool {
+        let mut ids: HashSet<u16> = HashSet::new();
+        stat_templates
+            .iter()
+            .all(|stat_template| stat_template.is_valid() && ids.insert(stat_template.id))
+    }
+
+    // return true if every modifier targets a stat defined by the class
+    pub fn is_valid_stat_modifiers(&self, stat_modifiers: &[StatModifier]) -> bool {
+        stat_modifiers.iter().all(|stat_modifier| {
+            self.stat_templates
+                .iter()
+                .any(|stat_template| stat_template.id == stat_modifier.stat_id)
+        })
+    }
+
+    pub fn update_stat_template<'info>(
+        &mut self,
+        new_stat_template: StatTemplate,
+        avatar_class: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) {
+        let old_space = self.current_space();
+
+        self.replace_stat_template(new_stat_template);
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar_class, payer, system_program).unwrap();
+    }
+
+    pub fn join_namespace<'info>(
+        &mut self,
+        namespace: Pubkey,
+        avatar_class: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        append_namespace(
+            &mut self.namespaces,
+            namespace,
+            ErrorCode::FailedToJoinNamespace,
+        )?;
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar_class, payer, system_program)
+    }
+
+    pub fn leave_namespace<'info>(
+        &mut self,
+        namespace: &Pubkey,
+        avatar_class: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        remove_namespace(
+            &mut self.namespaces,
+            namespace,
+            ErrorCode::FailedToLeaveNamespace,
+        )?;
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar_class, payer, system_program)
+    }
+
+    pub fn remove_stat_template<'info>(
+        &mut self,
+        stat_id: u16,
+        avatar_class: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        let stat_templates_len = self.stat_templates.len();
+        self.stat_templates.retain(|st| st.id != stat_id);
+        require!(
+            self.stat_templates.len() < stat_templates_len,
+            ErrorCode::InvalidStatId
+        );
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar_class, payer, system_program)
+    }
+
+    fn replace_stat_template(&mut self, new_stat_template: StatTemplate) {
+        self.stat_templates
+            .retain(|st| st.id != new_stat_template.id);
+
+        self.stat_templates.push(new_stat_template);
     }
 
     pub fn variant_metadata_space(variant_metadata: &Vec<VariantMetadata>) -> usize {
@@ -80,12 +211,12 @@ impl AvatarClass {
     }
 
     // return the matching variant
-    pub fn find_variant(&self, variant_id: &str) -> VariantMetadata {
+    pub fn find_variant(&self, variant_id: &str) -> Result<VariantMetadata> {
         self.variant_metadata
             .iter()
             .find(|vm| vm.id == variant_id)
-            .unwrap()
-            .clone()
+            .cloned()
+            .ok_or_else(|| ErrorCode::InvalidVariant.into())
     }
 
     pub fn update_attribute_metadata<'info>(
@@ -147,17 +278,21 @@ pub struct Avatar {
     pub image_uri: String,
     pub traits: Vec<TraitData>,
     pub variants: Vec<VariantOption>,
+    pub stats: Vec<Stat>,
+    pub namespaces: Vec<NamespaceAndIndex>,
 }
 
 impl Avatar {
     pub const PREFIX: &'static str = "avatar";
-    pub fn initial_space(variants: Vec<VariantOption>) -> usize {
+    pub fn initial_space(variants: Vec<VariantOption>, stat_count: usize) -> usize {
         8 + // anchor
         32 + // avatar class
         32 + // avatar mint
         4 + // empty image uri
         4 + // empty traits vector
-        variants_space(&variants)
+        variants_space(&variants) + // variants
+        4 + (stat_count * Stat::SPACE) + // stats
+        4 // empty namespaces vector
     }
 
     pub fn current_space(&self) -> usize {
@@ -176,38 +311,112 @@ impl Avatar {
         // current space of the variants
         total_bytes += variants_space(&self.variants);
 
+        // current space of the stats
+        total_bytes += 4 + (self.stats.len() * Stat::SPACE);
+
+        // current space of the namespaces
+        total_bytes += namespaces_space(&self.namespaces);
+
         total_bytes
     }
 
-    pub fn find_trait_mut(&mut self, trait_address: &Pubkey) -> &mut TraitData {
-        let trait_data = self
-            .traits
+    // aggregate the stat modifiers of every equipped trait into the class stat templates
+    pub fn compute_stats(&self, stat_templates: &[StatTemplate]) -> Result<Vec<Stat>> {
+        stat_templates
+            .iter()
+            .map(|stat_template| {
+                Ok(Stat {
+                    id: stat_template.id,
+                    value: stat_template.apply_modifiers(
+                        self.traits
+                            .iter()
+                            .flat_map(|trait_data| trait_data.stat_modifiers.iter()),
+                    )?,
+                })
+            })
+            .collect()
+    }
+
+    // recompute the cached stats, called after every change to the equipped traits
+    pub fn update_stats<'info>(
+        &mut self,
+        stat_templates: &[StatTemplate],
+        avatar: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        self.stats = self.compute_stats(stat_templates)?;
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar, payer, system_program)
+    }
+
+    pub fn join_namespace<'info>(
+        &mut self,
+        namespace: Pubkey,
+        avatar: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        append_namespace(
+            &mut self.namespaces,
+            namespace,
+            ErrorCode::FailedToJoinNamespace,
+        )?;
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar, payer, system_program)
+    }
+
+    pub fn leave_namespace<'info>(
+        &mut self,
+        namespace: &Pubkey,
+        avatar: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        remove_namespace(
+            &mut self.namespaces,
+            namespace,
+            ErrorCode::FailedToLeaveNamespace,
+        )?;
+
+        let new_space = self.current_space();
+
+        let diff: i64 = new_space as i64 - old_space as i64;
+
+        reallocate(diff, avatar, payer, system_program)
+    }
+
+    pub fn find_trait_mut(&mut self, trait_address: &Pubkey) -> Result<&mut TraitData> {
+        self.traits
             .iter_mut()
             .find(|trait_data| trait_data.trait_address.eq(trait_address))
-            .unwrap();
-        trait_data
+            .ok_or_else(|| ErrorCode::TraitNotEquipped.into())
     }
 
     pub fn add_trait<'info>(
         &mut self,
-        trait_address: Pubkey,
-        trait_id: u16,
-        attribute_ids: Vec<u16>,
-        variant_metadata: &[VariantMetadata],
-        trait_gate: Option<TraitGate>,
+        trait_data: TraitData,
         avatar: &AccountInfo<'info>,
         payer: Signer<'info>,
         system_program: Program<'info, System>,
     ) {
         let old_space = self.current_space();
 
-        self.add_trait_data(
-            trait_address,
-            trait_id,
-            attribute_ids,
-            variant_metadata,
-            trait_gate,
-        );
+        self.add_trait_data(trait_data);
 
         let new_space = self.current_space();
 
@@ -239,21 +448,15 @@ impl Avatar {
             .retain(|trait_data| trait_data.trait_address.ne(&trait_address))
     }
 
-    pub fn add_trait_data(
-        &mut self,
-        trait_address: Pubkey,
-        trait_id: u16,
-        attribute_ids: Vec<u16>,
-        variant_metadata: &[VariantMetadata],
-        trait_gate: Option<TraitGate>,
-    ) {
-        self.traits.push(TraitData::new(
-            attribute_ids,
-            trait_id,
-            trait_address,
-            variant_metadata,
-            trait_gate,
-        ))
+    pub fn add_trait_data(&mut self, trait_data: TraitData) {
+        self.traits.push(trait_data)
+    }
+
+    // return true if the equipped trait is on loan from a trait rental
+    pub fn is_trait_rented(&self, trait_address: &Pubkey) -> bool {
+        self.traits
+            .iter()
+            .any(|trait_data| trait_data.trait_address.eq(trait_address) && trait_data.is_rented())
     }
 
     pub fn reallocate<'info>(
@@ -350,7 +553,7 @@ impl Avatar {
         avatar: &AccountInfo<'info>,
         payer: Signer<'info>,
         system_program: Program<'info, System>,
-    ) {
+    ) -> Result<()> {
         let old_space = self.current_space();
 
         self.update_variant_selection_data(variant_selection);
@@ -359,7 +562,7 @@ impl Avatar {
 
         let diff: i64 = new_space as i64 - old_space as i64;
 
-        reallocate(diff, avatar, payer, system_program).unwrap();
+        reallocate(diff, avatar, payer, system_program)
     }
 
     fn update_variant_selection_data(&mut self, variant_selection: VariantOption) {
@@ -381,6 +584,11 @@ fn variants_space(variants: &Vec<VariantOption>) -> usize {
     total_bytes
 }
 
+// every entry is sized for a cached index so caching never has to reallocate
+fn namespaces_space(namespaces: &[NamespaceAndIndex]) -> usize {
+    4 + (namespaces.len() * NamespaceAndIndex::SPACE)
+}
+
 // seeds = [b'trait', avatar_class.key().as_ref(), trait_mint.key().as_ref()]
 #[account]
 pub struct Trait {
@@ -394,6 +602,7 @@ pub struct Trait {
     pub equip_payment_details: Option<PaymentDetails>,
     pub remove_payment_details: Option<PaymentDetails>,
     pub trait_gate: Option<TraitGate>,
+    pub stat_modifiers: Vec<StatModifier>,
 }
 
 impl Trait {
@@ -408,18 +617,22 @@ impl Trait {
         (4 + self.component_uri.len()) + // component uri
         TraitStatus::SPACE + // trait status
         Self::variant_metadata_space(&self.variant_metadata) + // variant metadata
-        (1 + PaymentDetails::SPACE) + // optional equip payment details
-        (1 + PaymentDetails::SPACE) + // optional remove payment method
+        PaymentDetails::option_space(&self.equip_payment_details) + // optional equip payment details
+        PaymentDetails::option_space(&self.remove_payment_details) + // optional remove payment details
         match &self.trait_gate {
             Some(trait_gate) => trait_gate.space(),
             None => 1,
-        }
+        } +
+        (4 + (self.stat_modifiers.len() * StatModifier::SPACE)) // stat modifiers
     }
 
     pub fn space(
         component_uri_bytes: usize,
         attribute_count: usize,
         variant_metadata: Vec<VariantMetadata>,
+        equip_payment_details: &Option<PaymentDetails>,
+        remove_payment_details: &Option<PaymentDetails>,
+        stat_modifier_count: usize,
     ) -> usize {
         8 + // anchor
         2 + // id
@@ -429,18 +642,19 @@ impl Trait {
         (4 + component_uri_bytes) + // component_uri
         TraitStatus::SPACE + // trait status
         Self::variant_metadata_space(&variant_metadata) + // variant metadata
-        (1 + PaymentDetails::SPACE) + // optional equip payment details
-        (1 + PaymentDetails::SPACE) + // optional remove payment method
-        (1 + TraitGate::INIT_SPACE) // optional trait gate space
+        PaymentDetails::option_space(equip_payment_details) + // optional equip payment details
+        PaymentDetails::option_space(remove_payment_details) + // optional remove payment details
+        (1 + TraitGate::INIT_SPACE) + // optional trait gate space
+        (4 + (stat_modifier_count * StatModifier::SPACE)) // stat modifiers
     }
 
     // return the matching variant
-    pub fn find_variant(&self, variant_id: &str) -> VariantMetadata {
+    pub fn find_variant(&self, variant_id: &str) -> Result<VariantMetadata> {
         self.variant_metadata
             .iter()
             .find(|vm| vm.id == variant_id)
-            .unwrap()
-            .clone()
+            .cloned()
+            .ok_or_else(|| ErrorCode::InvalidVariant.into())
     }
 
     pub fn update_variant_metadata<'info>(
@@ -528,29 +742,114 @@ pub struct PaymentMethod {
 
 impl PaymentMethod {
     pub const PREFIX: &'static str = "payment_method";
-    pub const SPACE: usize = 8 + // anchor
-    4 + // uri, empty for now
-    8 + // index
-    32 + // avatar_class
-    PaymentAssetClass::SPACE + // asset class
-    PaymentAction::SPACE; // action
+
+    pub fn space(action: &PaymentAction) -> usize {
+        8 + // anchor
+        4 + // uri, empty for now
+        8 + // index
+        32 + // avatar_class
+        PaymentAssetClass::SPACE + // asset class
+        action.space() // action
+    }
 }
 
 // seeds = ['update_state', avatar, target_hash]
 #[account]
 pub struct UpdateState {
+    // update states begun before the version start with the initialized flag instead,
+    // they are moved over by migrate_update_state
+    pub version: u8,
     pub initialized: bool,
     pub avatar: Pubkey,
     pub target: UpdateTarget,
+    pub payment_alternatives: UpdatePaymentAlternatives,
 }
 
 impl UpdateState {
     pub const PREFIX: &'static str = "update_state";
+    // first version storing payment alternatives
+    pub const VERSION: u8 = 2;
+    // what the initialized flag of an update state begun before the version reads as
+    pub const LEGACY_VERSION: u8 = 1;
+
     pub fn space(update_target: &UpdateTargetSelection) -> usize {
         8 + // anchor
+        1 + // version
         1 + // initialized
         32 + // avatar
-        update_target.space() // update target
+        update_target.space() + // update target
+        UpdatePaymentAlternatives::SPACE // payment alternatives
+    }
+
+    // begun update states have to be migrated before they can be continued
+    pub fn assert_migrated(&self) -> Result<()> {
+        require!(
+            self.version != Self::LEGACY_VERSION,
+            ErrorCode::UpdateStateNotMigrated
+        );
+
+        Ok(())
+    }
+
+    // update the payment state of the target, allowing its payment alternatives
+    pub fn update_payment_state(&mut self, payment_method: &Pubkey, amount: u64) -> Result<()> {
+        self.target
+            .update_payment_state(&mut self.payment_alternatives, payment_method, amount)
+    }
+}
+
+// seeds = ['variant_option_counter', avatar_class, target_hash]
+#[account]
+pub struct VariantOptionCounter {
+    pub avatar_class: Pubkey,
+    pub selection_count: u64,
+}
+
+impl VariantOptionCounter {
+    pub const PREFIX: &'static str = "variant_option_counter";
+    pub const SPACE: usize = 8 + // anchor
+    32 + // avatar_class
+    8; // selection_count
+}
+
+// seeds = ['trait_rental', trait_account, lender]
+#[account]
+pub struct TraitRental {
+    pub avatar_class: Pubkey,
+    pub trait_account: Pubkey,
+    pub lender: Pubkey,
+    pub duration: i64,
+    pub fee: u64,
+    pub avatar: Option<Pubkey>,
+    pub expiry: Option<i64>,
+}
+
+impl TraitRental {
+    pub const PREFIX: &'static str = "trait_rental";
+    pub const SPACE: usize = 8 + // anchor
+    32 + // avatar_class
+    32 + // trait_account
+    32 + // lender
+    8 + // duration
+    8 + // fee in lamports
+    (1 + 32) + // optional avatar renting the trait
+    (1 + 8); // optional expiry
+
+    pub fn is_rented(&self) -> bool {
+        self.avatar.is_some()
+    }
+
+    // return true if the rental period has ended
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        match self.expiry {
+            Some(expiry) => unix_timestamp >= expiry,
+            None => false,
+        }
+    }
+
+    pub fn rent(&mut self, avatar: Pubkey, unix_timestamp: i64) {
+        self.avatar = Some(avatar);
+        self.expiry = Some(unix_timestamp.saturating_add(self.duration));
     }
 }
 
@@ -610,7 +909,7 @@ impl TraitConflicts {
         conflicts_account: &AccountInfo<'info>,
         payer: Signer<'info>,
         system_program: Program<'info, System>,
-    ) {
+    ) -> Result<()> {
         let old_space = self.current_space();
 
         // add attribute ids
@@ -627,7 +926,7 @@ impl TraitConflicts {
 
         let diff: i64 = new_space as i64 - old_space as i64;
 
-        reallocate(diff, conflicts_account, payer, system_program).unwrap();
+        reallocate(diff, conflicts_account, payer, system_program)
     }
 
     fn add_trait_conflict(&mut self, new_conflict_id: u16) {
@@ -696,7 +995,12 @@ impl anchor_lang::Id for NoopProgram {
 mod tests {
     use std::{assert_eq, vec};
 
-    use crate::state::data::{AttributeStatus, AttributeType, VariantStatus};
+    use raindrops_common::utils::set_namespace_index;
+
+    use crate::instructions::OldUpdateState;
+    use crate::state::data::{
+        AttributeStatus, AttributeType, PaymentAlternative, PaymentState, VariantStatus,
+    };
 
     use super::*;
 
@@ -742,6 +1046,8 @@ mod tests {
             attribute_metadata,
             variant_metadata: vec![],
             global_rendering_config_uri: "https://foo.com/bar.json".to_string(),
+            stat_templates: vec![],
+            namespaces: vec![],
         };
 
         let head_mutable = avatar_class.is_trait_mutable(vec![1]);
@@ -771,9 +1077,14 @@ mod tests {
                 options: vec![],
             }],
             global_rendering_config_uri: "https://foo.com/bar.json".to_string(),
+            stat_templates: vec![],
+            namespaces: vec![],
         };
-        let variant_metadata = avatar_class.find_variant("lqowgh78");
+        let variant_metadata = avatar_class.find_variant("lqowgh78").unwrap();
         assert_eq!(variant_metadata.id, "lqowgh78".to_string());
+
+        let err = avatar_class.find_variant("lqowgh79").unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariant.into());
     }
 
     #[test]
@@ -787,6 +1098,8 @@ mod tests {
             trait_address: trait1,
             variant_selection: vec![],
             trait_gate: None,
+            rental: None,
+            stat_modifiers: vec![],
         });
 
         let trait2 = Pubkey::new_unique();
@@ -796,6 +1109,8 @@ mod tests {
             trait_address: trait2,
             variant_selection: vec![],
             trait_gate: None,
+            rental: None,
+            stat_modifiers: vec![],
         });
 
         let mut avatar = Avatar {
@@ -804,15 +1119,221 @@ mod tests {
             image_uri: "".to_string(),
             traits,
             variants: vec![],
+            stats: vec![],
+            namespaces: vec![],
         };
+        assert!(avatar.find_trait_mut(&trait1).is_ok());
         avatar.remove_trait_data(trait1);
         assert_eq!(avatar.traits.len(), 1);
+        let err = avatar.find_trait_mut(&trait1).unwrap_err();
+        assert_eq!(err, ErrorCode::TraitNotEquipped.into());
         assert!(avatar.traits.first().unwrap().trait_address.eq(&trait2));
 
         avatar.remove_trait_data(trait2);
         assert_eq!(avatar.traits.len(), 0);
     }
 
+    #[test]
+    fn test_trait_rental_expiry() {
+        let mut trait_rental = TraitRental {
+            avatar_class: Pubkey::new_unique(),
+            trait_account: Pubkey::new_unique(),
+            lender: Pubkey::new_unique(),
+            duration: 3600,
+            fee: 0,
+            avatar: None,
+            expiry: None,
+        };
+        assert!(!trait_rental.is_rented());
+        assert!(!trait_rental.is_expired(i64::MAX));
+
+        let avatar = Pubkey::new_unique();
+        trait_rental.rent(avatar, 1_000);
+        assert!(trait_rental.is_rented());
+        assert_eq!(trait_rental.avatar, Some(avatar));
+        assert!(!trait_rental.is_expired(4_599));
+        assert!(trait_rental.is_expired(4_600));
+        assert_eq!(
+            trait_rental.try_to_vec().unwrap().len() + 8,
+            TraitRental::SPACE
+        );
+    }
+
+    #[test]
+    fn test_is_trait_rented() {
+        let rented_trait = Pubkey::new_unique();
+        let owned_trait = Pubkey::new_unique();
+        let mut avatar = Avatar {
+            avatar_class: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            image_uri: "".to_string(),
+            traits: vec![],
+            variants: vec![],
+            stats: vec![],
+            namespaces: vec![],
+        };
+        avatar.add_trait_data(
+            TraitData::new(vec![], 0, owned_trait, &[], None, None, vec![]).unwrap(),
+        );
+        avatar.add_trait_data(
+            TraitData::new(
+                vec![],
+                1,
+                rented_trait,
+                &[],
+                None,
+                Some(Pubkey::new_unique()),
+                vec![],
+            )
+            .unwrap(),
+        );
+
+        assert!(avatar.is_trait_rented(&rented_trait));
+        assert!(!avatar.is_trait_rented(&owned_trait));
+        assert!(!avatar.is_trait_rented(&Pubkey::new_unique()));
+        for trait_data in &avatar.traits {
+            assert_eq!(
+                trait_data.try_to_vec().unwrap().len(),
+                trait_data.current_space()
+            );
+        }
+    }
+
+    #[test]
+    fn test_namespaces_space() {
+        let mut avatar = Avatar {
+            avatar_class: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            image_uri: "".to_string(),
+            traits: vec![],
+            variants: vec![],
+            stats: vec![],
+            namespaces: vec![],
+        };
+        let empty_space = avatar.current_space();
+
+        let namespace = Pubkey::new_unique();
+        append_namespace(
+            &mut avatar.namespaces,
+            namespace,
+            ErrorCode::FailedToJoinNamespace,
+        )
+        .unwrap();
+        set_namespace_index(
+            &mut avatar.namespaces,
+            &namespace,
+            Some(u64::MAX),
+            ErrorCode::FailedToCache,
+        )
+        .unwrap();
+
+        // a cached entry fits the space reserved on join
+        assert_eq!(
+            avatar.current_space() - empty_space,
+            NamespaceAndIndex::SPACE
+        );
+        assert!(avatar.try_to_vec().unwrap().len() + 8 <= avatar.current_space());
+    }
+
+    #[test]
+    fn test_is_valid_stat_templates() {
+        let strength = StatTemplate {
+            id: 0,
+            name: "strength".to_string(),
+            base_value: 10,
+            min_value: 0,
+            max_value: 100,
+        };
+        let speed = StatTemplate {
+            id: 1,
+            name: "speed".to_string(),
+            base_value: 5,
+            min_value: 0,
+            max_value: 10,
+        };
+        assert!(AvatarClass::is_valid_stat_templates(&[]));
+        assert!(AvatarClass::is_valid_stat_templates(&[
+            strength.clone(),
+            speed.clone()
+        ]));
+
+        // duplicate ids
+        assert!(!AvatarClass::is_valid_stat_templates(&[
+            strength.clone(),
+            strength.clone()
+        ]));
+
+        // base value outside of the bounds
+        let mut invalid = speed;
+        invalid.base_value = 11;
+        assert!(!AvatarClass::is_valid_stat_templates(&[strength, invalid]));
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let stat_templates = vec![
+            StatTemplate {
+                id: 0,
+                name: "strength".to_string(),
+                base_value: 10,
+                min_value: 0,
+                max_value: 100,
+            },
+            StatTemplate {
+                id: 1,
+                name: "speed".to_string(),
+                base_value: 5,
+                min_value: 0,
+                max_value: 10,
+            },
+        ];
+        let mut avatar = Avatar {
+            avatar_class: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            image_uri: "".to_string(),
+            traits: vec![],
+            variants: vec![],
+            stats: vec![],
+            namespaces: vec![],
+        };
+
+        // no traits equipped, every stat is at its base value
+        avatar.stats = avatar.compute_stats(&stat_templates).unwrap();
+        assert_eq!(
+            avatar.stats,
+            vec![Stat { id: 0, value: 10 }, Stat { id: 1, value: 5 }]
+        );
+
+        avatar.add_trait_data(
+            TraitData::new(
+                vec![],
+                0,
+                Pubkey::new_unique(),
+                &[],
+                None,
+                None,
+                vec![
+                    StatModifier {
+                        stat_id: 0,
+                        add: 5,
+                        multiply: 20_000,
+                    },
+                    StatModifier {
+                        stat_id: 1,
+                        add: 20,
+                        multiply: StatModifier::MULTIPLIER_DENOMINATOR,
+                    },
+                ],
+            )
+            .unwrap(),
+        );
+        avatar.stats = avatar.compute_stats(&stat_templates).unwrap();
+        assert_eq!(
+            avatar.stats,
+            vec![Stat { id: 0, value: 30 }, Stat { id: 1, value: 10 }]
+        );
+    }
+
     #[test]
     fn test_has_attribute_conflicts() {
         let trait_conflicts = TraitConflicts {
@@ -884,4 +1405,78 @@ mod tests {
         let has_conflicts = trait_conflicts.has_conflicts(&[], &[]);
         assert!(!has_conflicts);
     }
+
+    #[test]
+    fn test_update_state_migrated_from_old_bytes() {
+        let avatar = Pubkey::new_unique();
+        let target = UpdateTarget::EquipTrait {
+            trait_account: Pubkey::new_unique(),
+            payment_state: Some(PaymentState {
+                payment_method: Pubkey::new_unique(),
+                current_amount: 0,
+                required_amount: 10,
+            }),
+        };
+
+        // update states begun before the version byte start with the initialized flag
+        let old_bytes = OldUpdateState {
+            initialized: true,
+            avatar,
+            target: target.clone(),
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(old_bytes[0], UpdateState::LEGACY_VERSION);
+
+        let legacy = UpdateState::deserialize(&mut old_bytes.as_slice());
+        assert!(legacy.is_err() || legacy.unwrap().assert_migrated().is_err());
+
+        let old_update_state = OldUpdateState::deserialize(&mut old_bytes.as_slice()).unwrap();
+        let update_state: UpdateState = old_update_state.into();
+        assert_eq!(update_state.version, UpdateState::VERSION);
+        assert!(update_state.initialized);
+        assert_eq!(update_state.avatar, avatar);
+        assert_eq!(update_state.target, target);
+        assert_eq!(
+            update_state.payment_alternatives,
+            UpdatePaymentAlternatives::default()
+        );
+    }
+
+    #[test]
+    fn test_update_state_new_bytes() {
+        let payment_alternatives = UpdatePaymentAlternatives {
+            payment: vec![PaymentAlternative {
+                payment_method: Pubkey::new_unique(),
+                amount: 5,
+            }],
+            remove_payment: vec![],
+        };
+        let update_state = UpdateState {
+            version: UpdateState::VERSION,
+            initialized: true,
+            avatar: Pubkey::new_unique(),
+            target: UpdateTarget::RemoveTrait {
+                trait_account: Pubkey::new_unique(),
+                payment_state: None,
+            },
+            payment_alternatives: payment_alternatives.clone(),
+        };
+
+        let bytes = update_state.try_to_vec().unwrap();
+        assert_eq!(bytes[0], UpdateState::VERSION);
+
+        let read = UpdateState::deserialize(&mut bytes.as_slice()).unwrap();
+        assert!(read.assert_migrated().is_ok());
+        assert_eq!(read.avatar, update_state.avatar);
+        assert_eq!(read.target, update_state.target);
+        assert_eq!(read.payment_alternatives, payment_alternatives);
+
+        // a freshly allocated update state reads as not yet begun
+        let zeroed = vec![0u8; bytes.len()];
+        let fresh = UpdateState::deserialize(&mut zeroed.as_slice()).unwrap();
+        assert_eq!(fresh.version, 0);
+        assert!(!fresh.initialized);
+        assert!(fresh.assert_migrated().is_ok());
+    }
 }
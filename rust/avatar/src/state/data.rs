@@ -22,6 +22,86 @@ impl AttributeMetadata {
     }
 }
 
+// defines a numeric stat for every avatar in the class
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StatTemplate {
+    pub id: u16,
+    pub name: String,
+    pub base_value: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+}
+
+impl StatTemplate {
+    pub fn space(&self) -> usize {
+        2 + // id
+        (4 + self.name.len()) + // name
+        8 + // base value
+        8 + // min value
+        8 // max value
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min_value <= self.base_value && self.base_value <= self.max_value
+    }
+
+    // apply every matching modifier to the base value, additions are summed first and then
+    // each multiplier is applied in order, the result is clamped to the min and max values
+    pub fn apply_modifiers<'a>(
+        &self,
+        modifiers: impl Iterator<Item = &'a StatModifier>,
+    ) -> Result<i64> {
+        let mut add: i128 = 0;
+        let mut multipliers: Vec<u32> = vec![];
+        for modifier in modifiers.filter(|modifier| modifier.stat_id == self.id) {
+            add = add
+                .checked_add(modifier.add as i128)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+            multipliers.push(modifier.multiply);
+        }
+
+        let mut value: i128 = (self.base_value as i128)
+            .checked_add(add)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+        for multiply in multipliers {
+            value = value
+                .checked_mul(multiply as i128)
+                .ok_or(ErrorCode::NumericalOverflowError)?
+                / StatModifier::MULTIPLIER_DENOMINATOR as i128;
+        }
+
+        Ok(value.clamp(self.min_value as i128, self.max_value as i128) as i64)
+    }
+}
+
+// changes a stat while the trait is equipped
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StatModifier {
+    pub stat_id: u16,
+    pub add: i64,
+    // multiplier in basis points, 10_000 leaves the stat unchanged
+    pub multiply: u32,
+}
+
+impl StatModifier {
+    pub const SPACE: usize = 2 + // stat id
+    8 + // add
+    4; // multiply
+    pub const MULTIPLIER_DENOMINATOR: u32 = 10_000;
+}
+
+// cached value of a stat on an avatar
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Stat {
+    pub id: u16,
+    pub value: i64,
+}
+
+impl Stat {
+    pub const SPACE: usize = 2 + // id
+    8; // value
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct VariantMetadata {
     pub name: String,
@@ -32,16 +112,19 @@ pub struct VariantMetadata {
 
 impl VariantMetadata {
     // return true if the option_id exists for the variant
-    pub fn find_option(&self, option_id: &str) -> VariantOption {
+    pub fn find_option(&self, option_id: &str) -> Result<VariantOption> {
         self.options
             .iter()
             .find(|option| option.option_id == option_id)
-            .unwrap()
-            .clone()
+            .cloned()
+            .ok_or_else(|| ErrorCode::InvalidVariantOption.into())
     }
 
-    pub fn default_option(&self) -> VariantOption {
-        self.options.first().unwrap().clone()
+    pub fn default_option(&self) -> Result<VariantOption> {
+        self.options
+            .first()
+            .cloned()
+            .ok_or_else(|| ErrorCode::InvalidVariantOption.into())
     }
 
     pub fn space(&self) -> usize {
@@ -71,6 +154,9 @@ pub struct VariantOption {
     pub option_id: String,
     pub payment_details: Option<PaymentDetails>,
     pub trait_gate: Option<TraitGate>,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub max_selections: Option<u64>,
 }
 
 impl VariantOption {
@@ -82,11 +168,39 @@ impl VariantOption {
         }
     }
 
+    // return true if the current time is within the option's availability window
+    pub fn is_available(&self, unix_timestamp: i64) -> bool {
+        if let Some(start_time) = self.start_time {
+            if unix_timestamp < start_time {
+                return false;
+            }
+        }
+
+        if let Some(end_time) = self.end_time {
+            if unix_timestamp > end_time {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // return true if the option can still be selected given the current selection count
+    pub fn has_supply(&self, selection_count: u64) -> bool {
+        match self.max_selections {
+            Some(max_selections) => selection_count < max_selections,
+            None => true,
+        }
+    }
+
     pub fn space(&self) -> usize {
         (4 + self.variant_id.len()) + // variant_id
         (4 + self.option_id.len()) + // variant_value
-        (1 + PaymentDetails::SPACE) + // optional payment_details
-        (1 + VariantOption::trait_gate_space(&self.trait_gate)) // optional trait gate
+        PaymentDetails::option_space(&self.payment_details) + // optional payment_details
+        (1 + VariantOption::trait_gate_space(&self.trait_gate)) + // optional trait gate
+        (1 + 8) + // optional start_time
+        (1 + 8) + // optional end_time
+        (1 + 8) // optional max_selections
     }
 
     fn trait_gate_space(trait_gate: &Option<TraitGate>) -> usize {
@@ -101,11 +215,43 @@ impl VariantOption {
 pub struct PaymentDetails {
     pub payment_method: Pubkey,
     pub amount: u64,
+    pub alternatives: Vec<PaymentAlternative>,
 }
 
 impl PaymentDetails {
-    pub const SPACE: usize = 32 + // payment_method
-    8; // amount
+    pub const MAX_ALTERNATIVES: usize = 3;
+    pub const INIT_SPACE: usize = 32 + // payment_method
+    8 + // amount
+    4; // empty alternatives vector
+
+    pub fn space(&self) -> usize {
+        PaymentDetails::INIT_SPACE + (self.alternatives.len() * PaymentAlternative::SPACE)
+    }
+
+    // space for an optional payment details, if none we still reserve space for a payment details without alternatives
+    pub fn option_space(payment_details: &Option<PaymentDetails>) -> usize {
+        1 + match payment_details {
+            Some(payment_details) => payment_details.space(),
+            None => PaymentDetails::INIT_SPACE,
+        }
+    }
+
+    // return true if the alternatives fit in the payment state and don't repeat a payment method
+    pub fn is_valid(&self) -> bool {
+        if self.alternatives.len() > PaymentDetails::MAX_ALTERNATIVES {
+            return false;
+        }
+
+        let mut payment_methods: Vec<&Pubkey> = vec![&self.payment_method];
+        for alternative in &self.alternatives {
+            if payment_methods.contains(&&alternative.payment_method) {
+                return false;
+            }
+            payment_methods.push(&alternative.payment_method);
+        }
+
+        true
+    }
 }
 
 impl From<PaymentDetails> for PaymentState {
@@ -118,6 +264,18 @@ impl From<PaymentDetails> for PaymentState {
     }
 }
 
+// an alternative payment method which can be used instead of the primary payment method
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Clone, Debug)]
+pub struct PaymentAlternative {
+    pub payment_method: Pubkey,
+    pub amount: u64,
+}
+
+impl PaymentAlternative {
+    pub const SPACE: usize = 32 + // payment_method
+    8; // amount
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Clone, Debug)]
 pub struct TraitGate {
     pub operator: Operator,
@@ -172,6 +330,8 @@ pub struct TraitData {
     pub trait_address: Pubkey,
     pub variant_selection: Vec<VariantOption>,
     pub trait_gate: Option<TraitGate>,
+    pub rental: Option<Pubkey>,
+    pub stat_modifiers: Vec<StatModifier>,
 }
 
 impl TraitData {
@@ -181,18 +341,27 @@ impl TraitData {
         trait_address: Pubkey,
         variant_metadata: &[VariantMetadata],
         trait_gate: Option<TraitGate>,
-    ) -> Self {
+        rental: Option<Pubkey>,
+        stat_modifiers: Vec<StatModifier>,
+    ) -> Result<Self> {
         let variant_selection: Vec<VariantOption> = variant_metadata
             .iter()
             .map(|vm| vm.default_option())
-            .collect();
-        TraitData {
+            .collect::<Result<Vec<VariantOption>>>()?;
+        Ok(TraitData {
             attribute_ids,
             trait_id,
             trait_address,
             variant_selection,
             trait_gate,
-        }
+            rental,
+            stat_modifiers,
+        })
+    }
+
+    // rented traits can only be removed by reclaiming the rental
+    pub fn is_rented(&self) -> bool {
+        self.rental.is_some()
     }
 
     pub fn current_space(&self) -> usize {
@@ -200,7 +369,9 @@ impl TraitData {
         2 + // trait_id
         32 + // trait address
         4 + // variant selection vector bytes
-        1; // Option trait gate byte
+        1 + // Option trait gate byte
+        1 + // Option rental byte
+        4 + (self.stat_modifiers.len() * StatModifier::SPACE); // stat modifiers
 
         for variant in &self.variant_selection {
             total_bytes += variant.space();
@@ -210,6 +381,10 @@ impl TraitData {
             total_bytes += trait_gate.space();
         };
 
+        if self.rental.is_some() {
+            total_bytes += 32;
+        };
+
         total_bytes
     }
 
@@ -219,7 +394,7 @@ impl TraitData {
         avatar: &AccountInfo<'info>,
         payer: Signer<'info>,
         system_program: Program<'info, System>,
-    ) {
+    ) -> Result<()> {
         let old_space = self.current_space();
 
         self.update_variant_selection_data(variant_selection);
@@ -228,7 +403,7 @@ impl TraitData {
 
         let diff: i64 = new_space as i64 - old_space as i64;
 
-        reallocate(diff, avatar, payer, system_program).unwrap();
+        reallocate(diff, avatar, payer, system_program)
     }
 
     fn update_variant_selection_data(&mut self, variant_selection: VariantOption) {
@@ -254,6 +429,7 @@ impl TraitStatus {
 pub enum PaymentAssetClass {
     Fungible { mint: Pubkey },
     NonFungible { mints: Pubkey },
+    Native,
 }
 
 impl PaymentAssetClass {
@@ -264,10 +440,83 @@ impl PaymentAssetClass {
 pub enum PaymentAction {
     Transfer { treasury: Pubkey },
     Burn,
+    Split { recipients: Vec<PaymentSplit> },
 }
 
 impl PaymentAction {
-    pub const SPACE: usize = 1 + 32;
+    pub const INIT_SPACE: usize = 1 + 32;
+
+    pub fn space(&self) -> usize {
+        match self {
+            Self::Transfer { .. } | Self::Burn => PaymentAction::INIT_SPACE,
+            Self::Split { recipients } => std::cmp::max(
+                PaymentAction::INIT_SPACE,
+                1 + 4 + (recipients.len() * PaymentSplit::SPACE),
+            ),
+        }
+    }
+
+    // return true if the action can be used with the asset class
+    pub fn is_valid(&self, asset_class: &PaymentAssetClass) -> bool {
+        match self {
+            Self::Transfer { .. } => true,
+            Self::Burn => !matches!(asset_class, PaymentAssetClass::Native),
+            Self::Split { recipients } => {
+                !matches!(asset_class, PaymentAssetClass::NonFungible { .. })
+                    && PaymentSplit::is_valid_split(recipients)
+            }
+        }
+    }
+}
+
+// a share of a payment sent to a treasury, in basis points of the total amount
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PaymentSplit {
+    pub treasury: Pubkey,
+    pub basis_points: u16,
+}
+
+impl PaymentSplit {
+    pub const SPACE: usize = 32 + // treasury
+    2; // basis points
+    pub const TOTAL_BASIS_POINTS: u64 = 10_000;
+
+    // return true if the recipients are unique and their shares add up to the full amount
+    pub fn is_valid_split(recipients: &[PaymentSplit]) -> bool {
+        if recipients.is_empty() {
+            return false;
+        }
+
+        let mut treasuries: Vec<&Pubkey> = vec![];
+        let mut total_basis_points: u64 = 0;
+        for recipient in recipients {
+            if treasuries.contains(&&recipient.treasury) {
+                return false;
+            }
+            treasuries.push(&recipient.treasury);
+            total_basis_points += recipient.basis_points as u64;
+        }
+
+        total_basis_points == PaymentSplit::TOTAL_BASIS_POINTS
+    }
+
+    // divide the amount between the recipients, any remainder from rounding goes to the first recipient
+    pub fn split_amount(recipients: &[PaymentSplit], amount: u64) -> Vec<u64> {
+        let mut amounts: Vec<u64> = recipients
+            .iter()
+            .map(|recipient| {
+                ((amount as u128 * recipient.basis_points as u128)
+                    / PaymentSplit::TOTAL_BASIS_POINTS as u128) as u64
+            })
+            .collect();
+
+        let remainder = amount - amounts.iter().sum::<u64>();
+        if let Some(first) = amounts.first_mut() {
+            *first += remainder;
+        }
+
+        amounts
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -388,17 +637,20 @@ impl UpdateTarget {
     // check the payment state of the equip trait, if that required_amount has already been fulfilled it will check the
     // payment state of the removed trait and update that one
     // if the payment state already meets the requirements for the update or the payment method does not match this will error
-    pub fn update_payment_state(&mut self, payment_method: &Pubkey, amount: u64) -> Result<()> {
+    pub fn update_payment_state(
+        &mut self,
+        alternatives: &mut UpdatePaymentAlternatives,
+        payment_method: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
         match self {
             Self::ClassVariant { payment_state, .. }
             | Self::TraitVariant { payment_state, .. }
             | Self::EquipTrait { payment_state, .. }
             | Self::RemoveTrait { payment_state, .. } => match payment_state {
                 Some(state) => {
-                    if state.payment_method.eq(payment_method)
-                        && state.current_amount < state.required_amount
-                    {
-                        state.current_amount += amount;
+                    if state.accepts(&alternatives.payment, payment_method) {
+                        state.add_payment(&mut alternatives.payment, payment_method, amount);
                         return Ok(());
                     }
                 }
@@ -411,10 +663,8 @@ impl UpdateTarget {
             } => {
                 match equip_payment_state {
                     Some(state) => {
-                        if state.payment_method.eq(payment_method)
-                            && state.current_amount < state.required_amount
-                        {
-                            state.current_amount += amount;
+                        if state.accepts(&alternatives.payment, payment_method) {
+                            state.add_payment(&mut alternatives.payment, payment_method, amount);
                             return Ok(());
                         }
                     }
@@ -423,10 +673,12 @@ impl UpdateTarget {
 
                 match remove_payment_state {
                     Some(state) => {
-                        if state.payment_method.eq(payment_method)
-                            && state.current_amount < state.required_amount
-                        {
-                            state.current_amount += amount;
+                        if state.accepts(&alternatives.remove_payment, payment_method) {
+                            state.add_payment(
+                                &mut alternatives.remove_payment,
+                                payment_method,
+                                amount,
+                            );
                             return Ok(());
                         }
                     }
@@ -509,6 +761,80 @@ impl PaymentState {
     pub fn is_paid(&self) -> bool {
         self.current_amount >= self.required_amount
     }
+
+    // return true if a payment can be made with this payment method
+    // an alternative can only be chosen before any payment has been made
+    pub fn accepts(&self, alternatives: &[PaymentAlternative], payment_method: &Pubkey) -> bool {
+        if self.is_paid() {
+            return false;
+        }
+
+        if self.is_payment_method(payment_method) {
+            return true;
+        }
+
+        self.current_amount == 0
+            && alternatives
+                .iter()
+                .any(|alternative| alternative.payment_method.eq(payment_method))
+    }
+
+    // record a payment, if an alternative is used it becomes the required payment method
+    pub fn add_payment(
+        &mut self,
+        alternatives: &mut Vec<PaymentAlternative>,
+        payment_method: &Pubkey,
+        amount: u64,
+    ) {
+        if !self.is_payment_method(payment_method) {
+            if let Some(index) = alternatives
+                .iter()
+                .position(|alternative| alternative.payment_method.eq(payment_method))
+            {
+                let alternative = alternatives.remove(index);
+                alternatives.push(PaymentAlternative {
+                    payment_method: self.payment_method,
+                    amount: self.required_amount,
+                });
+                self.payment_method = alternative.payment_method;
+                self.required_amount = alternative.amount;
+            }
+        }
+
+        self.current_amount += amount;
+    }
+}
+
+// the payment alternatives of an update, kept next to the update target in the update state
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdatePaymentAlternatives {
+    // alternatives for the payment state, or the equip payment state of a trait swap
+    pub payment: Vec<PaymentAlternative>,
+    // alternatives for the remove payment state of a trait swap
+    pub remove_payment: Vec<PaymentAlternative>,
+}
+
+impl UpdatePaymentAlternatives {
+    pub const SPACE: usize = 4 + (PaymentDetails::MAX_ALTERNATIVES * PaymentAlternative::SPACE) + // payment alternatives
+    4 + (PaymentDetails::MAX_ALTERNATIVES * PaymentAlternative::SPACE); // remove payment alternatives
+
+    pub fn new(
+        payment_details: Option<&PaymentDetails>,
+        remove_payment_details: Option<&PaymentDetails>,
+    ) -> Self {
+        UpdatePaymentAlternatives {
+            payment: payment_details
+                .map(|details| details.alternatives.clone())
+                .unwrap_or_default(),
+            remove_payment: remove_payment_details
+                .map(|details| details.alternatives.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.payment.is_empty() && self.remove_payment.is_empty()
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -547,6 +873,43 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_apply_stat_modifiers() {
+        let stat_template = StatTemplate {
+            id: 0,
+            name: "strength".to_string(),
+            base_value: 10,
+            min_value: 0,
+            max_value: 50,
+        };
+        let modifier = |stat_id: u16, add: i64, multiply: u32| StatModifier {
+            stat_id,
+            add,
+            multiply,
+        };
+
+        // no modifiers leaves the base value
+        assert_eq!(stat_template.apply_modifiers([].iter()).unwrap(), 10);
+
+        // additions are summed before multipliers are applied
+        let modifiers = [modifier(0, 5, 10_000), modifier(0, 5, 15_000)];
+        assert_eq!(stat_template.apply_modifiers(modifiers.iter()).unwrap(), 30);
+
+        // modifiers for other stats are ignored
+        let modifiers = [modifier(1, 100, 50_000), modifier(0, -4, 10_000)];
+        assert_eq!(stat_template.apply_modifiers(modifiers.iter()).unwrap(), 6);
+
+        // the result is clamped to the template bounds
+        let modifiers = [modifier(0, 0, 100_000)];
+        assert_eq!(stat_template.apply_modifiers(modifiers.iter()).unwrap(), 50);
+        let modifiers = [modifier(0, -20, 10_000)];
+        assert_eq!(stat_template.apply_modifiers(modifiers.iter()).unwrap(), 0);
+
+        // multipliers too large to apply are an error instead of wrapping
+        let modifiers = vec![modifier(0, 0, u32::MAX); 20];
+        assert!(stat_template.apply_modifiers(modifiers.iter()).is_err());
+    }
+
     #[test]
     fn test_find_option() {
         let mut opts: Vec<VariantOption> = vec![];
@@ -555,24 +918,36 @@ mod tests {
             option_id: "lskdlso1".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         });
         opts.push(VariantOption {
             variant_id: "jflqkah8".to_string(),
             option_id: "lskdyso1".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         });
         opts.push(VariantOption {
             variant_id: "ryeholi9".to_string(),
             option_id: "qwelo12o".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         });
         opts.push(VariantOption {
             variant_id: "vbclko09".to_string(),
             option_id: "flqo32ty".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         });
 
         let variant_metadata = VariantMetadata {
@@ -581,8 +956,48 @@ mod tests {
             status: VariantStatus { enabled: true },
             options: opts,
         };
-        let opt = variant_metadata.find_option("flqo32ty");
-        assert_eq!(opt.option_id, "flqo32ty".to_string())
+        let opt = variant_metadata.find_option("flqo32ty").unwrap();
+        assert_eq!(opt.option_id, "flqo32ty".to_string());
+
+        let err = variant_metadata.find_option("flqo32tz").unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariantOption.into());
+    }
+
+    #[test]
+    fn test_default_option() {
+        let mut variant_metadata = VariantMetadata {
+            name: "test".to_string(),
+            id: "lqowkfd2".to_string(),
+            status: VariantStatus { enabled: true },
+            options: vec![],
+        };
+
+        // a variant without options has no default
+        let err = variant_metadata.default_option().unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariantOption.into());
+        let err = TraitData::new(
+            vec![],
+            0,
+            Pubkey::new_unique(),
+            std::slice::from_ref(&variant_metadata),
+            None,
+            None,
+            vec![],
+        )
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVariantOption.into());
+
+        variant_metadata.options.push(VariantOption {
+            variant_id: "lqowkfd2".to_string(),
+            option_id: "flqo32ty".to_string(),
+            payment_details: None,
+            trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
+        });
+        let opt = variant_metadata.default_option().unwrap();
+        assert_eq!(opt.option_id, "flqo32ty".to_string());
     }
 
     #[test]
@@ -596,6 +1011,9 @@ mod tests {
                 operator: Operator::And,
                 traits: trait_gate_requirements.clone(),
             }),
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         };
         let eligible = variant_option.is_eligible(&trait_gate_requirements);
         assert!(eligible);
@@ -611,11 +1029,216 @@ mod tests {
             option_id: "lskdyso1".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         };
         let eligible = variant_option.is_eligible(&create_pubkeys(10));
         assert!(eligible);
     }
 
+    #[test]
+    fn test_is_available() {
+        let variant_option = VariantOption {
+            variant_id: "vbclko09".to_string(),
+            option_id: "lskdyso1".to_string(),
+            payment_details: None,
+            trait_gate: None,
+            start_time: Some(1_696_118_400),
+            end_time: Some(1_698_796_799),
+            max_selections: None,
+        };
+        assert!(!variant_option.is_available(1_696_118_399));
+        assert!(variant_option.is_available(1_696_118_400));
+        assert!(variant_option.is_available(1_698_796_799));
+        assert!(!variant_option.is_available(1_698_796_800));
+    }
+
+    #[test]
+    fn test_is_available_no_window() {
+        let variant_option = VariantOption {
+            variant_id: "vbclko09".to_string(),
+            option_id: "lskdyso1".to_string(),
+            payment_details: None,
+            trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
+        };
+        assert!(variant_option.is_available(0));
+        assert!(variant_option.is_available(i64::MAX));
+    }
+
+    #[test]
+    fn test_has_supply() {
+        let variant_option = VariantOption {
+            variant_id: "vbclko09".to_string(),
+            option_id: "lskdyso1".to_string(),
+            payment_details: None,
+            trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: Some(500),
+        };
+        assert!(variant_option.has_supply(0));
+        assert!(variant_option.has_supply(499));
+        assert!(!variant_option.has_supply(500));
+
+        let uncapped_variant_option = VariantOption {
+            max_selections: None,
+            ..variant_option
+        };
+        assert!(uncapped_variant_option.has_supply(u64::MAX));
+    }
+
+    #[test]
+    fn test_payment_details_is_valid() {
+        let payment_method = Pubkey::new_unique();
+        let alternative = PaymentAlternative {
+            payment_method: Pubkey::new_unique(),
+            amount: 10,
+        };
+        let payment_details = PaymentDetails {
+            payment_method,
+            amount: 100,
+            alternatives: vec![alternative.clone()],
+        };
+        assert!(payment_details.is_valid());
+
+        // duplicate payment method
+        let duplicate = PaymentDetails {
+            alternatives: vec![PaymentAlternative {
+                payment_method,
+                amount: 10,
+            }],
+            ..payment_details.clone()
+        };
+        assert!(!duplicate.is_valid());
+
+        // too many alternatives
+        let too_many = PaymentDetails {
+            alternatives: (0..=PaymentDetails::MAX_ALTERNATIVES)
+                .map(|_| PaymentAlternative {
+                    payment_method: Pubkey::new_unique(),
+                    amount: 10,
+                })
+                .collect(),
+            ..payment_details
+        };
+        assert!(!too_many.is_valid());
+    }
+
+    #[test]
+    fn test_payment_state_alternative() {
+        let primary = Pubkey::new_unique();
+        let alternative = Pubkey::new_unique();
+        let payment_details = PaymentDetails {
+            payment_method: primary,
+            amount: 100,
+            alternatives: vec![PaymentAlternative {
+                payment_method: alternative,
+                amount: 5,
+            }],
+        };
+        let mut alternatives = UpdatePaymentAlternatives::new(Some(&payment_details), None);
+        let mut payment_state: PaymentState = payment_details.into();
+
+        assert!(payment_state.accepts(&alternatives.payment, &primary));
+        assert!(payment_state.accepts(&alternatives.payment, &alternative));
+        assert!(!payment_state.accepts(&alternatives.payment, &Pubkey::new_unique()));
+
+        // paying with the alternative makes it the required payment method
+        payment_state.add_payment(&mut alternatives.payment, &alternative, 2);
+        assert!(payment_state.is_payment_method(&alternative));
+        assert_eq!(payment_state.required_amount, 5);
+        assert_eq!(payment_state.current_amount, 2);
+        assert_eq!(alternatives.payment[0].payment_method, primary);
+
+        // once a payment is made the other methods can't be mixed in
+        assert!(!payment_state.accepts(&alternatives.payment, &primary));
+        assert!(payment_state.accepts(&alternatives.payment, &alternative));
+
+        payment_state.add_payment(&mut alternatives.payment, &alternative, 3);
+        assert!(payment_state.is_paid());
+        assert!(!payment_state.accepts(&alternatives.payment, &alternative));
+    }
+
+    #[test]
+    fn test_update_payment_alternatives_space() {
+        let alternatives = (0..PaymentDetails::MAX_ALTERNATIVES)
+            .map(|_| PaymentAlternative {
+                payment_method: Pubkey::new_unique(),
+                amount: 10,
+            })
+            .collect::<Vec<PaymentAlternative>>();
+        let payment_alternatives = UpdatePaymentAlternatives {
+            payment: alternatives.clone(),
+            remove_payment: alternatives,
+        };
+        assert_eq!(
+            payment_alternatives.try_to_vec().unwrap().len(),
+            UpdatePaymentAlternatives::SPACE
+        );
+    }
+
+    #[test]
+    fn test_split_amount() {
+        let recipients = vec![
+            PaymentSplit {
+                treasury: Pubkey::new_unique(),
+                basis_points: 3_333,
+            },
+            PaymentSplit {
+                treasury: Pubkey::new_unique(),
+                basis_points: 3_333,
+            },
+            PaymentSplit {
+                treasury: Pubkey::new_unique(),
+                basis_points: 3_334,
+            },
+        ];
+        assert!(PaymentSplit::is_valid_split(&recipients));
+
+        let amounts = PaymentSplit::split_amount(&recipients, 100);
+        assert_eq!(amounts, vec![34, 33, 33]);
+        assert_eq!(amounts.iter().sum::<u64>(), 100);
+
+        let amounts = PaymentSplit::split_amount(&recipients, u64::MAX);
+        assert_eq!(amounts.iter().sum::<u64>(), u64::MAX);
+    }
+
+    #[test]
+    fn test_invalid_split() {
+        let treasury = Pubkey::new_unique();
+        assert!(!PaymentSplit::is_valid_split(&[]));
+        assert!(!PaymentSplit::is_valid_split(&[PaymentSplit {
+            treasury,
+            basis_points: 9_999,
+        }]));
+        assert!(!PaymentSplit::is_valid_split(&[
+            PaymentSplit {
+                treasury,
+                basis_points: 5_000,
+            },
+            PaymentSplit {
+                treasury,
+                basis_points: 5_000,
+            },
+        ]));
+
+        let split = PaymentAction::Split {
+            recipients: vec![PaymentSplit {
+                treasury,
+                basis_points: 10_000,
+            }],
+        };
+        assert!(split.is_valid(&PaymentAssetClass::Native));
+        assert!(!split.is_valid(&PaymentAssetClass::NonFungible {
+            mints: Pubkey::new_unique()
+        }));
+        assert!(!PaymentAction::Burn.is_valid(&PaymentAssetClass::Native));
+    }
+
     #[test]
     fn test_trait_gate_and_operator_valid() {
         let trait_gate_requirements = create_pubkeys(10);
@@ -690,8 +1313,13 @@ mod tests {
                 option_id: "flowvk12".to_string(),
                 payment_details: None,
                 trait_gate: None,
+                start_time: None,
+                end_time: None,
+                max_selections: None,
             }],
             trait_gate: None,
+            rental: None,
+            stat_modifiers: vec![],
         };
         assert_eq!(
             trait_data.variant_selection.first().unwrap().option_id,
@@ -704,6 +1332,9 @@ mod tests {
             option_id: "qjelso90".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         };
         trait_data.update_variant_selection_data(new_variant_selection1);
 
@@ -718,6 +1349,9 @@ mod tests {
             option_id: "jfh67odq".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         };
         trait_data.update_variant_selection_data(new_variant_selection2);
 
@@ -739,8 +1373,13 @@ mod tests {
                 option_id: "jfh67odq".to_string(),
                 payment_details: None,
                 trait_gate: None,
+                start_time: None,
+                end_time: None,
+                max_selections: None,
             }],
             trait_gate: None,
+            rental: None,
+            stat_modifiers: vec![],
         };
         assert_eq!(
             trait_data.variant_selection.first().unwrap().option_id,
@@ -753,6 +1392,9 @@ mod tests {
             option_id: "xloal23d".to_string(),
             payment_details: None,
             trait_gate: None,
+            start_time: None,
+            end_time: None,
+            max_selections: None,
         };
         trait_data.update_variant_selection_data(new_variant_selection);
         assert_eq!(trait_data.variant_selection.len(), 2);
@@ -61,4 +61,64 @@ pub enum ErrorCode {
 
     #[msg("Failed to Validate Trait Gate")]
     TraitGateFailure,
+
+    #[msg("Variant Option is Outside its Availability Window")]
+    VariantUnavailable,
+
+    #[msg("Variant Option Supply Exhausted")]
+    VariantSupplyExhausted,
+
+    #[msg("Variant Option Counter Required")]
+    MissingVariantOptionCounter,
+
+    #[msg("Too Many Payment Alternatives")]
+    TooManyPaymentAlternatives,
+
+    #[msg("Invalid Payment Split")]
+    InvalidPaymentSplit,
+
+    #[msg("Trait Is Rented")]
+    TraitRented,
+
+    #[msg("Trait Rental Unavailable")]
+    TraitRentalUnavailable,
+
+    #[msg("Trait Rental Not Expired")]
+    TraitRentalNotExpired,
+
+    #[msg("Invalid Trait Rental Duration")]
+    InvalidTraitRentalDuration,
+
+    #[msg("Trait Can Not Be Rented")]
+    TraitRentalNotAllowed,
+
+    #[msg("Invalid Stat Template")]
+    InvalidStatTemplate,
+
+    #[msg("Invalid Stat Id")]
+    InvalidStatId,
+
+    #[msg("Invalid Variant Option")]
+    InvalidVariantOption,
+
+    #[msg("Only the Namespace Program Can Call This")]
+    UnauthorizedCaller,
+
+    #[msg("Failed to Join Namespace")]
+    FailedToJoinNamespace,
+
+    #[msg("Failed to Leave Namespace")]
+    FailedToLeaveNamespace,
+
+    #[msg("Failed to Cache")]
+    FailedToCache,
+
+    #[msg("Failed to Uncache")]
+    FailedToUncache,
+
+    #[msg("Numerical Overflow")]
+    NumericalOverflowError,
+
+    #[msg("Update State Must Be Migrated")]
+    UpdateStateNotMigrated,
 }
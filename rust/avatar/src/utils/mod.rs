@@ -1,13 +1,14 @@
 use std::str::FromStr;
 
-use anchor_lang::{
-    prelude::*,
-    system_program::{transfer, Transfer},
-};
+use anchor_lang::prelude::*;
 use anchor_spl::token;
+use raindrops_common::utils::resize_account;
 
 use crate::state::{
-    data::{AttributeMetadata, AttributeType, TraitData},
+    accounts::VariantOptionCounter,
+    data::{
+        AttributeMetadata, AttributeType, PaymentDetails, TraitData, VariantMetadata, VariantOption,
+    },
     errors::ErrorCode,
 };
 
@@ -51,41 +52,13 @@ pub fn reallocate<'info>(
     system_program: Program<'info, System>,
 ) -> Result<()> {
     let new_size = (account.data_len() as i64 + size_diff) as usize;
-    account.realloc(new_size, false)?;
-
-    let rent = Rent::get()?;
-
-    let lamports_required = rent.minimum_balance(new_size);
-
-    let current_lamports = account.lamports();
-
-    let transfer_amount: i64 = (lamports_required as i64) - (current_lamports as i64);
 
-    // no need to transfer
-    if transfer_amount == 0 {
-        return Ok(());
-    }
-
-    if transfer_amount > 0 {
-        // if transfer amount is more than 0 we need to transfer lamports to the account
-        let transfer_accounts = Transfer {
-            from: payer.to_account_info(),
-            to: account.to_account_info(),
-        };
-
-        transfer(
-            CpiContext::new(system_program.to_account_info(), transfer_accounts),
-            transfer_amount.try_into().unwrap(),
-        )
-    } else {
-        // if transfer amount is less than 0 this means we need to return lamports to the payer
-        let transfer_to_payer_amount = transfer_amount.unsigned_abs();
-
-        **account.try_borrow_mut_lamports()? -= transfer_to_payer_amount;
-        **payer.try_borrow_mut_lamports()? += transfer_to_payer_amount;
-
-        Ok(())
-    }
+    resize_account(
+        account,
+        &payer.to_account_info(),
+        &system_program.to_account_info(),
+        new_size,
+    )
 }
 
 pub fn validate_attribute_availability(
@@ -149,6 +122,55 @@ pub fn validate_essential_attribute_updates(
     Ok(())
 }
 
+// check the variant option is inside its availability window and has supply remaining
+pub fn validate_variant_option_availability(
+    variant_option: &VariantOption,
+    variant_option_counter: Option<&VariantOptionCounter>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        variant_option.is_available(clock.unix_timestamp),
+        ErrorCode::VariantUnavailable
+    );
+
+    // supply capped options must track their selections in a counter account
+    if variant_option.max_selections.is_some() {
+        let variant_option_counter =
+            variant_option_counter.ok_or(ErrorCode::MissingVariantOptionCounter)?;
+        require!(
+            variant_option.has_supply(variant_option_counter.selection_count),
+            ErrorCode::VariantSupplyExhausted
+        );
+    }
+
+    Ok(())
+}
+
+// check the payment alternatives fit in the payment state
+pub fn validate_payment_details(payment_details: &Option<PaymentDetails>) -> Result<()> {
+    if let Some(payment_details) = payment_details {
+        require!(
+            payment_details.is_valid(),
+            ErrorCode::TooManyPaymentAlternatives
+        );
+    }
+
+    Ok(())
+}
+
+// check the payment details of every variant option
+pub fn validate_variant_metadata_payment_details(
+    variant_metadata: &[VariantMetadata],
+) -> Result<()> {
+    for vm in variant_metadata {
+        for option in &vm.options {
+            validate_payment_details(&option.payment_details)?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::state::data::{AttributeStatus, AttributeType};
@@ -164,6 +186,8 @@ mod tests {
             trait_address: Pubkey::new_unique(),
             variant_selection: vec![],
             trait_gate: None,
+            rental: None,
+            stat_modifiers: vec![],
         }];
         let attribute_metadata: Vec<AttributeMetadata> = vec![
             AttributeMetadata {
@@ -202,6 +226,8 @@ mod tests {
                 trait_address: Pubkey::new_unique(),
                 variant_selection: vec![],
                 trait_gate: None,
+                rental: None,
+                stat_modifiers: vec![],
             },
             TraitData {
                 attribute_ids: vec![9],
@@ -209,6 +235,8 @@ mod tests {
                 trait_address: Pubkey::new_unique(),
                 variant_selection: vec![],
                 trait_gate: None,
+                rental: None,
+                stat_modifiers: vec![],
             },
         ];
         let attribute_metadata: Vec<AttributeMetadata> = vec![
@@ -269,6 +297,8 @@ mod tests {
             trait_address: Pubkey::new_unique(),
             variant_selection: vec![],
             trait_gate: None,
+            rental: None,
+            stat_modifiers: vec![],
         }];
         let attribute_metadata: Vec<AttributeMetadata> = vec![
             AttributeMetadata {
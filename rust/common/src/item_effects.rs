@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum BasicItemEffectType {
+    Increment,
+    Decrement,
+    IncrementPercent,
+    DecrementPercent,
+    IncrementPercentFromBase,
+    DecrementPercentFromBase,
+    // stat becomes amount while the effect is active
+    Set,
+    // stat is held between clamp_min and clamp_max while the effect is active
+    Clamp,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum StackingPolicy {
+    // every application adds on top of the others
+    Stack,
+    // applying again while active only extends how long it lasts
+    RefreshDuration,
+    // only the largest active effect on the stat applies
+    HighestWins,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BasicItemEffect {
+    pub amount: u64,
+    pub stat: String,
+    pub item_effect_type: BasicItemEffectType,
+    pub active_duration: Option<u64>,
+    pub staking_amount_numerator: Option<u64>,
+    pub staking_amount_divisor: Option<u64>,
+    pub staking_duration_numerator: Option<u64>,
+    pub staking_duration_divisor: Option<u64>,
+    // point where this effect no longer applies
+    pub max_uses: Option<u64>,
+    pub clamp_min: Option<i64>,
+    pub clamp_max: Option<i64>,
+    // None behaves as Stack
+    pub stacking_policy: Option<StackingPolicy>,
+}
+
+/// `BasicItemEffect` as stored before clamps and stacking policies were added,
+/// used to read data written in that layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyBasicItemEffect {
+    pub amount: u64,
+    pub stat: String,
+    pub item_effect_type: BasicItemEffectType,
+    pub active_duration: Option<u64>,
+    pub staking_amount_numerator: Option<u64>,
+    pub staking_amount_divisor: Option<u64>,
+    pub staking_duration_numerator: Option<u64>,
+    pub staking_duration_divisor: Option<u64>,
+    pub max_uses: Option<u64>,
+}
+
+impl From<LegacyBasicItemEffect> for BasicItemEffect {
+    fn from(effect: LegacyBasicItemEffect) -> Self {
+        BasicItemEffect {
+            amount: effect.amount,
+            stat: effect.stat,
+            item_effect_type: effect.item_effect_type,
+            active_duration: effect.active_duration,
+            staking_amount_numerator: effect.staking_amount_numerator,
+            staking_amount_divisor: effect.staking_amount_divisor,
+            staking_duration_numerator: effect.staking_duration_numerator,
+            staking_duration_divisor: effect.staking_duration_divisor,
+            max_uses: effect.max_uses,
+            clamp_min: None,
+            clamp_max: None,
+            stacking_policy: None,
+        }
+    }
+}
+
+impl BasicItemEffect {
+    /// None if the effect uses clamps or a stacking policy, which the legacy
+    /// layout cannot hold.
+    pub fn to_legacy(&self) -> Option<LegacyBasicItemEffect> {
+        if self.clamp_min.is_some() || self.clamp_max.is_some() || self.stacking_policy.is_some() {
+            return None;
+        }
+
+        Some(self.without_policies())
+    }
+
+    /// The effect in the legacy layout, dropping any clamps and stacking policy.
+    pub fn without_policies(&self) -> LegacyBasicItemEffect {
+        LegacyBasicItemEffect {
+            amount: self.amount,
+            stat: self.stat.clone(),
+            item_effect_type: self.item_effect_type,
+            active_duration: self.active_duration,
+            staking_amount_numerator: self.staking_amount_numerator,
+            staking_amount_divisor: self.staking_amount_divisor,
+            staking_duration_numerator: self.staking_duration_numerator,
+            staking_duration_divisor: self.staking_duration_divisor,
+            max_uses: self.max_uses,
+        }
+    }
+}
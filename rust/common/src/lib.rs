@@ -0,0 +1,290 @@
+//! Types and helpers shared by the raindrops programs.
+//!
+//! Anchor only generates IDL types for definitions that live inside a
+//! program crate, so the IDLs `anchor build` writes refer to these types
+//! without defining them. `scripts/add-common-idl-types.js`, run by the
+//! `build` script in `package.json`, parses `types.rs` and `item_effects.rs`
+//! and adds the types each program IDL refers to.
+pub mod item_effects;
+pub mod types;
+pub mod utils;
+
+pub use item_effects::*;
+pub use types::*;
+
+pub const NAMESPACE_ID: &str = "nameAxQRRBnd4kLfsVoZBBXfrByZdZTkh8mULLxLyqV";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::*;
+
+    // Layouts as they were hand-copied into the programs before this crate existed.
+    // Existing accounts were written with these, so the shared types must match them byte for byte.
+    mod legacy {
+        use anchor_lang::prelude::*;
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+        pub(super) enum PermissivenessType {
+            TokenHolder,
+            ParentTokenHolder,
+            UpdateAuthority,
+            Anybody,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
+        pub(super) enum InheritanceState {
+            NotInherited,
+            Inherited,
+            Overridden,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+        pub(super) struct NamespaceAndIndex {
+            pub(super) namespace: Pubkey,
+            pub(super) index: Option<u64>,
+            pub(super) inherited: InheritanceState,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+        pub(super) struct Callback {
+            pub(super) key: Pubkey,
+            pub(super) code: u64,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
+        pub(super) enum BasicItemEffectType {
+            Increment,
+            Decrement,
+            IncrementPercent,
+            DecrementPercent,
+            IncrementPercentFromBase,
+            DecrementPercentFromBase,
+            Set,
+            Clamp,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
+        pub(super) enum StackingPolicy {
+            Stack,
+            RefreshDuration,
+            HighestWins,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+        pub(super) struct BasicItemEffect {
+            pub(super) amount: u64,
+            pub(super) stat: String,
+            pub(super) item_effect_type: BasicItemEffectType,
+            pub(super) active_duration: Option<u64>,
+            pub(super) staking_amount_numerator: Option<u64>,
+            pub(super) staking_amount_divisor: Option<u64>,
+            pub(super) staking_duration_numerator: Option<u64>,
+            pub(super) staking_duration_divisor: Option<u64>,
+            pub(super) max_uses: Option<u64>,
+            pub(super) clamp_min: Option<i64>,
+            pub(super) clamp_max: Option<i64>,
+            pub(super) stacking_policy: Option<StackingPolicy>,
+        }
+    }
+
+    fn assert_same_bytes<A: AnchorSerialize, B: AnchorSerialize + AnchorDeserialize>(
+        legacy: &A,
+        shared: &B,
+    ) {
+        let legacy_bytes = legacy.try_to_vec().unwrap();
+        assert_eq!(legacy_bytes, shared.try_to_vec().unwrap());
+        // and the shared type reads what the legacy one wrote
+        let read = B::try_from_slice(&legacy_bytes).unwrap();
+        assert_eq!(legacy_bytes, read.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn enums_serialize_like_the_copies() {
+        let permissiveness = [
+            (
+                legacy::PermissivenessType::TokenHolder,
+                PermissivenessType::TokenHolder,
+            ),
+            (
+                legacy::PermissivenessType::ParentTokenHolder,
+                PermissivenessType::ParentTokenHolder,
+            ),
+            (
+                legacy::PermissivenessType::UpdateAuthority,
+                PermissivenessType::UpdateAuthority,
+            ),
+            (
+                legacy::PermissivenessType::Anybody,
+                PermissivenessType::Anybody,
+            ),
+        ];
+        for (legacy, shared) in permissiveness.iter() {
+            assert_same_bytes(legacy, shared);
+        }
+
+        let inheritance = [
+            (
+                legacy::InheritanceState::NotInherited,
+                InheritanceState::NotInherited,
+            ),
+            (
+                legacy::InheritanceState::Inherited,
+                InheritanceState::Inherited,
+            ),
+            (
+                legacy::InheritanceState::Overridden,
+                InheritanceState::Overridden,
+            ),
+        ];
+        for (legacy, shared) in inheritance.iter() {
+            assert_same_bytes(legacy, shared);
+        }
+
+        let effect_types = [
+            (
+                legacy::BasicItemEffectType::Increment,
+                BasicItemEffectType::Increment,
+            ),
+            (
+                legacy::BasicItemEffectType::Decrement,
+                BasicItemEffectType::Decrement,
+            ),
+            (
+                legacy::BasicItemEffectType::IncrementPercent,
+                BasicItemEffectType::IncrementPercent,
+            ),
+            (
+                legacy::BasicItemEffectType::DecrementPercent,
+                BasicItemEffectType::DecrementPercent,
+            ),
+            (
+                legacy::BasicItemEffectType::IncrementPercentFromBase,
+                BasicItemEffectType::IncrementPercentFromBase,
+            ),
+            (
+                legacy::BasicItemEffectType::DecrementPercentFromBase,
+                BasicItemEffectType::DecrementPercentFromBase,
+            ),
+            (legacy::BasicItemEffectType::Set, BasicItemEffectType::Set),
+            (
+                legacy::BasicItemEffectType::Clamp,
+                BasicItemEffectType::Clamp,
+            ),
+        ];
+        for (legacy, shared) in effect_types.iter() {
+            assert_same_bytes(legacy, shared);
+        }
+
+        let policies = [
+            (legacy::StackingPolicy::Stack, StackingPolicy::Stack),
+            (
+                legacy::StackingPolicy::RefreshDuration,
+                StackingPolicy::RefreshDuration,
+            ),
+            (
+                legacy::StackingPolicy::HighestWins,
+                StackingPolicy::HighestWins,
+            ),
+        ];
+        for (legacy, shared) in policies.iter() {
+            assert_same_bytes(legacy, shared);
+        }
+    }
+
+    #[test]
+    fn structs_serialize_like_the_copies() {
+        let namespace = Pubkey::new_unique();
+        for index in [None, Some(7)].iter() {
+            assert_same_bytes(
+                &legacy::NamespaceAndIndex {
+                    namespace,
+                    index: *index,
+                    inherited: legacy::InheritanceState::Overridden,
+                },
+                &NamespaceAndIndex {
+                    namespace,
+                    index: *index,
+                    inherited: InheritanceState::Overridden,
+                },
+            );
+        }
+
+        let key = Pubkey::new_unique();
+        assert_same_bytes(
+            &legacy::Callback { key, code: 42 },
+            &Callback { key, code: 42 },
+        );
+
+        assert_same_bytes(
+            &legacy::BasicItemEffect {
+                amount: 5,
+                stat: "strength".to_string(),
+                item_effect_type: legacy::BasicItemEffectType::Clamp,
+                active_duration: Some(60),
+                staking_amount_numerator: Some(1),
+                staking_amount_divisor: Some(2),
+                staking_duration_numerator: None,
+                staking_duration_divisor: Some(3),
+                max_uses: None,
+                clamp_min: Some(-4),
+                clamp_max: Some(9),
+                stacking_policy: Some(legacy::StackingPolicy::HighestWins),
+            },
+            &BasicItemEffect {
+                amount: 5,
+                stat: "strength".to_string(),
+                item_effect_type: BasicItemEffectType::Clamp,
+                active_duration: Some(60),
+                staking_amount_numerator: Some(1),
+                staking_amount_divisor: Some(2),
+                staking_duration_numerator: None,
+                staking_duration_divisor: Some(3),
+                max_uses: None,
+                clamp_min: Some(-4),
+                clamp_max: Some(9),
+                stacking_policy: Some(StackingPolicy::HighestWins),
+            },
+        );
+    }
+
+    #[test]
+    fn verify_checks_sorted_pair_proofs() {
+        let leaf = [1u8; 32];
+        let sibling = [2u8; 32];
+        let root = anchor_lang::solana_program::keccak::hashv(&[&[0x01], &leaf, &sibling]).0;
+
+        assert!(utils::verify(&[sibling], &root, leaf));
+        assert!(utils::verify(&[leaf], &root, sibling));
+        assert!(!utils::verify(&[sibling], &root, [3u8; 32]));
+    }
+
+    #[test]
+    fn namespaces_are_appended_and_removed() {
+        let mut namespaces = vec![];
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        utils::append_namespace(&mut namespaces, first, ProgramError::Custom(0)).unwrap();
+        utils::append_namespace(&mut namespaces, second, ProgramError::Custom(0)).unwrap();
+        assert!(utils::append_namespace(&mut namespaces, first, ProgramError::Custom(0)).is_err());
+        assert_eq!(namespaces.len(), 2);
+
+        utils::set_namespace_index(&mut namespaces, &first, Some(3), ProgramError::Custom(0))
+            .unwrap();
+        assert_eq!(namespaces[0].index, Some(3));
+        assert!(utils::remove_namespace(&mut namespaces, &first, ProgramError::Custom(0)).is_err());
+
+        utils::set_namespace_index(&mut namespaces, &first, None, ProgramError::Custom(0)).unwrap();
+        utils::remove_namespace(&mut namespaces, &first, ProgramError::Custom(0)).unwrap();
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].namespace, second);
+        assert!(utils::set_namespace_index(
+            &mut namespaces,
+            &first,
+            Some(1),
+            ProgramError::Custom(0)
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum PermissivenessType {
+    TokenHolder,
+    ParentTokenHolder,
+    UpdateAuthority,
+    Anybody,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Copy)]
+pub enum InheritanceState {
+    NotInherited,
+    Inherited,
+    Overridden,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct NamespaceAndIndex {
+    pub namespace: Pubkey,
+    pub index: Option<u64>,
+    pub inherited: InheritanceState,
+}
+
+impl NamespaceAndIndex {
+    pub const SPACE: usize = 32 + 9 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Callback {
+    pub key: Pubkey,
+    pub code: u64,
+}
+
+impl Callback {
+    pub const SPACE: usize = 32 + 8;
+}
@@ -0,0 +1,211 @@
+#![allow(clippy::result_large_err)]
+
+use crate::{InheritanceState, NamespaceAndIndex, NAMESPACE_ID};
+use anchor_lang::{
+    error::Error,
+    prelude::{msg, AccountInfo, ProgramError, Pubkey, Rent, Result, SolanaSysvar},
+    solana_program::{
+        hash,
+        program::{invoke, invoke_signed},
+        system_instruction,
+    },
+};
+use std::{convert::TryInto, str::FromStr};
+
+// error is the caller's own DerivedKeyInvalid so each program keeps its error codes
+pub fn assert_derivation(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+    path: &[&[u8]],
+    error: impl Into<Error>,
+) -> Result<u8> {
+    let (key, bump) = Pubkey::find_program_address(path, program_id);
+    if key != *account.key {
+        return Err(error.into());
+    }
+    Ok(bump)
+}
+
+/// Create account almost from scratch, lifted from
+/// https://github.com/solana-labs/solana-program-library/blob/7d4873c61721aca25464d42cc5ef651a7923ca79/associated-token-account/program/src/processor.rs#L51-L98
+#[inline(always)]
+pub fn create_or_allocate_account_raw<'a>(
+    program_id: Pubkey,
+    new_account_info: &AccountInfo<'a>,
+    rent_sysvar_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    size: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    let required_lamports = rent
+        .minimum_balance(size)
+        .max(1)
+        .saturating_sub(new_account_info.lamports());
+
+    if required_lamports > 0 {
+        msg!("Transfer {} lamports to the new account", required_lamports);
+        invoke(
+            &system_instruction::transfer(payer_info.key, new_account_info.key, required_lamports),
+            &[
+                payer_info.clone(),
+                new_account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    let accounts = &[new_account_info.clone(), system_program_info.clone()];
+
+    msg!("Allocate space for the account");
+    invoke_signed(
+        &system_instruction::allocate(new_account_info.key, size.try_into().unwrap()),
+        accounts,
+        &[signer_seeds],
+    )?;
+
+    msg!("Assign the account to the owning program");
+    invoke_signed(
+        &system_instruction::assign(new_account_info.key, &program_id),
+        accounts,
+        &[signer_seeds],
+    )?;
+    msg!("Completed assignation!");
+
+    Ok(())
+}
+
+// grows or shrinks the account to required_space, the payer covers or gets back the rent difference
+pub fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    required_space: usize,
+) -> Result<()> {
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(required_space);
+    let current_lamports = account.lamports();
+
+    if rent_exempt_lamports > current_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer.key,
+                account.key,
+                rent_exempt_lamports - current_lamports,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    } else if required_space < account.data_len() {
+        let payer_lamports = payer
+            .lamports()
+            .checked_add(current_lamports - rent_exempt_lamports)
+            .ok_or(ProgramError::InvalidRealloc)?;
+        **account.try_borrow_mut_lamports()? = rent_exempt_lamports;
+        **payer.try_borrow_mut_lamports()? = payer_lamports;
+    }
+
+    account.realloc(required_space, false)?;
+
+    Ok(())
+}
+
+/// Returns true if a `leaf` can be proved to be a part of a Merkle tree
+/// defined by `root`. For this, a `proof` must be provided, containing
+/// sibling hashes on the branch from the leaf to the root of the tree. Each
+/// pair of leaves and each pair of pre-images are assumed to be sorted.
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed_hash = leaf;
+    for proof_element in proof.iter() {
+        if computed_hash <= *proof_element {
+            // Hash(current computed hash + current element of the proof)
+            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
+                &[0x01],
+                &computed_hash,
+                proof_element,
+            ])
+            .0;
+        } else {
+            // Hash(current element of the proof + current computed hash)
+            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
+                &[0x01],
+                proof_element,
+                &computed_hash,
+            ])
+            .0;
+        }
+    }
+    // Check if the computed hash (root) is equal to the provided root
+    computed_hash == *root
+}
+
+pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
+    let preimage = format!("{namespace}:{name}");
+
+    let mut sighash = [0u8; 8];
+    sighash.copy_from_slice(&hash::hash(preimage.as_bytes()).to_bytes()[..8]);
+    sighash
+}
+
+// returns true if the namespace program called the current program
+pub fn is_namespace_program_caller(ixns: &AccountInfo) -> bool {
+    let current_ix =
+        anchor_lang::solana_program::sysvar::instructions::get_instruction_relative(0, ixns)
+            .unwrap();
+
+    if current_ix.program_id != Pubkey::from_str(NAMESPACE_ID).unwrap() {
+        return false;
+    };
+
+    true
+}
+
+// programs that size their accounts to fit append a namespace on join instead of
+// filling a preallocated slot, the caller grows the account by NamespaceAndIndex::SPACE
+pub fn append_namespace(
+    namespaces: &mut Vec<NamespaceAndIndex>,
+    namespace: Pubkey,
+    error: impl Into<Error>,
+) -> Result<()> {
+    if namespaces.iter().any(|ns| ns.namespace == namespace) {
+        return Err(error.into());
+    }
+
+    namespaces.push(NamespaceAndIndex {
+        namespace,
+        index: None,
+        inherited: InheritanceState::NotInherited,
+    });
+
+    Ok(())
+}
+
+// a cached artifact has to be uncached before it can leave
+pub fn remove_namespace(
+    namespaces: &mut Vec<NamespaceAndIndex>,
+    namespace: &Pubkey,
+    error: impl Into<Error>,
+) -> Result<()> {
+    let position = namespaces
+        .iter()
+        .position(|ns| ns.namespace == *namespace && ns.index.is_none())
+        .ok_or_else(|| error.into())?;
+    namespaces.remove(position);
+
+    Ok(())
+}
+
+// index is the cache page, None when the artifact is uncached
+pub fn set_namespace_index(
+    namespaces: &mut [NamespaceAndIndex],
+    namespace: &Pubkey,
+    index: Option<u64>,
+    error: impl Into<Error>,
+) -> Result<()> {
+    let ns = namespaces
+        .iter_mut()
+        .find(|ns| ns.namespace == *namespace)
+        .ok_or_else(|| error.into())?;
+    ns.index = index;
+
+    Ok(())
+}
@@ -0,0 +1,818 @@
+//! Storage layout of the `ItemClassData` kept after the `ItemClass` header.
+//!
+//! Legacy accounts store the borsh settings and config straight after the
+//! header, so reading anything means walking the whole blob with
+//! `get_class_write_offsets`. Versioned accounts put an `ItemClassDataLayout`
+//! there first, recording the length of each section so settings and config
+//! can be read on their own.
+//!
+//! Legacy accounts and version 1 store the config as it was before the
+//! versioned layout: basic item effects without clamps and stacking policies,
+//! and no weight or propagation version. They are read through the `Legacy*`
+//! copies of the config types below, and written back that way until migrated.
+#![allow(clippy::result_large_err)]
+
+use crate::{
+    utils::get_class_write_offsets, Callback, Component, DNPItem, ErrorCode, InheritanceState,
+    ItemClass, ItemClassConfig, ItemClassData, ItemClassDataLayout, ItemClassSettings,
+    ItemClassType, ItemUsage, PermissivenessType, Root, ITEM_CLASS_DATA_LAYOUT_MARKER,
+    ITEM_CLASS_DATA_VERSION,
+};
+use anchor_lang::prelude::*;
+use raindrops_common::LegacyBasicItemEffect;
+use std::{cell::RefCell, convert::TryFrom, io::Write};
+
+// version storing the config as LegacyItemClassConfig
+const LEGACY_CONFIG_VERSION: u8 = 1;
+
+impl ItemClassDataLayout {
+    pub const SIZE: usize = 1 + // marker
+    1 + // version
+    4 + // settings length
+    4; // config length
+
+    fn new(settings_len: usize, config_len: usize) -> Result<Self> {
+        Ok(ItemClassDataLayout {
+            marker: ITEM_CLASS_DATA_LAYOUT_MARKER,
+            version: ITEM_CLASS_DATA_VERSION,
+            settings_len: u32::try_from(settings_len)
+                .map_err(|_| ErrorCode::NumericalOverflowError)?,
+            config_len: u32::try_from(config_len).map_err(|_| ErrorCode::NumericalOverflowError)?,
+        })
+    }
+
+    // legacy accounts start with the free_build option tag here, which is never the marker
+    fn read(data: &[u8], offset: usize) -> Result<Option<Self>> {
+        if data.get(offset) != Some(&ITEM_CLASS_DATA_LAYOUT_MARKER) {
+            return Ok(None);
+        }
+        let header = data
+            .get(offset..offset + Self::SIZE)
+            .ok_or(ErrorCode::ItemClassDataLayoutMismatch)?;
+        let layout: ItemClassDataLayout = AnchorDeserialize::try_from_slice(header)?;
+        require!(
+            (LEGACY_CONFIG_VERSION..=ITEM_CLASS_DATA_VERSION).contains(&layout.version),
+            ErrorCode::UnsupportedItemClassDataVersion
+        );
+        require!(
+            layout.config_range(offset).end <= data.len(),
+            ErrorCode::ItemClassDataLayoutMismatch
+        );
+
+        Ok(Some(layout))
+    }
+
+    // each version stores exactly one settings and config struct, the sections are read in full
+    fn settings(&self, data: &[u8], offset: usize) -> Result<ItemClassSettings> {
+        AnchorDeserialize::try_from_slice(&data[self.settings_range(offset)])
+            .map_err(|_| ErrorCode::ItemClassDataLayoutMismatch.into())
+    }
+
+    fn config(&self, data: &[u8], offset: usize) -> Result<ItemClassConfig> {
+        let bytes = &data[self.config_range(offset)];
+        let config = match self.version {
+            LEGACY_CONFIG_VERSION => {
+                LegacyItemClassConfig::try_from_slice(bytes).map(ItemClassConfig::from)
+            }
+            _ => ItemClassConfig::try_from_slice(bytes),
+        };
+
+        config.map_err(|_| ErrorCode::ItemClassDataLayoutMismatch.into())
+    }
+
+    fn settings_range(&self, offset: usize) -> std::ops::Range<usize> {
+        let start = offset + Self::SIZE;
+        start..start + self.settings_len as usize
+    }
+
+    fn config_range(&self, offset: usize) -> std::ops::Range<usize> {
+        let start = self.settings_range(offset).end;
+        start..start + self.config_len as usize
+    }
+}
+
+impl ItemClass {
+    fn data_offset(&self) -> Result<usize> {
+        Ok(self.try_to_vec()?.len() + 8)
+    }
+
+    /// None for legacy accounts that have not been migrated yet.
+    pub fn item_class_data_layout(
+        &self,
+        data: &RefCell<&mut [u8]>,
+    ) -> Result<Option<ItemClassDataLayout>> {
+        ItemClassDataLayout::read(&data.borrow(), self.data_offset()?)
+    }
+
+    pub fn item_class_data(&self, data: &RefCell<&mut [u8]>) -> Result<ItemClassData> {
+        let offset = self.data_offset()?;
+        if let Some(layout) = ItemClassDataLayout::read(&data.borrow(), offset)? {
+            let bytes = data.borrow();
+            return Ok(ItemClassData {
+                settings: layout.settings(&bytes, offset)?,
+                config: layout.config(&bytes, offset)?,
+            });
+        }
+
+        let (ctr, end_ctr) = get_class_write_offsets(self, data)?;
+
+        //  msg!("Ctr {}->{} {:?}", ctr, end_ctr, &data.borrow());
+        let stored = data.borrow();
+        let bytes = stored
+            .get(ctr as usize..end_ctr as usize)
+            .ok_or(ErrorCode::ItemClassDataLayoutMismatch)?;
+        // unversioned data is only ever the baseline settings and config
+        let (settings, config): (ItemClassSettings, LegacyItemClassConfig) =
+            AnchorDeserialize::try_from_slice(bytes)
+                .map_err(|_| ErrorCode::ItemClassDataLayoutMismatch)?;
+
+        Ok(ItemClassData {
+            settings,
+            config: config.into(),
+        })
+    }
+
+    pub fn item_class_settings(&self, data: &RefCell<&mut [u8]>) -> Result<ItemClassSettings> {
+        let offset = self.data_offset()?;
+        let bytes = data.borrow();
+        match ItemClassDataLayout::read(&bytes, offset)? {
+            Some(layout) => layout.settings(&bytes, offset),
+            // unversioned data only reads as a whole baseline settings and config
+            None => Ok(self.item_class_data(data)?.settings),
+        }
+    }
+
+    pub fn item_class_config(&self, data: &RefCell<&mut [u8]>) -> Result<ItemClassConfig> {
+        let offset = self.data_offset()?;
+        let layout = ItemClassDataLayout::read(&data.borrow(), offset)?;
+        match layout {
+            Some(layout) => layout.config(&data.borrow(), offset),
+            None => Ok(self.item_class_data(data)?.config),
+        }
+    }
+
+    /// Account size needed to hold this header and data in the versioned layout.
+    pub fn versioned_data_space(&self, item_class_data: &ItemClassData) -> Result<usize> {
+        Ok(self.data_offset()? + ItemClassDataLayout::SIZE + item_class_data.try_to_vec()?.len())
+    }
+}
+
+/// Whether the data stored in the account uses the versioned layout. Reads the
+/// stored header, since the in memory one may have been changed by the handler.
+pub fn is_versioned_item_class_data(data: &[u8]) -> Result<bool> {
+    let stored: ItemClass = AnchorDeserialize::deserialize(&mut &data[8..])?;
+    Ok(ItemClassDataLayout::read(data, stored.data_offset()?)?.is_some())
+}
+
+/// Whether the data stored in the account uses the latest layout version.
+pub fn is_current_item_class_data(data: &[u8]) -> Result<bool> {
+    let stored: ItemClass = AnchorDeserialize::deserialize(&mut &data[8..])?;
+    Ok(matches!(
+        ItemClassDataLayout::read(data, stored.data_offset()?)?,
+        Some(layout) if layout.version == ITEM_CLASS_DATA_VERSION
+    ))
+}
+
+/// Writes the header and data after the discriminator, in the latest versioned
+/// layout or the legacy one.
+pub fn write_item_class_data(
+    dst: &mut [u8],
+    item_class: &ItemClass,
+    item_class_data: &ItemClassData,
+    versioned: bool,
+) -> Result<()> {
+    let mut cursor = std::io::Cursor::new(dst);
+    cursor.set_position(8);
+    AnchorSerialize::serialize(item_class, &mut cursor)?;
+
+    if versioned {
+        let settings = item_class_data.settings.try_to_vec()?;
+        let config = item_class_data.config.try_to_vec()?;
+        let layout = ItemClassDataLayout::new(settings.len(), config.len())?;
+        AnchorSerialize::serialize(&layout, &mut cursor)?;
+        cursor.write_all(&settings)?;
+        cursor.write_all(&config)?;
+    } else {
+        let config = item_class_data
+            .config
+            .to_legacy()
+            .ok_or(ErrorCode::ItemClassDataNotMigrated)?;
+        AnchorSerialize::serialize(&item_class_data.settings, &mut cursor)?;
+        AnchorSerialize::serialize(&config, &mut cursor)?;
+    }
+
+    Ok(())
+}
+
+/// `ItemUsage` with its basic item effects in the legacy layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyItemUsage {
+    pub index: u16,
+    pub basic_item_effects: Option<Vec<LegacyBasicItemEffect>>,
+    pub usage_permissiveness: Vec<PermissivenessType>,
+    pub inherited: InheritanceState,
+    pub item_class_type: ItemClassType,
+    pub callback: Option<Callback>,
+    pub validation: Option<Callback>,
+    pub do_not_pair_with_self: bool,
+    pub dnp: Option<Vec<DNPItem>>,
+}
+
+/// `ItemClassConfig` as stored before the versioned layout, without the
+/// weight and propagation version.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyItemClassConfig {
+    pub usage_root: Option<Root>,
+    pub usage_state_root: Option<Root>,
+    pub component_root: Option<Root>,
+    pub usages: Option<Vec<LegacyItemUsage>>,
+    pub components: Option<Vec<Component>>,
+}
+
+impl From<LegacyItemUsage> for ItemUsage {
+    fn from(usage: LegacyItemUsage) -> Self {
+        ItemUsage {
+            index: usage.index,
+            basic_item_effects: usage
+                .basic_item_effects
+                .map(|bies| bies.into_iter().map(Into::into).collect()),
+            usage_permissiveness: usage.usage_permissiveness,
+            inherited: usage.inherited,
+            item_class_type: usage.item_class_type,
+            callback: usage.callback,
+            validation: usage.validation,
+            do_not_pair_with_self: usage.do_not_pair_with_self,
+            dnp: usage.dnp,
+        }
+    }
+}
+
+impl ItemUsage {
+    /// None if an effect uses clamps or a stacking policy.
+    pub fn to_legacy(&self) -> Option<LegacyItemUsage> {
+        let basic_item_effects = match &self.basic_item_effects {
+            Some(bies) => Some(
+                bies.iter()
+                    .map(|bie| bie.to_legacy())
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+
+        Some(LegacyItemUsage {
+            index: self.index,
+            basic_item_effects,
+            usage_permissiveness: self.usage_permissiveness.clone(),
+            inherited: self.inherited,
+            item_class_type: self.item_class_type.clone(),
+            callback: self.callback.clone(),
+            validation: self.validation.clone(),
+            do_not_pair_with_self: self.do_not_pair_with_self,
+            dnp: self.dnp.clone(),
+        })
+    }
+}
+
+impl From<LegacyItemClassConfig> for ItemClassConfig {
+    fn from(config: LegacyItemClassConfig) -> Self {
+        ItemClassConfig {
+            usage_root: config.usage_root,
+            usage_state_root: config.usage_state_root,
+            component_root: config.component_root,
+            usages: config
+                .usages
+                .map(|usages| usages.into_iter().map(Into::into).collect()),
+            components: config.components,
+            weight: None,
+            propagation_version: None,
+        }
+    }
+}
+
+impl ItemClassConfig {
+    /// None if an effect uses clamps or a stacking policy, or the weight or
+    /// propagation version is set.
+    pub fn to_legacy(&self) -> Option<LegacyItemClassConfig> {
+        if self.weight.is_some() || self.propagation_version.is_some() {
+            return None;
+        }
+
+        let usages = match &self.usages {
+            Some(usages) => Some(
+                usages
+                    .iter()
+                    .map(ItemUsage::to_legacy)
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+
+        Some(LegacyItemClassConfig {
+            usage_root: self.usage_root.clone(),
+            usage_state_root: self.usage_state_root.clone(),
+            component_root: self.component_root.clone(),
+            usages,
+            components: self.components.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BasicItemEffect, BasicItemEffectType, Boolean, ChildUpdatePropagationPermissiveness,
+        ChildUpdatePropagationPermissivenessType, ItemUsageType, NamespaceAndIndex, Permissiveness,
+    };
+
+    fn item_class() -> ItemClass {
+        ItemClass {
+            namespaces: Some(vec![NamespaceAndIndex {
+                namespace: Pubkey::new_unique(),
+                index: Some(3),
+                inherited: InheritanceState::NotInherited,
+            }]),
+            parent: Some(Pubkey::new_unique()),
+            mint: Some(Pubkey::new_unique()),
+            index: Some(7),
+            metadata: Some(Pubkey::new_unique()),
+            edition: None,
+            bump: 254,
+            existing_children: 2,
+        }
+    }
+
+    fn empty_settings() -> ItemClassSettings {
+        ItemClassSettings {
+            free_build: None,
+            children_must_be_editions: None,
+            builder_must_be_holder: None,
+            update_permissiveness: None,
+            build_permissiveness: None,
+            staking_warm_up_duration: None,
+            staking_cooldown_duration: None,
+            staking_permissiveness: None,
+            unstaking_permissiveness: None,
+            child_update_propagation_permissiveness: None,
+        }
+    }
+
+    fn config() -> ItemClassConfig {
+        ItemClassConfig {
+            usage_root: Some(Root {
+                inherited: InheritanceState::Inherited,
+                root: [9; 32],
+            }),
+            usage_state_root: None,
+            component_root: None,
+            usages: Some(vec![]),
+            components: None,
+            weight: None,
+            propagation_version: None,
+        }
+    }
+
+    fn config_with_effect(effect: BasicItemEffect) -> ItemClassConfig {
+        ItemClassConfig {
+            usages: Some(vec![ItemUsage {
+                index: 0,
+                basic_item_effects: Some(vec![effect]),
+                usage_permissiveness: vec![PermissivenessType::TokenHolder],
+                inherited: InheritanceState::NotInherited,
+                item_class_type: ItemClassType::Consumable {
+                    max_uses: None,
+                    max_players_per_use: None,
+                    item_usage_type: ItemUsageType::Infinite,
+                    cooldown_duration: None,
+                    warmup_duration: None,
+                },
+                callback: None,
+                validation: None,
+                do_not_pair_with_self: false,
+                dnp: None,
+            }]),
+            ..config()
+        }
+    }
+
+    fn effect(item_effect_type: BasicItemEffectType) -> BasicItemEffect {
+        BasicItemEffect {
+            amount: 4,
+            stat: "strength".to_string(),
+            item_effect_type,
+            active_duration: None,
+            staking_amount_numerator: None,
+            staking_amount_divisor: None,
+            staking_duration_numerator: None,
+            staking_duration_divisor: None,
+            max_uses: Some(2),
+            clamp_min: None,
+            clamp_max: None,
+            stacking_policy: None,
+        }
+    }
+
+    fn boolean(boolean: bool) -> Option<Boolean> {
+        Some(Boolean {
+            inherited: InheritanceState::Overridden,
+            boolean,
+        })
+    }
+
+    fn permissiveness(permissiveness_type: PermissivenessType) -> Option<Vec<Permissiveness>> {
+        Some(vec![Permissiveness {
+            inherited: InheritanceState::NotInherited,
+            permissiveness_type,
+        }])
+    }
+
+    // one settings value per field, each with only that field set
+    fn single_field_settings() -> Vec<ItemClassSettings> {
+        let setters: Vec<fn(&mut ItemClassSettings)> = vec![
+            |s| s.free_build = boolean(true),
+            |s| s.children_must_be_editions = boolean(false),
+            |s| s.builder_must_be_holder = boolean(true),
+            |s| s.update_permissiveness = permissiveness(PermissivenessType::UpdateAuthority),
+            |s| s.build_permissiveness = permissiveness(PermissivenessType::TokenHolder),
+            |s| s.staking_warm_up_duration = Some(60),
+            |s| s.staking_cooldown_duration = Some(u64::MAX),
+            |s| s.staking_permissiveness = permissiveness(PermissivenessType::Anybody),
+            |s| s.unstaking_permissiveness = permissiveness(PermissivenessType::ParentTokenHolder),
+            |s| {
+                s.child_update_propagation_permissiveness =
+                    Some(vec![ChildUpdatePropagationPermissiveness {
+                        overridable: true,
+                        inherited: InheritanceState::Inherited,
+                        child_update_propagation_permissiveness_type:
+                            ChildUpdatePropagationPermissivenessType::StakingPermissiveness,
+                    }])
+            },
+        ];
+
+        setters
+            .into_iter()
+            .map(|set| {
+                let mut settings = empty_settings();
+                set(&mut settings);
+                settings
+            })
+            .collect()
+    }
+
+    fn all_fields_settings() -> ItemClassSettings {
+        let mut settings = empty_settings();
+        for single in single_field_settings() {
+            settings.free_build = settings.free_build.or(single.free_build);
+            settings.children_must_be_editions = settings
+                .children_must_be_editions
+                .or(single.children_must_be_editions);
+            settings.builder_must_be_holder = settings
+                .builder_must_be_holder
+                .or(single.builder_must_be_holder);
+            settings.update_permissiveness = settings
+                .update_permissiveness
+                .or(single.update_permissiveness);
+            settings.build_permissiveness = settings
+                .build_permissiveness
+                .or(single.build_permissiveness);
+            settings.staking_warm_up_duration = settings
+                .staking_warm_up_duration
+                .or(single.staking_warm_up_duration);
+            settings.staking_cooldown_duration = settings
+                .staking_cooldown_duration
+                .or(single.staking_cooldown_duration);
+            settings.staking_permissiveness = settings
+                .staking_permissiveness
+                .or(single.staking_permissiveness);
+            settings.unstaking_permissiveness = settings
+                .unstaking_permissiveness
+                .or(single.unstaking_permissiveness);
+            settings.child_update_propagation_permissiveness = settings
+                .child_update_propagation_permissiveness
+                .or(single.child_update_propagation_permissiveness);
+        }
+        settings
+    }
+
+    fn write(item_class_data: &ItemClassData, versioned: bool) -> Vec<u8> {
+        let mut buf = vec![0; 2048];
+        write_item_class_data(&mut buf, &item_class(), item_class_data, versioned).unwrap();
+        buf
+    }
+
+    fn assert_round_trip(settings: ItemClassSettings, versioned: bool) {
+        let item_class_data = ItemClassData {
+            settings,
+            config: config(),
+        };
+        let mut buf = write(&item_class_data, versioned);
+        let data = RefCell::new(&mut buf[..]);
+        let item_class = item_class();
+
+        let read = item_class.item_class_data(&data).unwrap();
+        assert_eq!(
+            read.try_to_vec().unwrap(),
+            item_class_data.try_to_vec().unwrap()
+        );
+        assert_eq!(
+            item_class
+                .item_class_settings(&data)
+                .unwrap()
+                .try_to_vec()
+                .unwrap(),
+            item_class_data.settings.try_to_vec().unwrap()
+        );
+        assert_eq!(
+            item_class
+                .item_class_config(&data)
+                .unwrap()
+                .try_to_vec()
+                .unwrap(),
+            item_class_data.config.try_to_vec().unwrap()
+        );
+    }
+
+    #[test]
+    fn settings_fields_round_trip_in_versioned_layout() {
+        assert_round_trip(empty_settings(), true);
+        assert_round_trip(all_fields_settings(), true);
+        for settings in single_field_settings() {
+            assert_round_trip(settings, true);
+        }
+    }
+
+    #[test]
+    fn settings_fields_round_trip_in_legacy_layout() {
+        assert_round_trip(empty_settings(), false);
+        assert_round_trip(all_fields_settings(), false);
+        for settings in single_field_settings() {
+            assert_round_trip(settings, false);
+        }
+    }
+
+    #[test]
+    fn layout_is_detected_from_stored_data() {
+        let item_class_data = ItemClassData {
+            settings: all_fields_settings(),
+            config: config(),
+        };
+        let mut legacy = write(&item_class_data, false);
+        let mut versioned = write(&item_class_data, true);
+        assert!(!is_versioned_item_class_data(&legacy).unwrap());
+        assert!(is_versioned_item_class_data(&versioned).unwrap());
+
+        let item_class = item_class();
+        assert!(item_class
+            .item_class_data_layout(&RefCell::new(&mut legacy[..]))
+            .unwrap()
+            .is_none());
+        let layout = item_class
+            .item_class_data_layout(&RefCell::new(&mut versioned[..]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(layout.marker, ITEM_CLASS_DATA_LAYOUT_MARKER);
+        assert_eq!(layout.version, ITEM_CLASS_DATA_VERSION);
+        assert_eq!(
+            layout.settings_len as usize,
+            item_class_data.settings.try_to_vec().unwrap().len()
+        );
+        assert_eq!(
+            layout.config_len as usize,
+            item_class_data.config.try_to_vec().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn migrated_data_matches_legacy_data() {
+        let item_class_data = ItemClassData {
+            settings: all_fields_settings(),
+            config: config(),
+        };
+        let item_class = item_class();
+        let mut buf = write(&item_class_data, false);
+
+        let legacy = item_class
+            .item_class_data(&RefCell::new(&mut buf[..]))
+            .unwrap();
+        write_item_class_data(&mut buf, &item_class, &legacy, true).unwrap();
+        let migrated = item_class
+            .item_class_data(&RefCell::new(&mut buf[..]))
+            .unwrap();
+
+        assert_eq!(
+            migrated.try_to_vec().unwrap(),
+            item_class_data.try_to_vec().unwrap()
+        );
+        assert_eq!(
+            item_class.versioned_data_space(&migrated).unwrap(),
+            8 + item_class.try_to_vec().unwrap().len()
+                + ItemClassDataLayout::SIZE
+                + item_class_data.try_to_vec().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn unknown_layout_version_is_rejected() {
+        let item_class_data = ItemClassData {
+            settings: empty_settings(),
+            config: config(),
+        };
+        let mut buf = write(&item_class_data, true);
+        let item_class = item_class();
+        let offset = item_class.data_offset().unwrap();
+        buf[offset + 1] = ITEM_CLASS_DATA_VERSION + 1;
+
+        assert!(item_class
+            .item_class_data(&RefCell::new(&mut buf[..]))
+            .is_err());
+    }
+
+    // legacy and versioned accounts holding the settings and config bytes given
+    fn stored_data(item_class: &ItemClass, settings: &[u8], config: &[u8]) -> [Vec<u8>; 2] {
+        let mut legacy = item_class.try_to_vec().unwrap();
+        legacy.splice(0..0, vec![0; 8]);
+        legacy.extend(settings);
+        legacy.extend(config);
+
+        let mut versioned = vec![0; item_class.data_offset().unwrap()];
+        versioned[8..].copy_from_slice(&item_class.try_to_vec().unwrap());
+        ItemClassDataLayout::new(settings.len(), config.len())
+            .unwrap()
+            .serialize(&mut versioned)
+            .unwrap();
+        versioned.extend(settings);
+        versioned.extend(config);
+
+        [legacy, versioned]
+    }
+
+    #[test]
+    fn unversioned_data_reads_only_the_baseline_config() {
+        let item_class_data = ItemClassData {
+            settings: all_fields_settings(),
+            config: config(),
+        };
+        let item_class = item_class();
+        let settings = item_class_data.settings.try_to_vec().unwrap();
+        let mut config = item_class_data
+            .config
+            .to_legacy()
+            .unwrap()
+            .try_to_vec()
+            .unwrap();
+        // a weight stored after the baseline config is not part of the legacy layout
+        config.extend([1, 12, 0, 0, 0, 0, 0, 0, 0]);
+
+        let [mut legacy, mut versioned] = stored_data(&item_class, &settings, &config);
+        let read = item_class
+            .item_class_data(&RefCell::new(&mut legacy[..]))
+            .unwrap();
+        assert_eq!(
+            read.try_to_vec().unwrap(),
+            item_class_data.try_to_vec().unwrap()
+        );
+        assert_eq!(read.config.weight, None);
+
+        // the versioned layout records the section lengths, so the extra bytes are rejected
+        let data = RefCell::new(&mut versioned[..]);
+        assert!(item_class.item_class_data(&data).is_err());
+        assert!(item_class.item_class_config(&data).is_err());
+    }
+
+    #[test]
+    fn versioned_data_must_match_its_section_lengths() {
+        let item_class_data = ItemClassData {
+            settings: all_fields_settings(),
+            config: ItemClassConfig {
+                weight: Some(12),
+                propagation_version: Some(3),
+                ..config()
+            },
+        };
+        let item_class = item_class();
+        let settings = item_class_data.settings.try_to_vec().unwrap();
+        let config = item_class_data.config.try_to_vec().unwrap();
+
+        let [_, mut versioned] = stored_data(&item_class, &settings, &config);
+        let read = item_class
+            .item_class_config(&RefCell::new(&mut versioned[..]))
+            .unwrap();
+        assert_eq!(read.weight, Some(12));
+        assert_eq!(read.propagation_version, Some(3));
+
+        // a latest config ending before its weight and propagation version tags is not guessed at
+        let baseline = ItemClassConfig {
+            weight: None,
+            propagation_version: None,
+            ..item_class_data.config
+        }
+        .try_to_vec()
+        .unwrap();
+        let [_, mut short] = stored_data(&item_class, &settings, &baseline[..baseline.len() - 2]);
+        assert!(item_class
+            .item_class_config(&RefCell::new(&mut short[..]))
+            .is_err());
+
+        // nor are section lengths running past the end of the account
+        versioned.truncate(versioned.len() - 1);
+        assert!(item_class
+            .item_class_data(&RefCell::new(&mut versioned[..]))
+            .is_err());
+    }
+
+    #[test]
+    fn version_1_data_reads_effects_without_policies() {
+        let item_class_data = ItemClassData {
+            settings: all_fields_settings(),
+            config: config_with_effect(effect(BasicItemEffectType::Increment)),
+        };
+        let item_class = item_class();
+        let settings = item_class_data.settings.try_to_vec().unwrap();
+        let config = item_class_data
+            .config
+            .to_legacy()
+            .unwrap()
+            .try_to_vec()
+            .unwrap();
+
+        let mut buf = vec![0; item_class.data_offset().unwrap()];
+        buf[8..].copy_from_slice(&item_class.try_to_vec().unwrap());
+        ItemClassDataLayout {
+            version: 1,
+            ..ItemClassDataLayout::new(settings.len(), config.len()).unwrap()
+        }
+        .serialize(&mut buf)
+        .unwrap();
+        buf.extend(&settings);
+        buf.extend(&config);
+        assert!(is_versioned_item_class_data(&buf).unwrap());
+        assert!(!is_current_item_class_data(&buf).unwrap());
+
+        let read = item_class
+            .item_class_data(&RefCell::new(&mut buf[..]))
+            .unwrap();
+        assert_eq!(
+            read.try_to_vec().unwrap(),
+            item_class_data.try_to_vec().unwrap()
+        );
+    }
+
+    #[test]
+    fn weight_and_propagation_version_need_the_latest_layout() {
+        let configs = [
+            ItemClassConfig {
+                weight: Some(12),
+                ..config()
+            },
+            ItemClassConfig {
+                propagation_version: Some(3),
+                ..config()
+            },
+        ];
+        for config in configs {
+            let item_class_data = ItemClassData {
+                settings: empty_settings(),
+                config,
+            };
+            let mut buf = vec![0; 2048];
+
+            assert!(
+                write_item_class_data(&mut buf, &item_class(), &item_class_data, false).is_err()
+            );
+
+            write_item_class_data(&mut buf, &item_class(), &item_class_data, true).unwrap();
+            assert_eq!(
+                item_class()
+                    .item_class_data(&RefCell::new(&mut buf[..]))
+                    .unwrap()
+                    .try_to_vec()
+                    .unwrap(),
+                item_class_data.try_to_vec().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn effect_policies_need_the_latest_layout() {
+        let mut clamp = effect(BasicItemEffectType::Clamp);
+        clamp.clamp_max = Some(10);
+        let item_class_data = ItemClassData {
+            settings: empty_settings(),
+            config: config_with_effect(clamp),
+        };
+        let mut buf = vec![0; 2048];
+
+        assert!(write_item_class_data(&mut buf, &item_class(), &item_class_data, false).is_err());
+
+        write_item_class_data(&mut buf, &item_class(), &item_class_data, true).unwrap();
+        assert!(is_current_item_class_data(&buf).unwrap());
+        assert_eq!(
+            item_class()
+                .item_class_data(&RefCell::new(&mut buf[..]))
+                .unwrap()
+                .try_to_vec()
+                .unwrap(),
+            item_class_data.try_to_vec().unwrap()
+        );
+    }
+}
@@ -1,17 +1,22 @@
+pub mod layout;
+pub mod merkle;
 pub mod utils;
 
+use crate::layout::is_current_item_class_data;
+use crate::merkle::{total_steps_leaf, usage_state_leaf};
 use crate::utils::{
-    assert_builder_must_be_holder_check, assert_is_ata, assert_keys_equal, assert_metadata_valid,
-    assert_mint_authority_matches_mint, assert_permissiveness_access,
-    assert_valid_item_settings_for_edition_type, check_data_for_duplicate_item_effects,
-    close_token_account, get_item_usage, get_item_usage_and_item_usage_state,
-    is_namespace_program_caller, propagate_item_class_data_fields_to_item_data, sighash,
-    spl_token_burn, spl_token_mint_to, spl_token_transfer, transfer_mint_authority,
+    assert_builder_must_be_holder_check, assert_is_ata, assert_item_class_data_fits,
+    assert_keys_equal, assert_metadata_valid, assert_mint_authority_matches_mint,
+    assert_permissiveness_access, assert_valid_item_settings_for_edition_type,
+    check_data_for_duplicate_item_effects, close_token_account, get_item_usage,
+    get_item_usage_and_item_usage_state, is_namespace_program_caller,
+    propagate_item_class_data_fields_to_item_data, resize_account, sighash, spl_token_burn,
+    spl_token_mint_to, spl_token_transfer, transfer_mint_authority,
     update_item_class_with_inherited_information, verify, verify_and_affect_item_state_update,
-    verify_component, verify_cooldown, write_data, AssertPermissivenessAccessArgs,
-    GetItemUsageAndItemUsageStateArgs, GetItemUsageArgs, TokenBurnParams, TokenTransferParams,
-    TransferMintAuthorityArgs, VerifyAndAffectItemStateUpdateArgs, VerifyComponentArgs,
-    VerifyCooldownArgs,
+    verify_component, verify_cooldown, write_data, write_versioned_data,
+    AssertPermissivenessAccessArgs, GetItemUsageAndItemUsageStateArgs, GetItemUsageArgs,
+    TokenBurnParams, TokenTransferParams, TransferMintAuthorityArgs,
+    VerifyAndAffectItemStateUpdateArgs, VerifyComponentArgs, VerifyCooldownArgs,
 };
 use anchor_lang::{
     prelude::*,
@@ -22,6 +27,10 @@ use anchor_lang::{
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use arrayref::array_ref;
+pub use raindrops_common::{
+    BasicItemEffect, BasicItemEffectType, Callback, InheritanceState, NamespaceAndIndex,
+    PermissivenessType, StackingPolicy,
+};
 use std::str::FromStr;
 
 pub mod instructions;
@@ -32,6 +41,11 @@ anchor_lang::declare_id!("itemX1XWs9dK8T2Zca4vEEPfCAhRc7yvYFntPjTTVx6");
 
 pub const PREFIX: &str = "item";
 pub const MARKER: &str = "marker";
+// first byte of the versioned item class data, never a valid option tag
+pub const ITEM_CLASS_DATA_LAYOUT_MARKER: u8 = 0xff;
+// version 1 stores the legacy config, without clamps and stacking policies on basic
+// item effects, weight or propagation version
+pub const ITEM_CLASS_DATA_VERSION: u8 = 2;
 pub const PLAYER_ID: &str = "p1ay5K7mcAZUkzR1ArMLCCQ6C58ULUt7SUi7puGEWc1";
 pub const NAMESPACE_ID: &str = "nameAxQRRBnd4kLfsVoZBBXfrByZdZTkh8mULLxLyqV";
 pub const STAKING_ID: &str = "stk9HFnKhZN2PZjnn5C4wTzmeiAEgsDkbqnHkNjX1Z4";
@@ -284,6 +298,8 @@ pub struct UpdateTokensStakedArgs {
     pub index: u64,
     pub staked: bool,
     pub amount: u64,
+    // amount scaled by the stake's lockup multiplier
+    pub weighted_amount: u64,
 }
 
 #[program]
@@ -459,7 +475,7 @@ pub mod raindrops_item {
         msg!("check_data_for_duplicate_item_effects");
         check_data_for_duplicate_item_effects(&item_class_data)?;
         msg!("write_data");
-        write_data(item_class, &item_class_data)?;
+        write_versioned_data(item_class, &item_class_data)?;
 
         Ok(())
     }
@@ -484,7 +500,7 @@ pub mod raindrops_item {
             item_class.item_class_data(item_class.to_account_info().data.borrow())?;
 
         msg!("assert_permissiveness_access check");
-        let mut new_item_class_data = if let Some(icd) = item_class_data {
+        let mut new_item_class_data = if let Some(mut icd) = item_class_data {
             assert_permissiveness_access(AssertPermissivenessAccessArgs {
                 program_id: ctx.program_id,
                 given_account: &item_class.to_account_info(),
@@ -497,6 +513,8 @@ pub mod raindrops_item {
                 allowed_delegate: None,
             })?;
 
+            // only the inheritance crank moves the propagation version
+            icd.config.propagation_version = original_item_class_data.config.propagation_version;
             icd
         } else {
             original_item_class_data
@@ -1132,14 +1150,11 @@ pub mod raindrops_item {
                         }
                     } else {
                         // Verify the merkle proof.
-                        let node = anchor_lang::solana_program::keccak::hashv(&[
-                            &[0x00],
-                            &total_s.to_le_bytes(),
-                        ]);
+                        let node = total_steps_leaf(total_s);
                         // Proof that the component root has as a leaf the number of steps,
                         // and that the one you sent up matches that
                         require!(
-                            verify(&en_proof, &component_root.root, node.0),
+                            verify(&en_proof, &component_root.root, node),
                             ErrorCode::InvalidProof
                         );
                         require!(
@@ -1698,19 +1713,13 @@ pub mod raindrops_item {
                         return Err(error!(ErrorCode::MustSubmitStatesInOrder));
                     }
                     if state.index != pc.ignore_index {
-                        let node = anchor_lang::solana_program::keccak::hashv(&[
-                            &[0x00],
-                            &AnchorSerialize::try_to_vec(&state)?,
-                        ]);
+                        let node = usage_state_leaf(state)?;
                         // Since these states were not altered by activation, they should be in both.
                         require!(
-                            verify(proof, &usage_state_root.root, node.0),
-                            ErrorCode::InvalidProof
-                        );
-                        require!(
-                            verify(new_proof, &new_root, node.0),
+                            verify(proof, &usage_state_root.root, node),
                             ErrorCode::InvalidProof
                         );
+                        require!(verify(new_proof, &new_root, node), ErrorCode::InvalidProof);
 
                         if state
                             .index
@@ -1948,21 +1957,108 @@ pub mod raindrops_item {
             ErrorCode::MustBeCalledByStakingProgram
         );
 
+        // stake from before lockup tiers carries no weight yet, it counts at 1x
+        if item.weighted_tokens_staked < item.tokens_staked {
+            item.weighted_tokens_staked = item.tokens_staked;
+        }
+
         if args.staked {
             item.tokens_staked = item
                 .tokens_staked
                 .checked_add(args.amount)
                 .ok_or(ErrorCode::NumericalOverflowError)?;
+            item.weighted_tokens_staked = item
+                .weighted_tokens_staked
+                .checked_add(args.weighted_amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
         } else {
             item.tokens_staked = item
                 .tokens_staked
                 .checked_sub(args.amount)
                 .ok_or(ErrorCode::NumericalOverflowError)?;
+            item.weighted_tokens_staked = item
+                .weighted_tokens_staked
+                .checked_sub(args.weighted_amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
         }
 
         Ok(())
     }
 
+    // permissionless crank, runs the same inheritance update as update_item_class
+    // on a batch of children passed as remaining accounts
+    pub fn propagate_item_class_inheritance<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, PropagateItemClassInheritance<'info>>,
+    ) -> Result<()> {
+        let parent = &ctx.accounts.parent;
+        let parent_info = parent.to_account_info();
+        let parent_item_class_data = parent.item_class_data(parent_info.data.borrow())?;
+
+        for child_info in ctx.remaining_accounts {
+            let mut child: Account<'_, ItemClass> = Account::try_from(child_info)?;
+            require!(child.parent == Some(parent.key()), ErrorCode::MustBeChild);
+
+            let original_namespaces = child.namespaces.try_to_vec()?;
+            let original_item_class_data = child.item_class_data(child_info.data.borrow())?;
+            let mut child_item_class_data = original_item_class_data.clone();
+            update_item_class_with_inherited_information(
+                &mut child,
+                &mut child_item_class_data,
+                parent,
+                &parent_item_class_data,
+            );
+
+            if child.namespaces.try_to_vec()? == original_namespaces
+                && child_item_class_data.try_to_vec()? == original_item_class_data.try_to_vec()?
+            {
+                msg!("{} already up to date", child_info.key);
+                continue;
+            }
+
+            child_item_class_data.config.propagation_version = Some(
+                child_item_class_data
+                    .config
+                    .propagation_version
+                    .unwrap_or(0)
+                    .checked_add(1)
+                    .ok_or(ErrorCode::NumericalOverflowError)?,
+            );
+
+            check_data_for_duplicate_item_effects(&child_item_class_data)?;
+            assert_item_class_data_fits(&child, &child_item_class_data)?;
+            write_data(&mut child, &child_item_class_data)?;
+        }
+
+        Ok(())
+    }
+
+    // permissionless, moves a legacy or older versioned item class over to the latest
+    // data layout. the payer tops up rent if the account has to grow to fit it
+    pub fn migrate_item_class_data<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigrateItemClassData<'info>>,
+    ) -> Result<()> {
+        let item_class = &mut ctx.accounts.item_class;
+        let item_class_info = item_class.to_account_info();
+        require!(
+            !is_current_item_class_data(&item_class_info.try_borrow_data()?)?,
+            ErrorCode::ItemClassDataAlreadyMigrated
+        );
+
+        let item_class_data = item_class.item_class_data(&item_class_info.data)?;
+        let required_space = item_class.versioned_data_space(&item_class_data)?;
+
+        if required_space > item_class_info.data_len() {
+            resize_account(
+                &item_class_info,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                required_space,
+            )?;
+        }
+
+        write_versioned_data(item_class, &item_class_data)
+    }
+
     pub fn create_item_class_v1(
         ctx: Context<CreateItemClassV1>,
         args: CreateItemClassV1Args,
@@ -2549,6 +2645,21 @@ pub struct UpdateItemClass<'info> {
     // See the [COMMON REMAINING ACCOUNTS] ctrl f for this
 }
 
+#[derive(Accounts)]
+pub struct PropagateItemClassInheritance<'info> {
+    parent: Account<'info, ItemClass>,
+    // remaining accounts are the child item classes [writable]
+}
+
+#[derive(Accounts)]
+pub struct MigrateItemClassData<'info> {
+    #[account(mut)]
+    item_class: Account<'info, ItemClass>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(args: UpdateItemArgs)]
 pub struct UpdateItem<'info> {
@@ -2948,12 +3059,6 @@ pub struct UpdateTokensStaked<'info> {
     instruction_sysvar_account: UncheckedAccount<'info>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Callback {
-    pub key: Pubkey,
-    pub code: u64,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ItemUsage {
     pub index: u16,
@@ -3004,30 +3109,6 @@ pub enum ItemUsageType {
     Infinite,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
-pub enum BasicItemEffectType {
-    Increment,
-    Decrement,
-    IncrementPercent,
-    DecrementPercent,
-    IncrementPercentFromBase,
-    DecrementPercentFromBase,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct BasicItemEffect {
-    pub amount: u64,
-    pub stat: String,
-    pub item_effect_type: BasicItemEffectType,
-    pub active_duration: Option<u64>,
-    pub staking_amount_numerator: Option<u64>,
-    pub staking_amount_divisor: Option<u64>,
-    pub staking_duration_numerator: Option<u64>,
-    pub staking_duration_divisor: Option<u64>,
-    // point where this effect no longer applies
-    pub max_uses: Option<u64>,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum ComponentCondition {
     Consumed,
@@ -3062,14 +3143,6 @@ pub struct Permissiveness {
     pub permissiveness_type: PermissivenessType,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
-pub enum PermissivenessType {
-    TokenHolder,
-    ParentTokenHolder,
-    UpdateAuthority,
-    Anybody,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct ChildUpdatePropagationPermissiveness {
     pub overridable: bool,
@@ -3091,20 +3164,6 @@ pub enum ChildUpdatePropagationPermissivenessType {
     FreeBuildPermissiveness,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Copy)]
-pub enum InheritanceState {
-    NotInherited,
-    Inherited,
-    Overridden,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct NamespaceAndIndex {
-    pub namespace: Pubkey,
-    pub index: Option<u64>,
-    pub inherited: InheritanceState,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
 pub struct DNPItem {
     pub key: Pubkey,
@@ -3112,6 +3171,7 @@ pub struct DNPItem {
 }
 
 pub const MIN_ITEM_CLASS_SIZE: usize = 8 + // key
+ItemClassDataLayout::SIZE + // data layout
 1 + // mint
 1 + // index
 1 + // metadata
@@ -3247,6 +3307,12 @@ pub struct ItemClassConfig {
     // cached values, and root is source of truth. Up to you to keep them up to date.
     pub usages: Option<Vec<ItemUsage>>,
     pub components: Option<Vec<Component>>,
+    // How heavy one item of this class is when carried in a player's backpack.
+    // Only stored in the latest data layout.
+    pub weight: Option<u64>,
+    // Bumped every time propagate_item_class_inheritance changes this class.
+    // Only stored in the latest data layout.
+    pub propagation_version: Option<u64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -3255,6 +3321,16 @@ pub struct ItemClassData {
     pub config: ItemClassConfig,
 }
 
+/// Written before the settings and config in the versioned layout, so either
+/// section can be found without deserializing the other.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemClassDataLayout {
+    pub marker: u8,
+    pub version: u8,
+    pub settings_len: u32,
+    pub config_len: u32,
+}
+
 #[account]
 pub struct ItemClass {
     pub namespaces: Option<Vec<NamespaceAndIndex>>,
@@ -3268,7 +3344,9 @@ pub struct ItemClass {
     pub edition: Option<Pubkey>,
     pub bump: u8,
     pub existing_children: u64,
-    // item class data is after this, but we cant serialize or deserialize because it blows stacks
+    // item class data is after this, but we cant serialize or deserialize because it blows stacks.
+    // It is either an ItemClassDataLayout followed by the settings and config, or for accounts
+    // created before the layout existed, the settings and config directly. See layout.rs.
 }
 
 #[account]
@@ -3320,7 +3398,8 @@ pub const MIN_ITEM_SIZE: usize = 8 + // key
 1 + // item usage states
 1 + // root
 8 + // unique tokens staked
-1; //bump
+1 + //bump
+8; // weighted tokens staked
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ItemData {
@@ -3369,6 +3448,9 @@ pub struct Item {
     pub bump: u8,
     pub tokens_staked: u64,
     pub data: ItemData,
+    // tokens_staked scaled by each stake's lockup multiplier, kept last so it
+    // reads from the spare space of existing item accounts
+    pub weighted_tokens_staked: u64,
 }
 
 #[error_code]
@@ -3511,4 +3593,16 @@ pub enum ErrorCode {
     CannotEffectTheSameStatTwice,
     #[msg("Cannot mint an SFT without mint auth")]
     MintAuthorityRequiredForSFTs,
+    #[msg("Item class account is too small for the propagated data")]
+    NotEnoughSpaceForPropagation,
+    #[msg("Unsupported item class data layout version")]
+    UnsupportedItemClassDataVersion,
+    #[msg("Item class data already uses the latest layout version")]
+    ItemClassDataAlreadyMigrated,
+    #[msg("Clamp effects need a min or max, and min cannot be above max")]
+    InvalidClampBounds,
+    #[msg("Item class data must be migrated before using clamps, stacking policies, weight or propagation versions")]
+    ItemClassDataNotMigrated,
+    #[msg("Item class data does not match its layout")]
+    ItemClassDataLayoutMismatch,
 }
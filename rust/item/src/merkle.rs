@@ -0,0 +1,752 @@
+//! Leaf hashing and off-chain tree building for the legacy item class roots.
+//!
+//! `ItemClassConfig::usage_root`, `ItemClassConfig::usage_state_root` and
+//! `ItemClassConfig::component_root` replace the on-account lists with a
+//! merkle root. The program only ever checks proofs with `utils::verify`, so
+//! the leaf functions below are what the instructions hash, and the trees are
+//! what clients use to produce roots and proofs for them.
+#![allow(clippy::result_large_err)]
+
+use crate::{
+    utils::verify, Component, CraftUsageInfo, ErrorCode, ItemUsage, ItemUsageState, UsageInfo,
+};
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
+use std::convert::TryFrom;
+
+/// Leaf checked against `usage_root` when activating an item.
+///
+/// The usage is hashed as a borsh `Option`, hence the `0x01` tag.
+pub fn usage_leaf(usage: &ItemUsage) -> Result<[u8; 32]> {
+    Ok(hashv(&[&[0x00], &[0x01], &AnchorSerialize::try_to_vec(usage)?]).0)
+}
+
+/// Leaf checked against the crafting item class's `usage_root` for cooldown components.
+pub fn craft_usage_leaf(item_class: &Pubkey, usage: &ItemUsage) -> Result<[u8; 32]> {
+    Ok(hashv(&[
+        &[0x00],
+        &item_class.to_bytes(),
+        &AnchorSerialize::try_to_vec(usage)?,
+    ])
+    .0)
+}
+
+/// Checks a usage against `usage_root`. Roots built before clamps and stacking
+/// policies hash usages in the legacy layout, so a usage using neither is also
+/// checked that way.
+pub fn verify_usage(proof: &[[u8; 32]], root: &[u8; 32], usage: &ItemUsage) -> Result<bool> {
+    if verify(proof, root, usage_leaf(usage)?) {
+        return Ok(true);
+    }
+    Ok(match usage.to_legacy() {
+        Some(legacy) => verify(
+            proof,
+            root,
+            hashv(&[&[0x00], &[0x01], &AnchorSerialize::try_to_vec(&legacy)?]).0,
+        ),
+        None => false,
+    })
+}
+
+/// `verify_usage` for `craft_usage_leaf`s.
+pub fn verify_craft_usage(
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+    item_class: &Pubkey,
+    usage: &ItemUsage,
+) -> Result<bool> {
+    if verify(proof, root, craft_usage_leaf(item_class, usage)?) {
+        return Ok(true);
+    }
+    Ok(match usage.to_legacy() {
+        Some(legacy) => verify(
+            proof,
+            root,
+            hashv(&[
+                &[0x00],
+                &item_class.to_bytes(),
+                &AnchorSerialize::try_to_vec(&legacy)?,
+            ])
+            .0,
+        ),
+        None => false,
+    })
+}
+
+/// Leaf checked against `usage_state_root` when activating or proving an item.
+pub fn usage_state_leaf(usage_state: &ItemUsageState) -> Result<[u8; 32]> {
+    Ok(hashv(&[&[0x00], &AnchorSerialize::try_to_vec(usage_state)?]).0)
+}
+
+/// Leaf checked against the crafting item's `usage_state_root` for cooldown components.
+pub fn craft_usage_state_leaf(item: &Pubkey, usage_state: &ItemUsageState) -> Result<[u8; 32]> {
+    Ok(hashv(&[
+        &[0x00],
+        &item.to_bytes(),
+        &AnchorSerialize::try_to_vec(usage_state)?,
+    ])
+    .0)
+}
+
+/// Leaf recording how many usage states a `usage_state_root` commits to.
+pub fn total_states_leaf(total_states: u16) -> [u8; 32] {
+    hashv(&[&[0x00], &total_states.to_le_bytes()]).0
+}
+
+/// Leaf checked against `component_root` when adding a craft item to an escrow.
+pub fn component_leaf(
+    step: u64,
+    craft_item_mint: &Pubkey,
+    component: &Component,
+) -> Result<[u8; 32]> {
+    Ok(hashv(&[
+        &[0x00],
+        &step.to_le_bytes(),
+        &craft_item_mint.to_bytes(),
+        &AnchorSerialize::try_to_vec(component)?,
+    ])
+    .0)
+}
+
+/// Leaf recording the number of steps in a component scope, checked when
+/// completing an escrow build phase.
+pub fn total_steps_leaf(total_steps: u64) -> [u8; 32] {
+    hashv(&[&[0x00], &total_steps.to_le_bytes()]).0
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    if left <= right {
+        hashv(&[&[0x01], left, right]).0
+    } else {
+        hashv(&[&[0x01], right, left]).0
+    }
+}
+
+/// Binary merkle tree with sorted pair hashing, matching `utils::verify`.
+///
+/// An unpaired node at the end of a layer is carried up unchanged, so its
+/// proof simply skips that layer. An empty tree has an all-zero root.
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        MerkleTree { layers }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or([0; 32])
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.layers[0]
+    }
+
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.leaves().len() {
+            return None;
+        }
+        let mut proof = vec![];
+        let mut position = index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(proof)
+    }
+
+    pub fn verify(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        verify(proof, &self.root(), leaf)
+    }
+}
+
+/// Tree for `usage_root`.
+///
+/// Always holds one `usage_leaf` per usage. When the item class key is given it
+/// also holds the `craft_usage_leaf`s needed to use the class as a cooldown
+/// component elsewhere.
+pub struct UsageTree {
+    tree: MerkleTree,
+    usages: Vec<ItemUsage>,
+    item_class: Option<Pubkey>,
+}
+
+impl UsageTree {
+    pub fn new(usages: Vec<ItemUsage>, item_class: Option<Pubkey>) -> Result<Self> {
+        let mut leaves = usages.iter().map(usage_leaf).collect::<Result<Vec<_>>>()?;
+        if let Some(key) = &item_class {
+            for usage in &usages {
+                leaves.push(craft_usage_leaf(key, usage)?);
+            }
+        }
+        Ok(UsageTree {
+            tree: MerkleTree::new(leaves),
+            usages,
+            item_class,
+        })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    fn position(&self, usage_index: u16) -> Option<usize> {
+        self.usages.iter().position(|u| u.index == usage_index)
+    }
+
+    pub fn usage(&self, usage_index: u16) -> Option<&ItemUsage> {
+        self.position(usage_index).map(|i| &self.usages[i])
+    }
+
+    pub fn usage_proof(&self, usage_index: u16) -> Option<Vec<[u8; 32]>> {
+        self.tree.proof(self.position(usage_index)?)
+    }
+
+    pub fn craft_usage_proof(&self, usage_index: u16) -> Option<Vec<[u8; 32]>> {
+        self.item_class?;
+        self.tree
+            .proof(self.usages.len() + self.position(usage_index)?)
+    }
+}
+
+/// Tree for `usage_state_root`.
+///
+/// Holds one `usage_state_leaf` per state followed by the `total_states_leaf`.
+/// When the item key is given it also holds the `craft_usage_state_leaf`s
+/// needed to use the item as a cooldown component.
+pub struct UsageStateTree {
+    tree: MerkleTree,
+    usage_states: Vec<ItemUsageState>,
+    item: Option<Pubkey>,
+}
+
+impl UsageStateTree {
+    pub fn new(usage_states: Vec<ItemUsageState>, item: Option<Pubkey>) -> Result<Self> {
+        let total_states =
+            u16::try_from(usage_states.len()).map_err(|_| ErrorCode::NumericalOverflowError)?;
+        let mut leaves = usage_states
+            .iter()
+            .map(usage_state_leaf)
+            .collect::<Result<Vec<_>>>()?;
+        leaves.push(total_states_leaf(total_states));
+        if let Some(key) = &item {
+            for usage_state in &usage_states {
+                leaves.push(craft_usage_state_leaf(key, usage_state)?);
+            }
+        }
+        Ok(UsageStateTree {
+            tree: MerkleTree::new(leaves),
+            usage_states,
+            item,
+        })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    pub fn total_states(&self) -> u16 {
+        self.usage_states.len() as u16
+    }
+
+    fn position(&self, usage_index: u16) -> Option<usize> {
+        self.usage_states
+            .iter()
+            .position(|s| s.index == usage_index)
+    }
+
+    pub fn usage_state(&self, usage_index: u16) -> Option<&ItemUsageState> {
+        self.position(usage_index).map(|i| &self.usage_states[i])
+    }
+
+    pub fn usage_state_proof(&self, usage_index: u16) -> Option<Vec<[u8; 32]>> {
+        self.tree.proof(self.position(usage_index)?)
+    }
+
+    pub fn total_states_proof(&self) -> Vec<[u8; 32]> {
+        self.tree.proof(self.usage_states.len()).unwrap()
+    }
+
+    pub fn craft_usage_state_proof(&self, usage_index: u16) -> Option<Vec<[u8; 32]>> {
+        self.item?;
+        self.tree
+            .proof(self.usage_states.len() + 1 + self.position(usage_index)?)
+    }
+}
+
+/// Builds the `UsageInfo` for activating `usage_index`, given the item's
+/// current states and the states after the activation has been applied.
+pub fn build_usage_info(
+    usages: &UsageTree,
+    usage_states: &UsageStateTree,
+    new_usage_states: &UsageStateTree,
+    usage_index: u16,
+) -> Option<UsageInfo> {
+    Some(UsageInfo {
+        usage_proof: usages.usage_proof(usage_index)?,
+        usage: usages.usage(usage_index)?.clone(),
+        usage_state_proof: usage_states.usage_state_proof(usage_index)?,
+        usage_state: usage_states.usage_state(usage_index)?.clone(),
+        new_usage_state_proof: new_usage_states.usage_state_proof(usage_index)?,
+        new_usage_state_root: new_usage_states.root(),
+        total_states: usage_states.total_states(),
+        total_states_proof: usage_states.total_states_proof(),
+        new_total_states_proof: new_usage_states.total_states_proof(),
+    })
+}
+
+/// Builds the `CraftUsageInfo` for using a crafting item's `usage_index` as a
+/// cooldown component. Both trees must have been built with their keys.
+pub fn build_craft_usage_info(
+    craft_usages: &UsageTree,
+    craft_usage_states: &UsageStateTree,
+    usage_index: u16,
+) -> Option<CraftUsageInfo> {
+    Some(CraftUsageInfo {
+        craft_usage_state_proof: craft_usage_states.craft_usage_state_proof(usage_index)?,
+        craft_usage_state: craft_usage_states.usage_state(usage_index)?.clone(),
+        craft_usage_proof: craft_usages.craft_usage_proof(usage_index)?,
+        craft_usage: craft_usages.usage(usage_index)?.clone(),
+    })
+}
+
+struct ComponentEntry {
+    component_scope: String,
+    step: u64,
+    craft_item_mint: Pubkey,
+}
+
+/// Tree for `component_root`.
+///
+/// Components are numbered by their position within their `component_scope`,
+/// the same way escrow steps walk a component list. Each step gets one
+/// `component_leaf` per craft item mint that may fill it, and every distinct
+/// scope length gets a `total_steps_leaf`.
+pub struct ComponentTree {
+    tree: MerkleTree,
+    entries: Vec<ComponentEntry>,
+    total_steps: Vec<u64>,
+    components: Vec<Component>,
+}
+
+impl ComponentTree {
+    /// Uses each component's class mint as the craft item mint, which is the
+    /// case for fungible components.
+    pub fn new(components: Vec<Component>) -> Result<Self> {
+        Self::with_craft_item_mints(components, |c| vec![c.mint])
+    }
+
+    pub fn with_craft_item_mints<F>(components: Vec<Component>, craft_item_mints: F) -> Result<Self>
+    where
+        F: Fn(&Component) -> Vec<Pubkey>,
+    {
+        let mut leaves = vec![];
+        let mut entries = vec![];
+        let mut scope_lengths: Vec<(String, u64)> = vec![];
+        for component in &components {
+            let step = match scope_lengths
+                .iter_mut()
+                .find(|(scope, _)| *scope == component.component_scope)
+            {
+                Some((_, length)) => {
+                    *length += 1;
+                    *length - 1
+                }
+                None => {
+                    scope_lengths.push((component.component_scope.clone(), 1));
+                    0
+                }
+            };
+            for mint in craft_item_mints(component) {
+                leaves.push(component_leaf(step, &mint, component)?);
+                entries.push(ComponentEntry {
+                    component_scope: component.component_scope.clone(),
+                    step,
+                    craft_item_mint: mint,
+                });
+            }
+        }
+
+        let mut total_steps: Vec<u64> = vec![];
+        for (_, length) in &scope_lengths {
+            if !total_steps.contains(length) {
+                total_steps.push(*length);
+                leaves.push(total_steps_leaf(*length));
+            }
+        }
+
+        Ok(ComponentTree {
+            tree: MerkleTree::new(leaves),
+            entries,
+            total_steps,
+            components,
+        })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    /// The component filling `step` of `component_scope`.
+    pub fn component(&self, component_scope: &str, step: u64) -> Option<&Component> {
+        self.components
+            .iter()
+            .filter(|c| c.component_scope == component_scope)
+            .nth(usize::try_from(step).ok()?)
+    }
+
+    pub fn component_proof(
+        &self,
+        component_scope: &str,
+        step: u64,
+        craft_item_mint: &Pubkey,
+    ) -> Option<Vec<[u8; 32]>> {
+        let position = self.entries.iter().position(|e| {
+            e.component_scope == component_scope
+                && e.step == step
+                && e.craft_item_mint == *craft_item_mint
+        })?;
+        self.tree.proof(position)
+    }
+
+    pub fn total_steps(&self, component_scope: &str) -> u64 {
+        self.components
+            .iter()
+            .filter(|c| c.component_scope == component_scope)
+            .count() as u64
+    }
+
+    /// Proof for the `total_steps_leaf` of `component_scope`, sent as the
+    /// end node proof when completing a build phase.
+    pub fn total_steps_proof(&self, component_scope: &str) -> Option<Vec<[u8; 32]>> {
+        let total_steps = self.total_steps(component_scope);
+        let position = self.total_steps.iter().position(|t| *t == total_steps)?;
+        self.tree.proof(self.entries.len() + position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        utils::enact_valid_state_change, BasicItemEffect, BasicItemEffectType, ComponentCondition,
+        InheritanceState, ItemClassType, ItemUsageType, StackingPolicy,
+    };
+
+    fn usage(index: u16) -> ItemUsage {
+        ItemUsage {
+            index,
+            basic_item_effects: None,
+            usage_permissiveness: vec![],
+            inherited: InheritanceState::NotInherited,
+            item_class_type: ItemClassType::Consumable {
+                max_uses: Some(3),
+                max_players_per_use: None,
+                item_usage_type: ItemUsageType::Exhaustion,
+                cooldown_duration: Some(10),
+                warmup_duration: None,
+            },
+            callback: None,
+            validation: None,
+            do_not_pair_with_self: false,
+            dnp: None,
+        }
+    }
+
+    fn usage_state(index: u16, uses: u64) -> ItemUsageState {
+        ItemUsageState {
+            index,
+            uses,
+            activated_at: None,
+        }
+    }
+
+    fn component(mint: Pubkey, component_scope: &str) -> Component {
+        Component {
+            mint,
+            class_index: 0,
+            amount: 1,
+            time_to_build: None,
+            component_scope: component_scope.to_string(),
+            use_usage_index: 0,
+            condition: ComponentCondition::Consumed,
+            inherited: InheritanceState::NotInherited,
+        }
+    }
+
+    #[test]
+    fn test_merkle_tree_proofs() {
+        for size in 0..10u8 {
+            let leaves: Vec<[u8; 32]> = (0..size).map(|i| hashv(&[&[i]]).0).collect();
+            let tree = MerkleTree::new(leaves.clone());
+            if size == 0 {
+                assert_eq!(tree.root(), [0; 32]);
+            }
+            if size == 1 {
+                assert_eq!(tree.root(), leaves[0]);
+            }
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(verify(&proof, &tree.root(), *leaf));
+                assert!(!verify(&proof, &tree.root(), hashv(&[&[0xff]]).0));
+            }
+            assert!(tree.proof(size as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn test_usage_leaf_matches_option_encoding() {
+        let u = usage(0);
+        let expected = hashv(&[
+            &[0x00],
+            &AnchorSerialize::try_to_vec(&Some(u.clone())).unwrap(),
+        ])
+        .0;
+        assert_eq!(usage_leaf(&u).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_roots_built_before_effect_policies_still_verify() {
+        let class = Pubkey::new_unique();
+        let mut u = usage(0);
+        u.basic_item_effects = Some(vec![BasicItemEffect {
+            amount: 5,
+            stat: "strength".to_string(),
+            item_effect_type: BasicItemEffectType::Increment,
+            active_duration: None,
+            staking_amount_numerator: None,
+            staking_amount_divisor: None,
+            staking_duration_numerator: None,
+            staking_duration_divisor: None,
+            max_uses: None,
+            clamp_min: None,
+            clamp_max: None,
+            stacking_policy: None,
+        }]);
+        let legacy = AnchorSerialize::try_to_vec(&u.to_legacy().unwrap()).unwrap();
+        let tree = MerkleTree::new(vec![
+            hashv(&[&[0x00], &[0x01], &legacy]).0,
+            hashv(&[&[0x00], &class.to_bytes(), &legacy]).0,
+        ]);
+
+        assert!(verify_usage(&tree.proof(0).unwrap(), &tree.root(), &u).unwrap());
+        assert!(verify_craft_usage(&tree.proof(1).unwrap(), &tree.root(), &class, &u).unwrap());
+
+        // a usage with a stacking policy cannot be what the old root hashed
+        u.basic_item_effects.as_mut().unwrap()[0].stacking_policy = Some(StackingPolicy::Stack);
+        assert!(!verify_usage(&tree.proof(0).unwrap(), &tree.root(), &u).unwrap());
+    }
+
+    #[test]
+    fn test_usage_tree() {
+        let class = Pubkey::new_unique();
+        let tree = UsageTree::new(vec![usage(0), usage(1), usage(2)], Some(class)).unwrap();
+        for index in 0..3 {
+            let u = tree.usage(index).unwrap();
+            assert!(verify(
+                &tree.usage_proof(index).unwrap(),
+                &tree.root(),
+                usage_leaf(u).unwrap()
+            ));
+            assert!(verify(
+                &tree.craft_usage_proof(index).unwrap(),
+                &tree.root(),
+                craft_usage_leaf(&class, u).unwrap()
+            ));
+            assert!(!verify(
+                &tree.craft_usage_proof(index).unwrap(),
+                &tree.root(),
+                craft_usage_leaf(&Pubkey::new_unique(), u).unwrap()
+            ));
+        }
+        assert!(tree.usage_proof(3).is_none());
+
+        let unkeyed = UsageTree::new(vec![usage(0)], None).unwrap();
+        assert!(unkeyed.craft_usage_proof(0).is_none());
+    }
+
+    #[test]
+    fn test_usage_state_tree() {
+        let item = Pubkey::new_unique();
+        let states = vec![usage_state(0, 0), usage_state(1, 2)];
+        let tree = UsageStateTree::new(states, Some(item)).unwrap();
+        assert_eq!(tree.total_states(), 2);
+        assert!(verify(
+            &tree.total_states_proof(),
+            &tree.root(),
+            total_states_leaf(2)
+        ));
+        for index in 0..2 {
+            let s = tree.usage_state(index).unwrap();
+            assert!(verify(
+                &tree.usage_state_proof(index).unwrap(),
+                &tree.root(),
+                usage_state_leaf(s).unwrap()
+            ));
+            assert!(verify(
+                &tree.craft_usage_state_proof(index).unwrap(),
+                &tree.root(),
+                craft_usage_state_leaf(&item, s).unwrap()
+            ));
+        }
+        assert!(!verify(
+            &tree.usage_state_proof(1).unwrap(),
+            &tree.root(),
+            usage_state_leaf(&usage_state(1, 3)).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_build_usage_info() {
+        let usages = UsageTree::new(vec![usage(0), usage(1)], None).unwrap();
+        let states = vec![usage_state(0, 0), usage_state(1, 1)];
+        let mut new_states = states.clone();
+        enact_valid_state_change(&mut new_states[1], usages.usage(1).unwrap(), 100).unwrap();
+
+        let old_tree = UsageStateTree::new(states, None).unwrap();
+        let new_tree = UsageStateTree::new(new_states, None).unwrap();
+        let info = build_usage_info(&usages, &old_tree, &new_tree, 1).unwrap();
+
+        // The same checks verify_and_affect_item_state_update performs.
+        assert!(verify(
+            &info.usage_proof,
+            &usages.root(),
+            usage_leaf(&info.usage).unwrap()
+        ));
+        assert!(verify(
+            &info.usage_state_proof,
+            &old_tree.root(),
+            usage_state_leaf(&info.usage_state).unwrap()
+        ));
+        assert!(verify(
+            &info.total_states_proof,
+            &old_tree.root(),
+            total_states_leaf(info.total_states)
+        ));
+        assert!(verify(
+            &info.new_total_states_proof,
+            &info.new_usage_state_root,
+            total_states_leaf(info.total_states)
+        ));
+        let mut enacted = info.usage_state.clone();
+        enact_valid_state_change(&mut enacted, &info.usage, 100).unwrap();
+        assert!(verify(
+            &info.new_usage_state_proof,
+            &info.new_usage_state_root,
+            usage_state_leaf(&enacted).unwrap()
+        ));
+
+        // States untouched by the activation are provable against both roots.
+        let untouched = old_tree.usage_state(0).unwrap();
+        assert!(verify(
+            &new_tree.usage_state_proof(0).unwrap(),
+            &new_tree.root(),
+            usage_state_leaf(untouched).unwrap()
+        ));
+
+        assert!(build_usage_info(&usages, &old_tree, &new_tree, 2).is_none());
+    }
+
+    #[test]
+    fn test_build_craft_usage_info() {
+        let class = Pubkey::new_unique();
+        let item = Pubkey::new_unique();
+        let usages = UsageTree::new(vec![usage(0)], Some(class)).unwrap();
+        let states = UsageStateTree::new(vec![usage_state(0, 1)], Some(item)).unwrap();
+        let info = build_craft_usage_info(&usages, &states, 0).unwrap();
+
+        // The same checks verify_cooldown performs.
+        assert!(verify(
+            &info.craft_usage_state_proof,
+            &states.root(),
+            craft_usage_state_leaf(&item, &info.craft_usage_state).unwrap()
+        ));
+        assert!(verify(
+            &info.craft_usage_proof,
+            &usages.root(),
+            craft_usage_leaf(&class, &info.craft_usage).unwrap()
+        ));
+
+        let unkeyed = UsageStateTree::new(vec![usage_state(0, 1)], None).unwrap();
+        assert!(build_craft_usage_info(&usages, &unkeyed, 0).is_none());
+    }
+
+    #[test]
+    fn test_component_tree() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let nft = Pubkey::new_unique();
+        let components = vec![
+            component(a, "sword"),
+            component(b, "sword"),
+            component(a, "shield"),
+            component(b, "sword"),
+        ];
+        let tree = ComponentTree::with_craft_item_mints(components, |c| {
+            if c.mint == a {
+                vec![a, nft]
+            } else {
+                vec![c.mint]
+            }
+        })
+        .unwrap();
+
+        assert_eq!(tree.total_steps("sword"), 3);
+        assert_eq!(tree.total_steps("shield"), 1);
+        assert_eq!(tree.total_steps("axe"), 0);
+
+        for (scope, step, mint) in [
+            ("sword", 0, a),
+            ("sword", 0, nft),
+            ("sword", 1, b),
+            ("sword", 2, b),
+            ("shield", 0, a),
+            ("shield", 0, nft),
+        ] {
+            let c = tree.component(scope, step).unwrap();
+            assert!(verify(
+                &tree.component_proof(scope, step, &mint).unwrap(),
+                &tree.root(),
+                component_leaf(step, &mint, c).unwrap()
+            ));
+        }
+        assert!(tree.component_proof("sword", 1, &nft).is_none());
+        assert!(tree.component_proof("sword", 3, &b).is_none());
+
+        for scope in ["sword", "shield"] {
+            assert!(verify(
+                &tree.total_steps_proof(scope).unwrap(),
+                &tree.root(),
+                total_steps_leaf(tree.total_steps(scope))
+            ));
+        }
+        assert!(tree.total_steps_proof("axe").is_none());
+
+        let fungible = ComponentTree::new(vec![component(a, "sword")]).unwrap();
+        assert!(verify(
+            &fungible.component_proof("sword", 0, &a).unwrap(),
+            &fungible.root(),
+            component_leaf(0, &a, fungible.component("sword", 0).unwrap()).unwrap()
+        ));
+    }
+}
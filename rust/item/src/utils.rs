@@ -1,25 +1,33 @@
 use std::str::FromStr;
 
+pub use raindrops_common::utils::{
+    assert_derivation, create_or_allocate_account_raw, is_namespace_program_caller, resize_account,
+    sighash, verify,
+};
+
 use crate::{
-    ChildUpdatePropagationPermissivenessType, Component, CraftUsageInfo, ErrorCode,
-    InheritanceState, Inherited, Item, ItemActivationMarker, ItemActivationMarkerProofCounter,
-    ItemClass, ItemClassData, ItemClassType, ItemEscrow, ItemUsage, ItemUsageState, ItemUsageType,
-    NamespaceAndIndex, Permissiveness, PermissivenessType, UsageInfo, NAMESPACE_ID, PLAYER_ID,
-    PREFIX,
+    layout::{is_versioned_item_class_data, write_item_class_data},
+    merkle::{
+        component_leaf, craft_usage_state_leaf, total_states_leaf, usage_state_leaf,
+        verify_craft_usage, verify_usage,
+    },
+    BasicItemEffectType, ChildUpdatePropagationPermissivenessType, Component, CraftUsageInfo,
+    ErrorCode, InheritanceState, Inherited, Item, ItemActivationMarker,
+    ItemActivationMarkerProofCounter, ItemClass, ItemClassData, ItemClassDataLayout, ItemClassType,
+    ItemEscrow, ItemUsage, ItemUsageState, ItemUsageType, NamespaceAndIndex, Permissiveness,
+    PermissivenessType, UsageInfo, PLAYER_ID, PREFIX,
 };
 use anchor_lang::{
     error,
     prelude::{
-        msg, Account, AccountInfo, AnchorDeserialize, AnchorSerialize, Program, ProgramError,
-        Pubkey, Rent, Result, SolanaSysvar, System, Sysvar, UncheckedAccount,
+        msg, Account, AccountInfo, AnchorSerialize, Program, ProgramError, Pubkey, Rent, Result,
+        System, Sysvar, UncheckedAccount,
     },
     require,
     solana_program::{
-        hash,
-        program::{invoke, invoke_signed},
+        program::invoke_signed,
         program_option::COption,
         program_pack::{IsInitialized, Pack},
-        system_instruction,
     },
     Key, ToAccountInfo,
 };
@@ -27,19 +35,7 @@ use anchor_spl::token::{Mint, Token};
 use arrayref::array_ref;
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::instruction::{close_account, initialize_account2, set_authority, AuthorityType};
-use std::{cell::RefCell, convert::TryInto};
-
-impl ItemClass {
-    pub fn item_class_data(&self, data: &RefCell<&mut [u8]>) -> Result<ItemClassData> {
-        let (ctr, end_ctr) = get_class_write_offsets(self, data)?;
-
-        //  msg!("Ctr {}->{} {:?}", ctr, end_ctr, &data.borrow());
-        let item_class_data: ItemClassData =
-            AnchorDeserialize::try_from_slice(&data.borrow()[ctr as usize..end_ctr as usize])?;
-
-        Ok(item_class_data)
-    }
-}
+use std::cell::RefCell;
 
 pub fn assert_initialized<T: Pack + IsInitialized>(account_info: &AccountInfo) -> Result<T> {
     let account: T = T::unpack_unchecked(&account_info.data.borrow())?;
@@ -73,6 +69,7 @@ pub struct TokenTransferParams<'a: 'b, 'b> {
     pub token_program: AccountInfo<'a>,
 }
 
+// walks data in the legacy layout, see layout.rs
 pub fn get_class_write_offsets(
     item_class: &ItemClass,
     item_data: &RefCell<&mut [u8]>,
@@ -161,21 +158,21 @@ pub fn get_class_write_offsets(
     // ItemClassConfig
     // usage_root
     if data[end_ctr] == 1 {
-        end_ctr += 33;
+        end_ctr += 34; // option, inherited, root
     } else {
         end_ctr += 1;
     }
 
     // usage_state_root
     if data[end_ctr] == 1 {
-        end_ctr += 33;
+        end_ctr += 34; // option, inherited, root
     } else {
         end_ctr += 1;
     }
 
     // component_root
     if data[end_ctr] == 1 {
-        end_ctr += 33;
+        end_ctr += 34; // option, inherited, root
     } else {
         end_ctr += 1;
     }
@@ -376,15 +373,38 @@ pub fn get_class_write_offsets(
     Ok((ctr as u64, end_ctr as u64))
 }
 
+// A stat may take one numeric change, one Set and one Clamp from the same usage,
+// so a usage can e.g. add to a stat and also cap it.
+fn item_effect_slot(item_effect_type: BasicItemEffectType) -> u8 {
+    match item_effect_type {
+        BasicItemEffectType::Set => 1,
+        BasicItemEffectType::Clamp => 2,
+        _ => 0,
+    }
+}
+
 pub fn check_data_for_duplicate_item_effects(item_class_data: &ItemClassData) -> Result<()> {
     if let Some(usages) = &item_class_data.config.usages {
         for usage in usages {
             if let Some(bie) = &usage.basic_item_effects {
                 let mut i1 = 0;
                 for b in bie {
+                    if b.item_effect_type == BasicItemEffectType::Clamp {
+                        match (b.clamp_min, b.clamp_max) {
+                            (None, None) => return Err(ErrorCode::InvalidClampBounds.into()),
+                            (Some(min), Some(max)) if min > max => {
+                                return Err(ErrorCode::InvalidClampBounds.into())
+                            }
+                            _ => {}
+                        }
+                    }
                     let mut i2 = 0;
                     for b2 in bie {
-                        if i1 != i2 && b.stat == b2.stat {
+                        if i1 != i2
+                            && b.stat == b2.stat
+                            && item_effect_slot(b.item_effect_type)
+                                == item_effect_slot(b2.item_effect_type)
+                        {
                             return Err(ErrorCode::CannotEffectTheSameStatTwice.into());
                         }
                         i2 += 1;
@@ -403,19 +423,18 @@ pub fn write_data(
 ) -> Result<()> {
     let item_class_info = item_class.to_account_info();
     let mut data = item_class_info.try_borrow_mut_data()?;
-    let dst: &mut [u8] = &mut data;
-    let mut i: u64 = 8;
-    let to_write = item_class.try_to_vec()?;
-    for el in to_write {
-        dst[i as usize] = el;
-        i += 1;
-    }
-    let mut cursor = std::io::Cursor::new(dst);
-    cursor.set_position(i);
-    AnchorSerialize::serialize(&item_class_data.settings, &mut cursor)?;
-    AnchorSerialize::serialize(&item_class_data.config, &mut cursor)?;
+    // keep the layout already on the account, legacy accounts are moved over by migrate_item_class_data
+    let versioned = is_versioned_item_class_data(&data)?;
+    write_item_class_data(&mut data, item_class, item_class_data, versioned)
+}
 
-    Ok(())
+pub fn write_versioned_data(
+    item_class: &mut Account<ItemClass>,
+    item_class_data: &ItemClassData,
+) -> Result<()> {
+    let item_class_info = item_class.to_account_info();
+    let mut data = item_class_info.try_borrow_mut_data()?;
+    write_item_class_data(&mut data, item_class, item_class_data, true)
 }
 
 #[inline(always)]
@@ -463,14 +482,6 @@ pub fn get_mask_and_index_for_seq(seq: u64) -> Result<(u8, usize)> {
     Ok((mask, my_position_in_index as usize))
 }
 
-pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<u8> {
-    let (key, bump) = Pubkey::find_program_address(path, program_id);
-    if key != *account.key {
-        return Err(error!(ErrorCode::DerivedKeyInvalid));
-    }
-    Ok(bump)
-}
-
 pub fn assert_derivation_by_key(
     program_id: &Pubkey,
     account: &Pubkey,
@@ -495,56 +506,6 @@ pub fn assert_derivation_with_bump(
     Ok(())
 }
 
-/// Create account almost from scratch, lifted from
-/// https://github.com/solana-labs/solana-program-library/blob/7d4873c61721aca25464d42cc5ef651a7923ca79/associated-token-account/program/src/processor.rs#L51-L98
-#[inline(always)]
-pub fn create_or_allocate_account_raw<'a>(
-    program_id: Pubkey,
-    new_account_info: &AccountInfo<'a>,
-    rent_sysvar_info: &AccountInfo<'a>,
-    system_program_info: &AccountInfo<'a>,
-    payer_info: &AccountInfo<'a>,
-    size: usize,
-    signer_seeds: &[&[u8]],
-) -> Result<()> {
-    let rent = &Rent::from_account_info(rent_sysvar_info)?;
-    let required_lamports = rent
-        .minimum_balance(size)
-        .max(1)
-        .saturating_sub(new_account_info.lamports());
-
-    if required_lamports > 0 {
-        msg!("Transfer {} lamports to the new account", required_lamports);
-        invoke(
-            &system_instruction::transfer(payer_info.key, new_account_info.key, required_lamports),
-            &[
-                payer_info.clone(),
-                new_account_info.clone(),
-                system_program_info.clone(),
-            ],
-        )?;
-    }
-
-    let accounts = &[new_account_info.clone(), system_program_info.clone()];
-
-    msg!("Allocate space for the account");
-    invoke_signed(
-        &system_instruction::allocate(new_account_info.key, size.try_into().unwrap()),
-        accounts,
-        &[signer_seeds],
-    )?;
-
-    msg!("Assign the account to the owning program");
-    invoke_signed(
-        &system_instruction::assign(new_account_info.key, &program_id),
-        accounts,
-        &[signer_seeds],
-    )?;
-    msg!("Completed assignation!");
-
-    Ok(())
-}
-
 pub fn spl_token_mint_to<'a: 'b, 'b>(
     mint: AccountInfo<'a>,
     destination: AccountInfo<'a>,
@@ -719,6 +680,7 @@ pub fn assert_permissiveness_access(args: AssertPermissivenessAccessArgs) -> Res
                             program_id,
                             given_account,
                             &[PREFIX.as_bytes(), mint.as_ref(), &index.to_le_bytes()],
+                            ErrorCode::DerivedKeyInvalid,
                         )?;
                     }
                     PermissivenessType::ParentTokenHolder => {
@@ -757,6 +719,7 @@ pub fn assert_permissiveness_access(args: AssertPermissivenessAccessArgs) -> Res
                                 class_mint.as_ref(),
                                 &class_index.unwrap().to_le_bytes(),
                             ],
+                            ErrorCode::DerivedKeyInvalid,
                         )?;
 
                         msg!(
@@ -819,6 +782,7 @@ pub fn assert_permissiveness_access(args: AssertPermissivenessAccessArgs) -> Res
                     program_id,
                     given_account,
                     &[PREFIX.as_bytes(), mint.as_ref(), &index.to_le_bytes()],
+                    ErrorCode::DerivedKeyInvalid,
                 )?;
             }
         }
@@ -911,7 +875,11 @@ pub fn propagate_parent<T: Inherited>(args: PropagateParentArgs<T>) -> Option<T>
             let mut new_vers = parent_val.clone();
             new_vers.set_inherited(InheritanceState::Overridden);
             return Some(new_vers);
-        } else if child.is_none() {
+        } else if !matches!(
+            child,
+            Some(child_val) if child_val.get_inherited() != &InheritanceState::Inherited
+        ) {
+            // a child value still marked inherited is the parent's copy, keep it current
             let mut new_vers = parent_val.clone();
             new_vers.set_inherited(InheritanceState::Inherited);
             return Some(new_vers);
@@ -1040,6 +1008,27 @@ pub fn update_item_class_with_inherited_information(
     }
 }
 
+// the item class account is allocated up front, make sure the propagated data still fits
+pub fn assert_item_class_data_fits(
+    item_class: &Account<ItemClass>,
+    item_class_data: &ItemClassData,
+) -> Result<()> {
+    let item_class_info = item_class.to_account_info();
+    let layout_space = if is_versioned_item_class_data(&item_class_info.data.borrow())? {
+        ItemClassDataLayout::SIZE
+    } else {
+        0
+    };
+    let required_space =
+        8 + item_class.try_to_vec()?.len() + layout_space + item_class_data.try_to_vec()?.len();
+    require!(
+        required_space <= item_class_info.data_len(),
+        ErrorCode::NotEnoughSpaceForPropagation
+    );
+
+    Ok(())
+}
+
 pub fn assert_valid_item_settings_for_edition_type(
     edition: Option<&AccountInfo>,
     item_data: &ItemClassData,
@@ -1196,6 +1185,7 @@ pub fn assert_metadata_valid(
             mpl_token_metadata::id().as_ref(),
             mint.as_ref(),
         ],
+        ErrorCode::DerivedKeyInvalid,
     )?;
     if metadata.data_is_empty() {
         return Err(error!(ErrorCode::MetadataDoesntExist));
@@ -1211,6 +1201,7 @@ pub fn assert_metadata_valid(
                 mint.as_ref(),
                 mpl_token_metadata::state::EDITION.as_bytes(),
             ],
+            ErrorCode::DerivedKeyInvalid,
         )?;
         if ed.data_is_empty() {
             return Err(error!(ErrorCode::EditionDoesntExist));
@@ -1304,35 +1295,6 @@ pub fn transfer_mint_authority(args: TransferMintAuthorityArgs) -> Result<()> {
     Ok(())
 }
 
-/// Returns true if a `leaf` can be proved to be a part of a Merkle tree
-/// defined by `root`. For this, a `proof` must be provided, containing
-/// sibling hashes on the branch from the leaf to the root of the tree. Each
-/// pair of leaves and each pair of pre-images are assumed to be sorted.
-pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
-    let mut computed_hash = leaf;
-    for proof_element in proof.iter() {
-        if computed_hash <= *proof_element {
-            // Hash(current computed hash + current element of the proof)
-            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x01],
-                &computed_hash,
-                proof_element,
-            ])
-            .0;
-        } else {
-            // Hash(current element of the proof + current computed hash)
-            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x01],
-                proof_element,
-                &computed_hash,
-            ])
-            .0;
-        }
-    }
-    // Check if the computed hash (root) is equal to the provided root
-    computed_hash == *root
-}
-
 pub struct VerifyCooldownArgs<'a, 'info> {
     pub craft_usage_info: Option<CraftUsageInfo>,
     pub craft_item_class: &'a Account<'info, ItemClass>,
@@ -1362,33 +1324,24 @@ pub fn verify_cooldown(args: VerifyCooldownArgs) -> Result<()> {
         } = csi;
 
         // Verify the merkle proof.
-        let node = anchor_lang::solana_program::keccak::hashv(&[
-            &[0x00],
-            &craft_item.key().to_bytes(),
-            &AnchorSerialize::try_to_vec(&craft_usage_state)?,
-        ]);
+        let node = craft_usage_state_leaf(&craft_item.key(), &craft_usage_state)?;
         if let Some(craft_usage_state_root) = &craft_item.data.usage_state_root {
             require!(
-                verify(
-                    &craft_usage_state_proof,
-                    &craft_usage_state_root.root,
-                    node.0
-                ),
+                verify(&craft_usage_state_proof, &craft_usage_state_root.root, node),
                 ErrorCode::InvalidProof
             );
         } else {
             return Err(error!(ErrorCode::MissingMerkleInfo));
         }
 
-        let class_node = anchor_lang::solana_program::keccak::hashv(&[
-            &[0x00],
-            &craft_item_class.key().to_bytes(),
-            &AnchorSerialize::try_to_vec(&craft_usage)?,
-        ]);
-
         if let Some(craft_usage_root) = &craft_item_class_data.config.usage_root {
             require!(
-                verify(&craft_usage_proof, &craft_usage_root.root, class_node.0),
+                verify_craft_usage(
+                    &craft_usage_proof,
+                    &craft_usage_root.root,
+                    &craft_item_class.key(),
+                    &craft_usage
+                )?,
                 ErrorCode::InvalidProof
             );
         } else {
@@ -1463,14 +1416,6 @@ pub fn verify_cooldown(args: VerifyCooldownArgs) -> Result<()> {
     Ok(())
 }
 
-pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
-    let preimage = format!("{namespace}:{name}");
-
-    let mut sighash = [0u8; 8];
-    sighash.copy_from_slice(&hash::hash(preimage.as_bytes()).to_bytes()[..8]);
-    sighash
-}
-
 pub struct VerifyComponentArgs<'a, 'info> {
     pub item_class: &'a Account<'info, ItemClass>,
     pub component: Option<Component>,
@@ -1497,14 +1442,9 @@ pub fn verify_component(args: VerifyComponentArgs) -> Result<Component> {
         if let Some(p) = component_proof {
             if let Some(c) = component {
                 // Verify the merkle proof.
-                let node = anchor_lang::solana_program::keccak::hashv(&[
-                    &[0x00],
-                    &item_escrow.step.to_le_bytes(),
-                    &craft_item_token_mint.to_bytes(),
-                    &AnchorSerialize::try_to_vec(&c)?,
-                ]);
+                let node = component_leaf(item_escrow.step, craft_item_token_mint, &c)?;
                 require!(
-                    verify(&p, &component_root.root, node.0),
+                    verify(&p, &component_root.root, node),
                     ErrorCode::InvalidProof
                 );
                 c
@@ -1757,17 +1697,14 @@ pub fn verify_and_affect_item_state_update(
             } = us_info;
 
             // Verify the new state and old state are both part of their respective trees
-            let chief_node = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x00],
-                &AnchorSerialize::try_to_vec(usage_state)?,
-            ]);
+            let chief_node = usage_state_leaf(usage_state)?;
             require!(
-                verify(usage_state_proof, &usage_state_root.root, chief_node.0),
+                verify(usage_state_proof, &usage_state_root.root, chief_node),
                 ErrorCode::InvalidProof
             );
 
             require!(
-                verify(new_usage_state_proof, new_usage_state_root, chief_node.0),
+                verify(new_usage_state_proof, new_usage_state_root, chief_node),
                 ErrorCode::InvalidProof
             );
 
@@ -1779,15 +1716,14 @@ pub fn verify_and_affect_item_state_update(
             );
 
             // Check that both states have the same total states
-            let node =
-                anchor_lang::solana_program::keccak::hashv(&[&[0x00], &total_states.to_le_bytes()]);
+            let node = total_states_leaf(*total_states);
             require!(
-                verify(total_states_proof, &usage_state_root.root, node.0),
+                verify(total_states_proof, &usage_state_root.root, node),
                 ErrorCode::InvalidProof
             );
 
             require!(
-                verify(new_total_states_proof, new_usage_state_root, node.0),
+                verify(new_total_states_proof, new_usage_state_root, node),
                 ErrorCode::InvalidProof
             );
 
@@ -1796,12 +1732,9 @@ pub fn verify_and_affect_item_state_update(
             let verify_new_usage_state = &mut usage_state.clone();
             enact_valid_state_change(verify_new_usage_state, &item_usage, unix_timestamp)?;
 
-            let node = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x00],
-                &AnchorSerialize::try_to_vec(verify_new_usage_state)?,
-            ]);
+            let node = usage_state_leaf(verify_new_usage_state)?;
             require!(
-                verify(new_usage_state_proof, new_usage_state_root, node.0),
+                verify(new_usage_state_proof, new_usage_state_root, node),
                 ErrorCode::InvalidProof
             );
             item_activation_marker.proof_counter = Some(ItemActivationMarkerProofCounter {
@@ -1861,13 +1794,9 @@ pub fn get_item_usage(args: GetItemUsageArgs) -> Result<ItemUsage> {
         if let Some(usage_proof) = &usage_proof {
             if let Some(us) = &usage {
                 // Verify the merkle proof.
-                let node = anchor_lang::solana_program::keccak::hashv(&[
-                    &[0x00],
-                    &AnchorSerialize::try_to_vec(&usage)?,
-                ]);
                 require!(us.index == usage_index, ErrorCode::UsageIndexMismatch);
                 require!(
-                    verify(usage_proof, &usage_root.root, node.0),
+                    verify_usage(usage_proof, &usage_root.root, us)?,
                     ErrorCode::InvalidProof
                 );
                 us.clone()
@@ -1897,19 +1826,6 @@ pub fn get_item_usage(args: GetItemUsageArgs) -> Result<ItemUsage> {
     Ok(item_usage)
 }
 
-// returns true if the namespace program called the item program
-pub fn is_namespace_program_caller(ixns: &AccountInfo) -> bool {
-    let current_ix =
-        anchor_lang::solana_program::sysvar::instructions::get_instruction_relative(0, ixns)
-            .unwrap();
-
-    if current_ix.program_id != Pubkey::from_str(NAMESPACE_ID).unwrap() {
-        return false;
-    };
-
-    true
-}
-
 pub struct GetItemUsageAndItemUsageStateArgs<'a, 'info> {
     pub item: &'a mut Account<'info, Item>,
     pub item_class: &'a Account<'info, ItemClass>,
@@ -1950,16 +1866,9 @@ pub fn get_item_usage_and_item_usage_state(
             } = us_info;
 
             // Verify the new state and old state are both part of their respective trees
-            let chief_node = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x00],
-                &AnchorSerialize::try_to_vec(craft_usage_state)?,
-            ]);
+            let chief_node = usage_state_leaf(craft_usage_state)?;
             require!(
-                verify(
-                    craft_usage_state_proof,
-                    &usage_state_root.root,
-                    chief_node.0
-                ),
+                verify(craft_usage_state_proof, &usage_state_root.root, chief_node),
                 ErrorCode::InvalidProof
             );
 
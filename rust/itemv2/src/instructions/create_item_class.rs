@@ -96,6 +96,7 @@ pub fn handler(ctx: Context<CreateItemClass>, args: CreateItemClassArgs) -> Resu
         authority_mint: ctx.accounts.item_class_authority_mint.key(),
         recipe_index: None,
         mode: mode,
+        namespaces: vec![],
     });
 
     Ok(())
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use raindrops_item::{ItemClass as ItemClassV1, ItemClassData as ItemClassDataV1};
+
+use crate::state::{
+    accounts::{ItemClass, ItemClassV1Import},
+    errors::ErrorCode,
+    ImportIssue, ItemClassMode, ItemClassModeSelection,
+};
+
+#[derive(Accounts)]
+#[instruction(args: ImportItemClassV1Args)]
+pub struct ImportItemClassV1<'info> {
+    #[account(init,
+        payer = authority, space = ItemClass::space(args.item_class_name),
+        seeds = [ItemClass::PREFIX.as_bytes(), item_class_authority_mint.key().as_ref()], bump)]
+    pub item_class: Account<'info, ItemClass>,
+
+    #[account(mint::authority = item_class, mint::decimals = 0)]
+    pub item_class_authority_mint: Account<'info, token::Mint>,
+
+    #[account(
+        constraint = item_class_authority_mint_ata.amount >= 1,
+        associated_token::mint = item_class_authority_mint, associated_token::authority = authority)]
+    pub item_class_authority_mint_ata: Account<'info, token::TokenAccount>,
+
+    #[account(init,
+        payer = authority, space = ItemClassV1Import::INIT_SPACE,
+        seeds = [ItemClassV1Import::PREFIX.as_bytes(), item_class_v1.key().as_ref()], bump)]
+    pub item_class_v1_import: Account<'info, ItemClassV1Import>,
+
+    pub item_class_v1: Box<Account<'info, ItemClassV1>>,
+
+    // only a holder of the legacy item class mint may import it
+    #[account(
+        constraint = item_class_v1.mint == Some(item_class_v1_mint_ata.mint),
+        constraint = item_class_v1_mint_ata.amount >= 1,
+        token::authority = authority)]
+    pub item_class_v1_mint_ata: Account<'info, token::TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ImportItemClassV1Args {
+    pub item_class_name: String,
+    pub mode: ItemClassModeSelection,
+}
+
+pub fn handler(ctx: Context<ImportItemClassV1>, args: ImportItemClassV1Args) -> Result<()> {
+    // merkle tree mode needs a tree initialized, use create_item_class and import recipes instead
+    let mode = match args.mode {
+        ItemClassModeSelection::Collection { collection_mint } => {
+            ItemClassMode::Collection { collection_mint }
+        }
+        ItemClassModeSelection::Pack => ItemClassMode::Pack { index: 0 },
+        ItemClassModeSelection::PresetOnly => ItemClassMode::PresetOnly,
+        ItemClassModeSelection::MerkleTree => return Err(ErrorCode::InvalidItemClassMode.into()),
+    };
+
+    ctx.accounts.item_class.set_inner(ItemClass {
+        name: args.item_class_name,
+        authority_mint: ctx.accounts.item_class_authority_mint.key(),
+        recipe_index: None,
+        mode,
+        namespaces: vec![],
+    });
+
+    let item_class_v1_data = ctx
+        .accounts
+        .item_class_v1
+        .item_class_data(&ctx.accounts.item_class_v1.to_account_info().data)?;
+    let issues = get_item_class_issues(&item_class_v1_data);
+    for issue in &issues {
+        msg!("not imported: {:?}", issue);
+    }
+
+    ctx.accounts
+        .item_class_v1_import
+        .set_inner(ItemClassV1Import {
+            item_class_v1: ctx.accounts.item_class_v1.key(),
+            item_class: ctx.accounts.item_class.key(),
+            imported_scopes: vec![],
+            issues: vec![],
+        });
+
+    let import_account = &ctx.accounts.item_class_v1_import.to_account_info();
+    ctx.accounts.item_class_v1_import.record_import(
+        None,
+        issues,
+        import_account,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )
+}
+
+fn get_item_class_issues(data: &ItemClassDataV1) -> Vec<ImportIssue> {
+    let mut issues = vec![];
+
+    let has_usages = matches!(&data.config.usages, Some(u) if !u.is_empty());
+    if has_usages || data.config.usage_root.is_some() {
+        issues.push(ImportIssue::UsagesNotImported);
+    }
+
+    let settings = &data.settings;
+    if settings.staking_permissiveness.is_some()
+        || settings.unstaking_permissiveness.is_some()
+        || settings.staking_warm_up_duration.is_some()
+        || settings.staking_cooldown_duration.is_some()
+    {
+        issues.push(ImportIssue::StakingNotImported);
+    }
+
+    if settings.build_permissiveness.is_some() {
+        issues.push(ImportIssue::BuildPermissivenessNotImported);
+    }
+
+    if data.config.component_root.is_some() && data.config.components.is_none() {
+        issues.push(ImportIssue::ComponentsOnlyInRoot);
+    }
+
+    issues
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use raindrops_item::{
+    Component, ComponentCondition, ItemClass as ItemClassV1, ItemClassType,
+    PREFIX as ITEM_V1_PREFIX,
+};
+
+use crate::state::{
+    accounts::{ItemClass, ItemClassV1Import, Recipe},
+    errors::ErrorCode,
+    BuildEffect, Cooldown, Degradation, ImportIssue, RecipeIngredientData,
+};
+
+#[derive(Accounts)]
+#[instruction(args: ImportRecipeV1Args)]
+pub struct ImportRecipeV1<'info> {
+    #[account(init,
+        payer = authority,
+        space = Recipe::INIT_SPACE,
+        seeds = [Recipe::PREFIX.as_bytes(), &item_class.get_next_recipe_index().to_le_bytes(), item_class.key().as_ref()], bump)]
+    pub recipe: Account<'info, Recipe>,
+
+    #[account(mut,
+        constraint = item_class.authority_mint.eq(&item_class_authority_mint.key()),
+        seeds = [ItemClass::PREFIX.as_bytes(), item_class_authority_mint.key().as_ref()], bump)]
+    pub item_class: Account<'info, ItemClass>,
+
+    #[account(mint::authority = item_class)]
+    pub item_class_authority_mint: Account<'info, token::Mint>,
+
+    #[account(
+        constraint = item_class_authority_mint_ata.amount >= 1,
+        associated_token::mint = item_class_authority_mint, associated_token::authority = authority)]
+    pub item_class_authority_mint_ata: Account<'info, token::TokenAccount>,
+
+    #[account(mut,
+        constraint = item_class_v1_import.item_class.eq(&item_class.key()),
+        seeds = [ItemClassV1Import::PREFIX.as_bytes(), item_class_v1.key().as_ref()], bump)]
+    pub item_class_v1_import: Account<'info, ItemClassV1Import>,
+
+    pub item_class_v1: Box<Account<'info, ItemClassV1>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ImportRecipeV1Args {
+    pub component_scope: String,
+    pub build_enabled: bool,
+}
+
+// remaining accounts, one pair per component in the scope, in component order:
+// [ingredient legacy item class, ingredient item class v1 import]
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ImportRecipeV1<'info>>,
+    args: ImportRecipeV1Args,
+) -> Result<()> {
+    require!(
+        !ctx.accounts
+            .item_class_v1_import
+            .imported_scopes
+            .contains(&args.component_scope),
+        ErrorCode::ComponentScopeAlreadyImported
+    );
+
+    let item_class_v1_data = ctx
+        .accounts
+        .item_class_v1
+        .item_class_data(&ctx.accounts.item_class_v1.to_account_info().data)?;
+    let components: Vec<Component> = item_class_v1_data
+        .config
+        .components
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| c.component_scope == args.component_scope)
+        .collect();
+    require!(!components.is_empty(), ErrorCode::ComponentScopeNotFound);
+    require!(
+        ctx.remaining_accounts.len() == components.len() * 2,
+        ErrorCode::InvalidImportAccounts
+    );
+
+    let mut issues = vec![];
+    if components.iter().any(|c| c.time_to_build.is_some()) {
+        issues.push(ImportIssue::TimeToBuildDropped {
+            component_scope: args.component_scope.clone(),
+        });
+    }
+
+    let mut ingredients = vec![];
+    for (step, component) in components.iter().enumerate() {
+        let step = step as u64;
+        let ingredient_v1_info = &ctx.remaining_accounts[2 * step as usize];
+        let ingredient_import_info = &ctx.remaining_accounts[2 * step as usize + 1];
+
+        if component.condition == ComponentCondition::Absence {
+            issues.push(ImportIssue::AbsenceComponentSkipped {
+                component_scope: args.component_scope.clone(),
+                step,
+            });
+            continue;
+        }
+
+        // the legacy item class must be the one the component points at
+        let ingredient_v1: Account<'info, ItemClassV1> = Account::try_from(ingredient_v1_info)?;
+        let expected_ingredient_v1 = Pubkey::create_program_address(
+            &[
+                ITEM_V1_PREFIX.as_bytes(),
+                component.mint.as_ref(),
+                &component.class_index.to_le_bytes(),
+                &[ingredient_v1.bump],
+            ],
+            &raindrops_item::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidImportAccounts)?;
+        require!(
+            expected_ingredient_v1.eq(ingredient_v1_info.key),
+            ErrorCode::InvalidImportAccounts
+        );
+
+        // and it must already have been imported so we know its itemv2 item class
+        let ingredient_import: Account<'info, ItemClassV1Import> =
+            Account::try_from(ingredient_import_info)?;
+        require!(
+            ingredient_import.item_class_v1.eq(ingredient_v1_info.key),
+            ErrorCode::InvalidImportAccounts
+        );
+
+        let degradation = match component.condition {
+            ComponentCondition::Consumed | ComponentCondition::CooldownAndConsume => {
+                Degradation::On {
+                    rate: Degradation::BRAND_NEW,
+                }
+            }
+            _ => Degradation::Off,
+        };
+
+        let cooldown = match component.condition {
+            ComponentCondition::Cooldown | ComponentCondition::CooldownAndConsume => {
+                let ingredient_v1_data = ingredient_v1.item_class_data(&ingredient_v1_info.data)?;
+                match get_cooldown_duration(&ingredient_v1_data.config.usages, component) {
+                    Some(seconds) => Cooldown::On {
+                        seconds: seconds as i64,
+                    },
+                    None => {
+                        issues.push(ImportIssue::CooldownDurationMissing {
+                            component_scope: args.component_scope.clone(),
+                            step,
+                        });
+                        Cooldown::Off
+                    }
+                }
+            }
+            _ => Cooldown::Off,
+        };
+
+        ingredients.push(RecipeIngredientData {
+            item_class: ingredient_import.item_class,
+            required_amount: component.amount,
+            build_effect: BuildEffect {
+                degradation,
+                cooldown,
+            },
+            is_deterministic: false,
+        });
+    }
+
+    for issue in &issues {
+        msg!("not imported: {:?}", issue);
+    }
+
+    let recipe_index = ctx.accounts.item_class.get_next_recipe_index();
+
+    // increment recipe index on item class
+    let item_class = &mut ctx.accounts.item_class;
+    item_class.recipe_index = Some(recipe_index);
+
+    // init recipe
+    ctx.accounts.recipe.set_inner(Recipe {
+        recipe_index,
+        item_class: ctx.accounts.item_class.key(),
+        build_enabled: args.build_enabled,
+        ingredients: vec![],
+        payment: None,
+        build_permit_required: false,
+        selectable_outputs: vec![],
+    });
+
+    let recipe_account = &ctx.accounts.recipe.to_account_info();
+    ctx.accounts.recipe.set_ingredient_data(
+        ingredients,
+        recipe_account,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )?;
+
+    let import_account = &ctx.accounts.item_class_v1_import.to_account_info();
+    ctx.accounts.item_class_v1_import.record_import(
+        Some(args.component_scope),
+        issues,
+        import_account,
+        ctx.accounts.authority.clone(),
+        ctx.accounts.system_program.clone(),
+    )
+}
+
+// cooldown of the ingredient usage the legacy component checks
+fn get_cooldown_duration(
+    usages: &Option<Vec<raindrops_item::ItemUsage>>,
+    component: &Component,
+) -> Option<u64> {
+    let usage = usages
+        .as_ref()?
+        .iter()
+        .find(|u| u.index == component.use_usage_index)?;
+
+    match usage.item_class_type {
+        ItemClassType::Consumable {
+            cooldown_duration, ..
+        } => cooldown_duration,
+        ItemClassType::Wearable { .. } => None,
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use raindrops_common::utils::is_namespace_program_caller;
+
+use crate::state::{accounts::ItemClass, errors::ErrorCode};
+
+#[derive(Accounts)]
+pub struct ItemClassJoinNamespace<'info> {
+    #[account(mut)]
+    pub item_class: Account<'info, ItemClass>,
+
+    /// CHECK: only the key is recorded on the item class
+    pub namespace: UncheckedAccount<'info>,
+
+    // the namespace token holder pays for the new namespace entry
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: address constraint
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ItemClassJoinNamespace>) -> Result<()> {
+    require!(
+        is_namespace_program_caller(&ctx.accounts.instructions.to_account_info()),
+        ErrorCode::UnauthorizedCaller
+    );
+
+    let item_class_account_info = ctx.accounts.item_class.to_account_info();
+    let namespace = ctx.accounts.namespace.key();
+    ctx.accounts.item_class.join_namespace(
+        namespace,
+        &item_class_account_info,
+        ctx.accounts.payer.clone(),
+        ctx.accounts.system_program.clone(),
+    )
+}
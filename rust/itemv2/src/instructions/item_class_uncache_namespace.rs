@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use raindrops_common::utils::{is_namespace_program_caller, set_namespace_index};
+
+use crate::state::{accounts::ItemClass, errors::ErrorCode};
+
+#[derive(Accounts)]
+pub struct ItemClassUncacheNamespace<'info> {
+    #[account(mut)]
+    pub item_class: Account<'info, ItemClass>,
+
+    /// CHECK: only the key is recorded on the item class
+    pub namespace: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ItemClassUncacheNamespace>) -> Result<()> {
+    require!(
+        is_namespace_program_caller(&ctx.accounts.instructions.to_account_info()),
+        ErrorCode::UnauthorizedCaller
+    );
+
+    let namespace = ctx.accounts.namespace.key();
+    set_namespace_index(
+        &mut ctx.accounts.item_class.namespaces,
+        &namespace,
+        None,
+        ErrorCode::FailedToUncache,
+    )
+}
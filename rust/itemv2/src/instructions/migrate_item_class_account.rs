@@ -56,6 +56,7 @@ pub fn handler(ctx: Context<MigrateItemClassAccount>) -> Result<()> {
         authority_mint: authority_mint,
         recipe_index: recipe_index,
         mode: mode,
+        namespaces: vec![],
     };
 
     // Serialize the new_item_class_data
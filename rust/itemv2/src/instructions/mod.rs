@@ -12,6 +12,12 @@ pub mod create_recipe;
 pub mod destroy_ingredient_pnft;
 pub mod destroy_ingredient_spl;
 pub mod escrow_payment;
+pub mod import_item_class_v1;
+pub mod import_recipe_v1;
+pub mod item_class_cache_namespace;
+pub mod item_class_join_namespace;
+pub mod item_class_leave_namespace;
+pub mod item_class_uncache_namespace;
 pub mod migrate_build_account;
 pub mod migrate_item_class_account;
 pub mod mint_authority_tokens;
@@ -40,6 +46,12 @@ pub use create_recipe::*;
 pub use destroy_ingredient_pnft::*;
 pub use destroy_ingredient_spl::*;
 pub use escrow_payment::*;
+pub use import_item_class_v1::*;
+pub use import_recipe_v1::*;
+pub use item_class_cache_namespace::*;
+pub use item_class_join_namespace::*;
+pub use item_class_leave_namespace::*;
+pub use item_class_uncache_namespace::*;
 pub use migrate_build_account::*;
 pub use migrate_item_class_account::*;
 pub use mint_authority_tokens::*;
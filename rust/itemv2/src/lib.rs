@@ -144,4 +144,37 @@ pub mod itemv2 {
     pub fn migrate_item_class_account(ctx: Context<MigrateItemClassAccount>) -> Result<()> {
         migrate_item_class_account::handler(ctx)
     }
+
+    pub fn item_class_join_namespace(ctx: Context<ItemClassJoinNamespace>) -> Result<()> {
+        item_class_join_namespace::handler(ctx)
+    }
+
+    pub fn item_class_leave_namespace(ctx: Context<ItemClassLeaveNamespace>) -> Result<()> {
+        item_class_leave_namespace::handler(ctx)
+    }
+
+    pub fn item_class_cache_namespace(
+        ctx: Context<ItemClassCacheNamespace>,
+        page: u64,
+    ) -> Result<()> {
+        item_class_cache_namespace::handler(ctx, page)
+    }
+
+    pub fn item_class_uncache_namespace(ctx: Context<ItemClassUncacheNamespace>) -> Result<()> {
+        item_class_uncache_namespace::handler(ctx)
+    }
+
+    pub fn import_item_class_v1(
+        ctx: Context<ImportItemClassV1>,
+        args: ImportItemClassV1Args,
+    ) -> Result<()> {
+        import_item_class_v1::handler(ctx, args)
+    }
+
+    pub fn import_recipe_v1<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ImportRecipeV1<'info>>,
+        args: ImportRecipeV1Args,
+    ) -> Result<()> {
+        import_recipe_v1::handler(ctx, args)
+    }
 }
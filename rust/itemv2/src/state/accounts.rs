@@ -1,17 +1,16 @@
-use std::convert::TryInto;
-
-use anchor_lang::{
-    prelude::*,
-    system_program::{transfer, Transfer},
-};
+use anchor_lang::prelude::*;
 use anchor_spl::metadata;
+use raindrops_common::{
+    utils::{append_namespace, remove_namespace, resize_account},
+    NamespaceAndIndex,
+};
 
 use crate::state::PaymentStatus;
 
 use super::{
     errors::ErrorCode, BuildIngredientData, BuildOutput, BuildStatus,
-    DeterministicIngredientOutput, IngredientMint, ItemClassMode, ItemState, OutputSelectionGroup,
-    Payment, PaymentState, RecipeIngredientData,
+    DeterministicIngredientOutput, ImportIssue, IngredientMint, ItemClassMode, ItemState,
+    OutputSelectionGroup, Payment, PaymentState, RecipeIngredientData,
 };
 
 // seeds = ['item_class', authority_mint.key().as_ref()]
@@ -27,6 +26,9 @@ pub struct ItemClass {
 
     // item class membership mode
     pub mode: ItemClassMode,
+
+    // namespaces this item class has joined, grows and shrinks with membership
+    pub namespaces: Vec<NamespaceAndIndex>,
 }
 
 impl ItemClass {
@@ -37,7 +39,58 @@ impl ItemClass {
         4 + name.len() + // name size
         32 + // authority mint
         (1 + 8) + // recipe_index
-        ItemClassMode::SPACE // membership mode
+        ItemClassMode::SPACE + // membership mode
+        4 // empty namespaces vector
+    }
+
+    pub fn current_space(&self) -> usize {
+        8 + // anchor
+        4 + self.name.len() + // name size
+        32 + // authority mint
+        (1 + 8) + // recipe_index
+        ItemClassMode::SPACE + // membership mode
+        4 + (self.namespaces.len() * NamespaceAndIndex::SPACE) // namespaces
+    }
+
+    pub fn join_namespace<'info>(
+        &mut self,
+        namespace: Pubkey,
+        item_class: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        append_namespace(
+            &mut self.namespaces,
+            namespace,
+            ErrorCode::FailedToJoinNamespace,
+        )?;
+
+        let diff: i64 = self.current_space() as i64 - old_space as i64;
+
+        reallocate(diff, item_class, payer, system_program)
+    }
+
+    // cached item classes have to be uncached first
+    pub fn leave_namespace<'info>(
+        &mut self,
+        namespace: &Pubkey,
+        item_class: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        remove_namespace(
+            &mut self.namespaces,
+            namespace,
+            ErrorCode::FailedToLeaveNamespace,
+        )?;
+
+        let diff: i64 = self.current_space() as i64 - old_space as i64;
+
+        reallocate(diff, item_class, payer, system_program)
     }
 
     pub fn get_next_recipe_index(&self) -> u64 {
@@ -457,50 +510,84 @@ impl DeterministicIngredient {
     }
 }
 
-pub fn reallocate<'info>(
-    size_diff: i64,
-    account: &AccountInfo<'info>,
-    payer: Signer<'info>,
-    system_program: Program<'info, System>,
-) -> Result<()> {
-    let new_size = (account.data_len() as i64 + size_diff) as usize;
-    account.realloc(new_size, false)?;
+// links a legacy item program ItemClass to the itemv2 ItemClass imported from it
+// seeds = ['item_class_v1_import', item_class_v1.key().as_ref()]
+#[account]
+pub struct ItemClassV1Import {
+    // legacy item class the data was read from
+    pub item_class_v1: Pubkey,
 
-    let rent = Rent::get()?;
+    // itemv2 item class created by the import
+    pub item_class: Pubkey,
 
-    let lamports_required = rent.minimum_balance(new_size);
+    // component scopes already imported as recipes
+    pub imported_scopes: Vec<String>,
 
-    let current_lamports = account.lamports();
+    // everything in the legacy item class that itemv2 could not represent
+    pub issues: Vec<ImportIssue>,
+}
 
-    let transfer_amount: i64 = (lamports_required as i64) - (current_lamports as i64);
+impl ItemClassV1Import {
+    pub const PREFIX: &'static str = "item_class_v1_import";
+    pub const INIT_SPACE: usize = 8 + // anchor
+    32 + // item class v1
+    32 + // item class
+    4 + // init imported scopes vector
+    4; // init issues vector
+
+    fn current_space(&self) -> usize {
+        let mut total_space = ItemClassV1Import::INIT_SPACE;
 
-    // no need to transfer
-    if transfer_amount == 0 {
-        return Ok(());
+        for scope in &self.imported_scopes {
+            total_space += 4 + scope.len();
+        }
+
+        for issue in &self.issues {
+            total_space += issue.space();
+        }
+
+        total_space
     }
 
-    if transfer_amount > 0 {
-        // if transfer amount is more than 0 we need to transfer lamports to the account
-        let transfer_accounts = Transfer {
-            from: payer.to_account_info(),
-            to: account.to_account_info(),
-        };
+    pub fn record_import<'info>(
+        &mut self,
+        component_scope: Option<String>,
+        issues: Vec<ImportIssue>,
+        import: &AccountInfo<'info>,
+        payer: Signer<'info>,
+        system_program: Program<'info, System>,
+    ) -> Result<()> {
+        let old_space = self.current_space();
+
+        if let Some(scope) = component_scope {
+            self.imported_scopes.push(scope);
+        }
+        self.issues.extend(issues);
 
-        transfer(
-            CpiContext::new(system_program.to_account_info(), transfer_accounts),
-            transfer_amount.try_into().unwrap(),
-        )
-    } else {
-        // if transfer amount is less than 0 this means we need to return lamports to the payer
-        let transfer_to_payer_amount = transfer_amount.unsigned_abs();
+        let new_space = self.current_space();
 
-        **account.try_borrow_mut_lamports()? -= transfer_to_payer_amount;
-        **payer.try_borrow_mut_lamports()? += transfer_to_payer_amount;
+        let diff: i64 = new_space as i64 - old_space as i64;
 
-        Ok(())
+        reallocate(diff, import, payer, system_program)
     }
 }
 
+pub fn reallocate<'info>(
+    size_diff: i64,
+    account: &AccountInfo<'info>,
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+) -> Result<()> {
+    let new_size = (account.data_len() as i64 + size_diff) as usize;
+
+    resize_account(
+        account,
+        &payer.to_account_info(),
+        &system_program.to_account_info(),
+        new_size,
+    )
+}
+
 pub fn is_collection_member(
     ingredient_mint_metadata: metadata::MetadataAccount,
     collection_mint: &Pubkey,
@@ -70,4 +70,28 @@ pub enum ErrorCode {
 
     #[msg("Error Migrating Account")]
     MigrationError,
+
+    #[msg("Component Scope Not Found")]
+    ComponentScopeNotFound,
+
+    #[msg("Component Scope Already Imported")]
+    ComponentScopeAlreadyImported,
+
+    #[msg("Invalid Import Accounts")]
+    InvalidImportAccounts,
+
+    #[msg("Only the Namespace Program Can Call This")]
+    UnauthorizedCaller,
+
+    #[msg("Failed to Join Namespace")]
+    FailedToJoinNamespace,
+
+    #[msg("Failed to Leave Namespace")]
+    FailedToLeaveNamespace,
+
+    #[msg("Failed to Cache")]
+    FailedToCache,
+
+    #[msg("Failed to Uncache")]
+    FailedToUncache,
 }
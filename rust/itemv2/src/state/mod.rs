@@ -314,6 +314,46 @@ impl ItemState {
     }
 }
 
+// parts of a legacy item class that have no itemv2 equivalent, recorded during import
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ImportIssue {
+    // usages, item effects and usage roots are not carried over
+    UsagesNotImported,
+
+    // staking settings are not carried over
+    StakingNotImported,
+
+    // build permissiveness is not carried over, use build permits instead
+    BuildPermissivenessNotImported,
+
+    // components only exist as a merkle root so no recipes can be read from the class
+    ComponentsOnlyInRoot,
+
+    // itemv2 builds have no build timer
+    TimeToBuildDropped { component_scope: String },
+
+    // itemv2 cannot require an item to be absent, the component was skipped
+    AbsenceComponentSkipped { component_scope: String, step: u64 },
+
+    // the ingredient usage has no cooldown duration, the cooldown was left off
+    CooldownDurationMissing { component_scope: String, step: u64 },
+}
+
+impl ImportIssue {
+    pub fn space(&self) -> usize {
+        1 + match self {
+            ImportIssue::TimeToBuildDropped { component_scope } => 4 + component_scope.len(),
+            ImportIssue::AbsenceComponentSkipped {
+                component_scope, ..
+            }
+            | ImportIssue::CooldownDurationMissing {
+                component_scope, ..
+            } => 4 + component_scope.len() + 8,
+            _ => 0,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct Payment {
     pub treasury: Pubkey,
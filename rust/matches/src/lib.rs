@@ -1,14 +1,17 @@
 pub mod utils;
 
 use crate::utils::{
-    assert_is_ata, close_token_account, is_namespace_program_caller, is_valid_validation,
-    spl_token_burn, spl_token_transfer, verify, TokenBurnParams, TokenTransferParams,
+    assert_derivation, assert_is_ata, basis_points_of, close_token_account, disburse_token_delta,
+    grow_to_fit, is_namespace_program_caller, is_valid_validation, prize_for_rank, set_claimed_bit,
+    spl_token_transfer, verify, DisburseTokenDeltaParams, TokenTransferParams,
 };
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize, Discriminator};
 use anchor_spl::token::{Mint, TokenAccount};
 use arrayref::array_ref;
+pub use raindrops_common::{Callback, InheritanceState, NamespaceAndIndex, PermissivenessType};
 anchor_lang::declare_id!("mtchsiT6WoLQ62fwCoiHMCfXJzogtfru4ovY8tXKrjJ");
 pub const PREFIX: &str = "matches";
+pub const PRIZE_POOL: &str = "prize_pool";
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CreateOrUpdateOracleArgs {
@@ -17,6 +20,7 @@ pub struct CreateOrUpdateOracleArgs {
     seed: Pubkey,
     space: u64,
     finalized: bool,
+    rankings: Option<Vec<Pubkey>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -51,6 +55,13 @@ pub struct UpdateMatchArgs {
     minimum_allowed_entry_time: Option<u64>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateMatchEconomicsArgs {
+    entry_fee_amount: u64,
+    rake: Option<Rake>,
+    payout_schedule: PayoutSchedule,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct JoinMatchArgs {
     amount: u64,
@@ -66,7 +77,21 @@ pub struct LeaveMatchArgs {
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct DisburseTokensByOracleArgs {
     token_delta_proof_info: Option<TokenDeltaProofInfo>,
+    // position of the delta in the win oracle, each one can only be disbursed once
+    index: u64,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DisburseTokensByOracleBatchArgs {
+    token_deltas: Vec<DisburseTokensByOracleArgs>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DisbursePrizePoolArgs {
+    // place in the oracle rankings to pay, none pays the rake to the treasury
+    rank: Option<u64>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TokenDeltaProofInfo {
     token_delta_proof: Vec<[u8; 32]>,
@@ -88,6 +113,7 @@ pub mod raindrops_matches {
             token_transfer_root,
             token_transfers,
             finalized,
+            rankings,
             ..
         } = args;
 
@@ -104,11 +130,23 @@ pub mod raindrops_matches {
 
         require!(!win_oracle.finalized, ErrorCode::OracleAlreadyFinalized);
 
+        if let Some(rankings) = &rankings {
+            require!(
+                rankings.len() <= WinOracle::MAX_RANKINGS,
+                ErrorCode::TooManyRankings
+            );
+        }
+
         win_oracle.finalized = finalized;
         win_oracle.token_transfer_root = token_transfer_root;
         win_oracle.token_transfers = token_transfers;
+        win_oracle.rankings = rankings;
 
-        Ok(())
+        grow_to_fit(
+            win_oracle,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
     }
 
     pub fn create_match<'a, 'b, 'c, 'info>(
@@ -244,6 +282,47 @@ pub mod raindrops_matches {
         Ok(())
     }
 
+    /// Sets the entry fee, rake and payout schedule, creating the prize pool escrow
+    /// for the entry fee mint. Only allowed once, while the match is still in draft.
+    pub fn update_match_economics<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, UpdateMatchEconomics<'info>>,
+        args: UpdateMatchEconomicsArgs,
+    ) -> Result<()> {
+        let UpdateMatchEconomicsArgs {
+            entry_fee_amount,
+            rake,
+            payout_schedule,
+        } = args;
+
+        let match_instance = &mut ctx.accounts.match_instance;
+
+        require!(
+            match_instance.state == MatchState::Draft && match_instance.entry_fee.is_none(),
+            ErrorCode::CannotSetEconomics
+        );
+
+        let places = payout_schedule.places();
+        require!(
+            !places.is_empty()
+                && places.len() <= PayoutSchedule::MAX_PLACES
+                && places.iter().map(|p| *p as u64).sum::<u64>() == 10000,
+            ErrorCode::InvalidPayoutSchedule
+        );
+
+        if let Some(rake) = &rake {
+            require!(rake.basis_points <= 10000, ErrorCode::InvalidRake);
+        }
+
+        match_instance.entry_fee = Some(EntryFee {
+            mint: ctx.accounts.entry_fee_mint.key(),
+            amount: entry_fee_amount,
+        });
+        match_instance.rake = rake;
+        match_instance.payout_schedule = payout_schedule;
+
+        Ok(())
+    }
+
     pub fn update_match_from_oracle<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, UpdateMatchFromOracle<'info>>,
     ) -> Result<()> {
@@ -269,6 +348,13 @@ pub mod raindrops_matches {
         );
 
         if win_oracle_instance.finalized {
+            // the prize pool is paid out by rank, without rankings it could never empty
+            if match_instance.prize_pool > 0 {
+                require!(
+                    matches!(&win_oracle_instance.rankings, Some(rankings) if !rankings.is_empty()),
+                    ErrorCode::NoRankingsFound
+                );
+            }
             match_instance.state = MatchState::Finalized;
         } else {
             match_instance.state = MatchState::Started;
@@ -322,6 +408,11 @@ pub mod raindrops_matches {
             ErrorCode::CannotDrainYet
         );
 
+        require!(
+            match_instance.prize_pool_paid == match_instance.prize_pool,
+            ErrorCode::PrizePoolNotPaidOut
+        );
+
         let info = match_instance.to_account_info();
         let snapshot: u64 = info.lamports();
 
@@ -391,6 +482,60 @@ pub mod raindrops_matches {
             match_seeds,
         )?;
 
+        // entry fees come back to anyone leaving before the match pays out
+        if let Some(entry_fee) = match_instance.entry_fee.clone() {
+            if match_instance.state != MatchState::PaidOut {
+                require!(
+                    ctx.remaining_accounts.len() == 2,
+                    ErrorCode::MissingPrizePoolAccounts
+                );
+                let prize_pool_escrow = &ctx.remaining_accounts[0];
+                let fee_destination = &ctx.remaining_accounts[1];
+
+                assert_derivation(
+                    &crate::id(),
+                    prize_pool_escrow,
+                    &[
+                        PREFIX.as_bytes(),
+                        match_instance.win_oracle.as_ref(),
+                        entry_fee.mint.as_ref(),
+                        PRIZE_POOL.as_bytes(),
+                    ],
+                    ErrorCode::DerivedKeyInvalid,
+                )?;
+                assert_is_ata(fee_destination, &receiver.key(), &entry_fee.mint, None)?;
+
+                let pool_amount = Account::<TokenAccount>::try_from(prize_pool_escrow)?.amount;
+
+                spl_token_transfer(TokenTransferParams {
+                    source: prize_pool_escrow.clone(),
+                    destination: fee_destination.clone(),
+                    amount: entry_fee.amount,
+                    authority: match_info.clone(),
+                    authority_signer_seeds: match_seeds,
+                    token_program: token_program.to_account_info(),
+                })?;
+
+                // a deactivated match has nothing left to pay out once the last fee is refunded
+                if match_instance.state == MatchState::Deactivated
+                    && pool_amount == entry_fee.amount
+                {
+                    close_token_account(
+                        prize_pool_escrow,
+                        &match_info,
+                        token_program,
+                        &match_info,
+                        match_seeds,
+                    )?;
+                }
+
+                match_instance.prize_pool = match_instance
+                    .prize_pool
+                    .checked_sub(entry_fee.amount)
+                    .ok_or(ErrorCode::NumericalOverflowError)?;
+            }
+        }
+
         match_instance.token_types_removed = match_instance
             .token_types_removed
             .checked_add(1)
@@ -402,138 +547,178 @@ pub mod raindrops_matches {
     pub fn disburse_tokens_by_oracle<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, DisburseTokensByOracle<'info>>,
         args: DisburseTokensByOracleArgs,
+    ) -> Result<()> {
+        disburse_token_delta(DisburseTokenDeltaParams {
+            match_instance: &mut ctx.accounts.match_instance,
+            win_oracle: &ctx.accounts.win_oracle,
+            token_account_escrow: &ctx.accounts.token_account_escrow,
+            token_mint: &ctx.accounts.token_mint,
+            destination_token_account: ctx.accounts.destination_token_account.to_account_info(),
+            original_sender: ctx.accounts.original_sender.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            args,
+        })?;
+
+        grow_to_fit(
+            &ctx.accounts.match_instance,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Disburses several deltas at once. For every delta the remaining accounts hold
+    /// the token account escrow, token mint, destination token account and original sender.
+    pub fn disburse_tokens_by_oracle_batch<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DisburseTokensByOracleBatch<'info>>,
+        args: DisburseTokensByOracleBatchArgs,
+    ) -> Result<()> {
+        let DisburseTokensByOracleBatchArgs { token_deltas } = args;
+
+        require!(
+            ctx.remaining_accounts.len() == token_deltas.len() * 4,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        for (delta_args, accounts) in token_deltas
+            .into_iter()
+            .zip(ctx.remaining_accounts.chunks(4))
+        {
+            let token_mint: Account<'info, Mint> = Account::try_from(&accounts[1])?;
+            let original_sender = &accounts[3];
+
+            assert_derivation(
+                &crate::id(),
+                &accounts[0],
+                &[
+                    PREFIX.as_bytes(),
+                    ctx.accounts.match_instance.win_oracle.as_ref(),
+                    token_mint.key().as_ref(),
+                    original_sender.key.as_ref(),
+                ],
+                ErrorCode::DerivedKeyInvalid,
+            )?;
+
+            let token_account_escrow: Account<'info, TokenAccount> =
+                Account::try_from(&accounts[0])?;
+            let destination_token_account: Account<'info, TokenAccount> =
+                Account::try_from(&accounts[2])?;
+            require!(
+                destination_token_account.mint == token_mint.key(),
+                ErrorCode::MintMismatch
+            );
+
+            disburse_token_delta(DisburseTokenDeltaParams {
+                match_instance: &mut ctx.accounts.match_instance,
+                win_oracle: &ctx.accounts.win_oracle,
+                token_account_escrow: &token_account_escrow,
+                token_mint: &token_mint,
+                destination_token_account: accounts[2].clone(),
+                original_sender: original_sender.clone(),
+                token_program: &ctx.accounts.token_program,
+                args: delta_args,
+            })?;
+        }
+
+        grow_to_fit(
+            &ctx.accounts.match_instance,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Pays a place in the oracle rankings, or the rake when no rank is given,
+    /// out of the prize pool according to the match payout schedule.
+    pub fn disburse_prize_pool<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DisbursePrizePool<'info>>,
+        args: DisbursePrizePoolArgs,
     ) -> Result<()> {
         let match_instance = &mut ctx.accounts.match_instance;
-        let token_account_escrow = &ctx.accounts.token_account_escrow;
-        let destination_token_account = &ctx.accounts.destination_token_account;
-        let token_mint = &ctx.accounts.token_mint;
         let win_oracle = &ctx.accounts.win_oracle;
-        let original_sender = &ctx.accounts.original_sender;
+        let prize_pool_escrow = &ctx.accounts.prize_pool_escrow;
+        let destination_token_account = &ctx.accounts.destination_token_account;
         let token_program = &ctx.accounts.token_program;
 
-        let DisburseTokensByOracleArgs {
-            token_delta_proof_info,
-            ..
-        } = args;
+        let DisbursePrizePoolArgs { rank } = args;
 
         require!(
-            match_instance.state == MatchState::Finalized,
+            match_instance.state == MatchState::Finalized
+                || match_instance.state == MatchState::PaidOut,
             ErrorCode::MatchMustBeInFinalized
         );
 
-        msg!("1");
-        let tfer = if let Some(proof_info) = token_delta_proof_info {
-            let TokenDeltaProofInfo {
-                token_delta_proof,
-                token_delta,
-                total_proof,
-                total,
-            } = proof_info;
-            if let Some(root) = &win_oracle.token_transfer_root {
-                let chief_node = anchor_lang::solana_program::keccak::hashv(&[
-                    &[0x00],
-                    &AnchorSerialize::try_to_vec(&token_delta)?,
-                    &match_instance.current_token_transfer_index.to_le_bytes(),
-                ]);
-                require!(
-                    verify(&token_delta_proof, &root.root, chief_node.0),
-                    ErrorCode::InvalidProof
-                );
+        let entry_fee = match_instance
+            .entry_fee
+            .clone()
+            .ok_or(ErrorCode::EntryFeeNotSet)?;
 
-                let total_node =
-                    anchor_lang::solana_program::keccak::hashv(&[&[0x00], &total.to_le_bytes()]);
-                require!(
-                    verify(&total_proof, &root.root, total_node.0),
-                    ErrorCode::InvalidProof
-                );
-                if match_instance.current_token_transfer_index == (total - 1) {
-                    match_instance.state = MatchState::PaidOut;
-                }
-                token_delta
-            } else {
-                return Err(error!(ErrorCode::RootNotPresent));
-            }
-        } else if let Some(tfer_arr) = &win_oracle.token_transfers {
-            if match_instance.current_token_transfer_index == (tfer_arr.len() - 1) as u64 {
-                match_instance.state = MatchState::PaidOut;
-            }
-            tfer_arr[match_instance.current_token_transfer_index as usize].clone()
-        } else {
-            return Err(error!(ErrorCode::NoDeltasFound));
+        let rake_amount = match &match_instance.rake {
+            Some(rake) => basis_points_of(match_instance.prize_pool, rake.basis_points)?,
+            None => 0,
         };
-        msg!("2");
-
-        require!(
-            tfer.token_transfer_type == TokenTransferType::Normal,
-            ErrorCode::UsePlayerEndpoint
-        );
 
-        require!(
-            token_account_escrow.amount <= tfer.amount,
-            ErrorCode::CannotDeltaMoreThanAmountPresent
-        );
-        msg!("3");
-
-        require!(
-            tfer.mint == token_mint.key(),
-            ErrorCode::DeltaMintDoesNotMatch
-        );
+        let (recipient, amount) = if let Some(rank) = rank {
+            let rankings = win_oracle
+                .rankings
+                .as_ref()
+                .ok_or(ErrorCode::NoRankingsFound)?;
+            let net_pool = match_instance
+                .prize_pool
+                .checked_sub(rake_amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+            let amount = prize_for_rank(
+                net_pool,
+                &match_instance.payout_schedule.places(),
+                rankings.len(),
+                rank as usize,
+            )?;
+            set_claimed_bit(
+                &mut match_instance.prizes_claimed,
+                rank,
+                ErrorCode::PrizeAlreadyClaimed,
+            )?;
+            (rankings[rank as usize], amount)
+        } else {
+            let rake = match_instance.rake.clone().ok_or(ErrorCode::RakeNotSet)?;
+            require!(!match_instance.rake_paid, ErrorCode::PrizeAlreadyClaimed);
+            match_instance.rake_paid = true;
+            (rake.treasury, rake_amount)
+        };
 
-        require!(
-            tfer.from == original_sender.key(),
-            ErrorCode::FromDoesNotMatch
-        );
+        assert_is_ata(
+            &destination_token_account.to_account_info(),
+            &recipient,
+            &entry_fee.mint,
+            None,
+        )?;
 
-        let time_to_close = token_account_escrow.amount == tfer.amount;
         let match_seeds = &[
             PREFIX.as_bytes(),
             match_instance.win_oracle.as_ref(),
             &[match_instance.bump],
         ];
 
-        if let Some(to) = tfer.to {
-            let dest_acct_info = destination_token_account.to_account_info();
-            if to != destination_token_account.key() {
-                assert_is_ata(&dest_acct_info, &to, &token_mint.key(), None)?;
-            }
-
-            spl_token_transfer(TokenTransferParams {
-                source: token_account_escrow.to_account_info(),
-                destination: dest_acct_info,
-                amount: tfer.amount,
-                authority: match_instance.to_account_info(),
-                authority_signer_seeds: match_seeds,
-                token_program: token_program.to_account_info(),
-            })?;
-        } else {
-            spl_token_burn(TokenBurnParams {
-                mint: token_mint.to_account_info(),
-                source: token_account_escrow.to_account_info(),
-                amount: tfer.amount,
-                authority: match_instance.to_account_info(),
-                authority_signer_seeds: Some(match_seeds),
-                token_program: token_program.to_account_info(),
-            })?;
-        }
-        msg!("4");
+        spl_token_transfer(TokenTransferParams {
+            source: prize_pool_escrow.to_account_info(),
+            destination: destination_token_account.to_account_info(),
+            amount,
+            authority: match_instance.to_account_info(),
+            authority_signer_seeds: match_seeds,
+            token_program: token_program.to_account_info(),
+        })?;
 
-        if time_to_close {
+        if prize_pool_escrow.amount == amount {
             close_token_account(
-                &token_account_escrow.to_account_info(),
-                &original_sender.to_account_info(),
+                &prize_pool_escrow.to_account_info(),
+                &match_instance.to_account_info(),
                 token_program,
                 &match_instance.to_account_info(),
                 match_seeds,
             )?;
-            match_instance.token_types_removed = match_instance
-                .token_types_removed
-                .checked_add(1)
-                .ok_or(ErrorCode::NumericalOverflowError)?;
         }
 
-        match_instance.current_token_transfer_index = match_instance
-            .current_token_transfer_index
-            .checked_add(1)
+        match_instance.prize_pool_paid = match_instance
+            .prize_pool_paid
+            .checked_add(amount)
             .ok_or(ErrorCode::NumericalOverflowError)?;
 
         Ok(())
@@ -625,6 +810,13 @@ pub mod raindrops_matches {
             }
         };
 
+        // the entry fee is charged once per escrow, leave_match refunds it once when the
+        // escrow closes. an escrow left empty would be charged again on the next join
+        let first_entry = token_account_escrow.amount == 0;
+        if match_instance.entry_fee.is_some() {
+            require!(amount > 0, ErrorCode::EntryFeeNeedsTokens);
+        }
+
         spl_token_transfer(TokenTransferParams {
             source: source_info,
             destination: token_account_escrow.to_account_info(),
@@ -634,6 +826,47 @@ pub mod raindrops_matches {
             token_program: token_info,
         })?;
 
+        if let Some(entry_fee) = match_instance.entry_fee.clone().filter(|_| first_entry) {
+            require!(
+                ctx.remaining_accounts.len() == 2,
+                ErrorCode::MissingPrizePoolAccounts
+            );
+            let prize_pool_escrow = &ctx.remaining_accounts[0];
+            let fee_source = &ctx.remaining_accounts[1];
+
+            assert_derivation(
+                &crate::id(),
+                prize_pool_escrow,
+                &[
+                    PREFIX.as_bytes(),
+                    match_instance.win_oracle.as_ref(),
+                    entry_fee.mint.as_ref(),
+                    PRIZE_POOL.as_bytes(),
+                ],
+                ErrorCode::DerivedKeyInvalid,
+            )?;
+            assert_is_ata(
+                fee_source,
+                &payer.key(),
+                &entry_fee.mint,
+                Some(&token_transfer_authority.key()),
+            )?;
+
+            spl_token_transfer(TokenTransferParams {
+                source: fee_source.clone(),
+                destination: prize_pool_escrow.clone(),
+                amount: entry_fee.amount,
+                authority: token_transfer_authority.to_account_info(),
+                authority_signer_seeds: &[],
+                token_program: token_program.to_account_info(),
+            })?;
+
+            match_instance.prize_pool = match_instance
+                .prize_pool
+                .checked_add(entry_fee.amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
+
         match_instance.token_types_added = match_instance
             .token_types_added
             .checked_add(1)
@@ -815,6 +1048,22 @@ pub struct UpdateMatch<'info> {
     authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(args: UpdateMatchEconomicsArgs)]
+pub struct UpdateMatchEconomics<'info> {
+    #[account(mut, constraint=match_instance.authority == authority.key(), seeds=[PREFIX.as_bytes(), match_instance.win_oracle.as_ref()], bump=match_instance.bump)]
+    match_instance: Box<Account<'info, Match>>,
+    #[account(init, seeds=[PREFIX.as_bytes(), match_instance.win_oracle.as_ref(), entry_fee_mint.key().as_ref(), PRIZE_POOL.as_bytes()], bump, token::mint = entry_fee_mint, token::authority = match_instance, payer=payer)]
+    prize_pool_escrow: Box<Account<'info, TokenAccount>>,
+    entry_fee_mint: Box<Account<'info, Mint>>,
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateMatchFromOracle<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), match_instance.win_oracle.as_ref()], bump=match_instance.bump)]
@@ -892,11 +1141,40 @@ pub struct DisburseTokensByOracle<'info> {
     /// CHECK: TODO
     #[account(mut)]
     original_sender: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
     system_program: Program<'info, System>,
     token_program: Program<'info, Token>,
     rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+#[instruction(args: DisburseTokensByOracleBatchArgs)]
+pub struct DisburseTokensByOracleBatch<'info> {
+    #[account(mut, seeds=[PREFIX.as_bytes(), match_instance.win_oracle.as_ref()], bump=match_instance.bump)]
+    match_instance: Account<'info, Match>,
+    #[account(constraint=win_oracle.key() == match_instance.win_oracle)]
+    win_oracle: Box<Account<'info, WinOracle>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: DisbursePrizePoolArgs)]
+pub struct DisbursePrizePool<'info> {
+    #[account(mut, seeds=[PREFIX.as_bytes(), match_instance.win_oracle.as_ref()], bump=match_instance.bump)]
+    match_instance: Box<Account<'info, Match>>,
+    #[account(constraint=win_oracle.key() == match_instance.win_oracle)]
+    win_oracle: Box<Account<'info, WinOracle>>,
+    #[account(mut, seeds=[PREFIX.as_bytes(), match_instance.win_oracle.as_ref(), prize_pool_escrow.mint.as_ref(), PRIZE_POOL.as_bytes()], bump)]
+    prize_pool_escrow: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint=destination_token_account.mint == prize_pool_escrow.mint)]
+    destination_token_account: Box<Account<'info, TokenAccount>>,
+    token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 #[instruction(args: DisburseTokensByOracleArgs)]
 pub struct DisbursePlayerTokensByOracle<'info> {
@@ -1021,14 +1299,6 @@ pub enum MatchState {
     Deactivated,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum PermissivenessType {
-    TokenHolder,
-    ParentTokenHolder,
-    UpdateAuthority,
-    Anybody,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct Root {
     root: [u8; 32],
@@ -1038,23 +1308,6 @@ impl Root {
     pub const SPACE: usize = 32;
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum InheritanceState {
-    NotInherited,
-    Inherited,
-    Overridden,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Callback {
-    pub key: Pubkey,
-    pub code: u64,
-}
-
-impl Callback {
-    pub const SPACE: usize = 32 + 8;
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ValidationArgs {
     // For enum detection on the other end.
@@ -1063,17 +1316,6 @@ pub struct ValidationArgs {
     token_validation: TokenValidation,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct NamespaceAndIndex {
-    pub namespace: Pubkey,
-    pub index: Option<u64>,
-    pub inherited: InheritanceState,
-}
-
-impl NamespaceAndIndex {
-    pub const SPACE: usize = 32 + 9 + 2;
-}
-
 use anchor_spl::token::Token;
 
 #[account]
@@ -1089,14 +1331,27 @@ pub struct Match {
     pub leave_allowed: bool,
     pub minimum_allowed_entry_time: Option<u64>,
     pub bump: u8,
-    /// Increased by 1 every time the next token transfer
-    /// in the win oracle is completed.
-    pub current_token_transfer_index: u64,
+    /// Increased by 1 every time a token transfer
+    /// in the win oracle is completed, in any order.
+    pub token_transfers_completed: u64,
     pub token_types_added: u64,
     pub token_types_removed: u64,
     pub token_entry_validation: Option<Vec<TokenValidation>>,
     pub token_entry_validation_root: Option<Root>,
     pub join_allowed_during_start: bool,
+    /// One bit per win oracle token transfer, set once it is disbursed.
+    pub token_transfers_claimed: Vec<u8>,
+    /// Paid into the prize pool on every join when present.
+    pub entry_fee: Option<EntryFee>,
+    pub rake: Option<Rake>,
+    pub payout_schedule: PayoutSchedule,
+    /// Entry fees collected, net of refunds to entrants who left.
+    pub prize_pool: u64,
+    /// Paid out of the prize pool to ranked players and the treasury.
+    pub prize_pool_paid: u64,
+    pub rake_paid: bool,
+    /// One bit per payout schedule place, set once it is paid.
+    pub prizes_claimed: Vec<u8>,
 }
 
 impl Match {
@@ -1110,12 +1365,63 @@ impl Match {
         + 1 // leave_allowed
         + (1 + 8) // minimum_allowed_entry_time
         + 1 // bump
-        + 8 // current_token_transfer_index
+        + 8 // token_transfers_completed
         + 8 // token_types_added
         + 8 // token_types_removed
         + (1 + 4 + (TokenValidation::SPACE * 10)) // max 10 token_entry_validation
         + (1 + Root::SPACE) // token_entry_validation_root
-        + 1; // join_allowed_during_start
+        + 1 // join_allowed_during_start
+        + (4 + 32) // token_transfers_claimed, 256 deltas, grown on disbursement past that
+        + (1 + EntryFee::SPACE) // entry_fee
+        + (1 + Rake::SPACE) // rake
+        + PayoutSchedule::SPACE // payout_schedule
+        + 8 // prize_pool
+        + 8 // prize_pool_paid
+        + 1 // rake_paid
+        + (4 + 2); // prizes_claimed, bitmap for max 10 places
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EntryFee {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl EntryFee {
+    pub const SPACE: usize = 32 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Rake {
+    // paid to the treasury's ata for the entry fee mint
+    pub treasury: Pubkey,
+    pub basis_points: u16,
+}
+
+impl Rake {
+    pub const SPACE: usize = 32 + 2;
+}
+
+/// How the prize pool, less the rake, is split across the oracle rankings.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum PayoutSchedule {
+    WinnerTakeAll,
+    /// Basis points per place, first place first. Must add up to 10000.
+    TopN {
+        basis_points: Vec<u16>,
+    },
+}
+
+impl PayoutSchedule {
+    pub const MAX_PLACES: usize = 10;
+    pub const SPACE: usize = 1 + 4 + (2 * PayoutSchedule::MAX_PLACES);
+
+    pub fn places(&self) -> Vec<u16> {
+        match self {
+            PayoutSchedule::WinnerTakeAll => vec![10000],
+            PayoutSchedule::TopN { basis_points } => basis_points.clone(),
+        }
+    }
 }
 
 #[account]
@@ -1146,6 +1452,16 @@ pub struct WinOracle {
     finalized: bool,
     token_transfer_root: Option<Root>,
     token_transfers: Option<Vec<TokenDelta>>,
+    // player wallets in finishing order, used to pay out the prize pool.
+    // oracles written before rankings existed only load if they have at least
+    // one spare zero byte after token_transfers, create_or_update_oracle grows
+    // the account to fit whatever rankings are set
+    rankings: Option<Vec<Pubkey>>,
+}
+
+impl WinOracle {
+    // only the paid places of a payout schedule are ever read
+    pub const MAX_RANKINGS: usize = PayoutSchedule::MAX_PLACES;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -1280,4 +1596,34 @@ pub enum ErrorCode {
     AlreadyCached,
     #[msg("Not cached")]
     NotCached,
+    #[msg("This token transfer has already been disbursed")]
+    TokenTransferAlreadyClaimed,
+    #[msg("Token transfer index is out of range for this oracle")]
+    InvalidTokenTransferIndex,
+    #[msg("Remaining accounts must hold escrow, mint, destination and original sender per delta")]
+    InvalidRemainingAccounts,
+    #[msg("Match economics can only be set once, while the match is in draft")]
+    CannotSetEconomics,
+    #[msg("Payout schedule must have between 1 and 10 places adding up to 10000 basis points")]
+    InvalidPayoutSchedule,
+    #[msg("Rake cannot be more than 10000 basis points")]
+    InvalidRake,
+    #[msg("Remaining accounts must hold the prize pool escrow and the entry fee token account")]
+    MissingPrizePoolAccounts,
+    #[msg("This match has no entry fee")]
+    EntryFeeNotSet,
+    #[msg("This match has no rake")]
+    RakeNotSet,
+    #[msg("This oracle lacks any rankings")]
+    NoRankingsFound,
+    #[msg("Rank is not paid by this payout schedule")]
+    InvalidRank,
+    #[msg("This prize has already been disbursed")]
+    PrizeAlreadyClaimed,
+    #[msg("Prize pool must be paid out first")]
+    PrizePoolNotPaidOut,
+    #[msg("Oracles can rank at most 10 players")]
+    TooManyRankings,
+    #[msg("Matches with an entry fee must be joined with tokens")]
+    EntryFeeNeedsTokens,
 }
@@ -1,27 +1,34 @@
-use crate::{ErrorCode, Filter, TokenValidation, ValidationArgs};
+pub use raindrops_common::{
+    utils::{
+        assert_derivation, create_or_allocate_account_raw, is_namespace_program_caller,
+        resize_account, sighash, verify,
+    },
+    NAMESPACE_ID,
+};
+
+use crate::{
+    DisburseTokensByOracleArgs, ErrorCode, Filter, Match, MatchState, TokenDeltaProofInfo,
+    TokenTransferType, TokenValidation, ValidationArgs, WinOracle, PREFIX,
+};
 use anchor_lang::{
     error,
     prelude::{
-        msg, Account, AccountInfo, AccountMeta, Program, Pubkey, Rent, Result, SolanaSysvar,
-        UncheckedAccount,
+        Account, AccountDeserialize, AccountInfo, AccountMeta, AccountSerialize, Error, Owner,
+        Program, Pubkey, Result, UncheckedAccount,
     },
     require,
     solana_program::{
-        hash,
         instruction::Instruction,
+        keccak,
         program::{invoke, invoke_signed},
         program_pack::{IsInitialized, Pack},
-        system_instruction,
     },
     AnchorSerialize, Key, ToAccountInfo,
 };
-use anchor_spl::token::{Mint, Token};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use arrayref::array_ref;
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::instruction::close_account;
-use std::{convert::TryInto, str::FromStr};
-
-pub const NAMESPACE_ID: &str = "nameAxQRRBnd4kLfsVoZBBXfrByZdZTkh8mULLxLyqV";
 
 pub fn assert_is_ata(
     ata: &AccountInfo,
@@ -161,60 +168,234 @@ pub fn get_mask_and_index_for_seq(seq: u64) -> Result<(u8, usize)> {
     Ok((mask, my_position_in_index as usize))
 }
 
-pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<u8> {
-    let (key, bump) = Pubkey::find_program_address(path, program_id);
-    if key != *account.key {
-        return Err(error!(ErrorCode::DerivedKeyInvalid));
+/// Flips the bit at index, erroring if it was already set.
+pub fn set_claimed_bit(claimed: &mut Vec<u8>, index: u64, error: impl Into<Error>) -> Result<()> {
+    let (mask, position) = get_mask_and_index_for_seq(index)?;
+    if claimed.len() <= position {
+        claimed.resize(position + 1, 0);
     }
-    Ok(bump)
+
+    if claimed[position] & mask != 0 {
+        return Err(error.into());
+    }
+    claimed[position] |= mask;
+
+    Ok(())
 }
 
-/// Create account almost from scratch, lifted from
-/// https://github.com/solana-labs/solana-program-library/blob/7d4873c61721aca25464d42cc5ef651a7923ca79/associated-token-account/program/src/processor.rs#L51-L98
-#[inline(always)]
-pub fn create_or_allocate_account_raw<'a>(
-    program_id: Pubkey,
-    new_account_info: &AccountInfo<'a>,
-    rent_sysvar_info: &AccountInfo<'a>,
-    system_program_info: &AccountInfo<'a>,
-    payer_info: &AccountInfo<'a>,
-    size: usize,
-    signer_seeds: &[&[u8]],
-) -> Result<()> {
-    let rent = &Rent::from_account_info(rent_sysvar_info)?;
-    let required_lamports = rent
-        .minimum_balance(size)
-        .max(1)
-        .saturating_sub(new_account_info.lamports());
-
-    if required_lamports > 0 {
-        msg!("Transfer {} lamports to the new account", required_lamports);
-        invoke(
-            &system_instruction::transfer(payer_info.key, new_account_info.key, required_lamports),
-            &[
-                payer_info.clone(),
-                new_account_info.clone(),
-                system_program_info.clone(),
-            ],
-        )?;
+pub fn basis_points_of(amount: u64, basis_points: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(basis_points as u128)
+        .ok_or(ErrorCode::NumericalOverflowError)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+
+    Ok(share as u64)
+}
+
+/// Amount owed to a ranked place. First place also takes the rounding dust and the
+/// share of any places the oracle did not rank anyone for, so the pool always empties.
+pub fn prize_for_rank(net_pool: u64, places: &[u16], ranked: usize, rank: usize) -> Result<u64> {
+    let paid_places = places.len().min(ranked);
+    require!(rank < paid_places, ErrorCode::InvalidRank);
+
+    if rank > 0 {
+        return basis_points_of(net_pool, places[rank]);
     }
 
-    let accounts = &[new_account_info.clone(), system_program_info.clone()];
+    let mut others = 0_u64;
+    for place in &places[1..paid_places] {
+        others = others
+            .checked_add(basis_points_of(net_pool, *place)?)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+    }
 
-    msg!("Allocate space for the account");
-    invoke_signed(
-        &system_instruction::allocate(new_account_info.key, size.try_into().unwrap()),
-        accounts,
-        &[signer_seeds],
-    )?;
+    net_pool
+        .checked_sub(others)
+        .ok_or_else(|| error!(ErrorCode::NumericalOverflowError))
+}
 
-    msg!("Assign the account to the owning program");
-    invoke_signed(
-        &system_instruction::assign(new_account_info.key, &program_id),
-        accounts,
-        &[signer_seeds],
+// grows the account to fit its serialized contents, the payer covers the rent.
+// the match claimed bitmap and oracle rankings can outgrow the space passed at creation
+pub fn grow_to_fit<'a, T>(
+    account: &Account<'a, T>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize + AnchorSerialize + Owner + Clone,
+{
+    let info = account.to_account_info();
+    let required_space = 8 + AnchorSerialize::try_to_vec(&**account)?.len();
+
+    if required_space > info.data_len() {
+        resize_account(&info, payer, system_program, required_space)?;
+    }
+
+    Ok(())
+}
+
+pub struct DisburseTokenDeltaParams<'a, 'b> {
+    pub match_instance: &'b mut Account<'a, Match>,
+    pub win_oracle: &'b WinOracle,
+    pub token_account_escrow: &'b Account<'a, TokenAccount>,
+    pub token_mint: &'b Account<'a, Mint>,
+    pub destination_token_account: AccountInfo<'a>,
+    pub original_sender: AccountInfo<'a>,
+    pub token_program: &'b Program<'a, Token>,
+    pub args: DisburseTokensByOracleArgs,
+}
+
+pub fn disburse_token_delta(params: DisburseTokenDeltaParams<'_, '_>) -> Result<()> {
+    let DisburseTokenDeltaParams {
+        match_instance,
+        win_oracle,
+        token_account_escrow,
+        token_mint,
+        destination_token_account,
+        original_sender,
+        token_program,
+        args,
+    } = params;
+
+    let DisburseTokensByOracleArgs {
+        token_delta_proof_info,
+        index,
+    } = args;
+
+    require!(
+        match_instance.state == MatchState::Finalized,
+        ErrorCode::MatchMustBeInFinalized
+    );
+
+    let (tfer, total) = if let Some(proof_info) = token_delta_proof_info {
+        let TokenDeltaProofInfo {
+            token_delta_proof,
+            token_delta,
+            total_proof,
+            total,
+        } = proof_info;
+        if let Some(root) = &win_oracle.token_transfer_root {
+            let chief_node = keccak::hashv(&[
+                &[0x00],
+                &AnchorSerialize::try_to_vec(&token_delta)?,
+                &index.to_le_bytes(),
+            ]);
+            require!(
+                verify(&token_delta_proof, &root.root, chief_node.0),
+                ErrorCode::InvalidProof
+            );
+
+            let total_node = keccak::hashv(&[&[0x00], &total.to_le_bytes()]);
+            require!(
+                verify(&total_proof, &root.root, total_node.0),
+                ErrorCode::InvalidProof
+            );
+            (token_delta, total)
+        } else {
+            return Err(error!(ErrorCode::RootNotPresent));
+        }
+    } else if let Some(tfer_arr) = &win_oracle.token_transfers {
+        let tfer = tfer_arr
+            .get(index as usize)
+            .ok_or(ErrorCode::InvalidTokenTransferIndex)?;
+        (tfer.clone(), tfer_arr.len() as u64)
+    } else {
+        return Err(error!(ErrorCode::NoDeltasFound));
+    };
+
+    require!(index < total, ErrorCode::InvalidTokenTransferIndex);
+
+    // matches part way through paying out in order before the bitmap existed
+    // already disbursed everything below the old cursor
+    if match_instance.token_transfers_claimed.is_empty() {
+        for seq in 0..match_instance.token_transfers_completed {
+            set_claimed_bit(
+                &mut match_instance.token_transfers_claimed,
+                seq,
+                ErrorCode::TokenTransferAlreadyClaimed,
+            )?;
+        }
+    }
+    set_claimed_bit(
+        &mut match_instance.token_transfers_claimed,
+        index,
+        ErrorCode::TokenTransferAlreadyClaimed,
     )?;
-    msg!("Completed assignation!");
+
+    require!(
+        tfer.token_transfer_type == TokenTransferType::Normal,
+        ErrorCode::UsePlayerEndpoint
+    );
+
+    require!(
+        tfer.amount <= token_account_escrow.amount,
+        ErrorCode::CannotDeltaMoreThanAmountPresent
+    );
+
+    require!(
+        tfer.mint == token_mint.key(),
+        ErrorCode::DeltaMintDoesNotMatch
+    );
+
+    require!(
+        tfer.from == original_sender.key(),
+        ErrorCode::FromDoesNotMatch
+    );
+
+    let time_to_close = token_account_escrow.amount == tfer.amount;
+    let match_seeds = &[
+        PREFIX.as_bytes(),
+        match_instance.win_oracle.as_ref(),
+        &[match_instance.bump],
+    ];
+
+    if let Some(to) = tfer.to {
+        if to != destination_token_account.key() {
+            assert_is_ata(&destination_token_account, &to, &token_mint.key(), None)?;
+        }
+
+        spl_token_transfer(TokenTransferParams {
+            source: token_account_escrow.to_account_info(),
+            destination: destination_token_account,
+            amount: tfer.amount,
+            authority: match_instance.to_account_info(),
+            authority_signer_seeds: match_seeds,
+            token_program: token_program.to_account_info(),
+        })?;
+    } else {
+        spl_token_burn(TokenBurnParams {
+            mint: token_mint.to_account_info(),
+            source: token_account_escrow.to_account_info(),
+            amount: tfer.amount,
+            authority: match_instance.to_account_info(),
+            authority_signer_seeds: Some(match_seeds),
+            token_program: token_program.to_account_info(),
+        })?;
+    }
+
+    if time_to_close {
+        close_token_account(
+            &token_account_escrow.to_account_info(),
+            &original_sender,
+            token_program,
+            &match_instance.to_account_info(),
+            match_seeds,
+        )?;
+        match_instance.token_types_removed = match_instance
+            .token_types_removed
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+    }
+
+    match_instance.token_transfers_completed = match_instance
+        .token_transfers_completed
+        .checked_add(1)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+
+    if match_instance.token_transfers_completed == total {
+        match_instance.state = MatchState::PaidOut;
+    }
 
     Ok(())
 }
@@ -286,35 +467,6 @@ pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> Result<()> {
     result.map_err(|_| error!(ErrorCode::TokenBurnFailed))
 }
 
-/// Returns true if a `leaf` can be proved to be a part of a Merkle tree
-/// defined by `root`. For this, a `proof` must be provided, containing
-/// sibling hashes on the branch from the leaf to the root of the tree. Each
-/// pair of leaves and each pair of pre-images are assumed to be sorted.
-pub fn verify(proof: &Vec<[u8; 32]>, root: &[u8; 32], leaf: [u8; 32]) -> bool {
-    let mut computed_hash = leaf;
-    for proof_element in proof.iter() {
-        if computed_hash <= *proof_element {
-            // Hash(current computed hash + current element of the proof)
-            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x01],
-                &computed_hash,
-                proof_element,
-            ])
-            .0;
-        } else {
-            // Hash(current element of the proof + current computed hash)
-            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x01],
-                proof_element,
-                &computed_hash,
-            ])
-            .0;
-        }
-    }
-    // Check if the computed hash (root) is equal to the provided root
-    computed_hash == *root
-}
-
 pub fn is_part_of_namespace<'a>(artifact: &AccountInfo<'a>, namespace: &Pubkey) -> bool {
     let data = artifact.data.borrow_mut();
     let number = u32::from_le_bytes(*array_ref![data, 9, 4]) as usize;
@@ -330,14 +482,6 @@ pub fn is_part_of_namespace<'a>(artifact: &AccountInfo<'a>, namespace: &Pubkey)
     false
 }
 
-pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
-    let preimage = format!("{namespace}:{name}");
-
-    let mut sighash = [0u8; 8];
-    sighash.copy_from_slice(&hash::hash(preimage.as_bytes()).to_bytes()[..8]);
-    sighash
-}
-
 pub fn grab_parent<'a>(artifact: &AccountInfo<'a>) -> Result<Pubkey> {
     let data = artifact.data.borrow();
 
@@ -427,15 +571,52 @@ pub fn is_valid_validation<'info>(
     Ok(true)
 }
 
-// returns true if the namespace program called the item program
-pub fn is_namespace_program_caller(ixns: &AccountInfo) -> bool {
-    let current_ix =
-        anchor_lang::solana_program::sysvar::instructions::get_instruction_relative(0, ixns)
-            .unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if current_ix.program_id != Pubkey::from_str(NAMESPACE_ID).unwrap() {
-        return false;
-    };
+    #[test]
+    fn basis_points_round_down_without_overflow() {
+        assert_eq!(basis_points_of(10_000, 10_000).unwrap(), 10_000);
+        assert_eq!(basis_points_of(101, 3_000).unwrap(), 30);
+        assert_eq!(basis_points_of(1, 9_999).unwrap(), 0);
+        assert_eq!(basis_points_of(u64::MAX, 10_000).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn first_place_takes_the_dust() {
+        let places = [5_000, 3_000, 2_000];
+
+        let prizes: Vec<u64> = (0..3)
+            .map(|rank| prize_for_rank(101, &places, 3, rank).unwrap())
+            .collect();
+        assert_eq!(prizes, vec![51, 30, 20]);
+        assert_eq!(prizes.iter().sum::<u64>(), 101);
+    }
+
+    #[test]
+    fn first_place_takes_unranked_places() {
+        let places = [5_000, 3_000, 2_000];
 
-    true
+        assert_eq!(prize_for_rank(1_000, &places, 2, 0).unwrap(), 700);
+        assert_eq!(prize_for_rank(1_000, &places, 2, 1).unwrap(), 300);
+        assert!(prize_for_rank(1_000, &places, 2, 2).is_err());
+        assert_eq!(prize_for_rank(1_000, &places, 1, 0).unwrap(), 1_000);
+        assert!(prize_for_rank(1_000, &places, 0, 0).is_err());
+    }
+
+    #[test]
+    fn rake_and_prizes_add_up_to_the_pool() {
+        let prize_pool = 1_003;
+        let places = [6_000, 4_000];
+
+        let rake = basis_points_of(prize_pool, 250).unwrap();
+        let net_pool = prize_pool - rake;
+        let prizes: u64 = (0..places.len())
+            .map(|rank| prize_for_rank(net_pool, &places, 5, rank).unwrap())
+            .sum();
+
+        assert_eq!(rake, 25);
+        assert_eq!(rake + prizes, prize_pool);
+    }
 }
@@ -1,48 +1,52 @@
 pub mod utils;
 
-use std::str::FromStr;
-
 use crate::utils::{
-    assert_initialized, assert_metadata_valid, check_permissiveness_against_holder,
-    lowest_available_page, pull_namespaces,
+    assert_initialized, assert_metadata_valid, assert_namespace_payment_accounts,
+    cache_artifact_page, check_permissiveness_against_holder, ensure_index_space, load_index,
+    lowest_available_page, pull_namespaces, resize_account, HolderProofs,
 };
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use itemv2::cpi::{
+    accounts::{ItemClassJoinNamespace, ItemClassLeaveNamespace, ItemClassUncacheNamespace},
+    item_class_join_namespace, item_class_leave_namespace, item_class_uncache_namespace,
+};
+use raindrops_avatar::cpi::{
+    accounts::{
+        AvatarArtifactJoinNamespace, AvatarArtifactLeaveNamespace, AvatarArtifactUncacheNamespace,
+    },
+    avatar_artifact_join_namespace, avatar_artifact_leave_namespace,
+    avatar_artifact_uncache_namespace,
+};
+pub use raindrops_common::{Callback, InheritanceState, NamespaceAndIndex};
 use raindrops_item::cpi::{
     accounts::{
-        ItemArtifactCacheNamespace, ItemArtifactJoinNamespace, ItemArtifactLeaveNamespace,
-        ItemArtifactUncacheNamespace,
+        ItemArtifactJoinNamespace, ItemArtifactLeaveNamespace, ItemArtifactUncacheNamespace,
     },
-    item_artifact_cache_namespace, item_artifact_join_namespace, item_artifact_leave_namespace,
-    item_artifact_uncache_namespace,
+    item_artifact_join_namespace, item_artifact_leave_namespace, item_artifact_uncache_namespace,
 };
 
 use raindrops_matches::cpi::{
-    accounts::{
-        MatchCacheNamespace, MatchJoinNamespace, MatchLeaveNamespace, MatchUncacheNamespace,
-    },
-    match_cache_namespace, match_join_namespace, match_leave_namespace, match_uncache_namespace,
+    accounts::{MatchJoinNamespace, MatchLeaveNamespace, MatchUncacheNamespace},
+    match_join_namespace, match_leave_namespace, match_uncache_namespace,
 };
 
 use raindrops_player::cpi::{
     accounts::{
-        PlayerArtifactCacheNamespace, PlayerArtifactJoinNamespace, PlayerArtifactLeaveNamespace,
-        PlayerArtifactUncacheNamespace,
+        PlayerArtifactJoinNamespace, PlayerArtifactLeaveNamespace, PlayerArtifactUncacheNamespace,
     },
-    player_artifact_cache_namespace, player_artifact_join_namespace,
-    player_artifact_leave_namespace, player_artifact_uncache_namespace,
+    player_artifact_join_namespace, player_artifact_leave_namespace,
+    player_artifact_uncache_namespace,
 };
 
 anchor_lang::declare_id!("nameAxQRRBnd4kLfsVoZBBXfrByZdZTkh8mULLxLyqV");
 pub const PREFIX: &str = "namespace";
 const GATEKEEPER: &str = "gatekeeper";
+pub const PROTOCOL_FEE: &str = "protocol_fee";
+pub const MEMBERSHIP_PAYMENT: &str = "membership_payment";
 const MAX_WHITELIST: usize = 5;
 const MAX_CACHED_ITEMS_PER_INDEX: usize = 100;
 
-const RAIN_TOKEN_MINT: &str = "rainH85N1vCoerCi4cQ3w6mCf7oYUdrsTFtFzpaRwjL";
-const RAIN_TOKEN_VAULT_AUTHORITY: &str = "D1ZCwPPkGNJabsP8GPshmaVTNHxYwQ9bp1fcE22gftLN";
-const RAIN_PAYMENT_AMOUNT: u64 = 100_000; // 1 token with 5 decimals
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct InitializeNamespaceArgs {
     desired_namespace_array_size: u64,
@@ -58,16 +62,43 @@ pub struct UpdateNamespaceArgs {
     pretty_name: Option<String>,
     permissiveness_settings: Option<PermissivenessSettings>,
     whitelisted_staking_mints: Option<Vec<Pubkey>>,
+    // needs a payment vault owned by the namespace so it can sign the refund
+    refund_payment_on_leave: Option<bool>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeProtocolFeeConfigArgs {
+    fee_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateProtocolFeeConfigArgs {
+    admin: Option<Pubkey>,
+    fee_amount: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct JoinNamespaceArgs {
+    // one proof per merkle allowlist filter, in gatekeeper order
+    allowlist_proofs: Vec<Vec<[u8; 32]>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CacheArtifactArgs {
     page: u64,
+    // orders the artifact within its index page only, pages are not sorted against
+    // each other. unkeyed artifacts sort as 0
+    sort_key: Option<u64>,
 }
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UncacheArtifactArgs {
     page: u64,
 }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompactIndexArgs {
+    from_page: u64,
+    to_page: u64,
+}
 
 #[program]
 pub mod raindrops_namespace {
@@ -173,6 +204,8 @@ pub mod raindrops_namespace {
         namespace.artifacts_cached = 0;
         namespace.artifacts_added = 0;
         namespace.gatekeeper = None;
+        namespace.protocol_fee_exempt = false;
+        namespace.refund_payment_on_leave = false;
 
         msg!("ok");
         Ok(())
@@ -186,6 +219,7 @@ pub mod raindrops_namespace {
             pretty_name,
             permissiveness_settings,
             whitelisted_staking_mints,
+            refund_payment_on_leave,
         } = args;
 
         let namespace = &mut ctx.accounts.namespace;
@@ -212,6 +246,58 @@ pub mod raindrops_namespace {
             namespace.permissiveness_settings = permissiveness;
         }
 
+        if let Some(refund) = refund_payment_on_leave {
+            if refund && namespace.payment_amount.is_none() {
+                return Err(error!(ErrorCode::PaymentsNotConfigured));
+            }
+            namespace.refund_payment_on_leave = refund;
+        }
+
+        Ok(())
+    }
+
+    pub fn initialize_protocol_fee_config<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeProtocolFeeConfig<'info>>,
+        args: InitializeProtocolFeeConfigArgs,
+    ) -> Result<()> {
+        let protocol_fee_config = &mut ctx.accounts.protocol_fee_config;
+
+        protocol_fee_config.bump = *ctx.bumps.get("protocol_fee_config").unwrap();
+        protocol_fee_config.admin = ctx.accounts.admin.key();
+        protocol_fee_config.fee_mint = ctx.accounts.fee_mint.key();
+        protocol_fee_config.fee_vault = ctx.accounts.fee_vault.key();
+        protocol_fee_config.fee_amount = args.fee_amount;
+
+        Ok(())
+    }
+
+    pub fn update_protocol_fee_config<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateProtocolFeeConfig<'info>>,
+        args: UpdateProtocolFeeConfigArgs,
+    ) -> Result<()> {
+        let UpdateProtocolFeeConfigArgs { admin, fee_amount } = args;
+
+        let protocol_fee_config = &mut ctx.accounts.protocol_fee_config;
+
+        protocol_fee_config.fee_mint = ctx.accounts.fee_mint.key();
+        protocol_fee_config.fee_vault = ctx.accounts.fee_vault.key();
+
+        if let Some(admin) = admin {
+            protocol_fee_config.admin = admin;
+        }
+
+        if let Some(fee_amount) = fee_amount {
+            protocol_fee_config.fee_amount = fee_amount;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_namespace_protocol_fee_exempt<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetNamespaceProtocolFeeExempt<'info>>,
+        exempt: bool,
+    ) -> Result<()> {
+        ctx.accounts.namespace.protocol_fee_exempt = exempt;
         Ok(())
     }
 
@@ -239,6 +325,8 @@ pub mod raindrops_namespace {
         if artifact.owner != &raindrops_player::ID
             && artifact.owner != &raindrops_matches::ID
             && artifact.owner != &raindrops_item::ID
+            && artifact.owner != &itemv2::ID
+            && artifact.owner != &raindrops_avatar::ID
             && artifact.owner != &id()
         {
             return Err(error!(ErrorCode::CanOnlyCacheValidRaindropsObjects));
@@ -250,8 +338,16 @@ pub mod raindrops_namespace {
             index.bump = *ctx.bumps.get("index").unwrap();
             index.page = args.page;
             index.caches = Vec::with_capacity(MAX_CACHED_ITEMS_PER_INDEX);
+            index.sort_keys = Vec::with_capacity(MAX_CACHED_ITEMS_PER_INDEX);
         };
 
+        ensure_index_space(
+            &index.to_account_info(),
+            &ctx.accounts.token_holder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        index.pad_sort_keys();
+
         // if the current page is not the lowest available page error
         let lowest_available_page = lowest_available_page(&mut namespace.full_pages.clone())?;
         if index.page != lowest_available_page {
@@ -268,7 +364,7 @@ pub mod raindrops_namespace {
         }
 
         // add item to cache
-        index.caches.push(artifact.key());
+        index.insert(artifact.key(), args.sort_key.unwrap_or(0));
 
         // if page is now full, add the page to the full_pages list
         if index.caches.len() == MAX_CACHED_ITEMS_PER_INDEX {
@@ -281,57 +377,13 @@ pub mod raindrops_namespace {
             .checked_add(1)
             .ok_or(ErrorCode::NumericalOverflowError)?;
 
-        let rd_program = ctx.accounts.raindrops_program.to_account_info();
-
-        if raindrops_item::check_id(&rd_program.key()) {
-            let accounts = ItemArtifactCacheNamespace {
-                item_artifact: ctx.accounts.artifact.to_account_info(),
-                namespace: namespace.to_account_info(),
-                instructions: ctx.accounts.instructions.to_account_info(),
-            };
-
-            item_artifact_cache_namespace(CpiContext::new(rd_program, accounts), args.page)
-        } else if raindrops_matches::check_id(&rd_program.key()) {
-            let accounts = MatchCacheNamespace {
-                match_instance: ctx.accounts.artifact.to_account_info(),
-                namespace: namespace.to_account_info(),
-                instructions: ctx.accounts.instructions.to_account_info(),
-            };
-
-            match_cache_namespace(CpiContext::new(rd_program, accounts), args.page)
-        } else if raindrops_player::check_id(&rd_program.key()) {
-            let accounts = PlayerArtifactCacheNamespace {
-                player_artifact: ctx.accounts.artifact.to_account_info(),
-                namespace: namespace.to_account_info(),
-                instructions: ctx.accounts.instructions.to_account_info(),
-            };
-
-            player_artifact_cache_namespace(CpiContext::new(rd_program, accounts), args.page)
-        } else if crate::id().eq(&rd_program.key()) {
-            let artifact_ns = &mut Account::<'_, Namespace>::try_from(&ctx.accounts.artifact)?;
-
-            let mut cached = false;
-            let mut new_namespaces: Vec<NamespaceAndIndex> = vec![];
-            for ns in artifact_ns.namespaces.clone().unwrap() {
-                if ns.namespace == namespace.key() && !cached {
-                    cached = true;
-                    new_namespaces.push(NamespaceAndIndex {
-                        namespace: namespace.key(),
-                        index: Some(index.page),
-                        inherited: InheritanceState::NotInherited,
-                    });
-                } else {
-                    new_namespaces.push(ns);
-                }
-            }
-            if !cached {
-                return Err(error!(ErrorCode::CannotCacheArtifact));
-            }
-            artifact_ns.namespaces = Some(new_namespaces);
-            artifact_ns.exit(&crate::id())
-        } else {
-            Err(error!(ErrorCode::CannotCacheArtifact))
-        }
+        cache_artifact_page(
+            &namespace.to_account_info(),
+            &ctx.accounts.artifact.to_account_info(),
+            &ctx.accounts.raindrops_program.to_account_info(),
+            &ctx.accounts.instructions.to_account_info(),
+            args.page,
+        )
     }
 
     pub fn uncache_artifact<'info>(
@@ -371,6 +423,22 @@ pub mod raindrops_namespace {
             };
 
             player_artifact_uncache_namespace(CpiContext::new(rd_program, accounts))?;
+        } else if itemv2::check_id(&rd_program.key()) {
+            let accounts = ItemClassUncacheNamespace {
+                item_class: ctx.accounts.artifact.to_account_info(),
+                namespace: namespace.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+            };
+
+            item_class_uncache_namespace(CpiContext::new(rd_program, accounts))?;
+        } else if raindrops_avatar::check_id(&rd_program.key()) {
+            let accounts = AvatarArtifactUncacheNamespace {
+                avatar_artifact: ctx.accounts.artifact.to_account_info(),
+                namespace: namespace.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+            };
+
+            avatar_artifact_uncache_namespace(CpiContext::new(rd_program, accounts))?;
         } else if crate::id().eq(&rd_program.key()) {
             let artifact_ns = &mut Account::<'_, Namespace>::try_from(&ctx.accounts.artifact)?;
 
@@ -400,14 +468,81 @@ pub mod raindrops_namespace {
         let UncacheArtifactArgs { page, .. } = args;
 
         // remove item from index
-        let index = &mut ctx.accounts.index;
-        let artifact_key = ctx.accounts.artifact.key();
-        index.caches.retain(|&item| &item != &artifact_key);
+        let index = &mut load_index(
+            &ctx.accounts.index,
+            &ctx.accounts.token_holder,
+            &ctx.accounts.system_program,
+        )?;
+        index.remove(&ctx.accounts.artifact.key());
 
         // if page was full, remove the page from full pages list
         namespace.full_pages.retain(|&i| i != page);
 
-        Ok(())
+        index.exit(&crate::id())
+    }
+
+    pub fn compact_index<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompactIndex<'info>>,
+        args: CompactIndexArgs,
+    ) -> Result<()> {
+        let CompactIndexArgs { from_page, to_page } = args;
+
+        // entries only move towards the front so repeated compaction settles
+        if to_page >= from_page {
+            return Err(error!(ErrorCode::InvalidCompaction));
+        }
+
+        if ctx.remaining_accounts.len() % 2 != 0 {
+            return Err(error!(ErrorCode::InvalidRemainingAccounts));
+        }
+
+        let from_index = &mut load_index(
+            &ctx.accounts.from_index,
+            &ctx.accounts.token_holder,
+            &ctx.accounts.system_program,
+        )?;
+        let to_index = &mut load_index(
+            &ctx.accounts.to_index,
+            &ctx.accounts.token_holder,
+            &ctx.accounts.system_program,
+        )?;
+
+        let namespace = &mut ctx.accounts.namespace;
+
+        for artifact_accounts in ctx.remaining_accounts.chunks(2) {
+            let artifact = &artifact_accounts[0];
+
+            if to_index.caches.len() >= MAX_CACHED_ITEMS_PER_INDEX {
+                return Err(error!(ErrorCode::IndexFull));
+            }
+
+            let sort_key = from_index
+                .remove(&artifact.key())
+                .ok_or(ErrorCode::ArtifactNotInIndex)?;
+            to_index.insert(artifact.key(), sort_key);
+
+            // point the artifact's cached index at its new page
+            cache_artifact_page(
+                &namespace.to_account_info(),
+                artifact,
+                &artifact_accounts[1],
+                &ctx.accounts.instructions.to_account_info(),
+                to_page,
+            )?;
+        }
+
+        if from_index.caches.len() < MAX_CACHED_ITEMS_PER_INDEX {
+            namespace.full_pages.retain(|&i| i != from_page);
+        }
+        if to_index.caches.len() == MAX_CACHED_ITEMS_PER_INDEX
+            && !namespace.full_pages.contains(&to_page)
+        {
+            msg!("{} page is full", to_page);
+            namespace.full_pages.push(to_page);
+        }
+
+        from_index.exit(&crate::id())?;
+        to_index.exit(&crate::id())
     }
 
     pub fn create_namespace_gatekeeper<'info>(
@@ -428,7 +563,13 @@ pub mod raindrops_namespace {
     ) -> Result<()> {
         let namespace_gatekeeper = &mut ctx.accounts.namespace_gatekeeper;
         namespace_gatekeeper.artifact_filters.push(artifact_filter);
-        Ok(())
+
+        resize_account(
+            &namespace_gatekeeper.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            namespace_gatekeeper.space(),
+        )
     }
 
     pub fn remove_from_namespace_gatekeeper<'info>(
@@ -439,7 +580,13 @@ pub mod raindrops_namespace {
         namespace_gatekeeper
             .artifact_filters
             .retain(|item| item != &artifact_filter);
-        Ok(())
+
+        resize_account(
+            &namespace_gatekeeper.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            namespace_gatekeeper.space(),
+        )
     }
 
     pub fn leave_namespace<'info>(
@@ -460,6 +607,7 @@ pub mod raindrops_namespace {
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                None,
                 &namespace.permissiveness_settings.item_permissiveness,
             )?;
 
@@ -469,13 +617,14 @@ pub mod raindrops_namespace {
                 instructions: ctx.accounts.instructions.to_account_info(),
             };
 
-            item_artifact_leave_namespace(CpiContext::new(rd_program, accounts))
+            item_artifact_leave_namespace(CpiContext::new(rd_program, accounts))?;
         } else if raindrops_matches::check_id(&rd_program.key()) {
             check_permissiveness_against_holder(
                 &rd_program.key(),
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                None,
                 &namespace.permissiveness_settings.match_permissiveness,
             )?;
 
@@ -485,13 +634,14 @@ pub mod raindrops_namespace {
                 instructions: ctx.accounts.instructions.to_account_info(),
             };
 
-            match_leave_namespace(CpiContext::new(rd_program, accounts))
+            match_leave_namespace(CpiContext::new(rd_program, accounts))?;
         } else if raindrops_player::check_id(&rd_program.key()) {
             check_permissiveness_against_holder(
                 &rd_program.key(),
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                None,
                 &namespace.permissiveness_settings.player_permissiveness,
             )?;
 
@@ -501,7 +651,45 @@ pub mod raindrops_namespace {
                 instructions: ctx.accounts.instructions.to_account_info(),
             };
 
-            player_artifact_leave_namespace(CpiContext::new(rd_program, accounts))
+            player_artifact_leave_namespace(CpiContext::new(rd_program, accounts))?;
+        } else if itemv2::check_id(&rd_program.key()) {
+            check_permissiveness_against_holder(
+                &rd_program.key(),
+                &ctx.accounts.artifact,
+                &ctx.accounts.token_holder.to_account_info(),
+                &ctx.accounts.namespace_gatekeeper,
+                None,
+                &namespace.permissiveness_settings.item_permissiveness,
+            )?;
+
+            let accounts = ItemClassLeaveNamespace {
+                item_class: ctx.accounts.artifact.to_account_info(),
+                namespace: namespace.to_account_info(),
+                payer: ctx.accounts.token_holder.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            };
+
+            item_class_leave_namespace(CpiContext::new(rd_program, accounts))?;
+        } else if raindrops_avatar::check_id(&rd_program.key()) {
+            check_permissiveness_against_holder(
+                &rd_program.key(),
+                &ctx.accounts.artifact,
+                &ctx.accounts.token_holder.to_account_info(),
+                &ctx.accounts.namespace_gatekeeper,
+                None,
+                &namespace.permissiveness_settings.player_permissiveness,
+            )?;
+
+            let accounts = AvatarArtifactLeaveNamespace {
+                avatar_artifact: ctx.accounts.artifact.to_account_info(),
+                namespace: namespace.to_account_info(),
+                payer: ctx.accounts.token_holder.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            };
+
+            avatar_artifact_leave_namespace(CpiContext::new(rd_program, accounts))?;
         } else if crate::id().eq(&rd_program.key()) {
             let artifact_ns = &mut Account::<'_, Namespace>::try_from(&ctx.accounts.artifact)?;
 
@@ -526,17 +714,83 @@ pub mod raindrops_namespace {
                 return Err(error!(ErrorCode::CannotLeaveNamespace));
             }
             artifact_ns.namespaces = Some(new_namespaces);
-            artifact_ns.exit(&crate::id())
+            artifact_ns.exit(&crate::id())?;
+        } else {
+            return Err(error!(ErrorCode::CannotLeaveNamespace));
+        }
+
+        // artifacts that joined before payments were recorded have nothing to refund
+        let membership_payment_info = ctx.accounts.membership_payment.to_account_info();
+        if membership_payment_info.data_is_empty() {
+            return Ok(());
+        }
+
+        let membership_payment =
+            Account::<'_, MembershipPayment>::try_from(&membership_payment_info)?;
+        let refund_amount = if ctx.accounts.namespace.refund_payment_on_leave {
+            membership_payment.amount
         } else {
-            Err(error!(ErrorCode::CannotLeaveNamespace))
+            0
+        };
+        membership_payment.close(ctx.accounts.token_holder.to_account_info())?;
+
+        if refund_amount == 0 {
+            return Ok(());
+        }
+
+        // refund the join payment out of the namespace owned payment vault
+        let (payment_vault, payment_ata) =
+            assert_namespace_payment_accounts(&ctx.accounts.namespace, ctx.remaining_accounts)?;
+
+        if payment_ata.owner != ctx.accounts.token_holder.key() {
+            return Err(error!(ErrorCode::InvalidRemainingAccounts));
+        }
+
+        if payment_vault.owner != ctx.accounts.namespace.key() {
+            return Err(error!(ErrorCode::PaymentVaultNotOwnedByNamespace));
         }
+
+        let namespace_seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.namespace.mint.as_ref(),
+            &[ctx.accounts.namespace.bump],
+        ];
+
+        let refund_token_accounts = token::Transfer {
+            from: payment_vault.to_account_info(),
+            to: payment_ata.to_account_info(),
+            authority: ctx.accounts.namespace.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                refund_token_accounts,
+                &[namespace_seeds],
+            ),
+            refund_amount,
+        )
     }
 
     pub fn join_namespace<'info>(
         ctx: Context<'_, '_, '_, 'info, JoinNamespace<'info>>,
+        args: JoinNamespaceArgs,
     ) -> Result<()> {
+        let JoinNamespaceArgs { allowlist_proofs } = args;
+
         let rd_program = ctx.accounts.raindrops_program.to_account_info();
 
+        // payment accounts come first when payments are configured, gatekeeper filter accounts follow
+        let payments_unset = ctx.accounts.namespace.payment_mint.is_none()
+            && ctx.accounts.namespace.payment_vault.is_none()
+            && ctx.accounts.namespace.payment_amount.is_none();
+        let payment_accounts_len = if payments_unset { 0 } else { 3 };
+        if ctx.remaining_accounts.len() < payment_accounts_len {
+            return Err(error!(ErrorCode::InvalidRemainingAccounts));
+        }
+        let (payment_accounts, filter_accounts) =
+            ctx.remaining_accounts.split_at(payment_accounts_len);
+
         let namespace = &mut ctx.accounts.namespace;
 
         namespace.artifacts_added = namespace
@@ -551,6 +805,10 @@ pub mod raindrops_namespace {
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                Some(HolderProofs {
+                    filter_accounts: filter_accounts.iter(),
+                    allowlist_proofs: allowlist_proofs.iter(),
+                }),
                 &namespace.permissiveness_settings.item_permissiveness,
             )?;
 
@@ -568,6 +826,10 @@ pub mod raindrops_namespace {
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                Some(HolderProofs {
+                    filter_accounts: filter_accounts.iter(),
+                    allowlist_proofs: allowlist_proofs.iter(),
+                }),
                 &namespace.permissiveness_settings.match_permissiveness,
             )?;
 
@@ -586,6 +848,10 @@ pub mod raindrops_namespace {
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                Some(HolderProofs {
+                    filter_accounts: filter_accounts.iter(),
+                    allowlist_proofs: allowlist_proofs.iter(),
+                }),
                 &namespace.permissiveness_settings.player_permissiveness,
             )?;
 
@@ -596,6 +862,55 @@ pub mod raindrops_namespace {
             };
 
             player_artifact_join_namespace(CpiContext::new(rd_program, accounts))?;
+        } else if itemv2::check_id(&rd_program.key()) {
+            msg!("joining item class to namespace");
+            check_permissiveness_against_holder(
+                &rd_program.key(),
+                &ctx.accounts.artifact,
+                &ctx.accounts.token_holder.to_account_info(),
+                &ctx.accounts.namespace_gatekeeper,
+                Some(HolderProofs {
+                    filter_accounts: filter_accounts.iter(),
+                    allowlist_proofs: allowlist_proofs.iter(),
+                }),
+                &namespace.permissiveness_settings.item_permissiveness,
+            )?;
+
+            // the token holder pays for the item class to grow by one namespace entry
+            let accounts = ItemClassJoinNamespace {
+                item_class: ctx.accounts.artifact.to_account_info(),
+                namespace: namespace.to_account_info(),
+                payer: ctx.accounts.token_holder.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            };
+
+            item_class_join_namespace(CpiContext::new(rd_program, accounts))?;
+        } else if raindrops_avatar::check_id(&rd_program.key()) {
+            msg!("joining avatar to namespace");
+
+            // avatar classes and avatars are gated like players
+            check_permissiveness_against_holder(
+                &rd_program.key(),
+                &ctx.accounts.artifact,
+                &ctx.accounts.token_holder.to_account_info(),
+                &ctx.accounts.namespace_gatekeeper,
+                Some(HolderProofs {
+                    filter_accounts: filter_accounts.iter(),
+                    allowlist_proofs: allowlist_proofs.iter(),
+                }),
+                &namespace.permissiveness_settings.player_permissiveness,
+            )?;
+
+            let accounts = AvatarArtifactJoinNamespace {
+                avatar_artifact: ctx.accounts.artifact.to_account_info(),
+                namespace: namespace.to_account_info(),
+                payer: ctx.accounts.token_holder.to_account_info(),
+                instructions: ctx.accounts.instructions.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            };
+
+            avatar_artifact_join_namespace(CpiContext::new(rd_program, accounts))?;
         } else if crate::id().eq(&rd_program.key()) {
             msg!("joining namespace to namespace");
             check_permissiveness_against_holder(
@@ -603,6 +918,10 @@ pub mod raindrops_namespace {
                 &ctx.accounts.artifact,
                 &ctx.accounts.token_holder.to_account_info(),
                 &ctx.accounts.namespace_gatekeeper,
+                Some(HolderProofs {
+                    filter_accounts: filter_accounts.iter(),
+                    allowlist_proofs: allowlist_proofs.iter(),
+                }),
                 &namespace.permissiveness_settings.namespace_permissiveness,
             )?;
 
@@ -631,52 +950,46 @@ pub mod raindrops_namespace {
             return Err(error!(ErrorCode::CannotJoinNamespace));
         }
 
-        // payment of $RAIN to vault
-        let payment_token_accounts = token::Transfer {
-            from: ctx.accounts.rain_payer_ata.to_account_info(),
-            to: ctx.accounts.rain_token_vault.to_account_info(),
-            authority: ctx.accounts.token_holder.to_account_info(),
-        };
+        // protocol fee, waived when the fee is zero or the namespace is exempt
+        let protocol_fee_config = &ctx.accounts.protocol_fee_config;
+        if protocol_fee_config.fee_amount > 0 && !ctx.accounts.namespace.protocol_fee_exempt {
+            let protocol_fee_payer_ata =
+                Account::<'_, TokenAccount>::try_from(&ctx.accounts.protocol_fee_payer_ata)?;
+            if protocol_fee_payer_ata.mint != protocol_fee_config.fee_mint {
+                return Err(error!(ErrorCode::InvalidProtocolFeeAccount));
+            }
 
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                payment_token_accounts,
-            ),
-            RAIN_PAYMENT_AMOUNT,
-        )?;
+            let protocol_fee_token_accounts = token::Transfer {
+                from: protocol_fee_payer_ata.to_account_info(),
+                to: ctx.accounts.protocol_fee_vault.to_account_info(),
+                authority: ctx.accounts.token_holder.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    protocol_fee_token_accounts,
+                ),
+                protocol_fee_config.fee_amount,
+            )?;
+        }
+
+        ctx.accounts.membership_payment.bump = *ctx.bumps.get("membership_payment").unwrap();
 
         // if payments is not configured, exit without error
-        if ctx.accounts.namespace.payment_mint.is_none()
-            && ctx.accounts.namespace.payment_vault.is_none()
-            && ctx.accounts.namespace.payment_amount.is_none()
-        {
+        if payments_unset {
             msg!("optional payment unset");
             return Ok(());
         };
 
-        // require that payments be passed in
-        if ctx.remaining_accounts.len() != 3 {
-            return Err(error!(ErrorCode::InvalidRemainingAccounts));
-        }
-
-        let payment_mint = Account::<'_, Mint>::try_from(&ctx.remaining_accounts[0])?;
-        let payment_vault =
-            &mut Account::<'_, token::TokenAccount>::try_from(&ctx.remaining_accounts[1])?;
-        let payment_ata =
-            &mut Account::<'_, token::TokenAccount>::try_from(&ctx.remaining_accounts[2])?;
-
-        // check the mint matches for all accounts
-        if payment_ata.mint != payment_mint.key() || payment_vault.mint != payment_mint.key() {
-            return Err(error!(ErrorCode::InvalidRemainingAccounts));
-        }
+        let payment_amount = ctx
+            .accounts
+            .namespace
+            .payment_amount
+            .ok_or(ErrorCode::PaymentsNotConfigured)?;
 
-        // check mint and vault is what is written in the Namespace account
-        if payment_mint.key() != ctx.accounts.namespace.payment_mint.unwrap()
-            || payment_vault.key() != ctx.accounts.namespace.payment_vault.unwrap()
-        {
-            return Err(error!(ErrorCode::InvalidRemainingAccounts));
-        };
+        let (payment_vault, payment_ata) =
+            assert_namespace_payment_accounts(&ctx.accounts.namespace, payment_accounts)?;
 
         let payment_token_accounts = token::Transfer {
             from: payment_ata.to_account_info(),
@@ -690,8 +1003,17 @@ pub mod raindrops_namespace {
                 ctx.accounts.token_program.to_account_info(),
                 payment_token_accounts,
             ),
-            ctx.accounts.namespace.payment_amount.unwrap(),
-        )
+            payment_amount,
+        )?;
+
+        // a refund on leave gives back what was paid here, not whatever the price is by then
+        let membership_payment = &mut ctx.accounts.membership_payment;
+        membership_payment.amount = membership_payment
+            .amount
+            .checked_add(payment_amount)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+
+        Ok(())
     }
 
     // FOR TESTING ONLY
@@ -742,9 +1064,33 @@ pub enum Filter {
         metadata: Pubkey,
         edition: Option<Pubkey>,
     },
+    // the token holder holds an nft from this verified collection
+    VerifiedCollection {
+        collection: Pubkey,
+    },
+    // the token holder is a leaf of this merkle tree
+    MerkleAllowlist {
+        root: [u8; 32],
+    },
+    // the token holder holds at least minimum_amount of mint
+    TokenBalance {
+        mint: Pubkey,
+        minimum_amount: u64,
+    },
 }
 
-pub const FILTER_SIZE: usize = (MAX_FILTER_SLOTS + 1) * 32;
+impl Filter {
+    pub fn space(&self) -> usize {
+        1 + match self {
+            Filter::Namespace { namespaces } => 4 + namespaces.len() * 32,
+            // sized for a present edition so the filter can always be written back
+            Filter::Key { .. } => 32 + 32 + 32 + 1 + 32,
+            Filter::VerifiedCollection { .. } => 32,
+            Filter::MerkleAllowlist { .. } => 32,
+            Filter::TokenBalance { .. } => 32 + 8,
+        }
+    }
+}
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub struct ArtifactFilter {
@@ -752,6 +1098,12 @@ pub struct ArtifactFilter {
     token_type: ArtifactType,
 }
 
+impl ArtifactFilter {
+    pub fn space(&self) -> usize {
+        self.filter.space() + 1 // artifact type
+    }
+}
+
 #[account]
 pub struct NamespaceGatekeeper {
     bump: u8,
@@ -759,11 +1111,22 @@ pub struct NamespaceGatekeeper {
     artifact_filters: Vec<ArtifactFilter>,
 }
 
-// TODO get this right
 impl NamespaceGatekeeper {
-    pub const SPACE: usize = 8 + 1 + 32 + ARTIFACT_FILTER_SIZE;
+    pub fn space(&self) -> usize {
+        MIN_NAMESPACE_GATEKEEPER_SIZE
+            + self
+                .artifact_filters
+                .iter()
+                .map(|artifact_filter| artifact_filter.space())
+                .sum::<usize>()
+    }
 }
 
+pub const MIN_NAMESPACE_GATEKEEPER_SIZE: usize = 8 + // key
+1 + // bump
+32 + // namespace
+4; // artifact filters
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct PermissivenessSettings {
     namespace_permissiveness: Permissiveness,
@@ -794,6 +1157,44 @@ pub struct Namespace {
     pub payment_mint: Option<Pubkey>,
     pub payment_vault: Option<Pubkey>,
     pub payment_amount: Option<u64>,
+    // set by the protocol fee admin, skips the protocol fee on join
+    pub protocol_fee_exempt: bool,
+    // give the payment back out of the payment vault on leave
+    pub refund_payment_on_leave: bool,
+}
+
+/// seed ['namespace', 'protocol_fee']
+#[account]
+pub struct ProtocolFeeConfig {
+    pub bump: u8,
+    pub admin: Pubkey,
+    pub fee_mint: Pubkey,
+    pub fee_vault: Pubkey,
+    // charged on every join_namespace, zero turns the fee off
+    pub fee_amount: u64,
+}
+
+impl ProtocolFeeConfig {
+    pub const SPACE: usize = 8 + // key
+    1 + // bump
+    32 + // admin
+    32 + // fee mint
+    32 + // fee vault
+    8; // fee amount
+}
+
+/// seed ['namespace', namespace, artifact, 'membership_payment']
+#[account]
+pub struct MembershipPayment {
+    pub bump: u8,
+    // paid into the payment vault on join, the most a leave can refund
+    pub amount: u64,
+}
+
+impl MembershipPayment {
+    pub const SPACE: usize = 8 + // key
+    1 + // bump
+    8; // amount
 }
 
 /// seed ['namespace', namespace program, mint, page number]
@@ -802,25 +1203,40 @@ pub struct NamespaceIndex {
     pub namespace: Pubkey,
     pub bump: u8,
     pub page: u64,
+    // ordered by sort key within this page only, ties keep their cache order.
+    // Artifacts go to the lowest page with room whatever their key, so reading the
+    // pages in order is stable but not sorted across pages.
     pub caches: Vec<Pubkey>,
+    // sort key of the cache at the same position
+    pub sort_keys: Vec<u64>,
 }
 
 impl NamespaceIndex {
-    pub const SPACE: usize = 8 + 32 + 1 + 8 + (4 + (32 * MAX_CACHED_ITEMS_PER_INDEX));
-}
+    pub const SPACE: usize = 8
+        + 32
+        + 1
+        + 8
+        + (4 + (32 * MAX_CACHED_ITEMS_PER_INDEX))
+        + (4 + (8 * MAX_CACHED_ITEMS_PER_INDEX));
+
+    // pages cached before sort keys existed read back without them, their caches sort as 0
+    pub fn pad_sort_keys(&mut self) {
+        self.sort_keys.resize(self.caches.len(), 0);
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct NamespaceAndIndex {
-    namespace: Pubkey,
-    index: Option<u64>,
-    inherited: InheritanceState,
-}
+    // keeps this page sorted, other pages are not looked at
+    pub fn insert(&mut self, artifact: Pubkey, sort_key: u64) {
+        let position = self.sort_keys.partition_point(|&key| key <= sort_key);
+        self.caches.insert(position, artifact);
+        self.sort_keys.insert(position, sort_key);
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum InheritanceState {
-    NotInherited,
-    Inherited,
-    Overridden,
+    // returns the sort key the artifact was cached with
+    pub fn remove(&mut self, artifact: &Pubkey) -> Option<u64> {
+        let position = self.caches.iter().position(|cache| cache == artifact)?;
+        self.caches.remove(position);
+        Some(self.sort_keys.remove(position))
+    }
 }
 
 pub const NAMESPACE_AND_INDEX_SIZE: usize = 34;
@@ -844,18 +1260,9 @@ pub const MIN_NAMESPACE_SIZE: usize = 8 + // key
 1 + 32 + // optional payment_token_mint
 1 + 32 + // optional payment_token_vault
 1 + 8 + // optional payment_token_amount
-200; // padding
-
-pub const ARTIFACT_FILTER_SIZE: usize = 8 + // key
-1 + // indexed
-33 + // namespace
-1 + // is blacklist
-FILTER_SIZE + //
-1 + // artifact type
-32 + // artifact
-8 + // filter index
-1 + // bump
-100; //padding
+1 + // protocol fee exempt
+1 + // refund payment on leave
+198; // padding
 
 // REMAINING_ACCOUNTS ORDERING
 // payment_mint
@@ -913,7 +1320,7 @@ pub struct CreateNamespaceGatekeeper<'info> {
     namespace: Account<'info, Namespace>,
     #[account(associated_token::mint = namespace.mint, associated_token::authority = token_holder, constraint = namespace_token.amount == 1)]
     namespace_token: Account<'info, TokenAccount>,
-    #[account(init, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), GATEKEEPER.as_bytes()], bump, payer=payer, space=NamespaceGatekeeper::SPACE)]
+    #[account(init, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), GATEKEEPER.as_bytes()], bump, payer=payer, space=MIN_NAMESPACE_GATEKEEPER_SIZE)]
     namespace_gatekeeper: Account<'info, NamespaceGatekeeper>,
     token_holder: Signer<'info>,
     #[account(mut)]
@@ -931,6 +1338,9 @@ pub struct AddToNamespaceGatekeeper<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), GATEKEEPER.as_bytes()], bump=namespace_gatekeeper.bump, has_one=namespace)]
     namespace_gatekeeper: Account<'info, NamespaceGatekeeper>,
     token_holder: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -942,6 +1352,9 @@ pub struct RemoveFromNamespaceGatekeeper<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), GATEKEEPER.as_bytes()], bump=namespace_gatekeeper.bump, has_one=namespace)]
     namespace_gatekeeper: Account<'info, NamespaceGatekeeper>,
     token_holder: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -957,12 +1370,6 @@ pub struct ValidationArgs {
     usage_info: Option<u8>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Callback {
-    pub key: Pubkey,
-    pub code: u64,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TokenValidation {
     filter: TokenValidationFilter,
@@ -1008,11 +1415,52 @@ pub struct MatchValidation<'info> {
     mint: UncheckedAccount<'info>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeProtocolFeeConfig<'info> {
+    #[account(init, seeds=[PREFIX.as_bytes(), PROTOCOL_FEE.as_bytes()], bump, payer=payer, space=ProtocolFeeConfig::SPACE)]
+    protocol_fee_config: Account<'info, ProtocolFeeConfig>,
+    fee_mint: Account<'info, Mint>,
+    #[account(constraint = fee_vault.mint == fee_mint.key() @ ErrorCode::InvalidProtocolFeeAccount)]
+    fee_vault: Account<'info, TokenAccount>,
+    // only the upgrade authority may set up the protocol fee
+    admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    program: Program<'info, crate::program::RaindropsNamespace>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotUpgradeAuthority)]
+    program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolFeeConfig<'info> {
+    #[account(mut, seeds=[PREFIX.as_bytes(), PROTOCOL_FEE.as_bytes()], bump=protocol_fee_config.bump, has_one=admin)]
+    protocol_fee_config: Account<'info, ProtocolFeeConfig>,
+    fee_mint: Account<'info, Mint>,
+    #[account(constraint = fee_vault.mint == fee_mint.key() @ ErrorCode::InvalidProtocolFeeAccount)]
+    fee_vault: Account<'info, TokenAccount>,
+    admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetNamespaceProtocolFeeExempt<'info> {
+    #[account(seeds=[PREFIX.as_bytes(), PROTOCOL_FEE.as_bytes()], bump=protocol_fee_config.bump, has_one=admin)]
+    protocol_fee_config: Account<'info, ProtocolFeeConfig>,
+    #[account(mut, seeds=[PREFIX.as_bytes(), namespace.mint.as_ref()], bump=namespace.bump)]
+    namespace: Account<'info, Namespace>,
+    admin: Signer<'info>,
+}
+
 // REMAINING_ACCOUNTS
 // Three optional accounts may be passed in if the Namespace was configured for optional payments
 // payment_mint
 // payment_vault (mut)
 // payment_ata (mut)
+// Followed by the token holder's proofs for each gatekeeper filter, in gatekeeper order
+// VerifiedCollection: holder token account, metadata of its mint
+// TokenBalance: holder token account
 #[derive(Accounts)]
 pub struct JoinNamespace<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), namespace_token.mint.as_ref()], bump)]
@@ -1031,14 +1479,18 @@ pub struct JoinNamespace<'info> {
     #[account(mut)]
     token_holder: Signer<'info>,
 
-    #[account(address = Pubkey::from_str(RAIN_TOKEN_MINT).unwrap())]
-    rain_token_mint: Account<'info, Mint>,
+    #[account(init_if_needed, payer = token_holder, space = MembershipPayment::SPACE, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), artifact.key().as_ref(), MEMBERSHIP_PAYMENT.as_bytes()], bump)]
+    membership_payment: Box<Account<'info, MembershipPayment>>,
+
+    #[account(seeds=[PREFIX.as_bytes(), PROTOCOL_FEE.as_bytes()], bump=protocol_fee_config.bump)]
+    protocol_fee_config: Box<Account<'info, ProtocolFeeConfig>>,
 
-    #[account(mut, associated_token::mint = rain_token_mint, associated_token::authority = Pubkey::from_str(RAIN_TOKEN_VAULT_AUTHORITY).unwrap())]
-    rain_token_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = protocol_fee_config.fee_vault)]
+    protocol_fee_vault: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, associated_token::mint = rain_token_mint, associated_token::authority = token_holder)]
-    rain_payer_ata: Account<'info, token::TokenAccount>,
+    /// CHECK: only deserialized when the protocol fee is charged
+    #[account(mut)]
+    protocol_fee_payer_ata: UncheckedAccount<'info>,
 
     /// CHECK: address constraint
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
@@ -1048,12 +1500,21 @@ pub struct JoinNamespace<'info> {
     #[account(constraint=raindrops_program.key() == crate::id() ||
         raindrops_program.key() == raindrops_item::id() ||
         raindrops_program.key() == raindrops_player::id() ||
-        raindrops_program.key() == raindrops_matches::id())]
+        raindrops_program.key() == raindrops_matches::id() ||
+        raindrops_program.key() == itemv2::id() ||
+        raindrops_program.key() == raindrops_avatar::id())]
     raindrops_program: UncheckedAccount<'info>,
 
     token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
 }
 
+// REMAINING_ACCOUNTS
+// Three optional accounts may be passed in if the Namespace refunds payments on leave
+// and the artifact paid to join
+// payment_mint
+// payment_vault (mut)
+// payment_ata (mut)
 #[derive(Accounts)]
 pub struct LeaveNamespace<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), namespace_token.mint.as_ref()], bump)]
@@ -1067,8 +1528,13 @@ pub struct LeaveNamespace<'info> {
 
     #[account(seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), GATEKEEPER.as_bytes()], bump, has_one=namespace)]
     namespace_gatekeeper: Account<'info, NamespaceGatekeeper>,
+    #[account(mut)]
     token_holder: Signer<'info>,
 
+    /// CHECK: empty for artifacts that joined before payments were recorded
+    #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), artifact.key().as_ref(), MEMBERSHIP_PAYMENT.as_bytes()], bump)]
+    membership_payment: UncheckedAccount<'info>,
+
     /// CHECK: constraints
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     instructions: UncheckedAccount<'info>,
@@ -1077,8 +1543,13 @@ pub struct LeaveNamespace<'info> {
     #[account(constraint=raindrops_program.key() == crate::id() ||
     raindrops_program.key() == raindrops_item::id() ||
     raindrops_program.key() == raindrops_player::id() ||
-    raindrops_program.key() == raindrops_matches::id())]
+    raindrops_program.key() == raindrops_matches::id() ||
+    raindrops_program.key() == itemv2::id() ||
+    raindrops_program.key() == raindrops_avatar::id())]
     raindrops_program: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -1107,7 +1578,9 @@ pub struct CacheArtifact<'info> {
     #[account(constraint=raindrops_program.key() == crate::id() ||
         raindrops_program.key() == raindrops_item::id() ||
         raindrops_program.key() == raindrops_player::id() ||
-        raindrops_program.key() == raindrops_matches::id())]
+        raindrops_program.key() == raindrops_matches::id() ||
+        raindrops_program.key() == itemv2::id() ||
+        raindrops_program.key() == raindrops_avatar::id())]
     raindrops_program: UncheckedAccount<'info>,
 }
 
@@ -1118,8 +1591,9 @@ pub struct UncacheArtifact<'info> {
     namespace: Account<'info, Namespace>,
     #[account(associated_token::mint = namespace.mint, associated_token::authority = token_holder, constraint = namespace_token.amount == 1)]
     namespace_token: Account<'info, TokenAccount>,
-    #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), &args.page.to_le_bytes()], bump=index.bump)]
-    index: Account<'info, NamespaceIndex>,
+    /// CHECK: deserialized once it is grown to the current index size
+    #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), &args.page.to_le_bytes()], bump)]
+    index: UncheckedAccount<'info>,
 
     /// CHECK: TODO
     #[account(mut)]
@@ -1137,10 +1611,41 @@ pub struct UncacheArtifact<'info> {
     #[account(constraint=raindrops_program.key() == crate::id() ||
         raindrops_program.key() == raindrops_item::id() ||
         raindrops_program.key() == raindrops_player::id() ||
-        raindrops_program.key() == raindrops_matches::id())]
+        raindrops_program.key() == raindrops_matches::id() ||
+        raindrops_program.key() == itemv2::id() ||
+        raindrops_program.key() == raindrops_avatar::id())]
     raindrops_program: UncheckedAccount<'info>,
 }
 
+// REMAINING_ACCOUNTS
+// For each artifact to move from from_index to to_index
+// artifact (mut)
+// raindrops_program of the artifact
+#[derive(Accounts)]
+#[instruction(args: CompactIndexArgs)]
+pub struct CompactIndex<'info> {
+    #[account(mut, seeds=[PREFIX.as_bytes(), namespace_token.mint.as_ref()], bump=namespace.bump)]
+    namespace: Account<'info, Namespace>,
+    #[account(associated_token::mint = namespace.mint, associated_token::authority = token_holder, constraint = namespace_token.amount == 1)]
+    namespace_token: Account<'info, TokenAccount>,
+
+    /// CHECK: deserialized once it is grown to the current index size
+    #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), &args.from_page.to_le_bytes()], bump)]
+    from_index: UncheckedAccount<'info>,
+
+    /// CHECK: deserialized once it is grown to the current index size
+    #[account(mut, seeds=[PREFIX.as_bytes(), namespace.key().as_ref(), &args.to_page.to_le_bytes()], bump)]
+    to_index: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    token_holder: Signer<'info>,
+
+    /// CHECK: constraints
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    instructions: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Account does not have correct owner!")]
@@ -1195,4 +1700,20 @@ pub enum ErrorCode {
     DesiredNamespacesNone,
     #[msg("Invalid Remaining Accounts")]
     InvalidRemainingAccounts,
+    #[msg("Protocol fee account does not match the protocol fee config")]
+    InvalidProtocolFeeAccount,
+    #[msg("Only the program upgrade authority can do this")]
+    NotUpgradeAuthority,
+    #[msg("Namespace payments are not configured")]
+    PaymentsNotConfigured,
+    #[msg("Payment vault must be owned by the namespace to refund payments")]
+    PaymentVaultNotOwnedByNamespace,
+    #[msg("Holder filters can only whitelist, a holder can always withhold their proof")]
+    HolderFilterCannotBlacklist,
+    #[msg("Missing merkle allowlist proof")]
+    MissingAllowlistProof,
+    #[msg("Index entries can only be compacted into an earlier page")]
+    InvalidCompaction,
+    #[msg("Artifact is not cached in this index")]
+    ArtifactNotInIndex,
 }
@@ -1,14 +1,48 @@
-use crate::{ErrorCode, Filter, Namespace, NamespaceGatekeeper, Permissiveness};
+pub use raindrops_common::utils::{assert_derivation, resize_account, verify};
+
+use crate::{
+    id, ErrorCode, Filter, InheritanceState, Namespace, NamespaceAndIndex, NamespaceGatekeeper,
+    NamespaceIndex, Permissiveness,
+};
 use anchor_lang::{
     error,
-    prelude::{msg, Account, AccountInfo, ProgramError, Pubkey, Result, UncheckedAccount},
-    solana_program::program_pack::{IsInitialized, Pack},
+    prelude::{
+        msg, Account, AccountInfo, AccountsExit, CpiContext, ProgramError, Pubkey, Result,
+        UncheckedAccount,
+    },
+    solana_program::{
+        account_info::next_account_info,
+        keccak::hashv,
+        program_pack::{IsInitialized, Pack},
+    },
     Key, ToAccountInfo,
 };
+use anchor_spl::{
+    metadata::MetadataAccount,
+    token::{Mint, TokenAccount},
+};
 use arrayref::array_ref;
-use raindrops_item::{Item, ItemClass, ItemEscrow};
-use raindrops_matches::Match;
-use raindrops_player::{Player, PlayerClass};
+use itemv2::{
+    cpi::{accounts::ItemClassCacheNamespace, item_class_cache_namespace},
+    state::accounts::ItemClass as ItemClassV2,
+};
+use raindrops_avatar::{
+    cpi::{accounts::AvatarArtifactCacheNamespace, avatar_artifact_cache_namespace},
+    state::accounts::{Avatar, AvatarClass},
+};
+use raindrops_item::{
+    cpi::{accounts::ItemArtifactCacheNamespace, item_artifact_cache_namespace},
+    Item, ItemClass, ItemEscrow,
+};
+use raindrops_matches::{
+    cpi::{accounts::MatchCacheNamespace, match_cache_namespace},
+    Match,
+};
+use raindrops_player::{
+    cpi::{accounts::PlayerArtifactCacheNamespace, player_artifact_cache_namespace},
+    Player, PlayerClass,
+};
+use std::slice::Iter;
 
 pub fn assert_part_of_namespace<'a>(
     artifact: &AccountInfo<'a>,
@@ -38,14 +72,6 @@ pub fn assert_initialized<T: Pack + IsInitialized>(account_info: &AccountInfo) -
     }
 }
 
-pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<u8> {
-    let (key, bump) = Pubkey::find_program_address(path, program_id);
-    if key != *account.key {
-        return Err(error!(ErrorCode::DerivedKeyInvalid));
-    }
-    Ok(bump)
-}
-
 pub fn assert_signer(account: &AccountInfo) -> Result<()> {
     if !account.is_signer {
         Err(ProgramError::MissingRequiredSignature.into())
@@ -140,17 +166,58 @@ pub fn pull_namespaces(artifact: &AccountInfo) -> Result<Vec<Pubkey>> {
             namespaces.push(ns.namespace);
         }
 
+        return Ok(namespaces);
+    } else if let Ok(item_class) = Account::<'_, ItemClassV2>::try_from(artifact) {
+        // itemv2 and avatar accounts grow their namespaces on join so the list is never unset
+        for ns in &item_class.namespaces {
+            namespaces.push(ns.namespace);
+        }
+
+        return Ok(namespaces);
+    } else if let Ok(avatar_class) = Account::<'_, AvatarClass>::try_from(artifact) {
+        for ns in &avatar_class.namespaces {
+            namespaces.push(ns.namespace);
+        }
+
+        return Ok(namespaces);
+    } else if let Ok(avatar) = Account::<'_, Avatar>::try_from(artifact) {
+        for ns in &avatar.namespaces {
+            namespaces.push(ns.namespace);
+        }
+
         return Ok(namespaces);
     }
 
     Err(error!(ErrorCode::IncorrectOwner))
 }
 
+// what the token holder brings to satisfy the holder filters, consumed in gatekeeper order
+pub struct HolderProofs<'b, 'a> {
+    pub filter_accounts: Iter<'b, AccountInfo<'a>>,
+    pub allowlist_proofs: Iter<'b, Vec<[u8; 32]>>,
+}
+
+fn next_holder_token_account<'a>(
+    holder_proofs: &mut HolderProofs<'_, 'a>,
+    token_holder: &AccountInfo<'a>,
+) -> Result<Account<'a, TokenAccount>> {
+    let token_account = Account::<'_, TokenAccount>::try_from(next_account_info(
+        &mut holder_proofs.filter_accounts,
+    )?)?;
+    if token_account.owner != token_holder.key() {
+        return Err(error!(ErrorCode::InvalidRemainingAccounts));
+    }
+
+    Ok(token_account)
+}
+
 pub fn check_permissiveness_against_holder<'a>(
     program_id: &Pubkey,
     artifact: &UncheckedAccount<'a>,
     token_holder: &AccountInfo<'a>,
     namespace_gatekeeper: &Account<'a, NamespaceGatekeeper>,
+    // None when leaving, the holder filters only gate joining
+    mut holder_proofs: Option<HolderProofs<'_, 'a>>,
     permissiveness: &Permissiveness,
 ) -> Result<()> {
     if !artifact.owner.eq(program_id) {
@@ -191,6 +258,72 @@ pub fn check_permissiveness_against_holder<'a>(
                         }
                         return Err(error!(ErrorCode::CannotJoinNamespace));
                     }
+                    Filter::VerifiedCollection { collection } => {
+                        msg!("verified collection filter");
+                        let holder_proofs = match holder_proofs.as_mut() {
+                            Some(holder_proofs) => holder_proofs,
+                            None => continue 'filter_loop,
+                        };
+                        let token_account = next_holder_token_account(holder_proofs, token_holder)?;
+                        let metadata = next_account_info(&mut holder_proofs.filter_accounts)?;
+                        assert_derivation(
+                            &metaplex_token_metadata::id(),
+                            metadata,
+                            &[
+                                metaplex_token_metadata::state::PREFIX.as_bytes(),
+                                metaplex_token_metadata::id().as_ref(),
+                                token_account.mint.as_ref(),
+                            ],
+                            ErrorCode::DerivedKeyInvalid,
+                        )?;
+                        let metadata = Account::<'_, MetadataAccount>::try_from(metadata)?;
+
+                        if let Some(metadata_collection) = &metadata.collection {
+                            if token_account.amount > 0
+                                && metadata_collection.verified
+                                && metadata_collection.key == *collection
+                            {
+                                msg!("Whitelisted!");
+                                continue 'filter_loop;
+                            }
+                        }
+                        return Err(error!(ErrorCode::CannotJoinNamespace));
+                    }
+                    Filter::MerkleAllowlist { root } => {
+                        msg!("merkle allowlist filter");
+                        let holder_proofs = match holder_proofs.as_mut() {
+                            Some(holder_proofs) => holder_proofs,
+                            None => continue 'filter_loop,
+                        };
+                        let proof = holder_proofs
+                            .allowlist_proofs
+                            .next()
+                            .ok_or(ErrorCode::MissingAllowlistProof)?;
+                        let leaf = hashv(&[&[0x00], token_holder.key.as_ref()]);
+
+                        if verify(proof, root, leaf.0) {
+                            msg!("Whitelisted!");
+                            continue 'filter_loop;
+                        }
+                        return Err(error!(ErrorCode::CannotJoinNamespace));
+                    }
+                    Filter::TokenBalance {
+                        mint,
+                        minimum_amount,
+                    } => {
+                        msg!("token balance filter");
+                        let holder_proofs = match holder_proofs.as_mut() {
+                            Some(holder_proofs) => holder_proofs,
+                            None => continue 'filter_loop,
+                        };
+                        let token_account = next_holder_token_account(holder_proofs, token_holder)?;
+
+                        if token_account.mint == *mint && token_account.amount >= *minimum_amount {
+                            msg!("Whitelisted!");
+                            continue 'filter_loop;
+                        }
+                        return Err(error!(ErrorCode::CannotJoinNamespace));
+                    }
                 }
             }
             Ok(())
@@ -220,6 +353,13 @@ pub fn check_permissiveness_against_holder<'a>(
                             return Err(error!(ErrorCode::CannotJoinNamespace));
                         }
                     }
+                    Filter::VerifiedCollection { .. }
+                    | Filter::MerkleAllowlist { .. }
+                    | Filter::TokenBalance { .. } => {
+                        if holder_proofs.is_some() {
+                            return Err(error!(ErrorCode::HolderFilterCannotBlacklist));
+                        }
+                    }
                 }
             }
             Ok(())
@@ -245,6 +385,7 @@ pub fn assert_metadata_valid<'a>(
             metaplex_token_metadata::id().as_ref(),
             mint.as_ref(),
         ],
+        ErrorCode::DerivedKeyInvalid,
     )?;
     if metadata.data_is_empty() {
         return Err(error!(ErrorCode::MetadataDoesntExist));
@@ -260,6 +401,7 @@ pub fn assert_metadata_valid<'a>(
                 mint.as_ref(),
                 metaplex_token_metadata::state::EDITION.as_bytes(),
             ],
+            ErrorCode::DerivedKeyInvalid,
         )?;
         if ed.data_is_empty() {
             return Err(error!(ErrorCode::EditionDoesntExist));
@@ -269,35 +411,6 @@ pub fn assert_metadata_valid<'a>(
     Ok(())
 }
 
-/// Returns true if a `leaf` can be proved to be a part of a Merkle tree
-/// defined by `root`. For this, a `proof` must be provided, containing
-/// sibling hashes on the branch from the leaf to the root of the tree. Each
-/// pair of leaves and each pair of pre-images are assumed to be sorted.
-pub fn verify(proof: Vec<[u8; 32]>, root: [u8; 32], leaf: [u8; 32]) -> bool {
-    let mut computed_hash = leaf;
-    for proof_element in proof.into_iter() {
-        if computed_hash <= proof_element {
-            // Hash(current computed hash + current element of the proof)
-            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x01],
-                &computed_hash,
-                &proof_element,
-            ])
-            .0;
-        } else {
-            // Hash(current element of the proof + current computed hash)
-            computed_hash = anchor_lang::solana_program::keccak::hashv(&[
-                &[0x01],
-                &proof_element,
-                &computed_hash,
-            ])
-            .0;
-        }
-    }
-    // Check if the computed hash (root) is equal to the provided root
-    computed_hash == root
-}
-
 // returns the lowest page that has space for new artifacts
 pub fn lowest_available_page(full_pages: &mut Vec<u64>) -> Result<u64> {
     full_pages.sort();
@@ -310,3 +423,134 @@ pub fn lowest_available_page(full_pages: &mut Vec<u64>) -> Result<u64> {
 
     Ok(page)
 }
+
+// checks the [payment_mint, payment_vault, payment_ata] remaining accounts against the
+// payment configured on the namespace, returns the vault and ata
+pub fn assert_namespace_payment_accounts<'a>(
+    namespace: &Namespace,
+    remaining_accounts: &[AccountInfo<'a>],
+) -> Result<(Account<'a, TokenAccount>, Account<'a, TokenAccount>)> {
+    // require that payments be passed in
+    if remaining_accounts.len() != 3 {
+        return Err(error!(ErrorCode::InvalidRemainingAccounts));
+    }
+
+    let payment_mint = Account::<'_, Mint>::try_from(&remaining_accounts[0])?;
+    let payment_vault = Account::<'_, TokenAccount>::try_from(&remaining_accounts[1])?;
+    let payment_ata = Account::<'_, TokenAccount>::try_from(&remaining_accounts[2])?;
+
+    // check the mint matches for all accounts
+    if payment_ata.mint != payment_mint.key() || payment_vault.mint != payment_mint.key() {
+        return Err(error!(ErrorCode::InvalidRemainingAccounts));
+    }
+
+    // check mint and vault is what is written in the Namespace account
+    if Some(payment_mint.key()) != namespace.payment_mint
+        || Some(payment_vault.key()) != namespace.payment_vault
+    {
+        return Err(error!(ErrorCode::InvalidRemainingAccounts));
+    };
+
+    Ok((payment_vault, payment_ata))
+}
+
+// index pages cached before sort keys existed are grown to the current size
+pub fn ensure_index_space<'a>(
+    index: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()> {
+    if index.data_len() < NamespaceIndex::SPACE {
+        resize_account(index, payer, system_program, NamespaceIndex::SPACE)?;
+    }
+
+    Ok(())
+}
+
+pub fn load_index<'a>(
+    index: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<Account<'a, NamespaceIndex>> {
+    ensure_index_space(index, payer, system_program)?;
+
+    let mut loaded = Account::<'_, NamespaceIndex>::try_from(index)?;
+    loaded.pad_sort_keys();
+
+    Ok(loaded)
+}
+
+// records the index page on the artifact through its program's cache cpi
+pub fn cache_artifact_page<'a>(
+    namespace: &AccountInfo<'a>,
+    artifact: &AccountInfo<'a>,
+    raindrops_program: &AccountInfo<'a>,
+    instructions: &AccountInfo<'a>,
+    page: u64,
+) -> Result<()> {
+    if raindrops_item::check_id(&raindrops_program.key()) {
+        let accounts = ItemArtifactCacheNamespace {
+            item_artifact: artifact.clone(),
+            namespace: namespace.clone(),
+            instructions: instructions.clone(),
+        };
+
+        item_artifact_cache_namespace(CpiContext::new(raindrops_program.clone(), accounts), page)
+    } else if raindrops_matches::check_id(&raindrops_program.key()) {
+        let accounts = MatchCacheNamespace {
+            match_instance: artifact.clone(),
+            namespace: namespace.clone(),
+            instructions: instructions.clone(),
+        };
+
+        match_cache_namespace(CpiContext::new(raindrops_program.clone(), accounts), page)
+    } else if raindrops_player::check_id(&raindrops_program.key()) {
+        let accounts = PlayerArtifactCacheNamespace {
+            player_artifact: artifact.clone(),
+            namespace: namespace.clone(),
+            instructions: instructions.clone(),
+        };
+
+        player_artifact_cache_namespace(CpiContext::new(raindrops_program.clone(), accounts), page)
+    } else if itemv2::check_id(&raindrops_program.key()) {
+        let accounts = ItemClassCacheNamespace {
+            item_class: artifact.clone(),
+            namespace: namespace.clone(),
+            instructions: instructions.clone(),
+        };
+
+        item_class_cache_namespace(CpiContext::new(raindrops_program.clone(), accounts), page)
+    } else if raindrops_avatar::check_id(&raindrops_program.key()) {
+        let accounts = AvatarArtifactCacheNamespace {
+            avatar_artifact: artifact.clone(),
+            namespace: namespace.clone(),
+            instructions: instructions.clone(),
+        };
+
+        avatar_artifact_cache_namespace(CpiContext::new(raindrops_program.clone(), accounts), page)
+    } else if id().eq(&raindrops_program.key()) {
+        let artifact_ns = &mut Account::<'_, Namespace>::try_from(artifact)?;
+
+        let mut cached = false;
+        let mut new_namespaces: Vec<NamespaceAndIndex> = vec![];
+        for ns in artifact_ns.namespaces.clone().unwrap() {
+            if ns.namespace == namespace.key() && !cached {
+                cached = true;
+                new_namespaces.push(NamespaceAndIndex {
+                    namespace: namespace.key(),
+                    index: Some(page),
+                    inherited: InheritanceState::NotInherited,
+                });
+            } else {
+                new_namespaces.push(ns);
+            }
+        }
+        if !cached {
+            return Err(error!(ErrorCode::CannotCacheArtifact));
+        }
+        artifact_ns.namespaces = Some(new_namespaces);
+        artifact_ns.exit(&id())
+    } else {
+        Err(error!(ErrorCode::CannotCacheArtifact))
+    }
+}
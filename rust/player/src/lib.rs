@@ -1,12 +1,10 @@
+pub mod simulation;
 pub mod utils;
 
 use crate::utils::*;
 use anchor_lang::{
-    prelude::{
-        borsh::{BorshDeserialize, BorshSerialize},
-        *,
-    },
-    AnchorDeserialize, AnchorSerialize,
+    prelude::*, solana_program::sysvar::instructions::get_instruction_relative, AnchorDeserialize,
+    AnchorSerialize,
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -15,14 +13,19 @@ use anchor_spl::{
         Transfer,
     },
 };
+pub use raindrops_common::{
+    BasicItemEffect, BasicItemEffectType, Callback, InheritanceState, LegacyBasicItemEffect,
+    NamespaceAndIndex, PermissivenessType, StackingPolicy,
+};
 use raindrops_item::utils::{
-    assert_initialized, assert_keys_equal, assert_metadata_valid, assert_signer, get_item_usage,
-    spl_token_transfer, GetItemUsageArgs, TokenTransferParams,
+    assert_initialized, assert_is_ata, assert_keys_equal, assert_metadata_valid, assert_signer,
+    get_item_usage, spl_token_transfer, GetItemUsageArgs, TokenTransferParams,
 };
 use std::str::FromStr;
 
 anchor_lang::declare_id!("p1ay5K7mcAZUkzR1ArMLCCQ6C58ULUt7SUi7puGEWc1");
 pub const NAMESPACE_ID: &str = "nameAxQRRBnd4kLfsVoZBBXfrByZdZTkh8mULLxLyqV";
+pub const STAKING_ID: &str = "stk9HFnKhZN2PZjnn5C4wTzmeiAEgsDkbqnHkNjX1Z4";
 
 pub const PREFIX: &str = "player";
 pub const VAULT: &str = "rain_vault";
@@ -44,53 +47,6 @@ pub struct ItemCallbackArgs {
     pub usage_info: Option<u8>,
 }
 
-// use Borsh here so that these foreign types do not get imported into our IDL and break the Anchor TS generation
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct CopyEndItemActivationBecauseAnchorSucksSometimesArgs {
-    pub instruction: [u8; 8],
-    pub item_class_mint: Pubkey,
-    pub usage_permissiveness_to_use: Option<PermissivenessType>,
-    pub usage_index: u16,
-    pub index: u64,
-    pub class_index: u64,
-    pub amount: u64,
-    // Required if using roots
-    pub usage_info: Option<raindrops_item::CraftUsageInfo>,
-}
-
-// use Borsh here so that these foreign types do not get imported into our IDL and break the Anchor TS generation
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct CopyBeginItemActivationBecauseAnchorSucksSometimesArgs {
-    pub instruction: [u8; 8],
-    pub class_index: u64,
-    pub index: u64,
-    pub item_class_mint: Pubkey,
-    pub item_mint: Pubkey,
-    // How much space to use for the item marker
-    pub item_marker_space: u8,
-    pub usage_permissiveness_to_use: Option<PermissivenessType>,
-    pub amount: u64,
-    pub usage_index: u16,
-    pub target: Option<Pubkey>,
-    // Use this if using roots
-    pub usage_info: Option<raindrops_item::UsageInfo>,
-}
-
-// use Borsh here so that these foreign types do not get imported into our IDL and break the Anchor TS generation
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct CopyUpdateValidForUseIfWarmupPassedBecauseAnchorSucksSometimesArgs {
-    pub instruction: [u8; 8],
-    pub item_mint: Pubkey,
-    pub index: u64,
-    pub usage_index: u16,
-    pub class_index: u64,
-    pub amount: u64,
-    pub item_class_mint: Pubkey,
-    // Required if using roots
-    pub usage_proof: Option<Vec<[u8; 32]>>,
-    pub usage: Option<raindrops_item::ItemUsage>,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct AddOrRemoveItemValidationArgs {
     // For enum detection on the other end.
@@ -260,6 +216,16 @@ pub struct UpdateValidForUseIfWarmupPassedOnItemArgs {
     pub item_usage: Option<Vec<u8>>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateTokensStakedArgs {
+    pub player_mint: Pubkey,
+    pub index: u64,
+    pub staked: bool,
+    pub amount: u64,
+    // amount scaled by the stake's lockup multiplier
+    pub weighted_amount: u64,
+}
+
 #[program]
 pub mod raindrops_player {
 
@@ -386,6 +352,7 @@ pub mod raindrops_player {
         }
 
         assert_index_and_name_uniqueness_in_player_class_data(&player_class_data)?;
+        assert_valid_equipment_sets(&player_class_data.config.equipment_sets)?;
         player_class.data = player_class_data;
 
         Ok(())
@@ -447,6 +414,7 @@ pub mod raindrops_player {
         }
 
         assert_index_and_name_uniqueness_in_player_class_data(&new_player_class_data)?;
+        assert_valid_equipment_sets(&new_player_class_data.config.equipment_sets)?;
         player_class.data = new_player_class_data;
 
         Ok(())
@@ -683,7 +651,7 @@ pub mod raindrops_player {
                 create_or_allocate_account_raw(
                     ctx.accounts.token_program.key(),
                     rain_token_program_account,
-                    &ctx.accounts.rent,
+                    &ctx.accounts.rent.to_account_info(),
                     &ctx.accounts.system_program,
                     payer,
                     anchor_spl::token::TokenAccount::LEN,
@@ -830,6 +798,15 @@ pub mod raindrops_player {
             .checked_add(amount)
             .ok_or(ErrorCode::NumericalOverflowError)?;
 
+        let weight = get_item_weight(item_class, amount)?;
+        add_backpack_item(&mut player.backpack_items, item.key(), amount, weight)?;
+        player.backpack_weight = player
+            .backpack_weight
+            .checked_add(weight)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+
+        assert_backpack_capacity(player_class, player)?;
+
         let new_amount = item_account
             .amount
             .checked_sub(amount)
@@ -922,6 +899,14 @@ pub mod raindrops_player {
             .checked_sub(amount)
             .ok_or(ErrorCode::NumericalOverflowError)?;
 
+        // the item class weight may have changed since the item was added, so
+        // take off what was added for it
+        let weight = remove_backpack_item(&mut player.backpack_items, item.key(), amount)?;
+        player.backpack_weight = player
+            .backpack_weight
+            .checked_sub(weight)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+
         let mut residual_amount = player_item_account
             .amount
             .checked_sub(amount)
@@ -1074,7 +1059,7 @@ pub mod raindrops_player {
             player,
             player_class,
             item,
-            basic_item_effects: &player_item_activation_marker.basic_item_effects.clone(),
+            basic_item_effects: &player_item_activation_marker.effects(),
             amount_change: player_item_activation_marker.amount,
             adding: false,
             stat_diff_type: StatDiffType::Consumable,
@@ -1097,6 +1082,95 @@ pub mod raindrops_player {
         Ok(())
     }
 
+    // permissionless crank, removes every expired effect from a batch of activation markers
+    // remaining accounts are [player item activation marker, item, marker payer] triples,
+    // optionally followed by the player token account, see ExpireItemEffects
+    pub fn expire_item_effects<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExpireItemEffects<'info>>,
+    ) -> Result<()> {
+        let player = &mut ctx.accounts.player;
+        let player_class = &ctx.accounts.player_class;
+        let clock = &ctx.accounts.clock;
+
+        let marker_accounts = ctx.remaining_accounts.chunks_exact(3);
+        let player_token_account = match marker_accounts.remainder() {
+            [] => None,
+            [player_token_account] => Some(player_token_account),
+            _ => return Err(error!(ErrorCode::InvalidExpiryAccounts)),
+        };
+
+        for accounts in marker_accounts {
+            let marker_info = &accounts[0];
+            let item_info = &accounts[1];
+            let payer_info = &accounts[2];
+
+            let mut player_item_activation_marker: Account<'_, PlayerItemActivationMarker> =
+                Account::try_from(marker_info)?;
+            require!(
+                player_item_activation_marker.player == player.key(),
+                ErrorCode::MarkerDoesNotBelongToPlayer
+            );
+            assert_keys_equal(player_item_activation_marker.item, item_info.key())?;
+            let item: Account<'_, raindrops_item::Item> = Account::try_from(item_info)?;
+
+            let act_at = player_item_activation_marker.activated_at as i64;
+
+            let no_more_waiting = toggle_item_to_basic_stats(ToggleItemToBasicStatsArgs {
+                player,
+                player_class,
+                item: &item,
+                basic_item_effects: &player_item_activation_marker.effects(),
+                amount_change: player_item_activation_marker.amount,
+                adding: false,
+                stat_diff_type: StatDiffType::Consumable,
+                bie_bitmap: &mut player_item_activation_marker.removed_bie_bitmap,
+                unix_timestamp: clock.unix_timestamp,
+                activated_at: act_at,
+            })?;
+
+            if no_more_waiting {
+                assert_derivation(
+                    ctx.program_id,
+                    marker_info,
+                    &[
+                        PREFIX.as_bytes(),
+                        player_item_activation_marker.item.as_ref(),
+                        player.key().as_ref(),
+                        &(player_item_activation_marker.usage_index as u64).to_le_bytes(),
+                        &player_item_activation_marker.amount.to_le_bytes(),
+                        raindrops_item::MARKER.as_bytes(),
+                    ],
+                    ErrorCode::DerivedKeyInvalid,
+                )?;
+
+                match player_item_activation_marker.additions.payer {
+                    Some(payer) => assert_keys_equal(payer, payer_info.key())?,
+                    // markers created before the payer was recorded refund the player's
+                    // token holder, who has to sign for it
+                    None => {
+                        let player_token_account =
+                            player_token_account.ok_or(ErrorCode::PlayerTokenHolderMustSign)?;
+                        assert_signer(payer_info)?;
+                        let token_account = assert_is_ata(
+                            player_token_account,
+                            payer_info.key,
+                            &player.mint.ok_or(ErrorCode::PlayerTokenHolderMustSign)?,
+                            None,
+                        )?;
+                        require!(token_account.amount > 0, ErrorCode::InsufficientBalance);
+                    }
+                }
+                player_item_activation_marker.close(payer_info.clone())?;
+            } else {
+                let mut marker_data = marker_info.try_borrow_mut_data()?;
+                let mut dst: &mut [u8] = &mut marker_data;
+                player_item_activation_marker.try_serialize(&mut dst)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn add_item_effect<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, AddItemEffect<'info>>,
         args: AddItemEffectArgs,
@@ -1153,6 +1227,7 @@ pub mod raindrops_player {
             *ctx.bumps.get("player_item_activation_marker").unwrap();
         player_item_activation_marker.player = player.key();
         player_item_activation_marker.item = item.key();
+        player_item_activation_marker.additions.payer = Some(payer.key());
 
         let item_usage_to_use = get_item_usage(GetItemUsageArgs {
             item_class,
@@ -1166,20 +1241,7 @@ pub mod raindrops_player {
         })?;
 
         player_item_activation_marker.usage_index = item_usage_index;
-        player_item_activation_marker.basic_item_effects =
-            if let Some(v) = &item_usage_to_use.basic_item_effects {
-                let mut new_vec = vec![];
-                for b in v {
-                    // convert from item's version to player's version
-                    // which should be identical but anchor sucks
-                    // and wont let us import item's into IDL.
-                    let as_bytes = b.try_to_vec()?;
-                    new_vec.push(BasicItemEffect::try_from_slice(&as_bytes)?)
-                }
-                Some(new_vec)
-            } else {
-                None
-            };
+        player_item_activation_marker.set_effects(&item_usage_to_use.basic_item_effects);
         if let Some(bie) = &item_usage_to_use.basic_item_effects {
             if !bie.is_empty() {
                 let bie_size = 1 + bie
@@ -1209,7 +1271,7 @@ pub mod raindrops_player {
             player,
             player_class,
             item,
-            basic_item_effects: &player_item_activation_marker.basic_item_effects.clone(),
+            basic_item_effects: &player_item_activation_marker.effects(),
             amount_change: player_item_activation_marker.amount,
             adding: true,
             stat_diff_type: StatDiffType::Consumable,
@@ -1358,16 +1420,7 @@ pub mod raindrops_player {
             player,
             player_class,
             item,
-            basic_item_effects: &(if let Some(v) = &item_usage.basic_item_effects {
-                let mut new_vec = vec![];
-                for val in v {
-                    let as_bytes = val.try_to_vec()?;
-                    new_vec.push(BasicItemEffect::try_from_slice(&as_bytes)?);
-                }
-                Some(new_vec)
-            } else {
-                None
-            }),
+            basic_item_effects: &item_usage.basic_item_effects,
             amount_change: amount,
             adding: equipping,
             stat_diff_type: StatDiffType::Wearable,
@@ -1376,6 +1429,17 @@ pub mod raindrops_player {
             activated_at: 0,
         })?;
 
+        toggle_equipment_set_bonuses(ToggleEquipmentSetBonusesArgs {
+            player,
+            player_class,
+            item_class: item.parent,
+            amount,
+            equipping,
+        })?;
+
+        // equipment can carry the stats that hold the backpack capacity up
+        assert_backpack_capacity(player_class, player)?;
+
         Ok(())
     }
 
@@ -1410,6 +1474,8 @@ pub mod raindrops_player {
         })?;
 
         player.equipped_items = vec![];
+        player.equipped_item_classes = vec![];
+        player.active_stat_effects = vec![];
         if let Some(stats) = &player.data.basic_stats {
             let mut new_stats = vec![];
             for stat in stats {
@@ -1436,6 +1502,49 @@ pub mod raindrops_player {
         Ok(())
     }
 
+    pub fn update_tokens_staked<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, UpdateTokensStaked<'info>>,
+        args: UpdateTokensStakedArgs,
+    ) -> Result<()> {
+        let player = &mut ctx.accounts.player;
+        let instruction_sysvar_account = &ctx.accounts.instruction_sysvar_account;
+
+        let instruction_sysvar_account_info = instruction_sysvar_account.to_account_info();
+        let current_ix = get_instruction_relative(0, &instruction_sysvar_account_info).unwrap();
+
+        require!(
+            current_ix.program_id == Pubkey::from_str(STAKING_ID).unwrap(),
+            ErrorCode::MustBeCalledByStakingProgram
+        );
+
+        // stake from before lockup tiers carries no weight yet, it counts at 1x
+        if player.weighted_tokens_staked < player.tokens_staked {
+            player.weighted_tokens_staked = player.tokens_staked;
+        }
+
+        if args.staked {
+            player.tokens_staked = player
+                .tokens_staked
+                .checked_add(args.amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+            player.weighted_tokens_staked = player
+                .weighted_tokens_staked
+                .checked_add(args.weighted_amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        } else {
+            player.tokens_staked = player
+                .tokens_staked
+                .checked_sub(args.amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+            player.weighted_tokens_staked = player
+                .weighted_tokens_staked
+                .checked_sub(args.weighted_amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
+
+        Ok(())
+    }
+
     pub fn player_artifact_join_namespace<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, PlayerArtifactJoinNamespace<'info>>,
     ) -> Result<()> {
@@ -1642,6 +1751,21 @@ pub struct SubtractItemEffect<'info> {
     clock: Sysvar<'info, Clock>,
 }
 
+#[derive(Accounts)]
+pub struct ExpireItemEffects<'info> {
+    #[account(mut)]
+    player: Box<Account<'info, Player>>,
+    #[account(constraint=player.parent == player_class.key())]
+    player_class: Box<Account<'info, PlayerClass>>,
+    clock: Sysvar<'info, Clock>,
+    // remaining accounts are triples of:
+    // player item activation marker [writable]
+    // item the marker was activated from
+    // payer of the marker [writable], gets the rent back once every effect has expired
+    // followed, when closing markers created before the payer was recorded, by:
+    // player token account of the token holder, who is then the payer and must sign
+}
+
 #[derive(Accounts)]
 #[instruction(args: AddItemEffectArgs)]
 pub struct AddItemEffect<'info> {
@@ -1967,6 +2091,24 @@ pub struct ResetPlayerStats<'info> {
     // See the [COMMON REMAINING ACCOUNTS] in lib.rs of item for accounts that come after for add item permissiveness
 }
 
+#[derive(Accounts)]
+#[instruction(args: UpdateTokensStakedArgs)]
+pub struct UpdateTokensStaked<'info> {
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            args.player_mint.as_ref(),
+            &args.index.to_le_bytes()
+        ],
+        bump=player.bump
+    )]
+    player: Box<Account<'info, Player>>,
+    /// CHECK: account constraints checked in account trait
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    instruction_sysvar_account: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(args: ToggleEquipItemArgs)]
 pub struct ToggleEquipItem<'info> {
@@ -2255,29 +2397,21 @@ pub struct EquippedItem {
     index: u16,
 }
 
-pub const MAX_NAMESPACES: usize = 10;
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum PermissivenessType {
-    TokenHolder,
-    ParentTokenHolder,
-    UpdateAuthority,
-    Anybody,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EquippedItemClass {
+    pub item_class: Pubkey,
+    pub amount: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Copy)]
-pub enum InheritanceState {
-    NotInherited,
-    Inherited,
-    Overridden,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BackpackItem {
+    pub item: Pubkey,
+    pub amount: u64,
+    // total weight of amount, as added
+    pub weight: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct NamespaceAndIndex {
-    pub namespace: Pubkey,
-    pub index: Option<u64>,
-    pub inherited: InheritanceState,
-}
+pub const MAX_NAMESPACES: usize = 10;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct ChildUpdatePropagationPermissiveness {
@@ -2318,6 +2452,24 @@ impl Inherited for BodyPart {
     }
 }
 
+impl Inherited for EquipmentSet {
+    fn set_inherited(&mut self, i: InheritanceState) {
+        self.inherited = i;
+    }
+    fn get_inherited(&self) -> &InheritanceState {
+        &self.inherited
+    }
+}
+
+impl Inherited for Backpack {
+    fn set_inherited(&mut self, i: InheritanceState) {
+        self.inherited = i;
+    }
+    fn get_inherited(&self) -> &InheritanceState {
+        &self.inherited
+    }
+}
+
 impl Inherited for PlayerCategory {
     fn set_inherited(&mut self, i: InheritanceState) {
         self.inherited = i;
@@ -2380,6 +2532,8 @@ pub enum ChildUpdatePropagationPermissivenessType {
     DefaultCategory,
     BodyParts,
     StatsUri,
+    Backpack,
+    EquipmentSets,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -2402,6 +2556,43 @@ pub struct BodyPart {
     pub inherited: InheritanceState,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Backpack {
+    // counted over every item added to the player, equipped or not
+    pub max_items: Option<u64>,
+    pub max_weight: Option<u64>,
+    // only integer stats count, a negative stat gives no bonus
+    pub stat_bonuses: Vec<BackpackCapacityBonus>,
+    pub inherited: InheritanceState,
+}
+
+/// Extra backpack capacity given by each point of an integer stat.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BackpackCapacityBonus {
+    pub stat_index: u16,
+    pub items_per_point: u64,
+    pub weight_per_point: u64,
+}
+
+/// Item classes that give extra effects when enough different ones of them are
+/// equipped at the same time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EquipmentSet {
+    pub index: u16,
+    pub name: String,
+    pub item_classes: Vec<Pubkey>,
+    // every bonus with enough pieces equipped applies, so thresholds stack
+    pub bonuses: Vec<EquipmentSetBonus>,
+    pub inherited: InheritanceState,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EquipmentSetBonus {
+    // number of different item classes of the set that must be equipped
+    pub pieces: u16,
+    pub basic_item_effects: Vec<BasicItemEffect>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct PlayerClassData {
     pub settings: PlayerClassSettings,
@@ -2470,6 +2661,8 @@ pub const MIN_PLAYER_CLASS_SIZE: usize = 8 + // key
 1 + // body parts
 1 + // equip callback
 1 + // add to pack callback
+1 + // backpack
+1 + // equipment sets
 8 + // existing children
 1; //bump
 
@@ -2480,6 +2673,9 @@ pub struct PlayerClassConfig {
     pub body_parts: Option<Vec<BodyPart>>,
     pub equip_validation: Option<Callback>,
     pub add_to_pack_validation: Option<Callback>,
+    // if not set, the backpack holds anything
+    pub backpack: Option<Backpack>,
+    pub equipment_sets: Option<Vec<EquipmentSet>>,
 }
 
 /// seed ['player', player program, mint, namespace]
@@ -2495,12 +2691,6 @@ pub struct PlayerClass {
     pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Callback {
-    pub key: Pubkey,
-    pub code: u64,
-}
-
 pub const MIN_PLAYER_SIZE: usize = 8 + // key
 1 + // namespaces
 1 + // padding(?)
@@ -2517,7 +2707,12 @@ pub const MIN_PLAYER_SIZE: usize = 8 + // key
 1 + // category
 4 + // equipped items
 1 + // basic stats
-8; // tokens paid in
+8 + // tokens paid in
+4 + // active stat effects
+8 + // backpack weight
+4 + // equipped item classes
+8 + // weighted tokens staked
+4; // backpack items
 
 /// seed ['player', player program, mint, namespace] also
 #[account]
@@ -2537,6 +2732,18 @@ pub struct Player {
     pub data: PlayerData,
     pub equipped_items: Vec<EquippedItem>,
     pub tokens_paid_in: u64,
+    // effects that are not simply added onto the stat, kept so they can be undone
+    pub active_stat_effects: Vec<ActiveStatEffect>,
+    // total weight of the items in items_in_backpack
+    pub backpack_weight: u64,
+    // equipped amounts per item class, used to count equipment set pieces
+    pub equipped_item_classes: Vec<EquippedItemClass>,
+    // tokens_staked scaled by each stake's lockup multiplier, kept last so it
+    // reads from the spare space of existing player accounts
+    pub weighted_tokens_staked: u64,
+    // weight each item added to backpack_weight, so removing it takes off the
+    // same weight even if its class changed since
+    pub backpack_items: Vec<BackpackItem>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -2609,6 +2816,21 @@ pub enum BasicStatState {
     Null,
 }
 
+/// A Set, Clamp, RefreshDuration or HighestWins effect currently applied to a
+/// stat. Applications of the same effect from the same item class share one
+/// entry and are counted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ActiveStatEffect {
+    pub stat_index: u16,
+    pub item_class: Pubkey,
+    pub item_effect_type: BasicItemEffectType,
+    pub stacking_policy: StackingPolicy,
+    pub amount: i64,
+    pub clamp_min: Option<i64>,
+    pub clamp_max: Option<i64>,
+    pub count: u64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
 pub enum StatDiffType {
     Wearable,
@@ -2624,7 +2846,9 @@ const PLAYER_ITEM_ACTIVATION_MARKER_MIN_SPACE: usize = 8 + // key
 1 + // bie bitmap option
 8 + // amount
 8 +  // activated_at
-8; // active_item_coiunter
+8 + // active_item_coiunter
+1 + 32 + // payer
+4; // effect policies
 
 #[account]
 pub struct PlayerItemActivationMarker {
@@ -2632,37 +2856,96 @@ pub struct PlayerItemActivationMarker {
     pub player: Pubkey,
     pub item: Pubkey,
     pub usage_index: u16,
-    pub basic_item_effects: Option<Vec<BasicItemEffect>>,
+    // clamps and stacking policies are kept in additions, read the full effects with effects()
+    pub basic_item_effects: Option<Vec<LegacyBasicItemEffect>>,
     pub removed_bie_bitmap: Option<Vec<u8>>,
     pub amount: u64,
     pub activated_at: u64,
     pub active_item_counter: u64,
+    pub additions: PlayerItemActivationMarkerAdditions,
+}
+
+impl PlayerItemActivationMarker {
+    pub fn effects(&self) -> Option<Vec<BasicItemEffect>> {
+        self.basic_item_effects.as_ref().map(|effects| {
+            effects
+                .iter()
+                .enumerate()
+                .map(|(i, effect)| {
+                    let mut effect: BasicItemEffect = effect.clone().into();
+                    if let Some(policy) = self.additions.effect_policies.get(i) {
+                        effect.clamp_min = policy.clamp_min;
+                        effect.clamp_max = policy.clamp_max;
+                        effect.stacking_policy = policy.stacking_policy;
+                    }
+                    effect
+                })
+                .collect()
+        })
+    }
+
+    pub fn set_effects(&mut self, effects: &Option<Vec<BasicItemEffect>>) {
+        self.additions.effect_policies = vec![];
+        self.basic_item_effects = effects
+            .as_ref()
+            .map(|effects| effects.iter().map(|e| e.without_policies()).collect());
+
+        // policies are only stored when an effect uses them
+        if let Some(effects) = effects {
+            if effects.iter().any(|effect| effect.to_legacy().is_none()) {
+                self.additions.effect_policies = effects
+                    .iter()
+                    .map(|effect| BasicItemEffectPolicy {
+                        clamp_min: effect.clamp_min,
+                        clamp_max: effect.clamp_max,
+                        stacking_policy: effect.stacking_policy,
+                    })
+                    .collect();
+            }
+        }
+    }
+}
+
+/// Marker fields kept after the original layout so markers created before
+/// them still read back. Those have no payer and no effect policies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlayerItemActivationMarkerAdditions {
+    // refunded the rent when the marker is closed by expire_item_effects
+    pub payer: Option<Pubkey>,
+    // clamps and stacking policy of each basic item effect, empty when none use them
+    pub effect_policies: Vec<BasicItemEffectPolicy>,
 }
 
-// Copied from Item class because Anchor sucks and can't do imports
+// nothing is written for markers created before the additions so they still fit their account
+impl AnchorSerialize for PlayerItemActivationMarkerAdditions {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.payer.is_none() && self.effect_policies.is_empty() {
+            return Ok(());
+        }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
-pub enum BasicItemEffectType {
-    Increment,
-    Decrement,
-    IncrementPercent,
-    DecrementPercent,
-    IncrementPercentFromBase,
-    DecrementPercentFromBase,
+        self.payer.serialize(writer)?;
+        self.effect_policies.serialize(writer)
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct BasicItemEffect {
-    pub amount: u64,
-    pub stat: String,
-    pub item_effect_type: BasicItemEffectType,
-    pub active_duration: Option<u64>,
-    pub staking_amount_numerator: Option<u64>,
-    pub staking_amount_divisor: Option<u64>,
-    pub staking_duration_numerator: Option<u64>,
-    pub staking_duration_divisor: Option<u64>,
-    // point where this effect no longer applies
-    pub max_uses: Option<u64>,
+impl AnchorDeserialize for PlayerItemActivationMarkerAdditions {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if buf.is_empty() {
+            return Ok(PlayerItemActivationMarkerAdditions::default());
+        }
+
+        Ok(PlayerItemActivationMarkerAdditions {
+            payer: Option::<Pubkey>::deserialize(buf)?,
+            effect_policies: Vec::<BasicItemEffectPolicy>::deserialize(buf)?,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BasicItemEffectPolicy {
+    pub clamp_min: Option<i64>,
+    pub clamp_max: Option<i64>,
+    pub stacking_policy: Option<StackingPolicy>,
 }
 
 #[error_code]
@@ -2767,4 +3050,20 @@ pub enum ErrorCode {
     RainTokenMintMismatch,
     #[msg("Amount must be greater than zero")]
     AmountMustBeGreaterThanZero,
+    #[msg("Markers must be passed as marker, item and payer account triples")]
+    InvalidExpiryAccounts,
+    #[msg("Activation marker belongs to a different player")]
+    MarkerDoesNotBelongToPlayer,
+    #[msg("Backpack cannot hold this many items")]
+    BackpackFull,
+    #[msg("Backpack cannot hold this much weight")]
+    BackpackTooHeavy,
+    #[msg("Equipment set bonuses need between one and all of the set's item classes")]
+    InvalidEquipmentSetBonus,
+    #[msg("An item class can only appear once in an equipment set")]
+    DuplicateEquipmentSetItemClass,
+    #[msg("Must be called by staking program")]
+    MustBeCalledByStakingProgram,
+    #[msg("Markers created before the payer was recorded refund the player's token holder, who must sign")]
+    PlayerTokenHolderMustSign,
 }
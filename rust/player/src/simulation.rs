@@ -0,0 +1,497 @@
+//! Off chain replay of the stat math run by the player program.
+//!
+//! Wearables are equipped and unequipped, consumables are used and their timed
+//! effects wear off, all through the same `apply_basic_item_effects` the
+//! instructions use, so designers can check how a player class behaves before
+//! anything is deployed.
+#![allow(clippy::result_large_err)]
+
+use crate::{
+    utils::{apply_basic_item_effects, ApplyBasicItemEffectsArgs},
+    BasicItemEffect, BasicStat, BasicStatState, ErrorCode, Player, PlayerClassData, StatDiffType,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct SimulatedItem {
+    pub item_class: Pubkey,
+    pub tokens_staked: u64,
+    pub weighted_tokens_staked: u64,
+    pub basic_item_effects: Vec<BasicItemEffect>,
+}
+
+#[derive(Clone, Debug)]
+pub enum StatSimulationEvent {
+    // equip a wearable
+    Add { item: SimulatedItem, amount: u64 },
+    // unequip a wearable
+    Remove { item: SimulatedItem, amount: u64 },
+    // use a consumable, its timed effects wear off by themselves
+    Use { item: SimulatedItem, amount: u64 },
+    // nothing happens besides time passing
+    Wait,
+}
+
+#[derive(Clone, Debug)]
+pub struct TimedStatSimulationEvent {
+    pub unix_timestamp: i64,
+    pub event: StatSimulationEvent,
+}
+
+#[derive(Clone, Debug)]
+pub struct StatSnapshot {
+    pub unix_timestamp: i64,
+    pub basic_stats: Vec<BasicStatState>,
+}
+
+// what the PlayerItemActivationMarker holds on chain
+struct SimulatedActivation {
+    item: SimulatedItem,
+    amount: u64,
+    activated_at: i64,
+    bie_bitmap: Option<Vec<u8>>,
+}
+
+/// Replays the events against a copy of the player and returns its stats
+/// after each one. Events are taken in timestamp order, and timed effects are
+/// removed at the first event after they run out, as if subtract_item_effect
+/// were cranked right away.
+pub fn simulate_stats(
+    player_class_data: &PlayerClassData,
+    player: &Player,
+    events: &[TimedStatSimulationEvent],
+) -> Result<Vec<StatSnapshot>> {
+    let basic_stat_templates = player_class_data
+        .config
+        .basic_stats
+        .clone()
+        .unwrap_or_default();
+    let mut basic_stats = player.data.basic_stats.clone().unwrap_or_default();
+    let mut active_stat_effects = player.active_stat_effects.clone();
+    let mut activations: Vec<SimulatedActivation> = vec![];
+
+    let mut ordered: Vec<&TimedStatSimulationEvent> = events.iter().collect();
+    ordered.sort_by_key(|e| e.unix_timestamp);
+
+    let mut snapshots = vec![];
+    for timed_event in ordered {
+        let unix_timestamp = timed_event.unix_timestamp;
+
+        let mut still_active = vec![];
+        for mut activation in activations {
+            let no_more_waiting = apply_basic_item_effects(ApplyBasicItemEffectsArgs {
+                basic_stat_templates: &basic_stat_templates,
+                basic_stats: &mut basic_stats,
+                active_stat_effects: &mut active_stat_effects,
+                item_class: activation.item.item_class,
+                tokens_staked: activation.item.tokens_staked,
+                weighted_tokens_staked: activation.item.weighted_tokens_staked,
+                basic_item_effects: &activation.item.basic_item_effects,
+                amount_change: activation.amount,
+                adding: false,
+                stat_diff_type: StatDiffType::Consumable,
+                bie_bitmap: &mut activation.bie_bitmap,
+                unix_timestamp,
+                activated_at: activation.activated_at,
+            })?;
+            if !no_more_waiting {
+                still_active.push(activation);
+            }
+        }
+        activations = still_active;
+
+        let (item, amount, adding, stat_diff_type) = match &timed_event.event {
+            StatSimulationEvent::Add { item, amount } => {
+                (item, *amount, true, StatDiffType::Wearable)
+            }
+            StatSimulationEvent::Remove { item, amount } => {
+                (item, *amount, false, StatDiffType::Wearable)
+            }
+            StatSimulationEvent::Use { item, amount } => {
+                (item, *amount, true, StatDiffType::Consumable)
+            }
+            StatSimulationEvent::Wait => {
+                snapshots.push(snapshot(unix_timestamp, &basic_stats));
+                continue;
+            }
+        };
+
+        apply_basic_item_effects(ApplyBasicItemEffectsArgs {
+            basic_stat_templates: &basic_stat_templates,
+            basic_stats: &mut basic_stats,
+            active_stat_effects: &mut active_stat_effects,
+            item_class: item.item_class,
+            tokens_staked: item.tokens_staked,
+            weighted_tokens_staked: item.weighted_tokens_staked,
+            basic_item_effects: &item.basic_item_effects,
+            amount_change: amount,
+            adding,
+            stat_diff_type,
+            bie_bitmap: &mut None,
+            unix_timestamp: 0,
+            activated_at: 0,
+        })?;
+
+        if stat_diff_type == StatDiffType::Consumable && !item.basic_item_effects.is_empty() {
+            let bie_size = 1 + item
+                .basic_item_effects
+                .len()
+                .checked_div(8)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+            activations.push(SimulatedActivation {
+                item: item.clone(),
+                amount,
+                activated_at: unix_timestamp,
+                bie_bitmap: Some(vec![0u8; bie_size]),
+            });
+        }
+
+        snapshots.push(snapshot(unix_timestamp, &basic_stats));
+    }
+
+    Ok(snapshots)
+}
+
+fn snapshot(unix_timestamp: i64, basic_stats: &[BasicStat]) -> StatSnapshot {
+    StatSnapshot {
+        unix_timestamp,
+        basic_stats: basic_stats.iter().map(|bs| bs.state.clone()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BasicItemEffectType, BasicStatTemplate, BasicStatType, InheritanceState, PlayerClassConfig,
+        PlayerClassSettings, PlayerData,
+    };
+
+    // small xorshift so the property checks are repeatable without extra crates
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn range(&mut self, low: u64, high: u64) -> u64 {
+            low + self.next() % (high - low + 1)
+        }
+    }
+
+    fn class_data(min: Option<i64>, max: Option<i64>) -> PlayerClassData {
+        PlayerClassData {
+            settings: PlayerClassSettings {
+                default_category: None,
+                children_must_be_editions: None,
+                builder_must_be_holder: None,
+                update_permissiveness: None,
+                instance_update_permissiveness: None,
+                build_permissiveness: None,
+                equip_item_permissiveness: None,
+                add_item_permissiveness: None,
+                use_item_permissiveness: None,
+                unequip_item_permissiveness: None,
+                remove_item_permissiveness: None,
+                staking_warm_up_duration: None,
+                staking_cooldown_duration: None,
+                staking_permissiveness: None,
+                unstaking_permissiveness: None,
+                child_update_propagation_permissiveness: None,
+            },
+            config: PlayerClassConfig {
+                starting_stats_uri: None,
+                basic_stats: Some(vec![BasicStatTemplate {
+                    index: 0,
+                    name: "strength".to_string(),
+                    stat_type: BasicStatType::Integer {
+                        min,
+                        max,
+                        starting: 100,
+                        staking_amount_scaler: None,
+                        staking_duration_scaler: None,
+                    },
+                    inherited: InheritanceState::NotInherited,
+                }]),
+                body_parts: None,
+                equip_validation: None,
+                add_to_pack_validation: None,
+                backpack: None,
+                equipment_sets: None,
+            },
+        }
+    }
+
+    fn player(base: i64) -> Player {
+        Player {
+            namespaces: None,
+            padding: 1,
+            parent: Pubkey::new_unique(),
+            class_index: 0,
+            mint: None,
+            metadata: None,
+            edition: None,
+            bump: 0,
+            tokens_staked: 0,
+            active_item_counter: 0,
+            items_in_backpack: 0,
+            data: PlayerData {
+                stats_uri: None,
+                category: None,
+                basic_stats: Some(vec![BasicStat {
+                    index: 0,
+                    state: BasicStatState::Integer {
+                        base,
+                        with_temporary_changes: base,
+                        temporary_numerator: 1,
+                        temporary_denominator: 1,
+                        finalized: base,
+                    },
+                }]),
+            },
+            equipped_items: vec![],
+            tokens_paid_in: 0,
+            active_stat_effects: vec![],
+            backpack_weight: 0,
+            equipped_item_classes: vec![],
+            weighted_tokens_staked: 0,
+            backpack_items: vec![],
+        }
+    }
+
+    fn item(item_effect_type: BasicItemEffectType, amount: u64) -> SimulatedItem {
+        SimulatedItem {
+            item_class: Pubkey::new_unique(),
+            tokens_staked: 0,
+            weighted_tokens_staked: 0,
+            basic_item_effects: vec![BasicItemEffect {
+                amount,
+                stat: "strength".to_string(),
+                item_effect_type,
+                active_duration: None,
+                staking_amount_numerator: None,
+                staking_amount_divisor: None,
+                staking_duration_numerator: None,
+                staking_duration_divisor: None,
+                max_uses: None,
+                clamp_min: None,
+                clamp_max: None,
+                stacking_policy: None,
+            }],
+        }
+    }
+
+    fn at(unix_timestamp: i64, event: StatSimulationEvent) -> TimedStatSimulationEvent {
+        TimedStatSimulationEvent {
+            unix_timestamp,
+            event,
+        }
+    }
+
+    fn integer(state: &BasicStatState) -> (i64, i64) {
+        match state {
+            BasicStatState::Integer {
+                base, finalized, ..
+            } => (*base, *finalized),
+            _ => panic!("not an integer stat"),
+        }
+    }
+
+    fn random_item(rng: &mut Rng) -> SimulatedItem {
+        let item_effect_type = match rng.range(0, 5) {
+            0 => BasicItemEffectType::Increment,
+            1 => BasicItemEffectType::Decrement,
+            2 => BasicItemEffectType::IncrementPercent,
+            3 => BasicItemEffectType::DecrementPercent,
+            4 => BasicItemEffectType::IncrementPercentFromBase,
+            _ => BasicItemEffectType::DecrementPercentFromBase,
+        };
+        // percent effects stay under 100 so a decrement never zeroes the numerator
+        item(item_effect_type, rng.range(1, 90))
+    }
+
+    #[test]
+    fn test_timed_effect_wears_off() {
+        let mut potion = item(BasicItemEffectType::Increment, 20);
+        potion.basic_item_effects[0].active_duration = Some(10);
+
+        let snapshots = simulate_stats(
+            &class_data(None, None),
+            &player(100),
+            &[
+                at(
+                    0,
+                    StatSimulationEvent::Use {
+                        item: potion,
+                        amount: 1,
+                    },
+                ),
+                at(10, StatSimulationEvent::Wait),
+                at(11, StatSimulationEvent::Wait),
+            ],
+        )
+        .unwrap();
+
+        let finalized: Vec<i64> = snapshots
+            .iter()
+            .map(|s| integer(&s.basic_stats[0]).1)
+            .collect();
+        assert_eq!(finalized, vec![120, 120, 100]);
+    }
+
+    #[test]
+    fn test_events_replay_in_timestamp_order() {
+        let sword = item(BasicItemEffectType::Increment, 5);
+        let snapshots = simulate_stats(
+            &class_data(None, None),
+            &player(100),
+            &[
+                at(
+                    2,
+                    StatSimulationEvent::Remove {
+                        item: sword.clone(),
+                        amount: 1,
+                    },
+                ),
+                at(
+                    1,
+                    StatSimulationEvent::Add {
+                        item: sword,
+                        amount: 1,
+                    },
+                ),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(snapshots[0].unix_timestamp, 1);
+        assert_eq!(integer(&snapshots[0].basic_stats[0]).1, 105);
+        assert_eq!(integer(&snapshots[1].basic_stats[0]).1, 100);
+    }
+
+    #[test]
+    fn test_stats_stay_within_bounds() {
+        let mut rng = Rng(0x5eed);
+        for _ in 0..200 {
+            let (min, max) = (rng.range(0, 90) as i64, rng.range(110, 200) as i64);
+            let mut events = vec![];
+            let mut equipped = vec![];
+            for t in 0..20 {
+                let event = if !equipped.is_empty() && rng.range(0, 2) == 0 {
+                    let i = rng.range(0, equipped.len() as u64 - 1) as usize;
+                    StatSimulationEvent::Remove {
+                        item: equipped.remove(i),
+                        amount: 1,
+                    }
+                } else if equipped.len() < 5 && rng.range(0, 1) == 0 {
+                    let new_item = random_item(&mut rng);
+                    equipped.push(new_item.clone());
+                    StatSimulationEvent::Add {
+                        item: new_item,
+                        amount: 1,
+                    }
+                } else {
+                    StatSimulationEvent::Use {
+                        item: random_item(&mut rng),
+                        amount: 1,
+                    }
+                };
+                events.push(at(t, event));
+            }
+
+            for s in
+                simulate_stats(&class_data(Some(min), Some(max)), &player(100), &events).unwrap()
+            {
+                let (base, finalized) = integer(&s.basic_stats[0]);
+                assert!(min <= base && base <= max, "base {} out of bounds", base);
+                assert!(
+                    min <= finalized && finalized <= max,
+                    "finalized {} out of bounds",
+                    finalized
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_percent_effects_round_down() {
+        for base in [1i64, 7, 33, 100, 999] {
+            for percent in 1..=90u64 {
+                let snapshots = simulate_stats(
+                    &class_data(None, None),
+                    &player(base),
+                    &[
+                        at(
+                            0,
+                            StatSimulationEvent::Add {
+                                item: item(BasicItemEffectType::IncrementPercent, percent),
+                                amount: 1,
+                            },
+                        ),
+                        at(
+                            1,
+                            StatSimulationEvent::Use {
+                                item: item(BasicItemEffectType::DecrementPercentFromBase, percent),
+                                amount: 1,
+                            },
+                        ),
+                    ],
+                )
+                .unwrap();
+
+                let (_, finalized) = integer(&snapshots[0].basic_stats[0]);
+                assert_eq!(finalized, base * (100 + percent as i64) / 100);
+
+                let (new_base, _) = integer(&snapshots[1].basic_stats[0]);
+                assert_eq!(new_base, base * (100 - percent as i64) / 100);
+            }
+        }
+    }
+
+    #[test]
+    fn test_temporary_effects_are_reversible() {
+        let mut rng = Rng(0xbeef);
+        for _ in 0..200 {
+            let class = class_data(Some(0), Some(150));
+            let start = player(rng.range(1, 150) as i64);
+            let start_state = start.data.basic_stats.clone().unwrap()[0].state.clone();
+
+            let items: Vec<SimulatedItem> = (0..rng.range(1, 8))
+                .map(|_| random_item(&mut rng))
+                .collect();
+            let mut events = vec![];
+            let mut t = 0;
+            for i in &items {
+                events.push(at(
+                    t,
+                    StatSimulationEvent::Add {
+                        item: i.clone(),
+                        amount: 1,
+                    },
+                ));
+                t += 1;
+            }
+            // take them off in a different order than they went on
+            let mut remaining = items;
+            while !remaining.is_empty() {
+                let i = rng.range(0, remaining.len() as u64 - 1) as usize;
+                events.push(at(
+                    t,
+                    StatSimulationEvent::Remove {
+                        item: remaining.remove(i),
+                        amount: 1,
+                    },
+                ));
+                t += 1;
+            }
+
+            let snapshots = simulate_stats(&class, &start, &events).unwrap();
+            let end_state = &snapshots.last().unwrap().basic_stats[0];
+            assert_eq!(format!("{:?}", end_state), format!("{:?}", start_state));
+        }
+    }
+}
@@ -1,14 +1,14 @@
-use std::{convert::TryInto, str::FromStr};
+pub use raindrops_common::utils::{
+    assert_derivation, create_or_allocate_account_raw, is_namespace_program_caller, sighash,
+};
 
 use crate::{
-    AddOrRemoveItemValidationArgs, BasicItemEffect, BasicItemEffectType, BasicStat, BasicStatState,
-    BasicStatTemplate, BasicStatType, BodyPart, ChildUpdatePropagationPermissivenessType,
-    CopyBeginItemActivationBecauseAnchorSucksSometimesArgs,
-    CopyEndItemActivationBecauseAnchorSucksSometimesArgs,
-    CopyUpdateValidForUseIfWarmupPassedBecauseAnchorSucksSometimesArgs, EquippedItem, ErrorCode,
-    InheritanceState, Inherited, ItemCallbackArgs, NamespaceAndIndex, Permissiveness,
-    PermissivenessType, Player, PlayerClass, PlayerClassData, StatDiffType,
-    UpdateValidForUseIfWarmupPassedOnItemArgs, UseItemArgs, NAMESPACE_ID, PREFIX,
+    ActiveStatEffect, AddOrRemoveItemValidationArgs, Backpack, BackpackItem, BasicItemEffect,
+    BasicItemEffectType, BasicStat, BasicStatState, BasicStatTemplate, BasicStatType, BodyPart,
+    ChildUpdatePropagationPermissivenessType, EquipmentSet, EquippedItem, EquippedItemClass,
+    ErrorCode, InheritanceState, Inherited, ItemCallbackArgs, NamespaceAndIndex, Permissiveness,
+    PermissivenessType, Player, PlayerClass, PlayerClassData, StackingPolicy, StatDiffType,
+    UpdateValidForUseIfWarmupPassedOnItemArgs, UseItemArgs, PREFIX,
 };
 use anchor_lang::{
     error,
@@ -18,21 +18,22 @@ use anchor_lang::{
     },
     require,
     solana_program::{
+        hash,
         instruction::Instruction,
         msg,
         program::{invoke, invoke_signed},
-        system_instruction,
     },
-    AnchorDeserialize, AnchorSerialize, Key, ToAccountInfo,
+    AnchorDeserialize, AnchorSerialize, InstructionData, Key, ToAccountInfo,
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use raindrops_item::{
     utils::{
-        assert_derivation, assert_is_ata, assert_keys_equal, assert_metadata_valid, assert_signer,
-        get_item_usage, grab_parent, grab_update_authority, sighash, GetItemUsageArgs,
+        assert_is_ata, assert_keys_equal, assert_metadata_valid, assert_signer, get_item_usage,
+        grab_parent, grab_update_authority, GetItemUsageArgs,
     },
     Item, ItemActivationMarker, ItemClass, ItemUsage,
 };
+use std::convert::TryFrom;
 
 pub fn update_player_class_with_inherited_information(
     player: &mut Account<PlayerClass>,
@@ -177,6 +178,20 @@ pub fn update_player_class_with_inherited_information(
                             overridable: update_perm.overridable,
                         });
                     },
+                    ChildUpdatePropagationPermissivenessType::EquipmentSets => {
+                        player_class_data.config.equipment_sets = propagate_parent_array(PropagateParentArrayArgs {
+                            parent_items: &parent_item_data.config.equipment_sets,
+                            child_items: &player_class_data.config.equipment_sets,
+                            overridable: update_perm.overridable,
+                        });
+                    },
+                    ChildUpdatePropagationPermissivenessType::Backpack => {
+                        player_class_data.config.backpack = propagate_parent(PropagateParentArgs {
+                            parent: &parent_item_data.config.backpack,
+                            child: &player_class_data.config.backpack,
+                            overridable: update_perm.overridable,
+                        });
+                    },
                 }
             }
         }
@@ -259,7 +274,7 @@ pub fn run_item_callback<'b, 'c, 'info>(args: RunItemCallbackArgs<'b, 'c, 'info>
                 program_id: callback.key,
                 accounts: keys,
                 data: AnchorSerialize::try_to_vec(&ItemCallbackArgs {
-                    instruction: raindrops_item::utils::sighash("global", "use_item_callback"),
+                    instruction: sighash("global", "use_item_callback"),
                     extra_identifier: callback.code,
                     amount,
                     class_index,
@@ -350,7 +365,7 @@ pub fn run_item_validation<'a, 'b, 'c, 'info>(
                 program_id: validation.key,
                 accounts: keys,
                 data: AnchorSerialize::try_to_vec(&AddOrRemoveItemValidationArgs {
-                    instruction: raindrops_item::utils::sighash("global", name),
+                    instruction: sighash("global", name),
                     extra_identifier: validation.code,
                     player_mint: *player_mint,
                     item_permissiveness_to_use,
@@ -585,6 +600,87 @@ pub fn verify_item_usage_appropriate_for_body_part<'a, 'b, 'info>(
     Ok(usage_to_check)
 }
 
+pub fn get_item_weight(item_class: &Account<ItemClass>, amount: u64) -> Result<u64> {
+    let config = item_class.item_class_config(&item_class.to_account_info().data)?;
+
+    Ok(config
+        .weight
+        .unwrap_or(0)
+        .checked_mul(amount)
+        .ok_or(ErrorCode::NumericalOverflowError)?)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BackpackLimits {
+    pub max_items: Option<u64>,
+    pub max_weight: Option<u64>,
+}
+
+// Stat bonuses only raise limits the backpack sets, an unset limit stays unlimited.
+pub fn get_backpack_limits(
+    backpack: &Backpack,
+    basic_stats: &Option<Vec<BasicStat>>,
+) -> Result<BackpackLimits> {
+    let mut limits = BackpackLimits {
+        max_items: backpack.max_items,
+        max_weight: backpack.max_weight,
+    };
+
+    if let Some(bss) = basic_stats {
+        for bonus in &backpack.stat_bonuses {
+            let points = match bss.get(bonus.stat_index as usize).map(|bs| &bs.state) {
+                Some(BasicStatState::Integer { finalized, .. }) if *finalized > 0 => {
+                    *finalized as u64
+                }
+                _ => continue,
+            };
+
+            if let Some(max_items) = limits.max_items {
+                limits.max_items = Some(
+                    points
+                        .checked_mul(bonus.items_per_point)
+                        .and_then(|extra| max_items.checked_add(extra))
+                        .ok_or(ErrorCode::NumericalOverflowError)?,
+                );
+            }
+            if let Some(max_weight) = limits.max_weight {
+                limits.max_weight = Some(
+                    points
+                        .checked_mul(bonus.weight_per_point)
+                        .and_then(|extra| max_weight.checked_add(extra))
+                        .ok_or(ErrorCode::NumericalOverflowError)?,
+                );
+            }
+        }
+    }
+
+    Ok(limits)
+}
+
+pub fn assert_backpack_capacity(player_class: &PlayerClass, player: &Player) -> Result<()> {
+    if let Some(backpack) = &player_class.data.config.backpack {
+        let BackpackLimits {
+            max_items,
+            max_weight,
+        } = get_backpack_limits(backpack, &player.data.basic_stats)?;
+
+        if let Some(max_items) = max_items {
+            require!(
+                player.items_in_backpack <= max_items,
+                ErrorCode::BackpackFull
+            );
+        }
+        if let Some(max_weight) = max_weight {
+            require!(
+                player.backpack_weight <= max_weight,
+                ErrorCode::BackpackTooHeavy
+            );
+        }
+    }
+
+    Ok(())
+}
+
 pub struct RunToggleEquipItemValidationArgs<'a, 'b, 'c, 'info> {
     pub item_class: &'b Account<'info, ItemClass>,
     pub item: &'b Account<'info, Item>,
@@ -639,25 +735,9 @@ pub fn run_toggle_equip_item_validation<'a, 'b, 'c, 'info>(
                 program_id: validation.key,
                 accounts: keys,
                 data: AnchorSerialize::try_to_vec(&raindrops_item::ValidationArgs {
-                    instruction: raindrops_item::utils::sighash("global", "item_validation"),
+                    instruction: sighash("global", "item_validation"),
                     extra_identifier: validation.code,
-                    usage_permissiveness_to_use: match permissiveness_to_use {
-                        Some(v) => match v {
-                            PermissivenessType::TokenHolder => {
-                                Some(raindrops_item::PermissivenessType::TokenHolder)
-                            }
-                            PermissivenessType::ParentTokenHolder => {
-                                Some(raindrops_item::PermissivenessType::ParentTokenHolder)
-                            }
-                            PermissivenessType::UpdateAuthority => {
-                                Some(raindrops_item::PermissivenessType::UpdateAuthority)
-                            }
-                            PermissivenessType::Anybody => {
-                                Some(raindrops_item::PermissivenessType::Anybody)
-                            }
-                        },
-                        None => None,
-                    },
+                    usage_permissiveness_to_use: permissiveness_to_use,
                     index: item_index,
                     amount,
                     usage_info: None,
@@ -865,6 +945,116 @@ pub fn toggle_item_to_basic_stats<'b, 'c, 'info>(
         unix_timestamp,
         activated_at,
     } = args;
+
+    let Player {
+        data,
+        active_stat_effects,
+        ..
+    } = &mut **player;
+    if let Some(bies) = basic_item_effects {
+        if let Some(bsts) = &player_class.data.config.basic_stats {
+            if let Some(bss) = &mut data.basic_stats {
+                return apply_basic_item_effects(ApplyBasicItemEffectsArgs {
+                    basic_stat_templates: bsts,
+                    basic_stats: bss,
+                    active_stat_effects,
+                    item_class: item.parent,
+                    tokens_staked: item.tokens_staked,
+                    weighted_tokens_staked: item.weighted_tokens_staked,
+                    basic_item_effects: bies,
+                    amount_change,
+                    adding,
+                    stat_diff_type,
+                    bie_bitmap,
+                    unix_timestamp,
+                    activated_at,
+                });
+            }
+        }
+    }
+    Ok(true)
+}
+
+pub struct ToggleEquipmentSetBonusesArgs<'b, 'info> {
+    pub player: &'b mut Account<'info, Player>,
+    pub player_class: &'b Account<'info, PlayerClass>,
+    pub item_class: Pubkey,
+    pub amount: u64,
+    pub equipping: bool,
+}
+
+pub fn toggle_equipment_set_bonuses<'b, 'info>(
+    args: ToggleEquipmentSetBonusesArgs<'b, 'info>,
+) -> Result<()> {
+    let ToggleEquipmentSetBonusesArgs {
+        player,
+        player_class,
+        item_class,
+        amount,
+        equipping,
+    } = args;
+
+    let Player {
+        data,
+        active_stat_effects,
+        equipped_item_classes,
+        ..
+    } = &mut **player;
+    let equipped_item_classes_before = equipped_item_classes.clone();
+    update_equipped_item_classes(equipped_item_classes, item_class, amount, equipping)?;
+
+    if let Some(sets) = &player_class.data.config.equipment_sets {
+        if let Some(bsts) = &player_class.data.config.basic_stats {
+            if let Some(bss) = &mut data.basic_stats {
+                apply_equipment_set_bonuses(ApplyEquipmentSetBonusesArgs {
+                    equipment_sets: sets,
+                    basic_stat_templates: bsts,
+                    basic_stats: bss,
+                    active_stat_effects,
+                    equipped_item_classes_before: &equipped_item_classes_before,
+                    equipped_item_classes_after: equipped_item_classes,
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub struct ApplyBasicItemEffectsArgs<'c> {
+    pub basic_stat_templates: &'c [BasicStatTemplate],
+    pub basic_stats: &'c mut [BasicStat],
+    pub active_stat_effects: &'c mut Vec<ActiveStatEffect>,
+    pub item_class: Pubkey,
+    pub tokens_staked: u64,
+    pub weighted_tokens_staked: u64,
+    pub basic_item_effects: &'c [BasicItemEffect],
+    pub amount_change: u64,
+    pub adding: bool,
+    pub stat_diff_type: StatDiffType,
+    pub bie_bitmap: &'c mut Option<Vec<u8>>,
+    pub unix_timestamp: i64,
+    pub activated_at: i64,
+}
+
+/// The stat math behind toggle_item_to_basic_stats, free of any accounts so
+/// it can also be run off chain. Returns false while a timed effect still has
+/// to wait before it can be removed.
+pub fn apply_basic_item_effects(args: ApplyBasicItemEffectsArgs) -> Result<bool> {
+    let ApplyBasicItemEffectsArgs {
+        basic_stat_templates,
+        basic_stats,
+        active_stat_effects,
+        item_class,
+        tokens_staked,
+        weighted_tokens_staked,
+        basic_item_effects,
+        amount_change,
+        adding,
+        stat_diff_type,
+        bie_bitmap,
+        unix_timestamp,
+        activated_at,
+    } = args;
     // for an item without active duration, is permanent increase
     // for an equipment, no active duration, is temporary until removal
     // for item with active duration, is by definition an NFT, so wont be more than one
@@ -875,63 +1065,65 @@ pub fn toggle_item_to_basic_stats<'b, 'c, 'info>(
     // therefore, this works.
 
     let mut no_more_waiting = true;
-    if let Some(bies) = basic_item_effects {
-        if let Some(bsts) = &player_class.data.config.basic_stats {
-            if let Some(bss) = &mut player.data.basic_stats {
-                for bst in bsts {
-                    // guaranteed to be at this index due to way player is created or updated
+    for bst in basic_stat_templates {
+        // guaranteed to be at this index due to way player is created or updated
+
+        let bs = &mut basic_stats[bst.index as usize];
+        for (i, bie) in basic_item_effects.iter().enumerate() {
+            if bie.stat == bst.name {
+                let modded_amount = get_modded_amount_given_tokens_staked_on_item(
+                    GetModdedAmountGivenTokensStakedOnItemArgs {
+                        amount: amount_change,
+                        tokens_staked,
+                        weighted_tokens_staked,
+                        bie,
+                        adding,
+                    },
+                )?;
 
-                    let bs = &mut bss[bst.index as usize];
-                    let mut i: usize = 0;
-                    for bie in bies {
-                        if bie.stat == bst.name {
-                            let modded_amount = get_modded_amount_given_tokens_staked_on_item(
-                                GetModdedAmountGivenTokensStakedOnItemArgs {
-                                    amount: amount_change,
-                                    item,
+                if bie.active_duration.is_none() && stat_diff_type == StatDiffType::Consumable {
+                    if adding {
+                        rebalance_stat_permanently(RebalanceStatPermanentlyArgs {
+                            bie,
+                            bs,
+                            bst,
+                            modded_amount,
+                            overrides: get_stat_overrides(active_stat_effects, bst.index),
+                        })?;
+                    } // there is no removing a consumable with no active duration, it is permanent...
+                } else {
+                    let tracked_effect = build_active_stat_effect(BuildActiveStatEffectArgs {
+                        item_class,
+                        tokens_staked,
+                        weighted_tokens_staked,
+                        bie,
+                        bst,
+                        count: amount_change,
+                    })?;
+                    let stat_effect = ToggleStatEffectArgs {
+                        active_stat_effects,
+                        tracked_effect,
+                        bs,
+                        bst,
+                        item_effect_type: bie.item_effect_type,
+                        modded_amount,
+                        adding,
+                    };
+                    if !adding && stat_diff_type == StatDiffType::Consumable {
+                        no_more_waiting = no_more_waiting
+                            && rebalance_stat_for_consumable_with_duration(
+                                RebalanceStatForConsumableWithDurationArgs {
+                                    bie_bitmap,
+                                    unix_timestamp,
                                     bie,
-                                    adding,
+                                    stat_effect,
+                                    tokens_staked,
+                                    i,
+                                    activated_at,
                                 },
-                            )?;
-
-                            if bie.active_duration.is_none()
-                                && stat_diff_type == StatDiffType::Consumable
-                            {
-                                if adding {
-                                    rebalance_stat_permanently(RebalanceStatPermanentlyArgs {
-                                        bie,
-                                        bs,
-                                        bst,
-                                        modded_amount,
-                                    })?;
-                                } // there is no removing a consumable with no active duration, it is permanent...
-                            } else if !adding && stat_diff_type == StatDiffType::Consumable {
-                                no_more_waiting = no_more_waiting
-                                    && rebalance_stat_for_consumable_with_duration(
-                                        RebalanceStatForConsumableWithDurationArgs {
-                                            bie_bitmap,
-                                            unix_timestamp,
-                                            bs,
-                                            bie,
-                                            bst,
-                                            modded_amount,
-                                            adding,
-                                            item,
-                                            i,
-                                            activated_at,
-                                        },
-                                    )?
-                            } else {
-                                rebalance_stat_temporarily(RebalanceStatTemporarilyArgs {
-                                    bie,
-                                    bs,
-                                    bst,
-                                    modded_amount,
-                                    adding,
-                                })?;
-                            }
-                        }
-                        i += 1;
+                            )?
+                    } else {
+                        toggle_stat_effect(stat_effect)?;
                     }
                 }
             }
@@ -940,31 +1132,25 @@ pub fn toggle_item_to_basic_stats<'b, 'c, 'info>(
     Ok(no_more_waiting)
 }
 
-struct RebalanceStatForConsumableWithDurationArgs<'b, 'c, 'info> {
+struct RebalanceStatForConsumableWithDurationArgs<'c> {
     pub bie_bitmap: &'c mut Option<Vec<u8>>,
     pub unix_timestamp: i64,
-    pub bs: &'c mut BasicStat,
     pub bie: &'c BasicItemEffect,
-    pub bst: &'c BasicStatTemplate,
-    pub modded_amount: i64,
-    pub adding: bool,
-    pub item: &'b Account<'info, Item>,
+    pub stat_effect: ToggleStatEffectArgs<'c>,
+    pub tokens_staked: u64,
     pub i: usize,
     pub activated_at: i64,
 }
 
-fn rebalance_stat_for_consumable_with_duration<'b, 'c, 'info>(
-    args: RebalanceStatForConsumableWithDurationArgs<'b, 'c, 'info>,
+fn rebalance_stat_for_consumable_with_duration(
+    args: RebalanceStatForConsumableWithDurationArgs,
 ) -> Result<bool> {
     let RebalanceStatForConsumableWithDurationArgs {
         bie_bitmap,
         unix_timestamp,
-        bs,
         bie,
-        bst,
-        modded_amount,
-        adding,
-        item,
+        stat_effect,
+        tokens_staked,
         i,
         activated_at,
     } = args;
@@ -978,7 +1164,7 @@ fn rebalance_stat_for_consumable_with_duration<'b, 'c, 'info>(
             if applied_mask == 0 {
                 // ok it has not been taken, we can remove IF the date is right. do that next.
                 if let Some(active) = bie.active_duration {
-                    let modded_duration = get_modded_duration(active, item, bie)?;
+                    let modded_duration = get_modded_duration(active, tokens_staked, bie)?;
                     msg!(
                         "Unix ts is {:?}, act_at is {:?}, check is is {:?}",
                         unix_timestamp,
@@ -991,13 +1177,7 @@ fn rebalance_stat_for_consumable_with_duration<'b, 'c, 'info>(
                             .ok_or(ErrorCode::NumericalOverflowError)?
                     {
                         arr[index_in_bie] |= mask;
-                        rebalance_stat_temporarily(RebalanceStatTemporarilyArgs {
-                            bie,
-                            bs,
-                            bst,
-                            modded_amount,
-                            adding,
-                        })?;
+                        toggle_stat_effect(stat_effect)?;
                     } else {
                         return Ok(false);
                     }
@@ -1011,15 +1191,11 @@ fn rebalance_stat_for_consumable_with_duration<'b, 'c, 'info>(
     Ok(true)
 }
 
-pub fn get_modded_duration(
-    active: u64,
-    item: &Account<Item>,
-    bie: &BasicItemEffect,
-) -> Result<i64> {
+pub fn get_modded_duration(active: u64, tokens_staked: u64, bie: &BasicItemEffect) -> Result<i64> {
     let mut modded_duration = active;
 
-    if item.tokens_staked > 0 {
-        let mut to_add: u64 = item.tokens_staked;
+    if tokens_staked > 0 {
+        let mut to_add: u64 = tokens_staked;
         if let Some(san) = bie.staking_duration_numerator {
             to_add = to_add
                 .checked_mul(san)
@@ -1050,6 +1226,7 @@ pub struct RebalanceStatPermanentlyArgs<'a> {
     pub bs: &'a mut BasicStat,
     pub bst: &'a BasicStatTemplate,
     pub modded_amount: i64,
+    pub overrides: StatOverrides,
 }
 
 pub fn rebalance_stat_permanently(args: RebalanceStatPermanentlyArgs) -> Result<()> {
@@ -1058,6 +1235,7 @@ pub fn rebalance_stat_permanently(args: RebalanceStatPermanentlyArgs) -> Result<
         bs,
         bst,
         modded_amount,
+        overrides,
     } = args;
     match bie.item_effect_type {
         BasicItemEffectType::Increment | BasicItemEffectType::Decrement => {
@@ -1070,6 +1248,7 @@ pub fn rebalance_stat_permanently(args: RebalanceStatPermanentlyArgs) -> Result<
                 new_numerator: 1,
                 remove_numerator: None,
                 remove_denominator: None,
+                overrides,
             })?
         }
         BasicItemEffectType::IncrementPercent | BasicItemEffectType::DecrementPercent => {
@@ -1094,6 +1273,7 @@ pub fn rebalance_stat_permanently(args: RebalanceStatPermanentlyArgs) -> Result<
                         new_numerator: 1,
                         remove_numerator: None,
                         remove_denominator: None,
+                        overrides,
                     })?
                 }
                 _ => return Err(ErrorCode::CannotAlterThisTypeNumerically.into()),
@@ -1120,30 +1300,62 @@ pub fn rebalance_stat_permanently(args: RebalanceStatPermanentlyArgs) -> Result<
                 new_numerator: 1,
                 remove_numerator: None,
                 remove_denominator: None,
+                overrides,
             })?,
             _ => return Err(ErrorCode::CannotAlterThisTypeNumerically.into()),
         },
+        // a consumable with no duration writes the value into the base for good
+        BasicItemEffectType::Set | BasicItemEffectType::Clamp => match bs.state {
+            BasicStatState::Integer { base, .. } => {
+                let mut new_base = base;
+                if bie.item_effect_type == BasicItemEffectType::Set {
+                    new_base = bie.amount as i64;
+                }
+                if let Some(m) = bie.clamp_max {
+                    new_base = std::cmp::min(m, new_base);
+                }
+                if let Some(m) = bie.clamp_min {
+                    new_base = std::cmp::max(m, new_base);
+                }
+                rebalance_basic_stat(RebalanceBasicStatArgs {
+                    basic_stat: bs,
+                    basic_stat_template: bst,
+                    base_change: new_base
+                        .checked_sub(base)
+                        .ok_or(ErrorCode::NumericalOverflowError)?,
+                    temp_change: 0,
+                    new_denominator: 1,
+                    new_numerator: 1,
+                    remove_numerator: None,
+                    remove_denominator: None,
+                    overrides,
+                })?
+            }
+            _ => return Err(ErrorCode::CannotAlterThisTypeNumerically.into()),
+        },
     }
     Ok(())
 }
 
 pub struct RebalanceStatTemporarilyArgs<'a> {
-    pub bie: &'a BasicItemEffect,
+    pub item_effect_type: BasicItemEffectType,
     pub bs: &'a mut BasicStat,
     pub bst: &'a BasicStatTemplate,
     pub modded_amount: i64,
     pub adding: bool,
+    pub overrides: StatOverrides,
 }
 
 pub fn rebalance_stat_temporarily(args: RebalanceStatTemporarilyArgs) -> Result<()> {
     let RebalanceStatTemporarilyArgs {
-        bie,
+        item_effect_type,
         bs,
         bst,
         modded_amount,
         adding,
+        overrides,
     } = args;
-    match bie.item_effect_type {
+    match item_effect_type {
         BasicItemEffectType::Increment | BasicItemEffectType::Decrement => {
             rebalance_basic_stat(RebalanceBasicStatArgs {
                 basic_stat: bs,
@@ -1154,6 +1366,7 @@ pub fn rebalance_stat_temporarily(args: RebalanceStatTemporarilyArgs) -> Result<
                 new_numerator: 1,
                 remove_numerator: None,
                 remove_denominator: None,
+                overrides,
             })?
         }
         BasicItemEffectType::IncrementPercent | BasicItemEffectType::DecrementPercent => {
@@ -1188,6 +1401,7 @@ pub fn rebalance_stat_temporarily(args: RebalanceStatTemporarilyArgs) -> Result<
                     )
                 },
                 remove_denominator: if adding { None } else { Some(100) },
+                overrides,
             })?
         }
         BasicItemEffectType::IncrementPercentFromBase
@@ -1205,23 +1419,325 @@ pub fn rebalance_stat_temporarily(args: RebalanceStatTemporarilyArgs) -> Result<
                 new_numerator: 1,
                 remove_numerator: None,
                 remove_denominator: None,
+                overrides,
             })?,
             _ => return Err(ErrorCode::CannotAlterThisTypeNumerically.into()),
         },
+        // nothing to add, these only act through the overrides
+        BasicItemEffectType::Set | BasicItemEffectType::Clamp => {
+            rebalance_basic_stat(RebalanceBasicStatArgs {
+                basic_stat: bs,
+                basic_stat_template: bst,
+                base_change: 0,
+                temp_change: 0,
+                new_denominator: 1,
+                new_numerator: 1,
+                remove_numerator: None,
+                remove_denominator: None,
+                overrides,
+            })?
+        }
     }
 
     Ok(())
 }
 
+/// Set and Clamp effects active on a stat. They are applied on top of the
+/// finalized value, so removing the effect gives back the value underneath.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StatOverrides {
+    pub set: Option<i64>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+/// A later Set replaces an earlier one, except that a HighestWins Set only
+/// replaces a lower value. Clamps narrow each other.
+pub fn get_stat_overrides(
+    active_stat_effects: &[ActiveStatEffect],
+    stat_index: u16,
+) -> StatOverrides {
+    let mut overrides = StatOverrides::default();
+    for effect in active_stat_effects {
+        if effect.stat_index != stat_index {
+            continue;
+        }
+        match effect.item_effect_type {
+            BasicItemEffectType::Set => {
+                overrides.set = Some(match (overrides.set, effect.stacking_policy) {
+                    (Some(s), StackingPolicy::HighestWins) => std::cmp::max(s, effect.amount),
+                    _ => effect.amount,
+                })
+            }
+            BasicItemEffectType::Clamp => {
+                if let Some(m) = effect.clamp_min {
+                    overrides.min = Some(overrides.min.map_or(m, |o| std::cmp::max(o, m)));
+                }
+                if let Some(m) = effect.clamp_max {
+                    overrides.max = Some(overrides.max.map_or(m, |o| std::cmp::min(o, m)));
+                }
+            }
+            _ => {}
+        }
+    }
+    overrides
+}
+
+fn is_numeric_effect(item_effect_type: BasicItemEffectType) -> bool {
+    !matches!(
+        item_effect_type,
+        BasicItemEffectType::Set | BasicItemEffectType::Clamp
+    )
+}
+
+// the HighestWins numeric effect currently applied to the stat
+fn get_highest_wins_effect(
+    active_stat_effects: &[ActiveStatEffect],
+    stat_index: u16,
+) -> Option<ActiveStatEffect> {
+    active_stat_effects
+        .iter()
+        .filter(|e| {
+            e.stat_index == stat_index
+                && e.stacking_policy == StackingPolicy::HighestWins
+                && is_numeric_effect(e.item_effect_type)
+        })
+        .max_by_key(|e| e.amount.unsigned_abs())
+        .cloned()
+}
+
+impl ActiveStatEffect {
+    fn is_same_effect(&self, other: &ActiveStatEffect) -> bool {
+        self.stat_index == other.stat_index
+            && self.item_class == other.item_class
+            && self.item_effect_type == other.item_effect_type
+            && self.stacking_policy == other.stacking_policy
+            && self.amount == other.amount
+            && self.clamp_min == other.clamp_min
+            && self.clamp_max == other.clamp_max
+    }
+}
+
+pub struct BuildActiveStatEffectArgs<'a> {
+    pub item_class: Pubkey,
+    pub tokens_staked: u64,
+    pub weighted_tokens_staked: u64,
+    pub bie: &'a BasicItemEffect,
+    pub bst: &'a BasicStatTemplate,
+    pub count: u64,
+}
+
+/// None for plain stacking numeric effects, which are added straight onto
+/// the stat and need no record on the player.
+pub fn build_active_stat_effect(
+    args: BuildActiveStatEffectArgs,
+) -> Result<Option<ActiveStatEffect>> {
+    let BuildActiveStatEffectArgs {
+        item_class,
+        tokens_staked,
+        weighted_tokens_staked,
+        bie,
+        bst,
+        count,
+    } = args;
+
+    let stacking_policy = bie.stacking_policy.unwrap_or(StackingPolicy::Stack);
+    if is_numeric_effect(bie.item_effect_type) && stacking_policy == StackingPolicy::Stack {
+        return Ok(None);
+    }
+
+    // these do not stack, so each application is worth a single unit
+    let amount = if is_numeric_effect(bie.item_effect_type) {
+        get_modded_amount_given_tokens_staked_on_item(GetModdedAmountGivenTokensStakedOnItemArgs {
+            amount: 1,
+            tokens_staked,
+            weighted_tokens_staked,
+            bie,
+            adding: true,
+        })?
+    } else {
+        bie.amount as i64
+    };
+
+    Ok(Some(ActiveStatEffect {
+        stat_index: bst.index,
+        item_class,
+        item_effect_type: bie.item_effect_type,
+        stacking_policy,
+        amount,
+        clamp_min: bie.clamp_min,
+        clamp_max: bie.clamp_max,
+        count,
+    }))
+}
+
+pub struct ToggleStatEffectArgs<'a> {
+    pub active_stat_effects: &'a mut Vec<ActiveStatEffect>,
+    pub tracked_effect: Option<ActiveStatEffect>,
+    pub bs: &'a mut BasicStat,
+    pub bst: &'a BasicStatTemplate,
+    pub item_effect_type: BasicItemEffectType,
+    pub modded_amount: i64,
+    pub adding: bool,
+}
+
+pub fn toggle_stat_effect(args: ToggleStatEffectArgs) -> Result<()> {
+    let ToggleStatEffectArgs {
+        active_stat_effects,
+        tracked_effect,
+        bs,
+        bst,
+        item_effect_type,
+        modded_amount,
+        adding,
+    } = args;
+
+    match tracked_effect {
+        Some(effect) => rebalance_stat_for_tracked_effect(RebalanceStatForTrackedEffectArgs {
+            active_stat_effects,
+            effect,
+            bs,
+            bst,
+            adding,
+        }),
+        None => rebalance_stat_temporarily(RebalanceStatTemporarilyArgs {
+            item_effect_type,
+            bs,
+            bst,
+            modded_amount,
+            adding,
+            overrides: get_stat_overrides(active_stat_effects, bst.index),
+        }),
+    }
+}
+
+pub struct RebalanceStatForTrackedEffectArgs<'a> {
+    pub active_stat_effects: &'a mut Vec<ActiveStatEffect>,
+    pub effect: ActiveStatEffect,
+    pub bs: &'a mut BasicStat,
+    pub bst: &'a BasicStatTemplate,
+    pub adding: bool,
+}
+
+/// Records or drops an effect on the player and moves the stat to match.
+/// RefreshDuration effects change the stat on their first application and
+/// when their last one is removed, HighestWins effects only while they are the
+/// largest on the stat, and Set and Clamp effects through the overrides.
+pub fn rebalance_stat_for_tracked_effect(args: RebalanceStatForTrackedEffectArgs) -> Result<()> {
+    let RebalanceStatForTrackedEffectArgs {
+        active_stat_effects,
+        effect,
+        bs,
+        bst,
+        adding,
+    } = args;
+
+    let winner_before = get_highest_wins_effect(active_stat_effects, bst.index);
+    let position = active_stat_effects
+        .iter()
+        .position(|e| e.is_same_effect(&effect));
+
+    // numeric effect that comes or goes with this change, outside of HighestWins
+    let mut changed: Option<ActiveStatEffect> = None;
+    if adding {
+        match position {
+            Some(p) => {
+                active_stat_effects[p].count = active_stat_effects[p]
+                    .count
+                    .checked_add(effect.count)
+                    .ok_or(ErrorCode::NumericalOverflowError)?;
+            }
+            None => {
+                if is_numeric_effect(effect.item_effect_type)
+                    && effect.stacking_policy != StackingPolicy::HighestWins
+                {
+                    changed = Some(effect.clone());
+                }
+                active_stat_effects.push(effect);
+            }
+        }
+    } else if let Some(p) = position {
+        active_stat_effects[p].count = active_stat_effects[p].count.saturating_sub(effect.count);
+        if active_stat_effects[p].count == 0 {
+            let removed = active_stat_effects.remove(p);
+            if is_numeric_effect(removed.item_effect_type)
+                && removed.stacking_policy != StackingPolicy::HighestWins
+            {
+                changed = Some(removed);
+            }
+        }
+    } // nothing recorded means nothing was applied, so there is nothing to take back
+
+    let winner_after = get_highest_wins_effect(active_stat_effects, bst.index);
+    let overrides = get_stat_overrides(active_stat_effects, bst.index);
+
+    let mut to_remove = vec![];
+    let mut to_add = vec![];
+    if let Some(c) = changed {
+        if adding {
+            to_add.push(c);
+        } else {
+            to_remove.push(c);
+        }
+    }
+    let winner_changed = match (&winner_before, &winner_after) {
+        (Some(b), Some(a)) => !b.is_same_effect(a),
+        (None, None) => false,
+        _ => true,
+    };
+    if winner_changed {
+        to_remove.extend(winner_before);
+        to_add.extend(winner_after);
+    }
+
+    for e in to_remove {
+        rebalance_stat_temporarily(RebalanceStatTemporarilyArgs {
+            item_effect_type: e.item_effect_type,
+            bs,
+            bst,
+            modded_amount: e
+                .amount
+                .checked_mul(-1)
+                .ok_or(ErrorCode::NumericalOverflowError)?,
+            adding: false,
+            overrides,
+        })?;
+    }
+    for e in to_add {
+        rebalance_stat_temporarily(RebalanceStatTemporarilyArgs {
+            item_effect_type: e.item_effect_type,
+            bs,
+            bst,
+            modded_amount: e.amount,
+            adding: true,
+            overrides,
+        })?;
+    }
+
+    // refinalize in case only the overrides moved
+    rebalance_basic_stat(RebalanceBasicStatArgs {
+        basic_stat: bs,
+        basic_stat_template: bst,
+        base_change: 0,
+        temp_change: 0,
+        new_denominator: 1,
+        new_numerator: 1,
+        remove_numerator: None,
+        remove_denominator: None,
+        overrides,
+    })
+}
+
 pub struct RebalanceBasicStatArgs<'a> {
-    basic_stat: &'a mut BasicStat,
-    basic_stat_template: &'a BasicStatTemplate,
-    base_change: i64,
-    temp_change: i64,
-    new_numerator: i64,
-    new_denominator: i64,
-    remove_numerator: Option<i64>,
-    remove_denominator: Option<i64>,
+    pub basic_stat: &'a mut BasicStat,
+    pub basic_stat_template: &'a BasicStatTemplate,
+    pub base_change: i64,
+    pub temp_change: i64,
+    pub new_numerator: i64,
+    pub new_denominator: i64,
+    pub remove_numerator: Option<i64>,
+    pub remove_denominator: Option<i64>,
+    pub overrides: StatOverrides,
 }
 pub fn rebalance_basic_stat(args: RebalanceBasicStatArgs) -> Result<()> {
     let RebalanceBasicStatArgs {
@@ -1233,6 +1749,7 @@ pub fn rebalance_basic_stat(args: RebalanceBasicStatArgs) -> Result<()> {
         new_denominator,
         remove_numerator,
         remove_denominator,
+        overrides,
     } = args;
     match basic_stat.state {
         BasicStatState::Integer {
@@ -1301,6 +1818,18 @@ pub fn rebalance_basic_stat(args: RebalanceBasicStatArgs) -> Result<()> {
                         .checked_div(new_temporary_denominator)
                         .ok_or(ErrorCode::NumericalOverflowError)?;
 
+                    if let Some(s) = overrides.set {
+                        new_finalized = s;
+                    }
+
+                    if let Some(m) = overrides.max {
+                        new_finalized = std::cmp::min(m, new_finalized);
+                    }
+
+                    if let Some(m) = overrides.min {
+                        new_finalized = std::cmp::max(m, new_finalized);
+                    }
+
                     if let Some(m) = max {
                         new_finalized = std::cmp::min(m, new_finalized);
                     }
@@ -1330,16 +1859,193 @@ pub fn rebalance_basic_stat(args: RebalanceBasicStatArgs) -> Result<()> {
     Ok(())
 }
 
+pub fn update_equipped_item_classes(
+    equipped_item_classes: &mut Vec<EquippedItemClass>,
+    item_class: Pubkey,
+    amount: u64,
+    equipping: bool,
+) -> Result<()> {
+    match equipped_item_classes
+        .iter()
+        .position(|eic| eic.item_class == item_class)
+    {
+        Some(i) => {
+            let eic = &mut equipped_item_classes[i];
+            eic.amount = if equipping {
+                eic.amount
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::NumericalOverflowError)?
+            } else {
+                // items equipped before classes were tracked were never counted
+                eic.amount.saturating_sub(amount)
+            };
+            if eic.amount == 0 {
+                equipped_item_classes.remove(i);
+            }
+        }
+        None => {
+            if equipping {
+                equipped_item_classes.push(EquippedItemClass { item_class, amount });
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn add_backpack_item(
+    backpack_items: &mut Vec<BackpackItem>,
+    item: Pubkey,
+    amount: u64,
+    weight: u64,
+) -> Result<()> {
+    match backpack_items.iter_mut().find(|bi| bi.item == item) {
+        Some(bi) => {
+            bi.amount = bi
+                .amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+            bi.weight = bi
+                .weight
+                .checked_add(weight)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
+        None => backpack_items.push(BackpackItem {
+            item,
+            amount,
+            weight,
+        }),
+    }
+    Ok(())
+}
+
+// Returns the weight added for amount of the item. Items added before their
+// weight was recorded never added any.
+pub fn remove_backpack_item(
+    backpack_items: &mut Vec<BackpackItem>,
+    item: Pubkey,
+    amount: u64,
+) -> Result<u64> {
+    let i = match backpack_items.iter().position(|bi| bi.item == item) {
+        Some(i) => i,
+        None => return Ok(0),
+    };
+    let bi = &mut backpack_items[i];
+
+    if amount >= bi.amount {
+        let weight = bi.weight;
+        backpack_items.remove(i);
+        return Ok(weight);
+    }
+
+    // amounts added at different class weights are averaged over
+    let weight = (bi.weight as u128)
+        .checked_mul(amount as u128)
+        .and_then(|w| w.checked_div(bi.amount as u128))
+        .and_then(|w| u64::try_from(w).ok())
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+    bi.amount -= amount;
+    bi.weight -= weight;
+
+    Ok(weight)
+}
+
+pub fn count_equipment_set_pieces(
+    equipment_set: &EquipmentSet,
+    equipped_item_classes: &[EquippedItemClass],
+) -> usize {
+    equipment_set
+        .item_classes
+        .iter()
+        .filter(|item_class| {
+            equipped_item_classes
+                .iter()
+                .any(|eic| &eic.item_class == *item_class)
+        })
+        .count()
+}
+
+// Set bonuses are tracked in active_stat_effects like item effects, under an item
+// class key made up from the set and threshold.
+pub fn get_equipment_set_bonus_key(set_index: u16, pieces: u16) -> Pubkey {
+    Pubkey::new_from_array(
+        hash::hashv(&[
+            b"equipment_set",
+            &set_index.to_le_bytes(),
+            &pieces.to_le_bytes(),
+        ])
+        .to_bytes(),
+    )
+}
+
+pub struct ApplyEquipmentSetBonusesArgs<'c> {
+    pub equipment_sets: &'c [EquipmentSet],
+    pub basic_stat_templates: &'c [BasicStatTemplate],
+    pub basic_stats: &'c mut [BasicStat],
+    pub active_stat_effects: &'c mut Vec<ActiveStatEffect>,
+    pub equipped_item_classes_before: &'c [EquippedItemClass],
+    pub equipped_item_classes_after: &'c [EquippedItemClass],
+}
+
+/// Adds the bonuses whose thresholds were just reached and removes the ones
+/// that were just lost, the same way wearables change stats.
+pub fn apply_equipment_set_bonuses(args: ApplyEquipmentSetBonusesArgs) -> Result<()> {
+    let ApplyEquipmentSetBonusesArgs {
+        equipment_sets,
+        basic_stat_templates,
+        basic_stats,
+        active_stat_effects,
+        equipped_item_classes_before,
+        equipped_item_classes_after,
+    } = args;
+
+    for set in equipment_sets {
+        let pieces_before = count_equipment_set_pieces(set, equipped_item_classes_before);
+        let pieces_after = count_equipment_set_pieces(set, equipped_item_classes_after);
+        if pieces_before == pieces_after {
+            continue;
+        }
+
+        for bonus in &set.bonuses {
+            let was_active = pieces_before >= bonus.pieces as usize;
+            let is_active = pieces_after >= bonus.pieces as usize;
+            if was_active != is_active {
+                apply_basic_item_effects(ApplyBasicItemEffectsArgs {
+                    basic_stat_templates,
+                    basic_stats,
+                    active_stat_effects,
+                    item_class: get_equipment_set_bonus_key(set.index, bonus.pieces),
+                    tokens_staked: 0,
+                    weighted_tokens_staked: 0,
+                    basic_item_effects: &bonus.basic_item_effects,
+                    amount_change: 1,
+                    adding: is_active,
+                    stat_diff_type: StatDiffType::Wearable,
+                    bie_bitmap: &mut None,
+                    unix_timestamp: 0,
+                    activated_at: 0,
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn map_new_stats_into_player(
     player_class: &Account<PlayerClass>,
     player: &mut Account<Player>,
     new_stats: &Option<Vec<BasicStat>>,
 ) -> Result<()> {
+    let Player {
+        data,
+        active_stat_effects,
+        ..
+    } = &mut **player;
     if let Some(bsts) = &player_class.data.config.basic_stats {
-        if let Some(bss) = &mut player.data.basic_stats {
+        if let Some(bss) = &mut data.basic_stats {
             if let Some(new_bss) = new_stats {
                 for bst in bsts {
                     let bs = &mut bss[bst.index as usize];
+                    let overrides = get_stat_overrides(active_stat_effects, bst.index);
 
                     for new_bs in new_bss {
                         if new_bs.index == bs.index {
@@ -1358,6 +2064,7 @@ pub fn map_new_stats_into_player(
                                                 new_numerator: 1,
                                                 remove_numerator: None,
                                                 remove_denominator: None,
+                                                overrides,
                                             })?;
                                         }
                                         _ => {
@@ -1381,18 +2088,21 @@ pub fn map_new_stats_into_player(
     Ok(())
 }
 
-pub struct GetModdedAmountGivenTokensStakedOnItemArgs<'a, 'b, 'info> {
+pub struct GetModdedAmountGivenTokensStakedOnItemArgs<'a> {
     pub amount: u64,
-    pub item: &'b Account<'info, Item>,
+    pub tokens_staked: u64,
+    // tokens_staked with staking lockup multipliers applied
+    pub weighted_tokens_staked: u64,
     pub bie: &'a BasicItemEffect,
     pub adding: bool,
 }
-pub fn get_modded_amount_given_tokens_staked_on_item<'a, 'b, 'info>(
-    args: GetModdedAmountGivenTokensStakedOnItemArgs<'a, 'b, 'info>,
+pub fn get_modded_amount_given_tokens_staked_on_item(
+    args: GetModdedAmountGivenTokensStakedOnItemArgs,
 ) -> Result<i64> {
     let GetModdedAmountGivenTokensStakedOnItemArgs {
         amount,
-        item,
+        tokens_staked,
+        weighted_tokens_staked,
         bie,
         adding,
     } = args;
@@ -1401,8 +2111,12 @@ pub fn get_modded_amount_given_tokens_staked_on_item<'a, 'b, 'info>(
         .checked_mul(bie.amount as i64)
         .ok_or(ErrorCode::NumericalOverflowError)?;
 
-    if item.tokens_staked > 0 {
-        let mut to_add: u64 = item.tokens_staked;
+    // multipliers are never below 1x. the staking program counts stake made
+    // before lockup tiers at 1x the next time the item's stake changes, until
+    // then none of it is weighted
+    let tokens_staked = tokens_staked.max(weighted_tokens_staked);
+    if tokens_staked > 0 {
+        let mut to_add: u64 = tokens_staked;
         if let Some(san) = bie.staking_amount_numerator {
             to_add = to_add
                 .checked_mul(san)
@@ -1516,9 +2230,8 @@ pub fn end_item_activation<'b, 'c, 'info>(
         &Instruction {
             program_id: item_program.key(),
             accounts: keys,
-            data: AnchorSerialize::try_to_vec(
-                &CopyEndItemActivationBecauseAnchorSucksSometimesArgs {
-                    instruction: sighash("global", "end_item_activation"),
+            data: raindrops_item::instruction::EndItemActivation {
+                args: raindrops_item::EndItemActivationArgs {
                     item_class_mint: *item_class_mint,
                     usage_permissiveness_to_use,
                     usage_index: item_usage_index,
@@ -1527,7 +2240,8 @@ pub fn end_item_activation<'b, 'c, 'info>(
                     amount,
                     usage_info,
                 },
-            )?,
+            }
+            .data(),
         },
         &account_infos,
         &[&[
@@ -1622,9 +2336,8 @@ pub fn begin_item_activation<'b, 'c, 'info>(
         &Instruction {
             program_id: item_program.key(),
             accounts: keys,
-            data: AnchorSerialize::try_to_vec(
-                &CopyBeginItemActivationBecauseAnchorSucksSometimesArgs {
-                    instruction: sighash("global", "begin_item_activation"),
+            data: raindrops_item::instruction::BeginItemActivation {
+                args: raindrops_item::BeginItemActivationArgs {
                     item_class_mint,
                     item_mint,
                     usage_permissiveness_to_use: use_item_permissiveness_to_use,
@@ -1640,7 +2353,8 @@ pub fn begin_item_activation<'b, 'c, 'info>(
                         None
                     },
                 },
-            )?,
+            }
+            .data(),
         },
         &account_infos,
         &[&[
@@ -1710,9 +2424,8 @@ pub fn update_valid_for_use_if_warmup_passed<'b, 'c, 'info>(
         &Instruction {
             program_id: item_program.key(),
             accounts: keys,
-            data: AnchorSerialize::try_to_vec(
-                &CopyUpdateValidForUseIfWarmupPassedBecauseAnchorSucksSometimesArgs {
-                    instruction: sighash("global", "update_valid_for_use_if_warmup_passed"),
+            data: raindrops_item::instruction::UpdateValidForUseIfWarmupPassed {
+                args: raindrops_item::UpdateValidForUseIfWarmupPassedArgs {
                     item_class_mint,
                     usage_index: item_usage_index,
                     index: item_index,
@@ -1726,7 +2439,8 @@ pub fn update_valid_for_use_if_warmup_passed<'b, 'c, 'info>(
                         None
                     },
                 },
-            )?,
+            }
+            .data(),
         },
         &account_infos,
         &[&[
@@ -1778,6 +2492,34 @@ pub fn assert_index_and_name_uniqueness_in_body_parts(
     Ok(())
 }
 
+pub fn assert_valid_equipment_sets(equipment_sets: &Option<Vec<EquipmentSet>>) -> Result<()> {
+    if let Some(sets) = equipment_sets {
+        let mut indices = vec![];
+        let mut names = vec![];
+        for set in sets {
+            indices.push(set.index);
+            names.push(&set.name);
+
+            for (i, item_class) in set.item_classes.iter().enumerate() {
+                require!(
+                    !set.item_classes[i + 1..].contains(item_class),
+                    ErrorCode::DuplicateEquipmentSetItemClass
+                );
+            }
+            for bonus in &set.bonuses {
+                require!(
+                    bonus.pieces > 0 && bonus.pieces as usize <= set.item_classes.len(),
+                    ErrorCode::InvalidEquipmentSetBonus
+                );
+            }
+        }
+
+        assert_index_uniqueness(indices)?;
+        assert_name_uniqueness(names)?;
+    }
+    Ok(())
+}
+
 pub fn assert_index_uniqueness(stats: Vec<u16>) -> Result<()> {
     let mut indices_seen: Vec<u8> = vec![];
     for stat in &stats {
@@ -1871,6 +2613,7 @@ pub fn assert_permissiveness_access(args: AssertPermissivenessAccessArgs) -> Res
                             program_id,
                             given_account,
                             &[PREFIX.as_bytes(), mint.as_ref(), &index.to_le_bytes()],
+                            ErrorCode::DerivedKeyInvalid,
                         )?;
                     }
                     PermissivenessType::ParentTokenHolder => {
@@ -1905,6 +2648,7 @@ pub fn assert_permissiveness_access(args: AssertPermissivenessAccessArgs) -> Res
                                 class_mint.as_ref(),
                                 &class_index.unwrap().to_le_bytes(),
                             ],
+                            ErrorCode::DerivedKeyInvalid,
                         )?;
 
                         assert_keys_equal(grab_parent(given_account)?, *class.key)?;
@@ -1962,6 +2706,7 @@ pub fn assert_permissiveness_access(args: AssertPermissivenessAccessArgs) -> Res
                     program_id,
                     given_account,
                     &[PREFIX.as_bytes(), mint.as_ref(), &index.to_le_bytes()],
+                    ErrorCode::DerivedKeyInvalid,
                 )?;
             }
         }
@@ -2164,63 +2909,583 @@ pub fn uncache_namespace(
     Ok(new_namespaces)
 }
 
-// returns true if the namespace program called the item program
-pub fn is_namespace_program_caller(ixns: &AccountInfo) -> bool {
-    let current_ix =
-        anchor_lang::solana_program::sysvar::instructions::get_instruction_relative(0, ixns)
-            .unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlayerItemActivationMarker, PlayerItemActivationMarkerAdditions};
+
+    fn template(min: Option<i64>, max: Option<i64>) -> BasicStatTemplate {
+        BasicStatTemplate {
+            index: 0,
+            name: "strength".to_string(),
+            stat_type: BasicStatType::Integer {
+                min,
+                max,
+                starting: 10,
+                staking_amount_scaler: None,
+                staking_duration_scaler: None,
+            },
+            inherited: InheritanceState::NotInherited,
+        }
+    }
 
-    if current_ix.program_id != Pubkey::from_str(NAMESPACE_ID).unwrap() {
-        return false;
-    };
+    fn stat(base: i64) -> BasicStat {
+        BasicStat {
+            index: 0,
+            state: BasicStatState::Integer {
+                base,
+                with_temporary_changes: base,
+                temporary_numerator: 1,
+                temporary_denominator: 1,
+                finalized: base,
+            },
+        }
+    }
 
-    true
-}
+    fn finalized(bs: &BasicStat) -> i64 {
+        match bs.state {
+            BasicStatState::Integer { finalized, .. } => finalized,
+            _ => panic!("not an integer stat"),
+        }
+    }
 
-/// Create account almost from scratch, lifted from
-/// https://github.com/solana-labs/solana-program-library/tree/master/associated-token-account/program/src/processor.rs#L51-L98
-#[inline(always)]
-pub fn create_or_allocate_account_raw<'a>(
-    program_id: Pubkey,
-    new_account_info: &AccountInfo<'a>,
-    rent: &Sysvar<'a, Rent>,
-    system_program: &Program<'a, System>,
-    payer_info: &AccountInfo<'a>,
-    size: usize,
-    signer_seeds: &[&[u8]],
-) -> Result<()> {
-    let required_lamports = rent
-        .minimum_balance(size)
-        .max(1)
-        .saturating_sub(new_account_info.lamports());
+    fn base(bs: &BasicStat) -> i64 {
+        match bs.state {
+            BasicStatState::Integer { base, .. } => base,
+            _ => panic!("not an integer stat"),
+        }
+    }
 
-    if required_lamports > 0 {
-        msg!("Transfer {} lamports to the new account", required_lamports);
-        invoke(
-            &system_instruction::transfer(payer_info.key, new_account_info.key, required_lamports),
-            &[
-                payer_info.clone(),
-                new_account_info.clone(),
-                system_program.to_account_info(),
+    fn effect(
+        item_class: Pubkey,
+        item_effect_type: BasicItemEffectType,
+        stacking_policy: StackingPolicy,
+        amount: i64,
+    ) -> ActiveStatEffect {
+        ActiveStatEffect {
+            stat_index: 0,
+            item_class,
+            item_effect_type,
+            stacking_policy,
+            amount,
+            clamp_min: None,
+            clamp_max: None,
+            count: 1,
+        }
+    }
+
+    fn clamp(item_class: Pubkey, min: Option<i64>, max: Option<i64>) -> ActiveStatEffect {
+        ActiveStatEffect {
+            clamp_min: min,
+            clamp_max: max,
+            ..effect(
+                item_class,
+                BasicItemEffectType::Clamp,
+                StackingPolicy::Stack,
+                0,
+            )
+        }
+    }
+
+    fn toggle(
+        active_stat_effects: &mut Vec<ActiveStatEffect>,
+        bs: &mut BasicStat,
+        bst: &BasicStatTemplate,
+        effect: Option<ActiveStatEffect>,
+        modded_amount: i64,
+        adding: bool,
+    ) {
+        toggle_stat_effect(ToggleStatEffectArgs {
+            active_stat_effects,
+            tracked_effect: effect,
+            bs,
+            bst,
+            item_effect_type: BasicItemEffectType::Increment,
+            modded_amount,
+            adding,
+        })
+        .unwrap();
+    }
+
+    fn bie(item_effect_type: BasicItemEffectType, amount: u64) -> BasicItemEffect {
+        BasicItemEffect {
+            amount,
+            stat: "strength".to_string(),
+            item_effect_type,
+            active_duration: None,
+            staking_amount_numerator: None,
+            staking_amount_divisor: None,
+            staking_duration_numerator: None,
+            staking_duration_divisor: None,
+            max_uses: None,
+            clamp_min: None,
+            clamp_max: None,
+            stacking_policy: None,
+        }
+    }
+
+    #[test]
+    fn test_stack_adds_every_application() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+        let mut active = vec![];
+
+        toggle(&mut active, &mut bs, &bst, None, 5, true);
+        toggle(&mut active, &mut bs, &bst, None, 5, true);
+        assert_eq!(finalized(&bs), 20);
+        assert!(active.is_empty());
+
+        toggle(&mut active, &mut bs, &bst, None, -5, false);
+        assert_eq!(finalized(&bs), 15);
+    }
+
+    #[test]
+    fn test_refresh_duration_applies_once_until_last_removed() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+        let mut active = vec![];
+        let potion = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Increment,
+            StackingPolicy::RefreshDuration,
+            5,
+        );
+
+        toggle(&mut active, &mut bs, &bst, Some(potion.clone()), 5, true);
+        toggle(&mut active, &mut bs, &bst, Some(potion.clone()), 5, true);
+        assert_eq!(finalized(&bs), 15);
+        assert_eq!(active[0].count, 2);
+
+        // the first application running out leaves the refreshed one in place
+        toggle(&mut active, &mut bs, &bst, Some(potion.clone()), -5, false);
+        assert_eq!(finalized(&bs), 15);
+
+        toggle(&mut active, &mut bs, &bst, Some(potion), -5, false);
+        assert_eq!(finalized(&bs), 10);
+        assert!(active.is_empty());
+    }
+
+    #[test]
+    fn test_highest_wins_applies_only_the_largest() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+        let mut active = vec![];
+        let small = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Increment,
+            StackingPolicy::HighestWins,
+            5,
+        );
+        let large = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Increment,
+            StackingPolicy::HighestWins,
+            8,
+        );
+
+        toggle(&mut active, &mut bs, &bst, Some(small.clone()), 5, true);
+        assert_eq!(finalized(&bs), 15);
+
+        toggle(&mut active, &mut bs, &bst, Some(large.clone()), 8, true);
+        assert_eq!(finalized(&bs), 18);
+
+        // removing the smaller one while the larger wins changes nothing
+        toggle(&mut active, &mut bs, &bst, Some(small.clone()), -5, false);
+        assert_eq!(finalized(&bs), 18);
+
+        toggle(&mut active, &mut bs, &bst, Some(small), 5, true);
+        toggle(&mut active, &mut bs, &bst, Some(large), -8, false);
+        assert_eq!(finalized(&bs), 15);
+    }
+
+    #[test]
+    fn test_highest_wins_swaps_percent_effects() {
+        let bst = template(None, None);
+        let mut bs = stat(100);
+        let mut active = vec![];
+        let small = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::IncrementPercent,
+            StackingPolicy::HighestWins,
+            10,
+        );
+        let large = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::IncrementPercent,
+            StackingPolicy::HighestWins,
+            50,
+        );
+
+        toggle(&mut active, &mut bs, &bst, Some(small.clone()), 10, true);
+        assert_eq!(finalized(&bs), 110);
+
+        toggle(&mut active, &mut bs, &bst, Some(large.clone()), 50, true);
+        assert_eq!(finalized(&bs), 150);
+
+        toggle(&mut active, &mut bs, &bst, Some(large), -50, false);
+        assert_eq!(finalized(&bs), 110);
+
+        toggle(&mut active, &mut bs, &bst, Some(small), -10, false);
+        assert_eq!(finalized(&bs), 100);
+    }
+
+    #[test]
+    fn test_set_overrides_the_stat_until_removed() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+        let mut active = vec![];
+        let set = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Set,
+            StackingPolicy::Stack,
+            3,
+        );
+
+        toggle(&mut active, &mut bs, &bst, None, 5, true);
+        toggle(&mut active, &mut bs, &bst, Some(set.clone()), 3, true);
+        assert_eq!(finalized(&bs), 3);
+
+        // changes underneath still count once the set is gone
+        toggle(&mut active, &mut bs, &bst, None, 2, true);
+        assert_eq!(finalized(&bs), 3);
+
+        toggle(&mut active, &mut bs, &bst, Some(set), -3, false);
+        assert_eq!(finalized(&bs), 17);
+    }
+
+    #[test]
+    fn test_highest_wins_set_keeps_the_largest_value() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+        let mut active = vec![];
+        let high = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Set,
+            StackingPolicy::HighestWins,
+            40,
+        );
+        let low = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Set,
+            StackingPolicy::HighestWins,
+            25,
+        );
+
+        toggle(&mut active, &mut bs, &bst, Some(high.clone()), 40, true);
+        toggle(&mut active, &mut bs, &bst, Some(low), 25, true);
+        assert_eq!(finalized(&bs), 40);
+
+        toggle(&mut active, &mut bs, &bst, Some(high), -40, false);
+        assert_eq!(finalized(&bs), 25);
+    }
+
+    #[test]
+    fn test_clamp_holds_the_stat_within_bounds() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+        let mut active = vec![];
+        let cap = clamp(Pubkey::new_unique(), Some(8), Some(12));
+        let floor = clamp(Pubkey::new_unique(), Some(11), None);
+
+        toggle(&mut active, &mut bs, &bst, Some(cap.clone()), 0, true);
+        toggle(&mut active, &mut bs, &bst, None, 5, true);
+        assert_eq!(finalized(&bs), 12);
+
+        toggle(&mut active, &mut bs, &bst, None, -9, false);
+        assert_eq!(finalized(&bs), 8);
+
+        toggle(&mut active, &mut bs, &bst, Some(floor), 0, true);
+        assert_eq!(finalized(&bs), 11);
+        assert_eq!(
+            get_stat_overrides(&active, 0),
+            StatOverrides {
+                set: None,
+                min: Some(11),
+                max: Some(12),
+            }
+        );
+
+        toggle(&mut active, &mut bs, &bst, Some(cap), 0, false);
+        assert_eq!(finalized(&bs), 11);
+    }
+
+    #[test]
+    fn test_template_bounds_still_apply_over_set() {
+        let bst = template(Some(0), Some(50));
+        let mut bs = stat(10);
+        let mut active = vec![];
+        let set = effect(
+            Pubkey::new_unique(),
+            BasicItemEffectType::Set,
+            StackingPolicy::Stack,
+            99,
+        );
+
+        toggle(&mut active, &mut bs, &bst, Some(set), 99, true);
+        assert_eq!(finalized(&bs), 50);
+    }
+
+    #[test]
+    fn test_permanent_set_and_clamp_write_the_base() {
+        let bst = template(None, None);
+        let mut bs = stat(10);
+
+        rebalance_stat_permanently(RebalanceStatPermanentlyArgs {
+            bie: &bie(BasicItemEffectType::Set, 30),
+            bs: &mut bs,
+            bst: &bst,
+            modded_amount: 30,
+            overrides: StatOverrides::default(),
+        })
+        .unwrap();
+        assert_eq!(base(&bs), 30);
+        assert_eq!(finalized(&bs), 30);
+
+        let mut cap = bie(BasicItemEffectType::Clamp, 0);
+        cap.clamp_max = Some(20);
+        rebalance_stat_permanently(RebalanceStatPermanentlyArgs {
+            bie: &cap,
+            bs: &mut bs,
+            bst: &bst,
+            modded_amount: 0,
+            overrides: StatOverrides::default(),
+        })
+        .unwrap();
+        assert_eq!(base(&bs), 20);
+        assert_eq!(finalized(&bs), 20);
+    }
+
+    fn backpack(
+        max_items: Option<u64>,
+        max_weight: Option<u64>,
+        items_per_point: u64,
+        weight_per_point: u64,
+    ) -> Backpack {
+        Backpack {
+            max_items,
+            max_weight,
+            stat_bonuses: vec![crate::BackpackCapacityBonus {
+                stat_index: 0,
+                items_per_point,
+                weight_per_point,
+            }],
+            inherited: InheritanceState::NotInherited,
+        }
+    }
+
+    #[test]
+    fn test_stat_bonus_raises_set_backpack_limits() {
+        let limits =
+            get_backpack_limits(&backpack(Some(10), None, 1, 5), &Some(vec![stat(4)])).unwrap();
+        assert_eq!(
+            limits,
+            BackpackLimits {
+                max_items: Some(14),
+                max_weight: None,
+            }
+        );
+
+        let limits =
+            get_backpack_limits(&backpack(Some(10), Some(100), 1, 5), &Some(vec![stat(4)]))
+                .unwrap();
+        assert_eq!(limits.max_weight, Some(120));
+    }
+
+    #[test]
+    fn test_negative_stat_gives_no_backpack_bonus() {
+        let limits =
+            get_backpack_limits(&backpack(Some(10), Some(10), 3, 3), &Some(vec![stat(-4)]))
+                .unwrap();
+        assert_eq!(
+            limits,
+            BackpackLimits {
+                max_items: Some(10),
+                max_weight: Some(10),
+            }
+        );
+    }
+
+    #[test]
+    fn test_removing_backpack_items_takes_off_the_weight_they_added() {
+        let sword = Pubkey::new_unique();
+        let mut backpack_items = vec![];
+
+        add_backpack_item(&mut backpack_items, sword, 2, 20).unwrap();
+        // the class weight went up before more were added
+        add_backpack_item(&mut backpack_items, sword, 2, 40).unwrap();
+
+        assert_eq!(
+            remove_backpack_item(&mut backpack_items, sword, 1).unwrap(),
+            15
+        );
+        assert_eq!(
+            remove_backpack_item(&mut backpack_items, sword, 3).unwrap(),
+            45
+        );
+        assert!(backpack_items.is_empty());
+
+        // added before weights were recorded
+        assert_eq!(
+            remove_backpack_item(&mut backpack_items, sword, 1).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_equipment_set_bonus_follows_equipped_pieces() {
+        let helm = Pubkey::new_unique();
+        let boots = Pubkey::new_unique();
+        let sets = vec![EquipmentSet {
+            index: 0,
+            name: "dragon".to_string(),
+            item_classes: vec![helm, boots, Pubkey::new_unique()],
+            bonuses: vec![
+                crate::EquipmentSetBonus {
+                    pieces: 2,
+                    basic_item_effects: vec![bie(BasicItemEffectType::Increment, 10)],
+                },
+                crate::EquipmentSetBonus {
+                    pieces: 3,
+                    basic_item_effects: vec![bie(BasicItemEffectType::Increment, 50)],
+                },
             ],
-        )?;
+            inherited: InheritanceState::NotInherited,
+        }];
+        let bsts = vec![template(None, None)];
+        let mut bss = vec![stat(10)];
+        let mut active = vec![];
+        let mut equipped: Vec<EquippedItemClass> = vec![];
+
+        let mut toggle_piece = |equipped: &mut Vec<EquippedItemClass>,
+                                item_class: Pubkey,
+                                amount: u64,
+                                equipping: bool,
+                                bss: &mut Vec<BasicStat>| {
+            let before = equipped.clone();
+            update_equipped_item_classes(equipped, item_class, amount, equipping).unwrap();
+            apply_equipment_set_bonuses(ApplyEquipmentSetBonusesArgs {
+                equipment_sets: &sets,
+                basic_stat_templates: &bsts,
+                basic_stats: bss,
+                active_stat_effects: &mut active,
+                equipped_item_classes_before: &before,
+                equipped_item_classes_after: equipped,
+            })
+            .unwrap();
+        };
+
+        toggle_piece(&mut equipped, helm, 1, true, &mut bss);
+        assert_eq!(finalized(&bss[0]), 10);
+
+        // a second helm is not a second piece
+        toggle_piece(&mut equipped, helm, 1, true, &mut bss);
+        assert_eq!(finalized(&bss[0]), 10);
+
+        toggle_piece(&mut equipped, boots, 1, true, &mut bss);
+        assert_eq!(finalized(&bss[0]), 20);
+
+        toggle_piece(&mut equipped, helm, 1, false, &mut bss);
+        assert_eq!(finalized(&bss[0]), 20);
+
+        toggle_piece(&mut equipped, helm, 1, false, &mut bss);
+        assert_eq!(finalized(&bss[0]), 10);
+        assert_eq!(
+            equipped,
+            vec![EquippedItemClass {
+                item_class: boots,
+                amount: 1,
+            }]
+        );
     }
 
-    let accounts = &[new_account_info.clone(), system_program.to_account_info()];
+    #[test]
+    fn test_equipment_set_bonus_needs_reachable_threshold() {
+        let mut set = EquipmentSet {
+            index: 0,
+            name: "dragon".to_string(),
+            item_classes: vec![Pubkey::new_unique()],
+            bonuses: vec![crate::EquipmentSetBonus {
+                pieces: 2,
+                basic_item_effects: vec![],
+            }],
+            inherited: InheritanceState::NotInherited,
+        };
+        assert!(assert_valid_equipment_sets(&Some(vec![set.clone()])).is_err());
 
-    msg!("Allocate space for the account");
-    invoke_signed(
-        &system_instruction::allocate(new_account_info.key, size.try_into().unwrap()),
-        accounts,
-        &[signer_seeds],
-    )?;
+        set.bonuses[0].pieces = 1;
+        assert!(assert_valid_equipment_sets(&Some(vec![set.clone()])).is_ok());
 
-    msg!("Assign the account to the owning program");
-    invoke_signed(
-        &system_instruction::assign(new_account_info.key, &program_id),
-        accounts,
-        &[signer_seeds],
-    )?;
+        set.item_classes.push(set.item_classes[0]);
+        assert!(assert_valid_equipment_sets(&Some(vec![set])).is_err());
+    }
 
-    Ok(())
+    #[test]
+    fn test_staking_bonus_uses_lockup_weighted_stake() {
+        let mut bie = bie(BasicItemEffectType::Increment, 5);
+        bie.staking_amount_numerator = Some(1);
+        bie.staking_amount_divisor = Some(10);
+        let modded = |tokens_staked, weighted_tokens_staked| {
+            get_modded_amount_given_tokens_staked_on_item(
+                GetModdedAmountGivenTokensStakedOnItemArgs {
+                    amount: 1,
+                    tokens_staked,
+                    weighted_tokens_staked,
+                    bie: &bie,
+                    adding: true,
+                },
+            )
+            .unwrap()
+        };
+
+        assert_eq!(modded(100, 100), 15);
+        assert_eq!(modded(100, 200), 25);
+        // stakes from before lockup tiers have no weight recorded
+        assert_eq!(modded(100, 0), 15);
+    }
+
+    #[test]
+    fn test_markers_created_before_additions_still_read() {
+        let mut clamp = bie(BasicItemEffectType::Clamp, 0);
+        clamp.clamp_max = Some(20);
+        let effects = Some(vec![bie(BasicItemEffectType::Increment, 5), clamp]);
+
+        // markers used to end after active_item_counter, with effects in the legacy layout
+        let old_marker = (
+            1u8,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0u16,
+            effects
+                .as_ref()
+                .map(|e| e.iter().map(|e| e.without_policies()).collect::<Vec<_>>()),
+            None::<Vec<u8>>,
+            1u64,
+            2u64,
+            3u64,
+        )
+            .try_to_vec()
+            .unwrap();
+        let marker = PlayerItemActivationMarker::deserialize(&mut &old_marker[..]).unwrap();
+        assert_eq!(
+            marker.additions,
+            PlayerItemActivationMarkerAdditions::default()
+        );
+        assert_eq!(marker.active_item_counter, 3);
+        let read = marker.effects().unwrap();
+        assert_eq!(read[1].clamp_max, None);
+        assert_eq!(read[0].amount, 5);
+
+        // and are written back at the size they were created with
+        assert_eq!(marker.try_to_vec().unwrap(), old_marker);
+
+        let mut new_marker = marker;
+        new_marker.additions.payer = Some(Pubkey::new_unique());
+        new_marker.set_effects(&effects);
+        let data = new_marker.try_to_vec().unwrap();
+        let marker = PlayerItemActivationMarker::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(marker.additions, new_marker.additions);
+        let read = marker.effects().unwrap();
+        assert_eq!(read[1].clamp_max, Some(20));
+        assert_eq!(read[0].clamp_max, None);
+    }
 }
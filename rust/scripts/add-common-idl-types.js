@@ -0,0 +1,115 @@
+// Adds the types the programs share through raindrops-common to their IDLs.
+//
+// Anchor only generates IDL types for definitions inside a program crate, so
+// the IDLs `anchor build` writes refer to the shared types without defining
+// them. This parses the common sources once, appends every shared type an IDL
+// refers to, and rewrites the TypeScript IDLs the way anchor writes them.
+//
+// Runs after `anchor build`, see the build script in package.json.
+import { execFileSync } from "child_process";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { fileURLToPath } from "url";
+
+const root = path.resolve(path.dirname(fileURLToPath(import.meta.url)), "..");
+const commonSources = ["types.rs", "item_effects.rs"];
+const idlDir = path.join(root, "target", "idl");
+const tsDirs = [path.join(root, "target", "types"), anchorTypesDir()];
+
+function anchorTypesDir() {
+  const anchorToml = fs.readFileSync(path.join(root, "Anchor.toml"), "utf8");
+  const types = anchorToml.match(/^types\s*=\s*"(.*)"/m);
+  return types ? path.resolve(root, types[1]) : null;
+}
+
+// the IDL parser only reads program crates, so the shared sources are parsed
+// as the modules of an empty program
+function parseCommonTypes() {
+  const dir = fs.mkdtempSync(path.join(os.tmpdir(), "raindrops-common-idl-"));
+  try {
+    const lib = ["use anchor_lang::prelude::*;", ""];
+    for (const source of commonSources) {
+      fs.copyFileSync(
+        path.join(root, "common", "src", source),
+        path.join(dir, source)
+      );
+      lib.push(`pub mod ${path.basename(source, ".rs")};`);
+    }
+    lib.push("", "#[program]", "pub mod raindrops_common {}", "");
+    fs.writeFileSync(path.join(dir, "lib.rs"), lib.join("\n"));
+
+    const out = path.join(dir, "raindrops_common.json");
+    execFileSync(
+      "anchor",
+      ["idl", "parse", "--file", path.join(dir, "lib.rs"), "--out", out],
+      { stdio: "inherit" }
+    );
+    const idl = JSON.parse(fs.readFileSync(out, "utf8"));
+    return new Map((idl.types ?? []).map((type) => [type.name, type]));
+  } finally {
+    fs.rmSync(dir, { recursive: true, force: true });
+  }
+}
+
+function definedTypes(value, names = new Set()) {
+  if (Array.isArray(value)) {
+    value.forEach((item) => definedTypes(item, names));
+  } else if (value !== null && typeof value === "object") {
+    if (typeof value.defined === "string") {
+      names.add(value.defined);
+    }
+    Object.values(value).forEach((item) => definedTypes(item, names));
+  }
+  return names;
+}
+
+function addCommonTypes(idl, commonTypes) {
+  idl.types = idl.types ?? [];
+  // shared types can refer to each other, keep going until nothing is missing
+  for (;;) {
+    const defined = new Set(idl.types.map((type) => type.name));
+    const missing = [...definedTypes(idl)].filter(
+      (name) => !defined.has(name) && commonTypes.has(name)
+    );
+    if (missing.length === 0) {
+      return idl;
+    }
+    idl.types.push(...missing.map((name) => commonTypes.get(name)));
+  }
+}
+
+// mirrors anchor's TypeScript IDL template
+function idlTs(idl) {
+  const tsIdl = {
+    ...idl,
+    accounts: idl.accounts?.map((account) => ({
+      ...account,
+      name: account.name.replace(/^\w/, (letter) => letter.toLowerCase()),
+    })),
+  };
+  const name = idl.name
+    .split("_")
+    .map((word) => word.replace(/^\w/, (letter) => letter.toUpperCase()))
+    .join("");
+  const json = JSON.stringify(tsIdl, null, 2);
+  return `export type ${name} = ${json};\n\nexport const IDL: ${name} = ${json};\n`;
+}
+
+const commonTypes = parseCommonTypes();
+for (const file of fs.readdirSync(idlDir).filter((f) => f.endsWith(".json"))) {
+  const idlPath = path.join(idlDir, file);
+  const idl = addCommonTypes(
+    JSON.parse(fs.readFileSync(idlPath, "utf8")),
+    commonTypes
+  );
+  fs.writeFileSync(idlPath, JSON.stringify(idl, null, 2));
+
+  for (const tsDir of tsDirs.filter(Boolean)) {
+    fs.mkdirSync(tsDir, { recursive: true });
+    fs.writeFileSync(
+      path.join(tsDir, `${path.basename(file, ".json")}.ts`),
+      idlTs(idl)
+    );
+  }
+}
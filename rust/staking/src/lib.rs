@@ -1,6 +1,11 @@
 pub mod utils;
 
-use crate::utils::{assert_is_proper_class, assert_is_proper_instance, assert_part_of_namespace};
+use crate::utils::{
+    accrue_reward_pool, assert_is_proper_class, assert_is_proper_instance,
+    assert_part_of_namespace, assert_reward_pool_scope, assert_valid_lockup_tiers,
+    backfill_legacy_position, enroll_in_reward_pool, open_stake_position, sync_stake_rewards,
+    unstake_from_position, update_stake_rewards, weigh_stake, UpdateStakeRewardsArgs,
+};
 use anchor_lang::{
     prelude::{
         borsh::{BorshDeserialize, BorshSerialize},
@@ -10,20 +15,29 @@ use anchor_lang::{
     AnchorDeserialize, AnchorSerialize,
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use raindrops_common::utils::resize_account;
+pub use raindrops_common::PermissivenessType;
 use raindrops_item::{
     program::RaindropsItem,
     utils::{
         assert_permissiveness_access, close_token_account, spl_token_transfer,
         AssertPermissivenessAccessArgs, TokenTransferParams,
     },
-    NamespaceAndIndex, PermissivenessType as ItemPermissivenessType,
+    NamespaceAndIndex,
 };
 use raindrops_player::program::RaindropsPlayer;
-use std::convert::From;
 
 anchor_lang::declare_id!("stk9HFnKhZN2PZjnn5C4wTzmeiAEgsDkbqnHkNjX1Z4");
 pub const PREFIX: &str = "staking";
 pub const STAKING_COUNTER: &str = "counter";
+pub const REWARD_POOL: &str = "reward_pool";
+pub const STAKE_REWARDS: &str = "stake_rewards";
+pub const STAKE_POSITIONS: &str = "positions";
+pub const LOCKUP_TIERS: &str = "lockup_tiers";
+// multiplier_bps that leaves a stake unweighted
+pub const LOCKUP_MULTIPLIER_BASE: u64 = 10_000;
+// staking_index of the position holding stake made before positions were tracked
+pub const LEGACY_STAKING_INDEX: u64 = u64::MAX;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct BeginArtifactStakeWarmupArgs {
@@ -35,6 +49,8 @@ pub struct BeginArtifactStakeWarmupArgs {
     pub artifact_mint: Pubkey,
     pub staking_amount: u64,
     pub staking_permissiveness_to_use: Option<PermissivenessType>,
+    // index into the class's lockup tiers, no lock and no multiplier if not set
+    pub lockup_tier: Option<u8>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -55,6 +71,8 @@ pub struct BeginArtifactStakeCooldownArgs {
     pub artifact_class_mint: Pubkey,
     pub artifact_mint: Pubkey,
     pub staking_permissiveness_to_use: Option<PermissivenessType>,
+    // how much of the position opened with staking_index to unstake, all of it if not set
+    pub amount: Option<u64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -66,6 +84,36 @@ pub struct EndArtifactStakeCooldownArgs {
     pub artifact_mint: Pubkey,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateRewardPoolArgs {
+    // reward tokens emitted per second, split across everything staked in the pool
+    pub emission_rate: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TopUpRewardPoolArgs {
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimRewardsArgs {
+    pub class_index: u64,
+    pub parent_class_index: Option<u64>,
+    pub index: u64,
+    pub artifact_class_mint: Pubkey,
+    pub artifact_mint: Pubkey,
+    pub staking_permissiveness_to_use: Option<PermissivenessType>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetLockupTiersArgs {
+    pub class_index: u64,
+    pub parent_class_index: Option<u64>,
+    pub artifact_class_mint: Pubkey,
+    pub update_permissiveness_to_use: Option<PermissivenessType>,
+    pub tiers: Vec<LockupTier>,
+}
+
 #[program]
 pub mod raindrops_staking {
 
@@ -83,6 +131,7 @@ pub mod raindrops_staking {
         let staking_account = &ctx.accounts.staking_account;
         let staking_mint = &ctx.accounts.staking_mint;
         let staking_transfer_authority = &ctx.accounts.staking_transfer_authority;
+        let lockup_tiers_unchecked = &ctx.accounts.lockup_tiers;
         let token_program = &ctx.accounts.token_program;
         let clock = &ctx.accounts.clock;
 
@@ -94,6 +143,7 @@ pub mod raindrops_staking {
             staking_amount,
             staking_permissiveness_to_use,
             index,
+            lockup_tier,
             ..
         } = args;
 
@@ -109,14 +159,11 @@ pub mod raindrops_staking {
 
         let namespace = assert_part_of_namespace(&artifact_unchecked.to_account_info(), namespace)?;
 
-        let permissiveness: Option<ItemPermissivenessType> =
-            staking_permissiveness_to_use.map(|p| p.into());
-
         assert_permissiveness_access(AssertPermissivenessAccessArgs {
             program_id: ctx.program_id,
             given_account: &artifact_class_unchecked.to_account_info(),
             remaining_accounts: ctx.remaining_accounts,
-            permissiveness_to_use: &permissiveness,
+            permissiveness_to_use: &staking_permissiveness_to_use,
             permissiveness_array: &artifact_class.data.staking_permissiveness,
             index: class_index,
             class_index: parent_class_index,
@@ -124,6 +171,22 @@ pub mod raindrops_staking {
             allowed_delegate: None,
         })?;
 
+        // the chosen tier is copied onto the counter so later changes to the tiers
+        // do not affect stakes already under way
+        let lockup = if let Some(tier) = lockup_tier {
+            let lockup_tiers: Account<LockupTiers> = Account::try_from(lockup_tiers_unchecked)?;
+            let tier = lockup_tiers
+                .tiers
+                .get(tier as usize)
+                .ok_or(ErrorCode::InvalidLockupTier)?;
+            StakingCounterLockup {
+                lock_duration: tier.lock_duration,
+                multiplier_bps: tier.multiplier_bps,
+            }
+        } else {
+            StakingCounterLockup::default()
+        };
+
         for wl in &namespace.whitelisted_staking_mints {
             if *wl == staking_mint.key() {
                 spl_token_transfer(TokenTransferParams {
@@ -141,6 +204,7 @@ pub mod raindrops_staking {
                     .unwrap();
                 staking_counter.event_start = clock.unix_timestamp;
                 staking_counter.event_type = 0;
+                staking_counter.lockup = lockup;
                 return Ok(());
             }
         }
@@ -157,8 +221,10 @@ pub mod raindrops_staking {
         let staking_escrow = &mut ctx.accounts.artifact_intermediary_staking_account;
         let staking_counter = &mut ctx.accounts.artifact_intermediary_staking_counter;
         let artifact_mint_staking_account = &ctx.accounts.artifact_mint_staking_account;
+        let stake_positions = &mut ctx.accounts.stake_positions;
         let staking_mint = &ctx.accounts.staking_mint;
         let item_program = &ctx.accounts.item_program;
+        let player_program = &ctx.accounts.player_program;
         let token_program = &ctx.accounts.token_program;
         let clock = &ctx.accounts.clock;
         let payer = &ctx.accounts.payer;
@@ -174,7 +240,7 @@ pub mod raindrops_staking {
         let artifact_class =
             assert_is_proper_class(artifact_class_unchecked, &artifact_class_mint, class_index)?;
 
-        assert_is_proper_instance(
+        let artifact = assert_is_proper_instance(
             artifact_unchecked,
             &artifact_class_unchecked.key(),
             &artifact_mint,
@@ -224,16 +290,6 @@ pub mod raindrops_staking {
             token_program: token_program.to_account_info(),
         })?;
 
-        let counter_info = staking_counter.to_account_info();
-        let snapshot: u64 = counter_info.lamports();
-
-        **counter_info.lamports.borrow_mut() = 0;
-
-        **payer.lamports.borrow_mut() = payer
-            .lamports()
-            .checked_add(snapshot)
-            .ok_or(ErrorCode::NumericalOverflowError)?;
-
         close_token_account(
             &staking_escrow.to_account_info(),
             payer,
@@ -242,6 +298,31 @@ pub mod raindrops_staking {
             &signer_seeds,
         )?;
 
+        // closed rather than just drained so exit does not write the counter back,
+        // counters from before lockup tiers are too small to hold the current layout
+        staking_counter.close(payer.to_account_info())?;
+
+        let position = StakePosition {
+            staking_mint: staking_mint_key,
+            staking_index: args.staking_index,
+            amount: staking_amount,
+            staked_at: clock.unix_timestamp,
+            unlocks_at: clock
+                .unix_timestamp
+                .checked_add(staking_counter.lockup.lock_duration as i64)
+                .ok_or(ErrorCode::NumericalOverflowError)?,
+            multiplier_bps: staking_counter.lockup.multiplier_bps,
+        };
+        let weighted_amount = position.weighted_amount()?;
+
+        stake_positions.bump = *ctx.bumps.get("stake_positions").unwrap();
+        backfill_legacy_position(
+            stake_positions,
+            &staking_mint_key,
+            artifact_mint_staking_account.amount,
+        )?;
+        open_stake_position(stake_positions, position)?;
+
         if *artifact_unchecked.owner == raindrops_item::program::RaindropsItem::id() {
             let cpi_accounts = raindrops_item::cpi::accounts::UpdateTokensStaked {
                 item: artifact_unchecked.to_account_info(),
@@ -256,13 +337,59 @@ pub mod raindrops_staking {
                 index,
                 staked: true,
                 amount: staking_amount,
+                weighted_amount,
             };
 
             let cpi_ctx = CpiContext::new(item_program.to_account_info(), cpi_accounts);
 
             raindrops_item::cpi::update_tokens_staked(cpi_ctx, cpi_args)?;
         } else {
-            // TODO: Add calling player::update_tokens_staked cpi
+            let cpi_accounts = raindrops_player::cpi::accounts::UpdateTokensStaked {
+                player: artifact_unchecked.to_account_info(),
+                instruction_sysvar_account: ctx
+                    .accounts
+                    .instruction_sysvar_account
+                    .to_account_info(),
+            };
+
+            let cpi_args = raindrops_player::UpdateTokensStakedArgs {
+                player_mint: artifact_mint.key(),
+                index,
+                staked: true,
+                amount: staking_amount,
+                weighted_amount,
+            };
+
+            let cpi_ctx = CpiContext::new(player_program.to_account_info(), cpi_accounts);
+
+            raindrops_player::cpi::update_tokens_staked(cpi_ctx, cpi_args)?;
+        }
+
+        update_stake_rewards(UpdateStakeRewardsArgs {
+            program_id: ctx.program_id,
+            remaining_accounts: ctx.remaining_accounts,
+            artifact_class: &artifact_class_unchecked.key(),
+            artifact: &artifact,
+            stake_positions,
+            staking_mint: &staking_mint_key,
+            artifact_mint_staking_account: &artifact_mint_staking_account.key(),
+            staked_amount: artifact_mint_staking_account
+                .amount
+                .checked_add(staking_amount)
+                .ok_or(ErrorCode::NumericalOverflowError)?,
+            payer: &payer.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+            now: clock.unix_timestamp,
+        })?;
+
+        if stake_positions.space() > stake_positions.to_account_info().data_len() {
+            resize_account(
+                &stake_positions.to_account_info(),
+                &payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                stake_positions.space(),
+            )?;
         }
 
         Ok(())
@@ -277,39 +404,40 @@ pub mod raindrops_staking {
         let staking_escrow = &mut ctx.accounts.artifact_intermediary_staking_account;
         let staking_counter = &mut ctx.accounts.artifact_intermediary_staking_counter;
         let artifact_mint_staking_account = &ctx.accounts.artifact_mint_staking_account;
+        let stake_positions = &mut ctx.accounts.stake_positions;
         let staking_mint = &ctx.accounts.staking_mint;
         let item_program = &ctx.accounts.item_program;
+        let player_program = &ctx.accounts.player_program;
         let token_program = &ctx.accounts.token_program;
         let clock = &ctx.accounts.clock;
+        let payer = &ctx.accounts.payer;
 
         let BeginArtifactStakeCooldownArgs {
             class_index,
             parent_class_index,
             index,
+            staking_index,
             artifact_class_mint,
             artifact_mint,
             staking_permissiveness_to_use,
-            ..
+            amount,
         } = args;
 
         let artifact_class =
             assert_is_proper_class(artifact_class_unchecked, &artifact_class_mint, class_index)?;
 
-        assert_is_proper_instance(
+        let artifact = assert_is_proper_instance(
             artifact_unchecked,
             &artifact_class_unchecked.key(),
             &artifact_mint,
             index,
         )?;
 
-        let permissiveness: Option<ItemPermissivenessType> =
-            staking_permissiveness_to_use.map(|p| p.into());
-
         assert_permissiveness_access(AssertPermissivenessAccessArgs {
             program_id: ctx.program_id,
             given_account: &artifact_class_unchecked.to_account_info(),
             remaining_accounts: ctx.remaining_accounts,
-            permissiveness_to_use: &permissiveness,
+            permissiveness_to_use: &staking_permissiveness_to_use,
             permissiveness_array: if artifact_class.data.unstaking_permissiveness.is_some() {
                 &artifact_class.data.unstaking_permissiveness
             } else {
@@ -336,7 +464,19 @@ pub mod raindrops_staking {
             &[staking_escrow_bump],
         ];
 
-        let amount_to_unstake = artifact_mint_staking_account.amount;
+        stake_positions.bump = *ctx.bumps.get("stake_positions").unwrap();
+        backfill_legacy_position(
+            stake_positions,
+            &staking_mint_key,
+            artifact_mint_staking_account.amount,
+        )?;
+        let (amount_to_unstake, weighted_amount) = unstake_from_position(
+            stake_positions,
+            &staking_mint_key,
+            staking_index,
+            amount,
+            clock.unix_timestamp,
+        )?;
 
         spl_token_transfer(TokenTransferParams {
             source: artifact_mint_staking_account.to_account_info(),
@@ -361,13 +501,59 @@ pub mod raindrops_staking {
                 index,
                 staked: false,
                 amount: amount_to_unstake,
+                weighted_amount,
             };
 
             let cpi_ctx = CpiContext::new(item_program.to_account_info(), cpi_accounts);
 
             raindrops_item::cpi::update_tokens_staked(cpi_ctx, cpi_args)?;
         } else {
-            // TODO: Add calling player::update_tokens_staked cpi
+            let cpi_accounts = raindrops_player::cpi::accounts::UpdateTokensStaked {
+                player: artifact_unchecked.to_account_info(),
+                instruction_sysvar_account: ctx
+                    .accounts
+                    .instruction_sysvar_account
+                    .to_account_info(),
+            };
+
+            let cpi_args = raindrops_player::UpdateTokensStakedArgs {
+                player_mint: artifact_mint.key(),
+                index,
+                staked: false,
+                amount: amount_to_unstake,
+                weighted_amount,
+            };
+
+            let cpi_ctx = CpiContext::new(player_program.to_account_info(), cpi_accounts);
+
+            raindrops_player::cpi::update_tokens_staked(cpi_ctx, cpi_args)?;
+        }
+
+        update_stake_rewards(UpdateStakeRewardsArgs {
+            program_id: ctx.program_id,
+            remaining_accounts: ctx.remaining_accounts,
+            artifact_class: &artifact_class_unchecked.key(),
+            artifact: &artifact,
+            stake_positions,
+            staking_mint: &staking_mint_key,
+            artifact_mint_staking_account: &artifact_mint_staking_account.key(),
+            staked_amount: artifact_mint_staking_account
+                .amount
+                .checked_sub(amount_to_unstake)
+                .ok_or(ErrorCode::NumericalOverflowError)?,
+            payer: &payer.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+            now: clock.unix_timestamp,
+        })?;
+
+        if stake_positions.space() > stake_positions.to_account_info().data_len() {
+            resize_account(
+                &stake_positions.to_account_info(),
+                &payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                stake_positions.space(),
+            )?;
         }
 
         staking_counter.bump = *ctx
@@ -455,16 +641,6 @@ pub mod raindrops_staking {
             token_program: token_program.to_account_info(),
         })?;
 
-        let counter_info = staking_counter.to_account_info();
-        let snapshot: u64 = counter_info.lamports();
-
-        **counter_info.lamports.borrow_mut() = 0;
-
-        **payer.lamports.borrow_mut() = payer
-            .lamports()
-            .checked_add(snapshot)
-            .ok_or(ErrorCode::NumericalOverflowError)?;
-
         close_token_account(
             &staking_escrow.to_account_info(),
             payer,
@@ -473,6 +649,275 @@ pub mod raindrops_staking {
             &signer_seeds,
         )?;
 
+        // closed rather than just drained so exit does not write the counter back,
+        // counters from before lockup tiers are too small to hold the current layout
+        staking_counter.close(payer.to_account_info())?;
+
+        Ok(())
+    }
+
+    pub fn create_reward_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateRewardPool<'info>>,
+        args: CreateRewardPoolArgs,
+    ) -> Result<()> {
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        let scope = &ctx.accounts.scope;
+        let clock = &ctx.accounts.clock;
+
+        // a pool hangs off either a namespace or an artifact class
+        require!(
+            scope.owner == &raindrops_namespace::id()
+                || scope.owner == &raindrops_item::id()
+                || scope.owner == &raindrops_player::id(),
+            ErrorCode::InvalidRewardPoolScope
+        );
+
+        reward_pool.bump = *ctx.bumps.get("reward_pool").unwrap();
+        reward_pool.authority = ctx.accounts.authority.key();
+        reward_pool.scope = scope.key();
+        reward_pool.staking_mint = ctx.accounts.staking_mint.key();
+        reward_pool.reward_mint = ctx.accounts.reward_mint.key();
+        reward_pool.emission_rate = args.emission_rate;
+        reward_pool.last_update = clock.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn top_up_reward_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, TopUpRewardPool<'info>>,
+        args: TopUpRewardPoolArgs,
+    ) -> Result<()> {
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        let reward_vault = &ctx.accounts.reward_vault;
+        let funding_account = &ctx.accounts.funding_account;
+        let funding_transfer_authority = &ctx.accounts.funding_transfer_authority;
+        let token_program = &ctx.accounts.token_program;
+        let clock = &ctx.accounts.clock;
+
+        require!(!reward_pool.closed, ErrorCode::RewardPoolClosed);
+
+        // settle emission at the old funding level before adding to it
+        accrue_reward_pool(reward_pool, clock.unix_timestamp)?;
+
+        spl_token_transfer(TokenTransferParams {
+            source: funding_account.to_account_info(),
+            destination: reward_vault.to_account_info(),
+            amount: args.amount,
+            authority: funding_transfer_authority.to_account_info(),
+            authority_signer_seeds: &[],
+            token_program: token_program.to_account_info(),
+        })?;
+
+        reward_pool.rewards_available = reward_pool
+            .rewards_available
+            .checked_add(args.amount)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+
+        Ok(())
+    }
+
+    pub fn close_reward_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseRewardPool<'info>>,
+    ) -> Result<()> {
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        let reward_vault = &ctx.accounts.reward_vault;
+        let destination = &ctx.accounts.destination;
+        let token_program = &ctx.accounts.token_program;
+        let clock = &ctx.accounts.clock;
+
+        // everything emitted up to now stays in the vault for stakers to claim,
+        // only the unemitted remainder goes back to the authority. calling this
+        // again once closed returns whatever stakers have forfeited since
+        accrue_reward_pool(reward_pool, clock.unix_timestamp)?;
+
+        let amount = reward_pool.rewards_available;
+
+        reward_pool.rewards_available = 0;
+        reward_pool.emission_rate = 0;
+        reward_pool.closed = true;
+
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.scope.as_ref(),
+            reward_pool.staking_mint.as_ref(),
+            reward_pool.reward_mint.as_ref(),
+            reward_pool.authority.as_ref(),
+            &[reward_pool.bump],
+        ];
+
+        if amount > 0 {
+            spl_token_transfer(TokenTransferParams {
+                source: reward_vault.to_account_info(),
+                destination: destination.to_account_info(),
+                amount,
+                authority: reward_pool.to_account_info(),
+                authority_signer_seeds: &signer_seeds,
+                token_program: token_program.to_account_info(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn claim_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRewards<'info>>,
+        args: ClaimRewardsArgs,
+    ) -> Result<()> {
+        let artifact_unchecked = &ctx.accounts.artifact;
+        let artifact_class_unchecked = &ctx.accounts.artifact_class;
+        let artifact_mint_staking_account = &ctx.accounts.artifact_mint_staking_account;
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        let reward_vault = &ctx.accounts.reward_vault;
+        let stake_rewards = &mut ctx.accounts.stake_rewards;
+        let stake_positions = &mut ctx.accounts.stake_positions;
+        let reward_account = &ctx.accounts.reward_account;
+        let token_program = &ctx.accounts.token_program;
+        let clock = &ctx.accounts.clock;
+        let payer = &ctx.accounts.payer;
+
+        let ClaimRewardsArgs {
+            class_index,
+            parent_class_index,
+            index,
+            artifact_class_mint,
+            artifact_mint,
+            staking_permissiveness_to_use,
+        } = args;
+
+        let artifact_class =
+            assert_is_proper_class(artifact_class_unchecked, &artifact_class_mint, class_index)?;
+
+        let artifact = assert_is_proper_instance(
+            artifact_unchecked,
+            &artifact_class_unchecked.key(),
+            &artifact_mint,
+            index,
+        )?;
+
+        // whoever may unstake the artifact may take its rewards
+        assert_permissiveness_access(AssertPermissivenessAccessArgs {
+            program_id: ctx.program_id,
+            given_account: &artifact_class_unchecked.to_account_info(),
+            remaining_accounts: ctx.remaining_accounts,
+            permissiveness_to_use: &staking_permissiveness_to_use,
+            permissiveness_array: if artifact_class.data.unstaking_permissiveness.is_some() {
+                &artifact_class.data.unstaking_permissiveness
+            } else {
+                &artifact_class.data.staking_permissiveness
+            },
+            index: class_index,
+            class_index: parent_class_index,
+            account_mint: Some(&artifact_class_mint),
+            allowed_delegate: None,
+        })?;
+
+        assert_reward_pool_scope(reward_pool, &artifact_class_unchecked.key(), &artifact)?;
+
+        if stake_rewards.reward_pool == Pubkey::default() {
+            stake_rewards.bump = *ctx.bumps.get("stake_rewards").unwrap();
+            stake_rewards.reward_pool = reward_pool.key();
+            stake_rewards.staking_account = artifact_mint_staking_account.key();
+        }
+
+        // the staking account is the source of truth, if it dropped without the pool
+        // being told, the accrual since the last checkpoint is forfeited
+        sync_stake_rewards(
+            reward_pool,
+            stake_rewards,
+            artifact_mint_staking_account.amount,
+            clock.unix_timestamp,
+            true,
+        )?;
+
+        stake_positions.bump = *ctx.bumps.get("stake_positions").unwrap();
+        enroll_in_reward_pool(
+            stake_positions,
+            &reward_pool.key(),
+            &reward_pool.staking_mint,
+            stake_rewards.amount,
+        );
+        if stake_positions.space() > stake_positions.to_account_info().data_len() {
+            resize_account(
+                &stake_positions.to_account_info(),
+                &payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                stake_positions.space(),
+            )?;
+        }
+
+        let amount = stake_rewards.pending;
+        stake_rewards.pending = 0;
+
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.scope.as_ref(),
+            reward_pool.staking_mint.as_ref(),
+            reward_pool.reward_mint.as_ref(),
+            reward_pool.authority.as_ref(),
+            &[reward_pool.bump],
+        ];
+
+        if amount > 0 {
+            spl_token_transfer(TokenTransferParams {
+                source: reward_vault.to_account_info(),
+                destination: reward_account.to_account_info(),
+                amount,
+                authority: reward_pool.to_account_info(),
+                authority_signer_seeds: &signer_seeds,
+                token_program: token_program.to_account_info(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_lockup_tiers<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLockupTiers<'info>>,
+        args: SetLockupTiersArgs,
+    ) -> Result<()> {
+        let artifact_class_unchecked = &ctx.accounts.artifact_class;
+        let lockup_tiers = &mut ctx.accounts.lockup_tiers;
+        let payer = &ctx.accounts.payer;
+
+        let SetLockupTiersArgs {
+            class_index,
+            parent_class_index,
+            artifact_class_mint,
+            update_permissiveness_to_use,
+            tiers,
+        } = args;
+
+        let artifact_class =
+            assert_is_proper_class(artifact_class_unchecked, &artifact_class_mint, class_index)?;
+
+        assert_permissiveness_access(AssertPermissivenessAccessArgs {
+            program_id: ctx.program_id,
+            given_account: &artifact_class_unchecked.to_account_info(),
+            remaining_accounts: ctx.remaining_accounts,
+            permissiveness_to_use: &update_permissiveness_to_use,
+            permissiveness_array: &artifact_class.data.update_permissiveness,
+            index: class_index,
+            class_index: parent_class_index,
+            account_mint: Some(&artifact_class_mint),
+            allowed_delegate: None,
+        })?;
+
+        assert_valid_lockup_tiers(&tiers)?;
+
+        lockup_tiers.bump = *ctx.bumps.get("lockup_tiers").unwrap();
+        lockup_tiers.tiers = tiers;
+
+        if lockup_tiers.space() > lockup_tiers.to_account_info().data_len() {
+            resize_account(
+                &lockup_tiers.to_account_info(),
+                &payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                lockup_tiers.space(),
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -528,7 +973,7 @@ pub struct BeginArtifactStakeWarmup<'info> {
             STAKING_COUNTER.as_bytes(),
         ],
         bump,
-        space = 8 + 1 + 8 + 1,
+        space = STAKING_COUNTER_SIZE,
         payer = payer,
     )]
     artifact_intermediary_staking_counter: Box<Account<'info, StakingCounter>>,
@@ -536,6 +981,16 @@ pub struct BeginArtifactStakeWarmup<'info> {
     staking_account: Box<Account<'info, TokenAccount>>,
     staking_mint: Box<Account<'info, Mint>>,
     staking_transfer_authority: Signer<'info>,
+    /// CHECK: only deserialized when a lockup tier is chosen
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            artifact_class.key().as_ref(),
+            LOCKUP_TIERS.as_bytes(),
+        ],
+        bump,
+    )]
+    lockup_tiers: UncheckedAccount<'info>,
     /// CHECK: assert_part_of_namespace
     namespace: UncheckedAccount<'info>,
     #[account(mut)]
@@ -597,6 +1052,20 @@ pub struct EndArtifactStakeWarmup<'info> {
         payer = payer,
     )]
     artifact_mint_staking_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX.as_bytes(),
+            args.artifact_class_mint.as_ref(),
+            args.artifact_mint.as_ref(),
+            &args.index.to_le_bytes(),
+            STAKE_POSITIONS.as_bytes(),
+        ],
+        bump,
+        space = MIN_STAKE_POSITIONS_SIZE,
+        payer = payer,
+    )]
+    stake_positions: Box<Account<'info, StakePositions>>,
     staking_mint: Box<Account<'info, Mint>>,
     #[account(mut)]
     payer: Signer<'info>,
@@ -654,7 +1123,7 @@ pub struct BeginArtifactStakeCooldown<'info> {
             staking_account.key().as_ref(),
         ],
         bump,
-        space = 8 + 1 + 8 + 1,
+        space = STAKING_COUNTER_SIZE,
         payer = payer,
     )]
     artifact_intermediary_staking_counter: Box<Account<'info, StakingCounter>>,
@@ -670,6 +1139,20 @@ pub struct BeginArtifactStakeCooldown<'info> {
         bump,
     )]
     artifact_mint_staking_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX.as_bytes(),
+            args.artifact_class_mint.as_ref(),
+            args.artifact_mint.as_ref(),
+            &args.index.to_le_bytes(),
+            STAKE_POSITIONS.as_bytes(),
+        ],
+        bump,
+        space = MIN_STAKE_POSITIONS_SIZE,
+        payer = payer,
+    )]
+    stake_positions: Box<Account<'info, StakePositions>>,
     #[account(mut, constraint = staking_account.mint == staking_mint.key())]
     staking_account: Box<Account<'info, TokenAccount>>,
     staking_mint: Box<Account<'info, Mint>>,
@@ -725,9 +1208,223 @@ pub struct EndArtifactStakeCooldown<'info> {
     #[account(mut, constraint = staking_account.mint == staking_mint.key())]
     staking_account: Box<Account<'info, TokenAccount>>,
     staking_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    token_program: Program<'info, Token>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRewardPool<'info> {
+    #[account(
+        init,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            scope.key().as_ref(),
+            staking_mint.key().as_ref(),
+            reward_mint.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+        space = REWARD_POOL_SIZE,
+        payer = payer,
+    )]
+    reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(
+        init,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.key().as_ref(),
+        ],
+        bump,
+        token::mint = reward_mint,
+        token::authority = reward_pool,
+        payer = payer,
+    )]
+    reward_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: owner checked in create_reward_pool
+    scope: UncheckedAccount<'info>,
+    staking_mint: Box<Account<'info, Mint>>,
+    reward_mint: Box<Account<'info, Mint>>,
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpRewardPool<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.scope.as_ref(),
+            reward_pool.staking_mint.as_ref(),
+            reward_pool.reward_mint.as_ref(),
+            reward_pool.authority.as_ref(),
+        ],
+        bump = reward_pool.bump,
+    )]
+    reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.key().as_ref(),
+        ],
+        bump,
+    )]
+    reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = funding_account.mint == reward_pool.reward_mint)]
+    funding_account: Box<Account<'info, TokenAccount>>,
+    funding_transfer_authority: Signer<'info>,
+    token_program: Program<'info, Token>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRewardPool<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.scope.as_ref(),
+            reward_pool.staking_mint.as_ref(),
+            reward_pool.reward_mint.as_ref(),
+            reward_pool.authority.as_ref(),
+        ],
+        bump = reward_pool.bump,
+        has_one = authority,
+    )]
+    reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.key().as_ref(),
+        ],
+        bump,
+    )]
+    reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = destination.mint == reward_pool.reward_mint)]
+    destination: Box<Account<'info, TokenAccount>>,
+    authority: Signer<'info>,
+    token_program: Program<'info, Token>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ClaimRewardsArgs)]
+pub struct ClaimRewards<'info> {
+    /// CHECK: assert_is_proper_class
+    artifact_class: UncheckedAccount<'info>,
+    /// CHECK: assert_is_proper_instance
+    artifact: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            args.artifact_class_mint.as_ref(),
+            args.artifact_mint.as_ref(),
+            &args.index.to_le_bytes(),
+            staking_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    artifact_mint_staking_account: Box<Account<'info, TokenAccount>>,
+    staking_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.scope.as_ref(),
+            reward_pool.staking_mint.as_ref(),
+            reward_pool.reward_mint.as_ref(),
+            reward_pool.authority.as_ref(),
+        ],
+        bump = reward_pool.bump,
+        has_one = staking_mint,
+    )]
+    reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            REWARD_POOL.as_bytes(),
+            reward_pool.key().as_ref(),
+        ],
+        bump,
+    )]
+    reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX.as_bytes(),
+            STAKE_REWARDS.as_bytes(),
+            reward_pool.key().as_ref(),
+            artifact_mint_staking_account.key().as_ref(),
+        ],
+        bump,
+        space = STAKE_REWARDS_SIZE,
+        payer = payer,
+    )]
+    stake_rewards: Box<Account<'info, StakeRewards>>,
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX.as_bytes(),
+            args.artifact_class_mint.as_ref(),
+            args.artifact_mint.as_ref(),
+            &args.index.to_le_bytes(),
+            STAKE_POSITIONS.as_bytes(),
+        ],
+        bump,
+        space = MIN_STAKE_POSITIONS_SIZE,
+        payer = payer,
+    )]
+    stake_positions: Box<Account<'info, StakePositions>>,
+    #[account(mut, constraint = reward_account.mint == reward_pool.reward_mint)]
+    reward_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
     payer: Signer<'info>,
+    system_program: Program<'info, System>,
     token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
     clock: Sysvar<'info, Clock>,
+    // See the [COMMON REMAINING ACCOUNTS] ctrl f for this
+}
+
+#[derive(Accounts)]
+#[instruction(args: SetLockupTiersArgs)]
+pub struct SetLockupTiers<'info> {
+    /// CHECK: assert_is_proper_class
+    artifact_class: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX.as_bytes(),
+            artifact_class.key().as_ref(),
+            LOCKUP_TIERS.as_bytes(),
+        ],
+        bump,
+        space = MIN_LOCKUP_TIERS_SIZE,
+        payer = payer,
+    )]
+    lockup_tiers: Box<Account<'info, LockupTiers>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+    // See the [COMMON REMAINING ACCOUNTS] ctrl f for this
 }
 
 #[account]
@@ -735,8 +1432,186 @@ pub struct StakingCounter {
     pub bump: u8,
     pub event_start: i64,
     pub event_type: u8,
+    // lockup tier chosen when warming up, carried onto the stake position
+    pub lockup: StakingCounterLockup,
+}
+
+pub const STAKING_COUNTER_SIZE: usize = 8 + // key
+1 + // bump
+8 + // event start
+1 + // event type
+8 + // lock duration
+8; // multiplier bps
+
+// Counters created before lockup tiers end after event_type, those read back as
+// a stake with no lock and no multiplier.
+#[derive(AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakingCounterLockup {
+    pub lock_duration: u64,
+    pub multiplier_bps: u64,
 }
 
+impl Default for StakingCounterLockup {
+    fn default() -> Self {
+        StakingCounterLockup {
+            lock_duration: 0,
+            multiplier_bps: LOCKUP_MULTIPLIER_BASE,
+        }
+    }
+}
+
+impl AnchorDeserialize for StakingCounterLockup {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if buf.is_empty() {
+            return Ok(StakingCounterLockup::default());
+        }
+
+        Ok(StakingCounterLockup {
+            lock_duration: u64::deserialize(buf)?,
+            multiplier_bps: u64::deserialize(buf)?,
+        })
+    }
+}
+
+/// seed ['staking', artifact class, 'lockup_tiers']
+#[account]
+pub struct LockupTiers {
+    pub bump: u8,
+    pub tiers: Vec<LockupTier>,
+}
+
+impl LockupTiers {
+    pub fn space(&self) -> usize {
+        MIN_LOCKUP_TIERS_SIZE + self.tiers.len() * LOCKUP_TIER_SIZE
+    }
+}
+
+pub const MIN_LOCKUP_TIERS_SIZE: usize = 8 + // key
+1 + // bump
+4; // tiers
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LockupTier {
+    // seconds after the stake goes live before its cooldown can begin
+    pub lock_duration: u64,
+    // scales the stake item effects see, LOCKUP_MULTIPLIER_BASE is 1x
+    pub multiplier_bps: u64,
+}
+
+pub const LOCKUP_TIER_SIZE: usize = 8 + // lock duration
+8; // multiplier bps
+
+/// seed ['staking', 'reward_pool', scope, staking mint, reward mint, authority]
+/// scope is the namespace or artifact class whose staked artifacts earn from the pool
+#[account]
+pub struct RewardPool {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub scope: Pubkey,
+    pub staking_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub emission_rate: u64,
+    // funded but not yet emitted
+    pub rewards_available: u64,
+    pub total_staked: u64,
+    // emitted per staked token since creation, scaled by REWARD_PRECISION
+    pub reward_per_token: u128,
+    pub last_update: i64,
+    pub closed: bool,
+}
+
+pub const REWARD_POOL_SIZE: usize = 8 + // key
+1 + // bump
+32 + // authority
+32 + // scope
+32 + // staking mint
+32 + // reward mint
+8 + // emission rate
+8 + // rewards available
+8 + // total staked
+16 + // reward per token
+8 + // last update
+1; // closed
+
+/// seed ['staking', 'stake_rewards', reward pool, artifact mint staking account]
+#[account]
+pub struct StakeRewards {
+    pub bump: u8,
+    pub reward_pool: Pubkey,
+    pub staking_account: Pubkey,
+    // what the pool last saw in the artifact's staking account
+    pub amount: u64,
+    pub reward_per_token_paid: u128,
+    // earned but not yet claimed
+    pub pending: u64,
+}
+
+pub const STAKE_REWARDS_SIZE: usize = 8 + // key
+1 + // bump
+32 + // reward pool
+32 + // staking account
+8 + // amount
+16 + // reward per token paid
+8; // pending
+
+/// seed ['staking', artifact class mint, artifact mint, index, 'positions']
+/// every open stake on the artifact, summing to its tokens_staked once stake from
+/// before positions existed is backfilled into a LEGACY_STAKING_INDEX position
+#[account]
+pub struct StakePositions {
+    pub bump: u8,
+    pub positions: Vec<StakePosition>,
+    // pools counting stake on the artifact, each has to be passed whenever that stake changes
+    pub reward_pools: Vec<EnrolledRewardPool>,
+}
+
+impl StakePositions {
+    pub fn space(&self) -> usize {
+        MIN_STAKE_POSITIONS_SIZE
+            + self.positions.len() * STAKE_POSITION_SIZE
+            + self.reward_pools.len() * ENROLLED_REWARD_POOL_SIZE
+    }
+}
+
+pub const MIN_STAKE_POSITIONS_SIZE: usize = 8 + // key
+1 + // bump
+4 + // positions
+4; // reward pools
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakePosition {
+    pub staking_mint: Pubkey,
+    // the staking_index the position was opened with, also used to unstake from it
+    pub staking_index: u64,
+    pub amount: u64,
+    pub staked_at: i64,
+    // cooldown cannot begin before this
+    pub unlocks_at: i64,
+    pub multiplier_bps: u64,
+}
+
+impl StakePosition {
+    pub fn weighted_amount(&self) -> Result<u64> {
+        weigh_stake(self.amount, self.multiplier_bps)
+    }
+}
+
+pub const STAKE_POSITION_SIZE: usize = 32 + // staking mint
+8 + // staking index
+8 + // amount
+8 + // staked at
+8 + // unlocks at
+8; // multiplier bps
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EnrolledRewardPool {
+    pub reward_pool: Pubkey,
+    pub staking_mint: Pubkey,
+}
+
+pub const ENROLLED_REWARD_POOL_SIZE: usize = 32 + // reward pool
+32; // staking mint
+
 // use Borsh here so that these foreign types do not get imported into our IDL and break the Anchor TS generation
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct ArtifactClassData {
@@ -780,40 +1655,6 @@ pub struct Artifact {
     pub tokens_staked: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum PermissivenessType {
-    TokenHolder,
-    ParentTokenHolder,
-    UpdateAuthority,
-    Anybody,
-}
-
-impl From<raindrops_item::PermissivenessType> for PermissivenessType {
-    fn from(value: raindrops_item::PermissivenessType) -> Self {
-        match value {
-            raindrops_item::PermissivenessType::Anybody => PermissivenessType::Anybody,
-            raindrops_item::PermissivenessType::ParentTokenHolder => {
-                PermissivenessType::ParentTokenHolder
-            }
-            raindrops_item::PermissivenessType::TokenHolder => PermissivenessType::TokenHolder,
-            raindrops_item::PermissivenessType::UpdateAuthority => {
-                PermissivenessType::UpdateAuthority
-            }
-        }
-    }
-}
-
-impl From<PermissivenessType> for ItemPermissivenessType {
-    fn from(val: PermissivenessType) -> Self {
-        match val {
-            PermissivenessType::TokenHolder => ItemPermissivenessType::TokenHolder,
-            PermissivenessType::ParentTokenHolder => ItemPermissivenessType::ParentTokenHolder,
-            PermissivenessType::UpdateAuthority => ItemPermissivenessType::UpdateAuthority,
-            PermissivenessType::Anybody => ItemPermissivenessType::Anybody,
-        }
-    }
-}
-
 #[error_code]
 pub enum ErrorCode {
     #[msg("Account does not have correct owner!")]
@@ -878,4 +1719,22 @@ pub enum ErrorCode {
     DiscriminatorMismatch,
     #[msg("Staking for player coming soon")]
     StakingForPlayerComingSoon,
+    #[msg("Reward pools must be scoped to a namespace or an artifact class")]
+    InvalidRewardPoolScope,
+    #[msg("Artifact is not in this reward pool's namespace or class")]
+    RewardPoolScopeMismatch,
+    #[msg("Reward pool is closed")]
+    RewardPoolClosed,
+    #[msg("A stake position with this staking index is already open")]
+    StakePositionAlreadyOpen,
+    #[msg("No open stake position for this staking mint and index")]
+    StakePositionNotFound,
+    #[msg("Unstake amount must be above zero and within the position")]
+    InvalidUnstakeAmount,
+    #[msg("Lockup tier does not exist or has a multiplier below 1x")]
+    InvalidLockupTier,
+    #[msg("Stake position is still locked")]
+    StakeStillLocked,
+    #[msg("Every reward pool counting this stake must be passed in the remaining accounts")]
+    RewardPoolAccountsMissing,
 }
@@ -1,7 +1,14 @@
-use crate::{Artifact, ArtifactClass, ArtifactClassData, ErrorCode};
-use anchor_lang::{prelude::*, require, solana_program::hash};
+use crate::{
+    Artifact, ArtifactClass, ArtifactClassData, EnrolledRewardPool, ErrorCode, LockupTier,
+    RewardPool, StakePosition, StakePositions, StakeRewards, LEGACY_STAKING_INDEX,
+    LOCKUP_MULTIPLIER_BASE, PREFIX, STAKE_REWARDS, STAKE_REWARDS_SIZE,
+};
+use anchor_lang::{prelude::*, require, solana_program::hash, Discriminator};
 use arrayref::array_ref;
+use raindrops_common::utils::{assert_derivation, create_or_allocate_account_raw};
 use raindrops_item::{utils::assert_derivation_with_bump, Item, ItemClass};
+use raindrops_player::{Player, PlayerClass};
+use std::convert::TryFrom;
 
 pub fn assert_is_proper_class(
     artifact_class: &UncheckedAccount<'_>,
@@ -42,36 +49,65 @@ pub fn assert_is_proper_class(
         ErrorCode::DiscriminatorMismatch
     );
 
-    // FIXME: Remove it after the player contract is done.
-    require!(
-        class_name != "PlayerClass",
-        ErrorCode::StakingForPlayerComingSoon
-    );
-
-    let item_class_deserialized: Account<ItemClass> = Account::try_from(artifact_class)?;
-    let item_class_data = item_class_deserialized.item_class_data(&artifact_class.data)?;
-
-    let class_deserialized: ArtifactClass = ArtifactClass {
-        namespaces: item_class_deserialized.namespaces.clone(),
-        parent: item_class_deserialized.parent,
-        mint: item_class_deserialized.mint,
-        metadata: item_class_deserialized.metadata,
-        edition: item_class_deserialized.edition,
-        bump: item_class_deserialized.bump,
-        existing_children: item_class_deserialized.existing_children,
-        data: ArtifactClassData {
-            children_must_be_editions: item_class_data.settings.children_must_be_editions,
-            builder_must_be_holder: item_class_data.settings.builder_must_be_holder,
-            update_permissiveness: item_class_data.settings.update_permissiveness,
-            build_permissiveness: item_class_data.settings.build_permissiveness,
-            staking_warm_up_duration: item_class_data.settings.staking_warm_up_duration,
-            staking_cooldown_duration: item_class_data.settings.staking_cooldown_duration,
-            staking_permissiveness: item_class_data.settings.staking_permissiveness,
-            unstaking_permissiveness: item_class_data.settings.unstaking_permissiveness,
-            child_update_propagation_permissiveness: item_class_data
-                .settings
-                .child_update_propagation_permissiveness,
-        },
+    let class_deserialized: ArtifactClass = if class_name == "PlayerClass" {
+        let player_class_deserialized: Account<PlayerClass> = Account::try_from(artifact_class)?;
+        let settings = &player_class_deserialized.data.settings;
+
+        ArtifactClass {
+            namespaces: player_class_deserialized.namespaces.clone(),
+            parent: player_class_deserialized.parent,
+            mint: player_class_deserialized.mint,
+            metadata: player_class_deserialized.metadata,
+            edition: player_class_deserialized.edition,
+            bump: player_class_deserialized.bump,
+            existing_children: player_class_deserialized.existing_children,
+            data: ArtifactClassData {
+                children_must_be_editions: player_boolean_to_item(
+                    &settings.children_must_be_editions,
+                ),
+                builder_must_be_holder: player_boolean_to_item(&settings.builder_must_be_holder),
+                update_permissiveness: player_permissiveness_to_item(
+                    &settings.update_permissiveness,
+                ),
+                build_permissiveness: player_permissiveness_to_item(&settings.build_permissiveness),
+                staking_warm_up_duration: settings.staking_warm_up_duration,
+                staking_cooldown_duration: settings.staking_cooldown_duration,
+                staking_permissiveness: player_permissiveness_to_item(
+                    &settings.staking_permissiveness,
+                ),
+                unstaking_permissiveness: player_permissiveness_to_item(
+                    &settings.unstaking_permissiveness,
+                ),
+                // player propagation settings carry no type, and staking never reads them
+                child_update_propagation_permissiveness: None,
+            },
+        }
+    } else {
+        let item_class_deserialized: Account<ItemClass> = Account::try_from(artifact_class)?;
+        let item_class_data = item_class_deserialized.item_class_data(&artifact_class.data)?;
+
+        ArtifactClass {
+            namespaces: item_class_deserialized.namespaces.clone(),
+            parent: item_class_deserialized.parent,
+            mint: item_class_deserialized.mint,
+            metadata: item_class_deserialized.metadata,
+            edition: item_class_deserialized.edition,
+            bump: item_class_deserialized.bump,
+            existing_children: item_class_deserialized.existing_children,
+            data: ArtifactClassData {
+                children_must_be_editions: item_class_data.settings.children_must_be_editions,
+                builder_must_be_holder: item_class_data.settings.builder_must_be_holder,
+                update_permissiveness: item_class_data.settings.update_permissiveness,
+                build_permissiveness: item_class_data.settings.build_permissiveness,
+                staking_warm_up_duration: item_class_data.settings.staking_warm_up_duration,
+                staking_cooldown_duration: item_class_data.settings.staking_cooldown_duration,
+                staking_permissiveness: item_class_data.settings.staking_permissiveness,
+                unstaking_permissiveness: item_class_data.settings.unstaking_permissiveness,
+                child_update_propagation_permissiveness: item_class_data
+                    .settings
+                    .child_update_propagation_permissiveness,
+            },
+        }
     };
 
     assert_derivation_with_bump(
@@ -88,12 +124,35 @@ pub fn assert_is_proper_class(
     Ok(class_deserialized)
 }
 
+fn player_boolean_to_item(
+    boolean: &Option<raindrops_player::Boolean>,
+) -> Option<raindrops_item::Boolean> {
+    boolean.as_ref().map(|b| raindrops_item::Boolean {
+        inherited: b.inherited,
+        boolean: b.boolean,
+    })
+}
+
+fn player_permissiveness_to_item(
+    permissiveness: &Option<Vec<raindrops_player::Permissiveness>>,
+) -> Option<Vec<raindrops_item::Permissiveness>> {
+    permissiveness.as_ref().map(|perms| {
+        perms
+            .iter()
+            .map(|p| raindrops_item::Permissiveness {
+                inherited: p.inherited,
+                permissiveness_type: p.permissiveness_type.clone(),
+            })
+            .collect()
+    })
+}
+
 pub fn assert_is_proper_instance(
     artifact: &UncheckedAccount<'_>,
     artifact_class: &Pubkey,
     mint: &Pubkey,
     index: u64,
-) -> Result<()> {
+) -> Result<Artifact> {
     require!(
         artifact.owner == &raindrops_player::id() || artifact.owner == &raindrops_item::id(),
         ErrorCode::InvalidProgramOwner
@@ -128,22 +187,30 @@ pub fn assert_is_proper_instance(
         ErrorCode::DiscriminatorMismatch
     );
 
-    // FIXME: Remove it after the player contract is done.
-    require!(
-        instance_name != "Player",
-        ErrorCode::StakingForPlayerComingSoon
-    );
-
-    let item_deserialized: Account<Item> = Account::try_from(artifact)?;
-
-    let instance_deserialized: Artifact = Artifact {
-        namespaces: item_deserialized.namespaces.clone(),
-        parent: item_deserialized.parent,
-        mint: item_deserialized.mint,
-        metadata: item_deserialized.metadata,
-        edition: item_deserialized.edition,
-        bump: item_deserialized.bump,
-        tokens_staked: item_deserialized.tokens_staked,
+    let instance_deserialized: Artifact = if instance_name == "Player" {
+        let player_deserialized: Account<Player> = Account::try_from(artifact)?;
+
+        Artifact {
+            namespaces: player_deserialized.namespaces.clone(),
+            parent: player_deserialized.parent,
+            mint: player_deserialized.mint,
+            metadata: player_deserialized.metadata,
+            edition: player_deserialized.edition,
+            bump: player_deserialized.bump,
+            tokens_staked: player_deserialized.tokens_staked,
+        }
+    } else {
+        let item_deserialized: Account<Item> = Account::try_from(artifact)?;
+
+        Artifact {
+            namespaces: item_deserialized.namespaces.clone(),
+            parent: item_deserialized.parent,
+            mint: item_deserialized.mint,
+            metadata: item_deserialized.metadata,
+            edition: item_deserialized.edition,
+            bump: item_deserialized.bump,
+            tokens_staked: item_deserialized.tokens_staked,
+        }
     };
 
     assert_derivation_with_bump(
@@ -162,7 +229,7 @@ pub fn assert_is_proper_instance(
         ErrorCode::PublicKeyMismatch
     );
 
-    Ok(())
+    Ok(instance_deserialized)
 }
 
 pub fn assert_part_of_namespace<'a, 'b>(
@@ -180,6 +247,7 @@ pub fn assert_part_of_namespace<'a, 'b>(
             raindrops_namespace::PREFIX.as_bytes(),
             deserialized.mint.key().as_ref(),
         ],
+        ErrorCode::DerivedKeyInvalid,
     )?;
 
     raindrops_namespace::utils::assert_part_of_namespace(artifact, &deserialized)?;
@@ -187,14 +255,6 @@ pub fn assert_part_of_namespace<'a, 'b>(
     Ok(deserialized)
 }
 
-pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<u8> {
-    let (key, bump) = Pubkey::find_program_address(path, program_id);
-    if key != *account.key {
-        return Err(error!(ErrorCode::DerivedKeyInvalid));
-    }
-    Ok(bump)
-}
-
 pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
     if account.owner != owner {
         Err(error!(ErrorCode::IncorrectOwner))
@@ -202,3 +262,735 @@ pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
         Ok(())
     }
 }
+
+// reward_per_token is scaled up by this so small stakes in large pools still earn
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Moves the pool's reward-per-token forward to `now`. Emission only happens
+/// while something is staked, and never beyond what has been funded.
+pub fn accrue_reward_pool(reward_pool: &mut RewardPool, now: i64) -> Result<()> {
+    if now <= reward_pool.last_update {
+        return Ok(());
+    }
+
+    if reward_pool.total_staked > 0 {
+        let elapsed = now
+            .checked_sub(reward_pool.last_update)
+            .ok_or(ErrorCode::NumericalOverflowError)? as u128;
+        let emitted = elapsed
+            .checked_mul(reward_pool.emission_rate as u128)
+            .ok_or(ErrorCode::NumericalOverflowError)?
+            .min(reward_pool.rewards_available as u128);
+
+        reward_pool.reward_per_token = reward_pool
+            .reward_per_token
+            .checked_add(
+                emitted
+                    .checked_mul(REWARD_PRECISION)
+                    .ok_or(ErrorCode::NumericalOverflowError)?
+                    .checked_div(reward_pool.total_staked as u128)
+                    .ok_or(ErrorCode::NumericalOverflowError)?,
+            )
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+        reward_pool.rewards_available = reward_pool
+            .rewards_available
+            .checked_sub(emitted as u64)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+    }
+
+    reward_pool.last_update = now;
+
+    Ok(())
+}
+
+pub fn earned_since_checkpoint(
+    reward_pool: &RewardPool,
+    stake_rewards: &StakeRewards,
+) -> Result<u64> {
+    let earned = (stake_rewards.amount as u128)
+        .checked_mul(
+            reward_pool
+                .reward_per_token
+                .checked_sub(stake_rewards.reward_per_token_paid)
+                .ok_or(ErrorCode::NumericalOverflowError)?,
+        )
+        .ok_or(ErrorCode::NumericalOverflowError)?
+        .checked_div(REWARD_PRECISION)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+
+    u64::try_from(earned).map_err(|_| error!(ErrorCode::NumericalOverflowError))
+}
+
+/// Checkpoints a position against its pool and moves it to `staked_amount`.
+/// With `forfeit_on_decrease` set, a position whose stake left without the pool
+/// being told hands what it accrued since its last checkpoint back to the pool.
+/// A closed pool emits nothing more, what it gets back is returned to its
+/// authority by calling close_reward_pool again.
+pub fn sync_stake_rewards(
+    reward_pool: &mut RewardPool,
+    stake_rewards: &mut StakeRewards,
+    staked_amount: u64,
+    now: i64,
+    forfeit_on_decrease: bool,
+) -> Result<()> {
+    accrue_reward_pool(reward_pool, now)?;
+
+    let earned = earned_since_checkpoint(reward_pool, stake_rewards)?;
+
+    if forfeit_on_decrease && staked_amount < stake_rewards.amount {
+        reward_pool.rewards_available = reward_pool
+            .rewards_available
+            .checked_add(earned)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+    } else {
+        stake_rewards.pending = stake_rewards
+            .pending
+            .checked_add(earned)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+    }
+
+    stake_rewards.reward_per_token_paid = reward_pool.reward_per_token;
+    reward_pool.total_staked = reward_pool
+        .total_staked
+        .checked_sub(stake_rewards.amount)
+        .ok_or(ErrorCode::NumericalOverflowError)?
+        .checked_add(staked_amount)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+    stake_rewards.amount = staked_amount;
+
+    Ok(())
+}
+
+pub fn assert_reward_pool_scope(
+    reward_pool: &RewardPool,
+    artifact_class: &Pubkey,
+    artifact: &Artifact,
+) -> Result<()> {
+    let in_scoped_namespace = artifact
+        .namespaces
+        .as_ref()
+        .map(|namespaces| namespaces.iter().any(|n| n.namespace == reward_pool.scope))
+        .unwrap_or(false);
+
+    require!(
+        reward_pool.scope == *artifact_class || in_scoped_namespace,
+        ErrorCode::RewardPoolScopeMismatch
+    );
+
+    Ok(())
+}
+
+pub struct UpdateStakeRewardsArgs<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+    pub artifact_class: &'a Pubkey,
+    pub artifact: &'a Artifact,
+    pub stake_positions: &'a mut StakePositions,
+    pub staking_mint: &'a Pubkey,
+    pub artifact_mint_staking_account: &'a Pubkey,
+    pub staked_amount: u64,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub now: i64,
+}
+
+// Reward accounts ride at the end of the remaining accounts as [reward_pool, stake_rewards]
+// pairs, after anything the permissiveness check needs, so stakes without a pool pass nothing
+// extra. Once a pool counts the artifact's stake it has to be passed on every change to it.
+pub fn update_stake_rewards(args: UpdateStakeRewardsArgs) -> Result<()> {
+    let UpdateStakeRewardsArgs {
+        program_id,
+        remaining_accounts,
+        artifact_class,
+        artifact,
+        stake_positions,
+        staking_mint,
+        artifact_mint_staking_account,
+        staked_amount,
+        payer,
+        system_program,
+        rent,
+        now,
+    } = args;
+
+    let mut updated_pools = vec![];
+    let mut remaining_accounts = remaining_accounts;
+
+    while let [rest @ .., reward_pool_info, stake_rewards_info] = remaining_accounts {
+        if reward_pool_info.owner != program_id
+            || reward_pool_info.data_len() < 8
+            || reward_pool_info.data.borrow()[..8] != RewardPool::discriminator()
+        {
+            break;
+        }
+        remaining_accounts = rest;
+
+        let mut reward_pool: Account<RewardPool> = Account::try_from(reward_pool_info)?;
+
+        require!(
+            reward_pool.staking_mint == *staking_mint,
+            ErrorCode::MintMismatch
+        );
+        assert_reward_pool_scope(&reward_pool, artifact_class, artifact)?;
+
+        let reward_pool_key = reward_pool_info.key();
+        updated_pools.push(reward_pool_key);
+
+        let bump = assert_derivation(
+            program_id,
+            stake_rewards_info,
+            &[
+                PREFIX.as_bytes(),
+                STAKE_REWARDS.as_bytes(),
+                reward_pool_key.as_ref(),
+                artifact_mint_staking_account.as_ref(),
+            ],
+            ErrorCode::DerivedKeyInvalid,
+        )?;
+
+        let mut stake_rewards: Account<StakeRewards> = if stake_rewards_info.data_is_empty() {
+            if staked_amount == 0 {
+                continue;
+            }
+
+            create_or_allocate_account_raw(
+                *program_id,
+                stake_rewards_info,
+                rent,
+                system_program,
+                payer,
+                STAKE_REWARDS_SIZE,
+                &[
+                    PREFIX.as_bytes(),
+                    STAKE_REWARDS.as_bytes(),
+                    reward_pool_key.as_ref(),
+                    artifact_mint_staking_account.as_ref(),
+                    &[bump],
+                ],
+            )?;
+
+            let mut stake_rewards: Account<StakeRewards> =
+                Account::try_from_unchecked(stake_rewards_info)?;
+            stake_rewards.bump = bump;
+            stake_rewards.reward_pool = reward_pool_key;
+            stake_rewards.staking_account = *artifact_mint_staking_account;
+            stake_rewards
+        } else {
+            Account::try_from(stake_rewards_info)?
+        };
+
+        sync_stake_rewards(
+            &mut reward_pool,
+            &mut stake_rewards,
+            staked_amount,
+            now,
+            false,
+        )?;
+        enroll_in_reward_pool(
+            stake_positions,
+            &reward_pool_key,
+            staking_mint,
+            stake_rewards.amount,
+        );
+
+        reward_pool.exit(program_id)?;
+        stake_rewards.exit(program_id)?;
+    }
+
+    assert_enrolled_reward_pools_updated(stake_positions, staking_mint, &updated_pools)
+}
+
+/// Tracks whether `reward_pool` counts stake on the artifact, it does while the
+/// pool's last checkpoint of the staking account holds anything.
+pub fn enroll_in_reward_pool(
+    stake_positions: &mut StakePositions,
+    reward_pool: &Pubkey,
+    staking_mint: &Pubkey,
+    counted_amount: u64,
+) {
+    let enrolled = stake_positions
+        .reward_pools
+        .iter()
+        .position(|p| p.reward_pool == *reward_pool);
+
+    match (enrolled, counted_amount > 0) {
+        (None, true) => stake_positions.reward_pools.push(EnrolledRewardPool {
+            reward_pool: *reward_pool,
+            staking_mint: *staking_mint,
+        }),
+        (Some(enrolled), false) => {
+            stake_positions.reward_pools.remove(enrolled);
+        }
+        _ => {}
+    }
+}
+
+/// A pool left out of a stake change would keep counting the old stake and
+/// dilute everyone else in it, so every pool counting the mint must be updated.
+pub fn assert_enrolled_reward_pools_updated(
+    stake_positions: &StakePositions,
+    staking_mint: &Pubkey,
+    updated_pools: &[Pubkey],
+) -> Result<()> {
+    require!(
+        stake_positions
+            .reward_pools
+            .iter()
+            .filter(|p| p.staking_mint == *staking_mint)
+            .all(|p| updated_pools.contains(&p.reward_pool)),
+        ErrorCode::RewardPoolAccountsMissing
+    );
+
+    Ok(())
+}
+
+pub fn open_stake_position(
+    stake_positions: &mut StakePositions,
+    position: StakePosition,
+) -> Result<()> {
+    require!(
+        !stake_positions.positions.iter().any(|p| {
+            p.staking_mint == position.staking_mint && p.staking_index == position.staking_index
+        }),
+        ErrorCode::StakePositionAlreadyOpen
+    );
+
+    stake_positions.positions.push(position);
+
+    Ok(())
+}
+
+/// Stake made before positions were tracked has no position of its own. Tops up
+/// a single unlocked, unweighted position for the mint so that the positions add
+/// back up to `staked_amount`, what the artifact's staking account holds.
+pub fn backfill_legacy_position(
+    stake_positions: &mut StakePositions,
+    staking_mint: &Pubkey,
+    staked_amount: u64,
+) -> Result<()> {
+    let tracked = stake_positions
+        .positions
+        .iter()
+        .filter(|p| p.staking_mint == *staking_mint)
+        .try_fold(0u64, |sum, p| sum.checked_add(p.amount))
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+
+    if staked_amount <= tracked {
+        return Ok(());
+    }
+
+    let untracked = staked_amount - tracked;
+    match stake_positions
+        .positions
+        .iter_mut()
+        .find(|p| p.staking_mint == *staking_mint && p.staking_index == LEGACY_STAKING_INDEX)
+    {
+        Some(position) => {
+            position.amount = position
+                .amount
+                .checked_add(untracked)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
+        None => stake_positions.positions.push(StakePosition {
+            staking_mint: *staking_mint,
+            staking_index: LEGACY_STAKING_INDEX,
+            amount: untracked,
+            staked_at: 0,
+            unlocks_at: 0,
+            multiplier_bps: LOCKUP_MULTIPLIER_BASE,
+        }),
+    }
+
+    Ok(())
+}
+
+pub fn weigh_stake(amount: u64, multiplier_bps: u64) -> Result<u64> {
+    let weighted = (amount as u128)
+        .checked_mul(multiplier_bps as u128)
+        .ok_or(ErrorCode::NumericalOverflowError)?
+        / LOCKUP_MULTIPLIER_BASE as u128;
+
+    u64::try_from(weighted).map_err(|_| error!(ErrorCode::NumericalOverflowError))
+}
+
+pub fn assert_valid_lockup_tiers(tiers: &[LockupTier]) -> Result<()> {
+    require!(
+        tiers.len() <= u8::MAX as usize + 1,
+        ErrorCode::InvalidLockupTier
+    );
+    for tier in tiers {
+        require!(
+            tier.multiplier_bps >= LOCKUP_MULTIPLIER_BASE,
+            ErrorCode::InvalidLockupTier
+        );
+        require!(
+            tier.lock_duration <= i64::MAX as u64,
+            ErrorCode::NumericalOverflowError
+        );
+    }
+
+    Ok(())
+}
+
+/// Takes `amount` (the whole position if not set) off the position opened with
+/// `staking_index`, or the mint's legacy position if there is none, dropping it
+/// once empty. Returns the amount taken and the weighted amount to take off the item.
+pub fn unstake_from_position(
+    stake_positions: &mut StakePositions,
+    staking_mint: &Pubkey,
+    staking_index: u64,
+    amount: Option<u64>,
+    now: i64,
+) -> Result<(u64, u64)> {
+    let position_index = stake_positions
+        .positions
+        .iter()
+        .position(|p| p.staking_mint == *staking_mint && p.staking_index == staking_index)
+        .or_else(|| {
+            stake_positions.positions.iter().position(|p| {
+                p.staking_mint == *staking_mint && p.staking_index == LEGACY_STAKING_INDEX
+            })
+        })
+        .ok_or(ErrorCode::StakePositionNotFound)?;
+
+    let position = &mut stake_positions.positions[position_index];
+    let amount = amount.unwrap_or(position.amount);
+
+    require!(now >= position.unlocks_at, ErrorCode::StakeStillLocked);
+    require!(
+        amount > 0 && amount <= position.amount,
+        ErrorCode::InvalidUnstakeAmount
+    );
+
+    // weigh what is left rather than what is taken so that partial unstakes add
+    // up to exactly the weight that was staked
+    let weighted_before = position.weighted_amount()?;
+    position.amount = position
+        .amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+    let weighted_amount = weighted_before
+        .checked_sub(position.weighted_amount()?)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+
+    if position.amount == 0 {
+        stake_positions.positions.remove(position_index);
+    }
+
+    Ok((amount, weighted_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward_pool(emission_rate: u64, rewards_available: u64) -> RewardPool {
+        RewardPool {
+            bump: 255,
+            authority: Pubkey::new_unique(),
+            scope: Pubkey::new_unique(),
+            staking_mint: Pubkey::new_unique(),
+            reward_mint: Pubkey::new_unique(),
+            emission_rate,
+            rewards_available,
+            total_staked: 0,
+            reward_per_token: 0,
+            last_update: 0,
+            closed: false,
+        }
+    }
+
+    fn stake_rewards() -> StakeRewards {
+        StakeRewards {
+            bump: 255,
+            reward_pool: Pubkey::new_unique(),
+            staking_account: Pubkey::new_unique(),
+            amount: 0,
+            reward_per_token_paid: 0,
+            pending: 0,
+        }
+    }
+
+    #[test]
+    fn nothing_is_emitted_while_nothing_is_staked() {
+        let mut pool = reward_pool(10, 1_000);
+
+        accrue_reward_pool(&mut pool, 50).unwrap();
+
+        assert_eq!(pool.reward_per_token, 0);
+        assert_eq!(pool.rewards_available, 1_000);
+        assert_eq!(pool.last_update, 50);
+    }
+
+    #[test]
+    fn stakers_split_emission_by_amount_and_time() {
+        let mut pool = reward_pool(10, 1_000_000);
+        let mut alice = stake_rewards();
+        let mut bob = stake_rewards();
+
+        sync_stake_rewards(&mut pool, &mut alice, 100, 0, false).unwrap();
+        // alice alone for 10s earns all 100
+        sync_stake_rewards(&mut pool, &mut bob, 300, 10, false).unwrap();
+        // then 10s where bob holds three quarters of the pool
+        sync_stake_rewards(&mut pool, &mut alice, 100, 20, false).unwrap();
+        sync_stake_rewards(&mut pool, &mut bob, 300, 20, false).unwrap();
+
+        assert_eq!(alice.pending, 125);
+        assert_eq!(bob.pending, 75);
+        assert_eq!(pool.total_staked, 400);
+        assert_eq!(pool.rewards_available, 1_000_000 - 200);
+    }
+
+    #[test]
+    fn stake_changes_mid_period_only_affect_later_accrual() {
+        let mut pool = reward_pool(10, 1_000_000);
+        let mut alice = stake_rewards();
+        let mut bob = stake_rewards();
+
+        sync_stake_rewards(&mut pool, &mut alice, 100, 0, false).unwrap();
+        sync_stake_rewards(&mut pool, &mut bob, 100, 0, false).unwrap();
+        // bob unstakes half way through
+        sync_stake_rewards(&mut pool, &mut bob, 0, 10, false).unwrap();
+        sync_stake_rewards(&mut pool, &mut alice, 100, 20, false).unwrap();
+        sync_stake_rewards(&mut pool, &mut bob, 0, 20, false).unwrap();
+
+        assert_eq!(alice.pending, 150);
+        assert_eq!(bob.pending, 50);
+        assert_eq!(pool.total_staked, 100);
+    }
+
+    #[test]
+    fn emission_stops_when_funding_runs_out() {
+        let mut pool = reward_pool(10, 55);
+        let mut alice = stake_rewards();
+
+        sync_stake_rewards(&mut pool, &mut alice, 100, 0, false).unwrap();
+        sync_stake_rewards(&mut pool, &mut alice, 100, 100, false).unwrap();
+
+        assert_eq!(alice.pending, 55);
+        assert_eq!(pool.rewards_available, 0);
+    }
+
+    #[test]
+    fn unreported_unstake_forfeits_accrual_back_to_the_pool() {
+        let mut pool = reward_pool(10, 1_000);
+        let mut alice = stake_rewards();
+
+        sync_stake_rewards(&mut pool, &mut alice, 100, 0, false).unwrap();
+        sync_stake_rewards(&mut pool, &mut alice, 100, 10, false).unwrap();
+        // stake left at some point after t=10 without the pool hearing about it
+        sync_stake_rewards(&mut pool, &mut alice, 0, 30, true).unwrap();
+
+        assert_eq!(alice.pending, 100);
+        assert_eq!(alice.amount, 0);
+        assert_eq!(pool.total_staked, 0);
+        assert_eq!(pool.rewards_available, 900);
+    }
+
+    #[test]
+    fn forfeits_on_a_closed_pool_are_left_for_the_authority() {
+        let mut pool = reward_pool(10, 1_000);
+        let mut alice = stake_rewards();
+
+        sync_stake_rewards(&mut pool, &mut alice, 100, 0, false).unwrap();
+        // closing at t=10 settles what was emitted and takes back the rest
+        accrue_reward_pool(&mut pool, 10).unwrap();
+        pool.rewards_available = 0;
+        pool.emission_rate = 0;
+        pool.closed = true;
+
+        sync_stake_rewards(&mut pool, &mut alice, 0, 30, true).unwrap();
+        accrue_reward_pool(&mut pool, 50).unwrap();
+
+        assert_eq!(alice.pending, 0);
+        assert_eq!(pool.rewards_available, 100);
+    }
+
+    #[test]
+    fn pools_counting_the_stake_must_be_updated_with_it() {
+        let mut positions = StakePositions {
+            bump: 255,
+            positions: vec![],
+            reward_pools: vec![],
+        };
+        let mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let other_pool = Pubkey::new_unique();
+
+        enroll_in_reward_pool(&mut positions, &pool, &mint, 100);
+        enroll_in_reward_pool(&mut positions, &pool, &mint, 50);
+        enroll_in_reward_pool(&mut positions, &other_pool, &other_mint, 10);
+        assert_eq!(positions.reward_pools.len(), 2);
+
+        assert!(assert_enrolled_reward_pools_updated(&positions, &mint, &[]).is_err());
+        assert!(assert_enrolled_reward_pools_updated(&positions, &mint, &[other_pool]).is_err());
+        assert!(assert_enrolled_reward_pools_updated(&positions, &mint, &[pool]).is_ok());
+
+        // a pool that no longer counts anything can be left out again
+        enroll_in_reward_pool(&mut positions, &pool, &mint, 0);
+        assert!(assert_enrolled_reward_pools_updated(&positions, &mint, &[]).is_ok());
+        assert!(assert_enrolled_reward_pools_updated(&positions, &other_mint, &[]).is_err());
+    }
+
+    fn stake_position(staking_index: u64, amount: u64) -> StakePosition {
+        StakePosition {
+            staking_mint: Pubkey::default(),
+            staking_index,
+            amount,
+            staked_at: 0,
+            unlocks_at: 0,
+            multiplier_bps: LOCKUP_MULTIPLIER_BASE,
+        }
+    }
+
+    #[test]
+    fn positions_are_unique_per_staking_index() {
+        let mut positions = StakePositions {
+            bump: 255,
+            positions: vec![],
+            reward_pools: vec![],
+        };
+
+        open_stake_position(&mut positions, stake_position(0, 100)).unwrap();
+        open_stake_position(&mut positions, stake_position(1, 50)).unwrap();
+
+        assert!(open_stake_position(&mut positions, stake_position(0, 10)).is_err());
+        assert_eq!(positions.positions.len(), 2);
+    }
+
+    #[test]
+    fn partial_unstake_keeps_the_rest_of_the_position() {
+        let mut positions = StakePositions {
+            bump: 255,
+            positions: vec![stake_position(0, 100), stake_position(1, 50)],
+            reward_pools: vec![],
+        };
+        let mint = Pubkey::default();
+
+        assert_eq!(
+            unstake_from_position(&mut positions, &mint, 0, Some(40), 0).unwrap(),
+            (40, 40)
+        );
+        assert_eq!(positions.positions[0], stake_position(0, 60));
+
+        assert!(unstake_from_position(&mut positions, &mint, 0, Some(61), 0).is_err());
+        assert!(unstake_from_position(&mut positions, &mint, 0, Some(0), 0).is_err());
+        assert!(unstake_from_position(&mut positions, &mint, 2, None, 0).is_err());
+
+        assert_eq!(
+            unstake_from_position(&mut positions, &mint, 0, None, 0).unwrap(),
+            (60, 60)
+        );
+        assert_eq!(positions.positions, vec![stake_position(1, 50)]);
+    }
+
+    #[test]
+    fn locked_positions_cannot_be_unstaked_early() {
+        let mut position = stake_position(0, 100);
+        position.unlocks_at = 1_000;
+        let mut positions = StakePositions {
+            bump: 255,
+            positions: vec![position],
+            reward_pools: vec![],
+        };
+        let mint = Pubkey::default();
+
+        assert!(unstake_from_position(&mut positions, &mint, 0, None, 999).is_err());
+        assert_eq!(
+            unstake_from_position(&mut positions, &mint, 0, None, 1_000).unwrap(),
+            (100, 100)
+        );
+    }
+
+    #[test]
+    fn partial_unstakes_remove_exactly_the_staked_weight() {
+        let mut position = stake_position(0, 7);
+        position.multiplier_bps = 15_000;
+        let staked_weight = position.weighted_amount().unwrap();
+        let mut positions = StakePositions {
+            bump: 255,
+            positions: vec![position],
+            reward_pools: vec![],
+        };
+        let mint = Pubkey::default();
+
+        assert_eq!(staked_weight, 10);
+
+        let mut removed_weight = 0;
+        for _ in 0..7 {
+            let (amount, weighted) =
+                unstake_from_position(&mut positions, &mint, 0, Some(1), 0).unwrap();
+            assert_eq!(amount, 1);
+            removed_weight += weighted;
+        }
+
+        assert_eq!(removed_weight, staked_weight);
+        assert!(positions.positions.is_empty());
+    }
+
+    #[test]
+    fn stake_from_before_positions_is_backfilled_and_unstakeable() {
+        let mut positions = StakePositions {
+            bump: 255,
+            positions: vec![],
+            reward_pools: vec![],
+        };
+        let mint = Pubkey::default();
+
+        // 80 staked before positions existed, then a new stake of 20 lands on top
+        backfill_legacy_position(&mut positions, &mint, 80).unwrap();
+        open_stake_position(&mut positions, stake_position(3, 20)).unwrap();
+        backfill_legacy_position(&mut positions, &mint, 100).unwrap();
+
+        assert_eq!(
+            positions.positions,
+            vec![
+                stake_position(LEGACY_STAKING_INDEX, 80),
+                stake_position(3, 20)
+            ]
+        );
+
+        // the old stake cools down under whatever staking_index it was made with
+        assert_eq!(
+            unstake_from_position(&mut positions, &mint, 7, Some(30), 0).unwrap(),
+            (30, 30)
+        );
+        assert_eq!(
+            unstake_from_position(&mut positions, &mint, 3, None, 0).unwrap(),
+            (20, 20)
+        );
+        assert_eq!(
+            positions.positions,
+            vec![stake_position(LEGACY_STAKING_INDEX, 50)]
+        );
+    }
+
+    #[test]
+    fn counters_from_before_lockup_tiers_read_as_unlocked() {
+        let mut data = crate::StakingCounter::discriminator().to_vec();
+        data.push(254);
+        data.extend_from_slice(&100i64.to_le_bytes());
+        data.push(0);
+
+        let counter = crate::StakingCounter::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(counter.event_start, 100);
+        assert_eq!(counter.lockup, crate::StakingCounterLockup::default());
+    }
+
+    #[test]
+    fn lockup_tiers_cannot_weaken_stakes() {
+        assert!(assert_valid_lockup_tiers(&[LockupTier {
+            lock_duration: 30 * 86_400,
+            multiplier_bps: LOCKUP_MULTIPLIER_BASE,
+        }])
+        .is_ok());
+        assert!(assert_valid_lockup_tiers(&[LockupTier {
+            lock_duration: 0,
+            multiplier_bps: LOCKUP_MULTIPLIER_BASE - 1,
+        }])
+        .is_err());
+    }
+}
@@ -180,6 +180,7 @@ describe("matches", () => {
     const disburseTokensByOracleArgs: Instructions.Matches.DisburseTokensByOracleArgs =
       {
         tokenDeltaProofInfo: null,
+        index: new anchor.BN(0),
       };
 
     const disburseTokensByOracleAccounts: Instructions.Matches.DisburseTokensByOracleAccounts =
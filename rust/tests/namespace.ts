@@ -37,18 +37,33 @@ describe("namespace", () => {
 
   const connection = anchor.getProvider().connection;
 
-  before("create rain token vault", async () => {
+  before("create rain token vault and protocol fee config", async () => {
     const tokenVaultPayer = await newPayer(
       connection,
       rainTokenMint,
       rainTokenMintAuthority
     );
-    await splToken.getOrCreateAssociatedTokenAccount(
+    const rainTokenVault = await splToken.getOrCreateAssociatedTokenAccount(
       connection,
       tokenVaultPayer,
       rainTokenMint,
       rainVaultAuthority
     );
+
+    // the provider wallet deployed the program so it is the upgrade authority
+    const namespaceProgram = await NamespaceProgram.getProgramWithConfig(
+      NamespaceProgram,
+      {
+        asyncSigning: false,
+        provider: anchor.getProvider() as anchor.AnchorProvider,
+        idl: Idls.NamespaceIDL,
+      }
+    );
+
+    await namespaceProgram.initializeProtocolFeeConfig(
+      { feeAmount: new BN(Constants.Player.RAIN_PAYMENT_AMOUNT) },
+      { feeMint: rainTokenMint, feeVault: rainTokenVault.address }
+    );
   });
 
   it("init namespace", async () => {
@@ -436,6 +451,7 @@ describe("namespace", () => {
       prettyName: "new-name",
       permissivenessSettings: null,
       whitelistedStakingMints: [wlStakingMint2],
+      refundPaymentOnLeave: null,
     };
 
     const updateNsAccounts: Instructions.Namespace.UpdateNamespaceAccounts = {